@@ -12,6 +12,7 @@ use std::{
 use anyhow::{Context, Result};
 use fs_err as fs;
 use mint_lib::DRGInstallationType;
+use tracing::{error, info};
 use windows::Win32::System::Memory::{VirtualProtect, PAGE_EXECUTE_READWRITE};
 
 use crate::{
@@ -281,6 +282,20 @@ unsafe extern "system" fn exec_get_mod_json(
     std::mem::forget(ret);
 
     stack.code = stack.code.add(1);
+
+    // mint's integration only asks for the mod list once the game started with the mods loaded
+    if let Some(bin_dir) = &globals().smoke_test {
+        pass_smoke_test(bin_dir);
+    }
+}
+
+fn pass_smoke_test(bin_dir: &Path) -> ! {
+    match mint_lib::smoke_test::pass(bin_dir) {
+        Ok(()) => info!("smoke test passed, exiting"),
+        Err(e) => error!("failed to mark smoke test as passed: {e}"),
+    }
+    drop(LOG_GUARD.with_borrow_mut(|g| g.take()));
+    std::process::exit(0);
 }
 
 unsafe extern "system" fn exec_print_string(
@@ -1,7 +1,10 @@
 mod hooks;
 mod ue;
 
-use std::{io::BufReader, path::Path};
+use std::{
+    io::BufReader,
+    path::{Path, PathBuf},
+};
 
 use anyhow::{Context, Result};
 use fs_err as fs;
@@ -25,6 +28,9 @@ thread_local! {
 pub struct Globals {
     resolution: hook_resolvers::HookResolution,
     meta: Meta,
+    /// Directory of the game executable if mint requested a smoke test of this launch, see
+    /// [`mint_lib::smoke_test`].
+    smoke_test: Option<PathBuf>,
 }
 
 impl Globals {
@@ -112,7 +118,7 @@ unsafe fn patch() -> Result<()> {
     let pak_path = bin_dir
         .and_then(Path::parent)
         .and_then(Path::parent)
-        .map(|p| p.join("Content/Paks/mods_P.pak"))
+        .map(|p| mint_lib::resolve_links(p.join("Content/Paks/mods_P.pak")))
         .context("could not determine pak path")?;
 
     let mut pak_reader = BufReader::new(fs::File::open(pak_path)?);
@@ -125,7 +131,23 @@ unsafe fn patch() -> Result<()> {
     let resolution = image.resolve(hook_resolvers::HookResolution::resolver())?;
     info!("PS scan: {:#x?}", resolution);
 
-    GLOBALS = Some(Globals { resolution, meta });
+    let smoke_test = bin_dir.and_then(|bin_dir| {
+        let remaining = mint_lib::smoke_test::pending(bin_dir)?;
+        info!("smoke test requested");
+        // never leave the game running if it does not get far enough to pass in time
+        std::thread::spawn(move || {
+            std::thread::sleep(remaining);
+            warn!("smoke test timed out, exiting");
+            std::process::exit(1);
+        });
+        Some(bin_dir.to_path_buf())
+    });
+
+    GLOBALS = Some(Globals {
+        resolution,
+        meta,
+        smoke_test,
+    });
     LOG_GUARD.with_borrow_mut(|g| *g = guard);
 
     hooks::initialize()?;
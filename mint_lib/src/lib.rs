@@ -1,6 +1,9 @@
 pub mod error;
 pub mod mod_info;
+pub mod save;
+pub mod smoke_test;
 pub mod update;
+pub mod version_range;
 
 use std::{
     io::BufWriter,
@@ -72,6 +75,8 @@ impl DRGInstallationType {
 pub struct DRGInstallation {
     pub root: PathBuf,
     pub installation_type: DRGInstallationType,
+    /// The Paks directory with links resolved, see [`resolve_links`].
+    paks_path: PathBuf,
 }
 
 impl DRGInstallation {
@@ -93,6 +98,9 @@ impl DRGInstallation {
             })
             .and_then(|path| Self::from_pak_path(path).ok())
     }
+    /// Installation containing `pak`. The root and Paks directories are derived from where `pak`
+    /// is found and then have links resolved, so a Paks directory linked to another location is
+    /// written to and looked up in its real location.
     pub fn from_pak_path<P: AsRef<Path>>(pak: P) -> Result<Self> {
         let root = pak
             .as_ref()
@@ -101,9 +109,11 @@ impl DRGInstallation {
             .and_then(Path::parent)
             .context("failed to get pak parent directory")?
             .to_path_buf();
+        let paks_path = resolve_links(root.join("Content").join("Paks"));
         Ok(Self {
-            root,
+            root: resolve_links(root),
             installation_type: DRGInstallationType::from_pak_path(pak)?,
+            paks_path,
         })
     }
     pub fn binaries_directory(&self) -> PathBuf {
@@ -112,12 +122,13 @@ impl DRGInstallation {
             .join(self.installation_type.binaries_directory_name())
     }
     pub fn paks_path(&self) -> PathBuf {
-        self.root.join("Content").join("Paks")
+        self.paks_path.clone()
+    }
+    pub fn mods_pak(&self) -> PathBuf {
+        self.paks_path().join("mods_P.pak")
     }
     pub fn main_pak(&self) -> PathBuf {
-        self.root
-            .join("Content")
-            .join("Paks")
+        self.paks_path()
             .join(self.installation_type.main_pak_name())
     }
     pub fn modio_directory(&self) -> Option<PathBuf> {
@@ -147,6 +158,43 @@ impl DRGInstallation {
     }
 }
 
+/// Resolve symlinks and junctions in `path` if it contains any, e.g. a Paks directory linked to
+/// another drive. Paths without links, or which do not exist, are returned unchanged.
+pub fn resolve_links(path: PathBuf) -> PathBuf {
+    let linked = path
+        .ancestors()
+        .any(|p| fs::symlink_metadata(p).is_ok_and(|m| m.file_type().is_symlink()));
+    if !linked {
+        return path;
+    }
+    match fs::canonicalize(&path) {
+        Ok(resolved) => {
+            let resolved = strip_verbatim_prefix(resolved);
+            info!("resolved link {} to {}", path.display(), resolved.display());
+            resolved
+        }
+        Err(e) => {
+            warn!("failed to resolve link {}: {e}", path.display());
+            path
+        }
+    }
+}
+
+/// Turn `\\?\C:\...` paths returned by [`fs::canonicalize`] on Windows back into regular ones,
+/// which are understood by more programs and compare equal to paths entered by the user.
+fn strip_verbatim_prefix(path: PathBuf) -> PathBuf {
+    #[cfg(windows)]
+    {
+        use std::path::{Component, Prefix};
+        if let Some(Component::Prefix(prefix)) = path.components().next() {
+            if let Prefix::VerbatimDisk(_) = prefix.kind() {
+                return PathBuf::from(&path.to_string_lossy()[4..]);
+            }
+        }
+    }
+    path
+}
+
 pub fn setup_logging<P: AsRef<Path>>(
     log_path: P,
     target: &str,
@@ -2,6 +2,8 @@ use std::{collections::BTreeSet, fmt::Display};
 
 use serde::{Deserialize, Serialize};
 
+use crate::version_range::VersionRange;
+
 /// Tags from mod.io.
 #[derive(Debug, Clone)]
 pub struct ModioTags {
@@ -15,6 +17,42 @@ pub struct ModioTags {
     pub approval_status: ApprovalStatus,
 }
 
+/// Popularity of a mod on mod.io.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModioStats {
+    pub downloads: u32,
+    pub subscribers: u32,
+    pub ratings_positive: u32,
+    pub ratings_negative: u32,
+}
+
+impl ModioStats {
+    /// Share of positive ratings in percent, or `None` if the mod has not been rated.
+    pub fn rating_percent(&self) -> Option<u32> {
+        let total = self.ratings_positive as u64 + self.ratings_negative as u64;
+        (total > 0).then(|| (self.ratings_positive as u64 * 100 / total) as u32)
+    }
+}
+
+impl Display for ModioStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} downloads", format_count(self.downloads))?;
+        if let Some(percent) = self.rating_percent() {
+            write!(f, ", {percent}% positive")?;
+        }
+        Ok(())
+    }
+}
+
+/// Abbreviate large counts, e.g. `12345` as `12.3k`.
+pub fn format_count(count: u32) -> String {
+    match count {
+        0..1_000 => count.to_string(),
+        1_000..1_000_000 => format!("{:.1}k", count as f64 / 1e3),
+        _ => format!("{:.1}M", count as f64 / 1e6),
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum RequiredStatus {
     RequiredByAll,
@@ -47,6 +85,17 @@ pub struct ModInfo {
     pub suggested_dependencies: Vec<ModSpecification>, // ModResponse
     pub modio_tags: Option<ModioTags>,                 // only available for mods from mod.io
     pub modio_id: Option<u32>,                         // only available for mods from mod.io
+    pub modio_stats: Option<ModioStats>,               // only available for mods from mod.io
+    pub description: Option<String>,                   // only available for mods from mod.io
+}
+
+impl ModInfo {
+    /// Whether `spec` is one of the suggested dependencies of this mod.
+    pub fn depends_on(&self, spec: &ModSpecification) -> bool {
+        self.suggested_dependencies
+            .iter()
+            .any(|d| spec.satisfies_dependency(d))
+    }
 }
 
 /// Returned from ModProvider
@@ -70,7 +119,40 @@ impl ModSpecification {
     }
     pub fn satisfies_dependency(&self, other: &ModSpecification) -> bool {
         // TODO this hack works surprisingly well but is still a complete hack and should be replaced
-        self.url.starts_with(&other.url) || other.url.starts_with(&self.url)
+        let (a, _) = self.split_version_range();
+        let (b, _) = other.split_version_range();
+        a.url.starts_with(&b.url) || b.url.starts_with(&a.url)
+    }
+    /// Split off a trailing version range, e.g. `https://mod.io/g/drg/m/example@>=1.2, <2`.
+    /// Transforms are dropped from the returned specification, see [`Self::split_transforms`].
+    pub fn split_version_range(&self) -> (ModSpecification, Option<&str>) {
+        let (spec, _) = self.split_transforms();
+        let url = &self.url[..spec.url.len()];
+        // `@` also separates user info from the host of a URL, so only the last path segment
+        // can have a range
+        let segment = url.rfind(['/', '\\']).map_or(0, |i| i + 1);
+        match url[segment..].rsplit_once('@') {
+            Some((name, range)) if range.parse::<VersionRange>().is_ok() => (
+                Self::new(url[..segment + name.len()].to_string()),
+                Some(range.trim()),
+            ),
+            _ => (spec, None),
+        }
+    }
+    /// Split off the post-download transforms a mod needs, given after a `|` separated by
+    /// commas, e.g. `https://example.com/mod.zip|extract-pak,repack`.
+    pub fn split_transforms(&self) -> (ModSpecification, Vec<&str>) {
+        match self.url.split_once('|') {
+            Some((url, transforms)) => (
+                Self::new(url.to_string()),
+                transforms
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|t| !t.is_empty())
+                    .collect(),
+            ),
+            None => (self.clone(), vec![]),
+        }
     }
 }
 
@@ -176,4 +258,30 @@ impl Meta {
             )
             .join(";")
     }
+
+    /// Mods advertised in a string created by [`Meta::to_server_list_string`]. Returns `None` if
+    /// the string was not created by mint.
+    pub fn parse_server_list_string(s: &str) -> Option<Vec<(ApprovalStatus, String)>> {
+        let mut parts = s.split(';');
+        if parts.next()? != "mint" {
+            return None;
+        }
+        parts.next()?;
+        let parts = parts.collect::<Vec<_>>();
+        parts
+            .chunks(2)
+            .map(|chunk| {
+                let [approval, name] = chunk else {
+                    return None;
+                };
+                let approval = match *approval {
+                    "V" => ApprovalStatus::Verified,
+                    "A" => ApprovalStatus::Approved,
+                    "S" => ApprovalStatus::Sandbox,
+                    _ => return None,
+                };
+                Some((approval, name.to_string()))
+            })
+            .collect()
+    }
 }
@@ -0,0 +1,101 @@
+use std::io::Cursor;
+use std::path::Path;
+
+use fs_err as fs;
+use serde::{Deserialize, Serialize};
+use snafu::prelude::*;
+
+/// Name of the save the in-game mod integration UI keeps its mod list in, found in the game's
+/// `FSD/Saved/SaveGames` directory.
+pub const MOD_INTEGRATION_SAVE: &str = "ModIntegration.sav";
+
+#[derive(Debug, Snafu)]
+pub enum SaveError {
+    #[snafu(transparent)]
+    Io { source: std::io::Error },
+    #[snafu(display("failed to read save: {source}"))]
+    Uesave { source: uesave::Error },
+    #[snafu(display("save does not contain a mod config"))]
+    MissingConfig,
+    #[snafu(display("failed to parse mod config of save: {source}"))]
+    Json { source: serde_json::Error },
+}
+
+/// Mod list configured in game, stored as JSON in a string property of the save. Fields mint does
+/// not know about are kept as they are so writing the config back does not lose them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModIntegrationConfig {
+    /// Whether syncing the mods between the game and mint was requested and is still pending.
+    #[serde(default)]
+    pub request_sync: bool,
+    /// Mods in the order they are listed in game.
+    #[serde(rename = "Mods", default)]
+    pub mods: Vec<SaveMod>,
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SaveMod {
+    pub url: String,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// `ModIntegration.sav` with its [`ModIntegrationConfig`] parsed.
+#[derive(Debug)]
+pub struct ModIntegrationSave {
+    save: uesave::Save,
+    /// Name of the property holding the config.
+    property: String,
+    pub config: ModIntegrationConfig,
+}
+
+impl ModIntegrationSave {
+    /// Read the save at `path`. The config is taken from the first string property holding a
+    /// JSON object.
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<Self, SaveError> {
+        let data = fs::read(path.as_ref())?;
+        let save = uesave::Save::read(&mut Cursor::new(data)).context(UesaveSnafu)?;
+        let (property, json) = save
+            .root
+            .properties
+            .0
+            .iter()
+            .find_map(|(name, property)| match property {
+                uesave::Property::Str { value, .. } if value.trim_start().starts_with('{') => {
+                    Some((name.clone(), value))
+                }
+                _ => None,
+            })
+            .context(MissingConfigSnafu)?;
+        let config = serde_json::from_str(json).context(JsonSnafu)?;
+        Ok(Self {
+            save,
+            property,
+            config,
+        })
+    }
+
+    /// Write the save with the current [`Self::config`] to `path`.
+    pub fn write<P: AsRef<Path>>(&mut self, path: P) -> Result<(), SaveError> {
+        let json = serde_json::to_string(&self.config).context(JsonSnafu)?;
+        if let Some(uesave::Property::Str { value, .. }) =
+            self.save.root.properties.0.get_mut(&self.property)
+        {
+            *value = json;
+        }
+        let mut data = vec![];
+        self.save.write(&mut data).context(UesaveSnafu)?;
+        fs::write(path.as_ref(), data)?;
+        Ok(())
+    }
+}
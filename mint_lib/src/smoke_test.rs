@@ -0,0 +1,61 @@
+//! Checking that the game still starts with the installed mods. mint requests a smoke test by
+//! writing [`SMOKE_TEST_FILE`] next to the game executable before launching the game. Once the
+//! game loaded mint's integration, the hook marks the test as passed in the same file and exits
+//! the game.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use fs_err as fs;
+
+pub const SMOKE_TEST_FILE: &str = "mint_smoke_test";
+const PASSED: &str = "passed";
+
+fn unix_time_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+fn smoke_test_path(bin_dir: &Path) -> PathBuf {
+    bin_dir.join(SMOKE_TEST_FILE)
+}
+
+/// Request a smoke test of the next launch of the game in `bin_dir`, which has to pass within
+/// `timeout`. The request expires afterwards, so one left behind e.g. by mint crashing does not
+/// affect later launches.
+pub fn request(bin_dir: &Path, timeout: Duration) -> std::io::Result<()> {
+    let deadline = unix_time_now() + timeout.as_secs();
+    fs::write(smoke_test_path(bin_dir), deadline.to_string())
+}
+
+/// Time left to pass the requested smoke test, unless none was requested or it expired or passed.
+pub fn pending(bin_dir: &Path) -> Option<Duration> {
+    let deadline = fs::read_to_string(smoke_test_path(bin_dir))
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    deadline
+        .checked_sub(unix_time_now())
+        .filter(|secs| *secs > 0)
+        .map(Duration::from_secs)
+}
+
+/// Mark the requested smoke test as passed.
+pub fn pass(bin_dir: &Path) -> std::io::Result<()> {
+    fs::write(smoke_test_path(bin_dir), PASSED)
+}
+
+pub fn passed(bin_dir: &Path) -> bool {
+    fs::read_to_string(smoke_test_path(bin_dir)).is_ok_and(|s| s.trim() == PASSED)
+}
+
+/// Remove the request or result of a smoke test.
+pub fn clear(bin_dir: &Path) -> std::io::Result<()> {
+    match fs::remove_file(smoke_test_path(bin_dir)) {
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        res => res,
+    }
+}
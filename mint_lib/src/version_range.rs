@@ -0,0 +1,232 @@
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Constraint on the version of a mod, e.g. `>=1.2` or `>=1.2, <2`.
+///
+/// Mod versions are free-form strings, so they are compared component-wise on their leading
+/// numeric parts (`v1.2.3-beta` is treated as `1.2.3`) and missing components count as zero.
+/// The bounds of a range have to be plain versions like `1.2` or `v1.2`, so a range is not
+/// confused with other text after an `@`, see
+/// [`ModSpecification::split_version_range`](crate::mod_info::ModSpecification::split_version_range).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionRange {
+    comparators: Vec<(Op, Vec<u64>)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Eq,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+impl Op {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Op::Eq => "=",
+            Op::Gt => ">",
+            Op::Ge => ">=",
+            Op::Lt => "<",
+            Op::Le => "<=",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidVersionRange;
+
+fn parse_version(version: &str) -> Option<Vec<u64>> {
+    let version = version.trim();
+    let version = version
+        .strip_prefix(['v', 'V'])
+        .unwrap_or(version)
+        .split(|c: char| !c.is_ascii_digit() && c != '.')
+        .next()?;
+    let components = version
+        .trim_end_matches('.')
+        .split('.')
+        .map(|c| c.parse().ok())
+        .collect::<Option<Vec<u64>>>()?;
+    Some(components)
+}
+
+fn compare_versions(a: &[u64], b: &[u64]) -> Ordering {
+    (0..a.len().max(b.len()))
+        .map(|i| a.get(i).unwrap_or(&0).cmp(b.get(i).unwrap_or(&0)))
+        .find(|o| o.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+impl VersionRange {
+    pub fn matches(&self, version: &str) -> bool {
+        let Some(version) = parse_version(version) else {
+            return false;
+        };
+        self.comparators.iter().all(|(op, bound)| {
+            let ord = compare_versions(&version, bound);
+            match op {
+                Op::Eq => ord.is_eq(),
+                Op::Gt => ord.is_gt(),
+                Op::Ge => ord.is_ge(),
+                Op::Lt => ord.is_lt(),
+                Op::Le => ord.is_le(),
+            }
+        })
+    }
+
+    /// Pick the highest version satisfying the range from `(item, version)` candidates.
+    pub fn select<'a, T>(&self, candidates: impl IntoIterator<Item = (T, &'a str)>) -> Option<T> {
+        candidates
+            .into_iter()
+            .filter(|(_, version)| self.matches(version))
+            .filter_map(|(item, version)| Some((item, parse_version(version)?)))
+            .max_by(|(_, a), (_, b)| compare_versions(a, b))
+            .map(|(item, _)| item)
+    }
+}
+
+impl FromStr for VersionRange {
+    type Err = InvalidVersionRange;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let comparators = s
+            .split(',')
+            .map(|c| {
+                let c = c.trim();
+                let (op, version) = if let Some(v) = c.strip_prefix(">=") {
+                    (Op::Ge, v)
+                } else if let Some(v) = c.strip_prefix("<=") {
+                    (Op::Le, v)
+                } else if let Some(v) = c.strip_prefix('>') {
+                    (Op::Gt, v)
+                } else if let Some(v) = c.strip_prefix('<') {
+                    (Op::Lt, v)
+                } else if let Some(v) = c.strip_prefix('=') {
+                    (Op::Eq, v)
+                } else {
+                    (Op::Eq, c)
+                };
+                let version = version.trim();
+                let plain = version.strip_prefix(['v', 'V']).unwrap_or(version);
+                let is_plain = plain
+                    .split('.')
+                    .all(|c| !c.is_empty() && c.bytes().all(|b| b.is_ascii_digit()));
+                if !is_plain {
+                    return None;
+                }
+                Some((op, parse_version(version)?))
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or(InvalidVersionRange)?;
+        Ok(Self { comparators })
+    }
+}
+
+impl Display for VersionRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, (op, version)) in self.comparators.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", op.as_str())?;
+            for (j, c) in version.iter().enumerate() {
+                if j != 0 {
+                    write!(f, ".")?;
+                }
+                write!(f, "{c}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mod_info::ModSpecification;
+
+    fn range(s: &str) -> VersionRange {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_inclusive_bounds() {
+        let r = range(">=1.2, <=2.0");
+        assert!(!r.matches("1.1.9"));
+        assert!(r.matches("1.2"));
+        assert!(r.matches("1.2.0"));
+        assert!(r.matches("v1.5.3-beta"));
+        assert!(r.matches("2"));
+        assert!(!r.matches("2.0.1"));
+    }
+
+    #[test]
+    fn test_exclusive_bounds() {
+        let r = range(">1.2, <2");
+        assert!(!r.matches("1.2"));
+        assert!(r.matches("1.2.1"));
+        assert!(r.matches("1.99"));
+        assert!(!r.matches("2.0"));
+    }
+
+    #[test]
+    fn test_exact_and_invalid() {
+        assert!(range("1.3").matches("1.3.0"));
+        assert!(range("=1.3").matches("v1.3"));
+        assert!(!range("1.3").matches("1.3.1"));
+        assert!(!range(">=1").matches("not a version"));
+        assert_eq!(">=x".parse::<VersionRange>(), Err(InvalidVersionRange));
+        assert_eq!("".parse::<VersionRange>(), Err(InvalidVersionRange));
+        assert_eq!("2x.zip".parse::<VersionRange>(), Err(InvalidVersionRange));
+        assert_eq!(
+            ">=1.2-beta".parse::<VersionRange>(),
+            Err(InvalidVersionRange)
+        );
+        assert_eq!(range(" >=1.2 ,<2").to_string(), ">=1.2, <2");
+    }
+
+    #[test]
+    fn test_select() {
+        let candidates = [("a", "1.0"), ("b", "1.4"), ("c", "1.10"), ("d", "2.1")];
+        assert_eq!(range(">=1.2, <2").select(candidates), Some("c"));
+        assert_eq!(range("<1.4").select(candidates), Some("a"));
+        assert_eq!(range(">=3").select(candidates), None);
+    }
+
+    #[test]
+    fn test_split_version_range() {
+        let split = |url: &str| {
+            let spec = ModSpecification::new(url.to_string());
+            let (spec, range) = spec.split_version_range();
+            (spec.url, range.map(str::to_string))
+        };
+        let ranged = |url: &str, range: &str| (url.to_string(), Some(range.to_string()));
+        let none = |url: &str| (url.to_string(), None);
+
+        assert_eq!(
+            split("https://mod.io/g/drg/m/example@>=1.2, <2"),
+            ranged("https://mod.io/g/drg/m/example", ">=1.2, <2")
+        );
+        assert_eq!(
+            split("C:\\mods\\foo.pak@1"),
+            ranged("C:\\mods\\foo.pak", "1")
+        );
+        assert_eq!(
+            split("mods/foo.pak@v2|repack"),
+            ranged("mods/foo.pak", "v2")
+        );
+
+        // `@` which is not followed by a range is part of the URL
+        for url in [
+            "https://host/file@2x.zip",
+            "C:\\mods\\foo@1.pak",
+            "https://user@1.2.3.4/mod.zip",
+            "https://mod.io/g/drg/m/example@latest",
+        ] {
+            assert_eq!(split(url), none(url));
+        }
+    }
+}
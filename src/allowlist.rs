@@ -0,0 +1,96 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use fs_err as fs;
+use snafu::prelude::*;
+
+use crate::providers::{parse_spec, ModInfo, ModSpecification};
+
+#[derive(Debug, Snafu)]
+pub enum AllowlistError {
+    #[snafu(transparent)]
+    IoError { source: std::io::Error },
+}
+
+/// Version independent form of a spec: without a version range and, for mod.io, without the
+/// mod and file IDs.
+fn unversioned(spec: &ModSpecification) -> ModSpecification {
+    let (mut spec, _) = spec.split_version_range();
+    if spec.url.starts_with("https://mod.io/") {
+        if let Some((url, _)) = spec.url.split_once('#') {
+            spec.url = url.to_string();
+        }
+    }
+    spec
+}
+
+/// Mods permitted by a server.
+///
+/// The file lists one mod per line, either as a numeric mod.io mod ID or as a mod URL. Blank lines
+/// and lines starting with `#` are ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Allowlist {
+    modio_ids: BTreeSet<u32>,
+    specs: BTreeSet<ModSpecification>,
+}
+
+impl Allowlist {
+    pub fn parse(content: &str) -> Self {
+        let mut allowlist = Self::default();
+        for line in content.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Ok(id) = line.parse() {
+                allowlist.modio_ids.insert(id);
+            } else {
+                allowlist.specs.insert(unversioned(&parse_spec(line)));
+            }
+        }
+        allowlist
+    }
+
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<Self, AllowlistError> {
+        Ok(Self::parse(&fs::read_to_string(path)?))
+    }
+
+    /// Whether a mod is permitted. `modio_id` is only known once the mod has been resolved.
+    pub fn allows(&self, spec: &ModSpecification, modio_id: Option<u32>) -> bool {
+        modio_id.is_some_and(|id| self.modio_ids.contains(&id))
+            || self.specs.contains(&unversioned(spec))
+    }
+
+    pub fn allows_mod(&self, info: &ModInfo) -> bool {
+        self.allows(&info.spec, info.modio_id)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn spec(url: &str) -> ModSpecification {
+        ModSpecification::new(url.to_string())
+    }
+
+    #[test]
+    fn test_allowlist() {
+        let allowlist = Allowlist::parse(
+            "# server mods\n\
+             1234\n\
+             \n\
+             https://mod.io/g/drg/m/sandbox-utilities\n\
+             https://example.com/mod.zip\n",
+        );
+        assert!(allowlist.allows(&spec("https://mod.io/g/drg/m/other"), Some(1234)));
+        assert!(!allowlist.allows(&spec("https://mod.io/g/drg/m/other"), Some(4321)));
+        assert!(allowlist.allows(&spec("https://mod.io/g/drg/m/sandbox-utilities"), None));
+        assert!(allowlist.allows(
+            &spec("https://mod.io/g/drg/m/sandbox-utilities#5678/9012"),
+            None
+        ));
+        assert!(allowlist.allows(&spec("https://example.com/mod.zip"), None));
+        assert!(!allowlist.allows(&spec("https://mod.io/g/drg/m/sandbox"), None));
+        assert!(!Allowlist::parse("").allows(&spec("https://example.com/mod.zip"), None));
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::BTreeMap;
+
+use indexmap::IndexSet;
+
+use crate::providers::ModSpecification;
+
+/// Directory of the files integrating a set of mods writes to the mods pak, each annotated with
+/// the mod it is taken from. Built by [`plan`](crate::integrate::plan).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AssetTree {
+    pub dirs: BTreeMap<String, AssetTree>,
+    pub files: BTreeMap<String, AssetLeaf>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetLeaf {
+    /// The mod the file is taken from.
+    pub winner: ModSpecification,
+    /// Mods which also provide the file, in integration order.
+    pub overridden: Vec<ModSpecification>,
+}
+
+impl AssetTree {
+    /// Build the tree from normalized paths and the mods providing them in integration order.
+    /// Files integration never writes, such as shader bytecode, are left out.
+    pub fn new(assets: BTreeMap<String, IndexSet<ModSpecification>>) -> Self {
+        let mut root = Self::default();
+        for (path, mods) in assets {
+            let mut mods = mods.into_iter();
+            let Some(winner) = mods.next() else {
+                continue;
+            };
+            let mut components = path.split('/').collect::<Vec<_>>();
+            let name = components.pop().unwrap();
+            if is_stripped(name) {
+                continue;
+            }
+            let dir = components.into_iter().fold(&mut root, |dir, component| {
+                dir.dirs.entry(component.to_string()).or_default()
+            });
+            dir.files.insert(
+                name.to_string(),
+                AssetLeaf {
+                    winner,
+                    overridden: mods.collect(),
+                },
+            );
+        }
+        root
+    }
+
+    /// Number of files in the tree, including subdirectories.
+    pub fn file_count(&self) -> usize {
+        self.files.len() + self.dirs.values().map(Self::file_count).sum::<usize>()
+    }
+
+    /// Number of files in the tree, including subdirectories, which more than one mod provides.
+    pub fn override_count(&self) -> usize {
+        self.files
+            .values()
+            .filter(|leaf| !leaf.overridden.is_empty())
+            .count()
+            + self.dirs.values().map(Self::override_count).sum::<usize>()
+    }
+
+    /// The tree with only the files whose path contains `filter` and, if `overrides_only`, which
+    /// more than one mod provides. Directories left empty are removed.
+    pub fn filtered(&self, filter: &str, overrides_only: bool) -> Self {
+        let filter = filter.to_ascii_lowercase();
+        self.filtered_in("", &filter, overrides_only)
+    }
+
+    fn filtered_in(&self, prefix: &str, filter: &str, overrides_only: bool) -> Self {
+        let dirs = self
+            .dirs
+            .iter()
+            .map(|(name, dir)| {
+                let path = format!("{prefix}{name}/");
+                (name.clone(), dir.filtered_in(&path, filter, overrides_only))
+            })
+            .filter(|(_, dir)| !dir.is_empty())
+            .collect();
+        let files = self
+            .files
+            .iter()
+            .filter(|(name, leaf)| {
+                (!overrides_only || !leaf.overridden.is_empty())
+                    && format!("{prefix}{name}").contains(filter)
+            })
+            .map(|(name, leaf)| (name.clone(), leaf.clone()))
+            .collect();
+        Self { dirs, files }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.dirs.is_empty() && self.files.is_empty()
+    }
+
+    /// Every file in the tree with its full path, sorted by path.
+    pub fn paths(&self) -> Vec<(String, &AssetLeaf)> {
+        let mut paths = vec![];
+        self.paths_in("", &mut paths);
+        paths.sort_by(|a, b| a.0.cmp(&b.0));
+        paths
+    }
+
+    fn paths_in<'a>(&'a self, prefix: &str, paths: &mut Vec<(String, &'a AssetLeaf)>) {
+        for (name, dir) in &self.dirs {
+            dir.paths_in(&format!("{prefix}{name}/"), paths);
+        }
+        for (name, leaf) in &self.files {
+            paths.push((format!("{prefix}{name}"), leaf));
+        }
+    }
+}
+
+/// Whether integration leaves the file with lowercase `name` out of the mods pak.
+fn is_stripped(name: &str) -> bool {
+    name == "assetregistry.bin" || name.ends_with(".ushaderbytecode")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn spec(name: &str) -> ModSpecification {
+        ModSpecification::new(format!("https://example.com/{name}"))
+    }
+
+    #[test]
+    fn test_asset_tree() {
+        let assets = BTreeMap::from([
+            (
+                "fsd/content/a.uasset".to_string(),
+                IndexSet::from([spec("A"), spec("B")]),
+            ),
+            (
+                "fsd/content/ui/b.uasset".to_string(),
+                IndexSet::from([spec("B")]),
+            ),
+            (
+                "fsd/content/c.ushaderbytecode".to_string(),
+                IndexSet::from([spec("A")]),
+            ),
+        ]);
+        let tree = AssetTree::new(assets);
+        assert_eq!(tree.file_count(), 2);
+        assert_eq!(tree.override_count(), 1);
+
+        let content = &tree.dirs["fsd"].dirs["content"];
+        assert_eq!(
+            content.files["a.uasset"],
+            AssetLeaf {
+                winner: spec("A"),
+                overridden: vec![spec("B")],
+            }
+        );
+        assert_eq!(content.dirs["ui"].files["b.uasset"].winner, spec("B"));
+
+        let overrides = tree.filtered("", true);
+        assert_eq!(overrides.file_count(), 1);
+        assert!(!overrides.dirs["fsd"].dirs["content"]
+            .dirs
+            .contains_key("ui"));
+        assert_eq!(tree.filtered("UI/", false).file_count(), 1);
+        assert!(tree.filtered("missing", false).is_empty());
+
+        let paths = tree
+            .paths()
+            .into_iter()
+            .map(|(path, leaf)| (path, leaf.winner.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            [
+                ("fsd/content/a.uasset".to_string(), spec("A")),
+                ("fsd/content/ui/b.uasset".to_string(), spec("B")),
+            ]
+        );
+    }
+}
@@ -0,0 +1,118 @@
+use std::path::Path;
+
+use fs_err as fs;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::providers::ModSpecification;
+
+/// Name of the file in the data directory an unfinished bisection is kept in.
+pub const BISECT_FILE: &str = "bisect.json";
+
+/// Search for the mod of a profile which makes the game crash by installing half of the remaining
+/// suspects at a time and narrowing them down to the half which still crashes. Assumes a single
+/// mod causes the crash on its own.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Bisection {
+    pub profile: String,
+    /// Mods which may cause the crash, in integration order.
+    pub suspects: Vec<ModSpecification>,
+    /// Steps answered so far.
+    pub steps: u32,
+}
+
+impl Bisection {
+    pub fn new(profile: String, mods: Vec<ModSpecification>) -> Self {
+        Self {
+            profile,
+            suspects: mods,
+            steps: 0,
+        }
+    }
+
+    /// The mod causing the crash once only one suspect is left.
+    pub fn culprit(&self) -> Option<&ModSpecification> {
+        match self.suspects.as_slice() {
+            [culprit] => Some(culprit),
+            _ => None,
+        }
+    }
+
+    /// The half of the suspects to install next.
+    pub fn testing(&self) -> &[ModSpecification] {
+        &self.suspects[..self.suspects.len().div_ceil(2)]
+    }
+
+    /// Narrow the suspects down by whether the game still crashed with [`Self::testing`]
+    /// installed.
+    pub fn record(&mut self, crashed: bool) {
+        let half = self.testing().len();
+        if crashed {
+            self.suspects.truncate(half);
+        } else {
+            self.suspects.drain(..half);
+        }
+        self.steps += 1;
+    }
+
+    /// Steps needed at most until the culprit is found.
+    pub fn remaining_steps(&self) -> u32 {
+        self.suspects.len().next_power_of_two().trailing_zeros()
+    }
+
+    /// Read the unfinished bisection from `data_dir`, if there is one. An unreadable bisection is
+    /// discarded.
+    pub fn read(data_dir: &Path) -> Option<Self> {
+        let path = data_dir.join(BISECT_FILE);
+        let data = fs::read(&path).ok()?;
+        serde_json::from_slice(&data)
+            .inspect_err(|e| warn!("failed to parse {}, starting over: {e}", path.display()))
+            .ok()
+    }
+
+    pub fn write(&self, data_dir: &Path) -> std::io::Result<()> {
+        fs::write(data_dir.join(BISECT_FILE), serde_json::to_vec_pretty(self)?)
+    }
+
+    /// Forget the unfinished bisection in `data_dir`.
+    pub fn remove(data_dir: &Path) -> std::io::Result<()> {
+        match fs::remove_file(data_dir.join(BISECT_FILE)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_bisection() {
+        let spec = |url: &str| ModSpecification::new(url.to_string());
+        let mods = ["a", "b", "c", "d", "e"].map(spec).to_vec();
+
+        let mut bisection = Bisection::new("default".to_string(), mods.clone());
+        assert_eq!(bisection.culprit(), None);
+        assert_eq!(bisection.remaining_steps(), 3);
+        assert_eq!(bisection.testing(), &mods[..3]);
+
+        // c crashes the game
+        bisection.record(true);
+        assert_eq!(bisection.suspects, &mods[..3]);
+        assert_eq!(bisection.testing(), &mods[..2]);
+        bisection.record(false);
+        assert_eq!(bisection.suspects, [spec("c")]);
+        assert_eq!(bisection.culprit(), Some(&spec("c")));
+        assert_eq!(bisection.remaining_steps(), 0);
+        assert_eq!(bisection.steps, 2);
+
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(Bisection::read(dir.path()), None);
+        bisection.write(dir.path()).unwrap();
+        assert_eq!(Bisection::read(dir.path()), Some(bisection));
+        Bisection::remove(dir.path()).unwrap();
+        Bisection::remove(dir.path()).unwrap();
+        assert_eq!(Bisection::read(dir.path()), None);
+    }
+}
@@ -0,0 +1,294 @@
+use std::error::Error;
+use std::fmt::Display;
+
+use crate::providers::{ModInfo, ModSpecification, ModStore, ProviderError, ProviderFactory};
+use crate::state::State;
+use crate::MintError;
+
+/// Why a mod URL is not usable, as far as it can be told apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkProblem {
+    /// The server responded with 404 or 410, or mod.io has no such mod.
+    NotFound,
+    /// The server responded with 401 or 403, e.g. a private or hidden mod.
+    Forbidden,
+    /// Any other unsuccessful HTTP status.
+    Status(u16),
+    /// The server could not be reached or did not respond in time.
+    Network,
+    /// Anything else, e.g. an invalid URL or a URL no provider supports.
+    Other,
+}
+
+impl LinkProblem {
+    fn from_status(status: u16) -> Self {
+        match status {
+            404 | 410 => Self::NotFound,
+            401 | 403 => Self::Forbidden,
+            _ => Self::Status(status),
+        }
+    }
+
+    /// Classify a resolution failure by the first HTTP status or network error in its sources.
+    fn from_error(error: &ProviderError) -> Self {
+        if matches!(error, ProviderError::NoModsForNameId { .. }) {
+            return Self::NotFound;
+        }
+        let mut source: Option<&(dyn Error + 'static)> = Some(error);
+        while let Some(e) = source {
+            if let Some(e) = e.downcast_ref::<reqwest::Error>() {
+                if let Some(status) = e.status() {
+                    return Self::from_status(status.as_u16());
+                }
+                if e.is_connect() || e.is_timeout() || e.is_request() {
+                    return Self::Network;
+                }
+            }
+            if let Some(e) = e.downcast_ref::<modio::Error>() {
+                if let Some(status) = e.status() {
+                    return Self::from_status(status.as_u16());
+                }
+                if e.is_request() {
+                    return Self::Network;
+                }
+            }
+            source = e.source();
+        }
+        Self::Other
+    }
+}
+
+impl Display for LinkProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotFound => write!(f, "not found"),
+            Self::Forbidden => write!(f, "forbidden"),
+            Self::Status(status) => write!(f, "HTTP {status}"),
+            Self::Network => write!(f, "network error"),
+            Self::Other => write!(f, "error"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct LinkReport {
+    pub spec: ModSpecification,
+    /// Name of the mod if it resolved, otherwise what is wrong with it and the full error.
+    pub result: Result<String, (LinkProblem, String)>,
+}
+
+/// Read a list of mod URLs with one per line, skipping blank lines and lines starting with `#`.
+pub fn parse_links(text: &str) -> Vec<ModSpecification> {
+    text.lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(crate::providers::parse_spec)
+        .collect()
+}
+
+/// Check whether every one of `specs` resolves, without following dependencies. Metadata is kept
+/// in memory so the persistent cache is left untouched, and mods hosted on plain web servers are
+/// requested since resolving them does not contact the server.
+pub async fn check_links_with_provider_init<F>(
+    state: &mut State,
+    specs: &[ModSpecification],
+    init: F,
+) -> Result<Vec<LinkReport>, MintError>
+where
+    F: Fn(&mut State, String, &ProviderFactory) -> Result<(), MintError>,
+{
+    for spec in specs {
+        if let Err(ProviderError::NoProvider { url, factory }) = state.store.get_provider(&spec.url)
+        {
+            init(state, url, factory)?;
+        }
+    }
+    let store = ModStore::new_in_memory(&state.dirs.cache_dir, &state.config.provider_parameters)?;
+    Ok(check_links(&store, specs).await)
+}
+
+pub async fn check_links(store: &ModStore, specs: &[ModSpecification]) -> Vec<LinkReport> {
+    use futures::stream::{self, StreamExt};
+
+    let client = reqwest::Client::new();
+    stream::iter(specs)
+        .map(|spec| check_spec(store, &client, spec, false))
+        .buffered(5)
+        .collect()
+        .await
+}
+
+/// Check whether the mods of a profile are still available, e.g. to replace mods which were
+/// removed from mod.io before they break an install. Metadata of all mods is fetched again as a
+/// batch, which updates the cache, and only the mods which fail are resolved one by one to tell
+/// why. Mods hosted on plain web servers are requested like in [`check_links`].
+pub async fn check_availability(
+    store: &ModStore,
+    specs: &[ModSpecification],
+) -> Result<Vec<LinkReport>, ProviderError> {
+    use futures::stream::{self, StreamExt};
+
+    let (resolved, _, error) = store.resolve_mods_lenient(specs, true).await;
+    if let Some(error @ ProviderError::NoProvider { .. }) = error {
+        return Err(error);
+    }
+
+    let client = reqwest::Client::new();
+    Ok(stream::iter(specs)
+        .map(|spec| {
+            let client = &client;
+            let info = resolved.get(spec);
+            async move {
+                match info {
+                    Some(info) => LinkReport {
+                        spec: spec.clone(),
+                        result: probe_resolved(client, info).await,
+                    },
+                    None => check_spec(store, client, spec, true).await,
+                }
+            }
+        })
+        .buffered(5)
+        .collect()
+        .await)
+}
+
+pub async fn check_availability_with_provider_init<F>(
+    state: &mut State,
+    specs: &[ModSpecification],
+    init: F,
+) -> Result<Vec<LinkReport>, MintError>
+where
+    F: Fn(&mut State, String, &ProviderFactory) -> Result<(), MintError>,
+{
+    loop {
+        match check_availability(&state.store, specs).await {
+            Ok(reports) => return Ok(reports),
+            Err(ProviderError::NoProvider { ref url, factory }) => {
+                init(state, url.clone(), factory)?
+            }
+            Err(e) => Err(e)?,
+        }
+    }
+}
+
+async fn check_spec(
+    store: &ModStore,
+    client: &reqwest::Client,
+    spec: &ModSpecification,
+    update: bool,
+) -> LinkReport {
+    let result = match store.resolve_mod(spec.clone(), update).await {
+        Ok((_, info)) => probe_resolved(client, &info).await,
+        Err(e) => Err((LinkProblem::from_error(&e), e.to_string())),
+    };
+    LinkReport {
+        spec: spec.clone(),
+        result,
+    }
+}
+
+/// Name of the resolved mod, after making sure the server still has it if it is hosted on a plain
+/// web server, since resolving those does not contact the server.
+async fn probe_resolved(
+    client: &reqwest::Client,
+    info: &ModInfo,
+) -> Result<String, (LinkProblem, String)> {
+    if matches!(info.provider, "http" | "http-index") {
+        probe(client, &info.resolution.url.0).await?;
+    }
+    Ok(info.name.clone())
+}
+
+/// Request the headers of `url`, falling back to a GET for servers which do not allow HEAD.
+async fn probe(client: &reqwest::Client, url: &str) -> Result<(), (LinkProblem, String)> {
+    let error = |e: reqwest::Error| {
+        let problem = match e.status() {
+            Some(status) => LinkProblem::from_status(status.as_u16()),
+            None => LinkProblem::Network,
+        };
+        (problem, format!("request for <{url}> failed: {e}"))
+    };
+    let mut response = client.head(url).send().await.map_err(error)?;
+    if matches!(response.status().as_u16(), 405 | 501) {
+        // the body is not read, dropping the response closes the connection
+        response = client.get(url).send().await.map_err(error)?;
+    }
+    response.error_for_status().map_err(error)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use fs_err as fs;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_links() {
+        let links = parse_links(
+            "# community mod list\n\
+             https://mod.io/g/drg/m/example\n\
+             \n\
+             \x20 https://example.com/mods/a.pak  \n\
+             #https://example.com/disabled.pak\n",
+        );
+        assert_eq!(
+            links,
+            [
+                ModSpecification::new("https://mod.io/g/drg/m/example".to_string()),
+                ModSpecification::new("https://example.com/mods/a.pak".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_from_status() {
+        assert_eq!(LinkProblem::from_status(404), LinkProblem::NotFound);
+        assert_eq!(LinkProblem::from_status(410), LinkProblem::NotFound);
+        assert_eq!(LinkProblem::from_status(403), LinkProblem::Forbidden);
+        assert_eq!(LinkProblem::from_status(500), LinkProblem::Status(500));
+        assert_eq!(LinkProblem::Status(502).to_string(), "HTTP 502");
+    }
+
+    #[tokio::test]
+    async fn test_check_local_links() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = dir.path().join("cache");
+        fs::create_dir(&cache).unwrap();
+        let pak = dir.path().join("a.pak");
+        fs::write(&pak, b"").unwrap();
+        let missing = dir.path().join("missing.pak");
+
+        let store = ModStore::new_in_memory(&cache, &HashMap::new()).unwrap();
+        let specs = [pak, missing].map(|p| ModSpecification::new(p.display().to_string()));
+        let reports = check_links(&store, &specs).await;
+
+        assert_eq!(reports[0].result, Ok("a.pak".to_string()));
+        assert!(matches!(reports[1].result, Err((LinkProblem::Other, _))));
+        // metadata is not persisted
+        assert!(!cache.join("cache.json").exists());
+    }
+
+    #[tokio::test]
+    async fn test_check_availability() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.pak");
+        let b = dir.path().join("b.pak");
+        fs::write(&a, b"").unwrap();
+        fs::write(&b, b"").unwrap();
+
+        let store = ModStore::new_in_memory(dir.path(), &HashMap::new()).unwrap();
+        let specs = [&a, &b].map(|p| ModSpecification::new(p.display().to_string()));
+        // b was removed after it was added
+        store.resolve_mods(&specs, false).await.unwrap();
+        fs::remove_file(&b).unwrap();
+
+        let reports = check_availability(&store, &specs).await.unwrap();
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0].result, Ok("a.pak".to_string()));
+        assert!(matches!(reports[1].result, Err((LinkProblem::Other, _))));
+    }
+}
@@ -0,0 +1,279 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Write as _;
+
+use serde::Serialize;
+
+use crate::providers::{ModInfo, ModSpecification};
+
+/// Dependency graph of resolved mods. Edges point from a mod to its suggested dependencies.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct DepGraph {
+    pub nodes: Vec<DepNode>,
+    pub edges: Vec<DepEdge>,
+    /// Groups of mods which depend on each other in a cycle, as indices into `nodes`.
+    pub cycles: Vec<Vec<usize>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DepNode {
+    pub url: String,
+    pub name: String,
+    /// Whether the mod was requested directly rather than only pulled in as a dependency.
+    pub requested: bool,
+    /// Whether the mod suggests being required by every player.
+    pub required: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DepEdge {
+    pub from: usize,
+    pub to: usize,
+    pub in_cycle: bool,
+}
+
+impl DepGraph {
+    /// Build the graph from the result of resolving `requested` and their dependencies.
+    pub fn new(requested: &[ModSpecification], mods: &HashMap<ModSpecification, ModInfo>) -> Self {
+        let specs = mods.keys().collect::<BTreeSet<_>>();
+        let index = specs
+            .iter()
+            .enumerate()
+            .map(|(i, spec)| (*spec, i))
+            .collect::<BTreeMap<_, _>>();
+
+        let nodes = specs
+            .iter()
+            .map(|spec| {
+                let info = &mods[*spec];
+                DepNode {
+                    url: spec.url.clone(),
+                    name: info.name.clone(),
+                    requested: requested.contains(spec),
+                    required: info.suggested_require,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // dependencies may be declared with a different version than the mod they are satisfied
+        // by, a requested mod is preferred over a dependency resolved on its own
+        let mut adjacency = vec![BTreeSet::new(); nodes.len()];
+        for (spec, from) in &index {
+            for dep in &mods[*spec].suggested_dependencies {
+                let satisfying = index
+                    .iter()
+                    .filter(|(s, _)| s.satisfies_dependency(dep))
+                    .collect::<Vec<_>>();
+                let requested_satisfying = satisfying
+                    .iter()
+                    .filter(|(s, _)| requested.contains(s))
+                    .map(|(_, to)| **to)
+                    .collect::<Vec<_>>();
+                if requested_satisfying.is_empty() {
+                    adjacency[*from].extend(satisfying.iter().map(|(_, to)| **to));
+                } else {
+                    adjacency[*from].extend(requested_satisfying);
+                }
+            }
+        }
+
+        let cycles = find_cycles(&adjacency);
+        let cycle_of = cycles
+            .iter()
+            .enumerate()
+            .flat_map(|(c, members)| members.iter().map(move |n| (*n, c)))
+            .collect::<HashMap<_, _>>();
+        let edges = adjacency
+            .iter()
+            .enumerate()
+            .flat_map(|(from, tos)| tos.iter().map(move |to| (from, *to)))
+            .map(|(from, to)| DepEdge {
+                from,
+                to,
+                in_cycle: cycle_of
+                    .get(&from)
+                    .is_some_and(|c| cycle_of.get(&to) == Some(c)),
+            })
+            .collect();
+
+        Self {
+            nodes,
+            edges,
+            cycles,
+        }
+    }
+
+    /// Graphviz representation. Requested mods are drawn bold and cycles red.
+    pub fn to_dot(&self) -> String {
+        let quote = |s: &str| format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""));
+        let mut dot = "digraph dependencies {\n".to_string();
+        for (i, node) in self.nodes.iter().enumerate() {
+            let mut attrs = vec![format!("label={}", quote(&node.name))];
+            attrs.push(format!("tooltip={}", quote(&node.url)));
+            if node.requested {
+                attrs.push("style=bold".to_string());
+            }
+            if node.required {
+                attrs.push("shape=box".to_string());
+            }
+            writeln!(dot, "  n{i} [{}];", attrs.join(", ")).unwrap();
+        }
+        for edge in &self.edges {
+            let attrs = if edge.in_cycle { " [color=red]" } else { "" };
+            writeln!(dot, "  n{} -> n{}{attrs};", edge.from, edge.to).unwrap();
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// Strongly connected components with more than one node or a self loop, found with Tarjan's
+/// algorithm.
+fn find_cycles(adjacency: &[BTreeSet<usize>]) -> Vec<Vec<usize>> {
+    struct Tarjan<'a> {
+        adjacency: &'a [BTreeSet<usize>],
+        index: Vec<Option<usize>>,
+        low: Vec<usize>,
+        on_stack: Vec<bool>,
+        stack: Vec<usize>,
+        next: usize,
+        cycles: Vec<Vec<usize>>,
+    }
+
+    impl Tarjan<'_> {
+        fn visit(&mut self, v: usize) {
+            self.index[v] = Some(self.next);
+            self.low[v] = self.next;
+            self.next += 1;
+            self.stack.push(v);
+            self.on_stack[v] = true;
+
+            for &w in self.adjacency[v].iter() {
+                match self.index[w] {
+                    None => {
+                        self.visit(w);
+                        self.low[v] = self.low[v].min(self.low[w]);
+                    }
+                    Some(index) if self.on_stack[w] => self.low[v] = self.low[v].min(index),
+                    Some(_) => {}
+                }
+            }
+
+            if Some(self.low[v]) == self.index[v] {
+                let mut component = vec![];
+                while let Some(w) = self.stack.pop() {
+                    self.on_stack[w] = false;
+                    component.push(w);
+                    if w == v {
+                        break;
+                    }
+                }
+                if component.len() > 1 || self.adjacency[v].contains(&v) {
+                    component.sort();
+                    self.cycles.push(component);
+                }
+            }
+        }
+    }
+
+    let mut tarjan = Tarjan {
+        adjacency,
+        index: vec![None; adjacency.len()],
+        low: vec![0; adjacency.len()],
+        on_stack: vec![false; adjacency.len()],
+        stack: vec![],
+        next: 0,
+        cycles: vec![],
+    };
+    for v in 0..adjacency.len() {
+        if tarjan.index[v].is_none() {
+            tarjan.visit(v);
+        }
+    }
+    tarjan.cycles.sort();
+    tarjan.cycles
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::state::mod_data_tests::mod_info;
+
+    fn info(url: &str, deps: &[&str]) -> (ModSpecification, ModInfo) {
+        (
+            ModSpecification::new(url.to_string()),
+            ModInfo {
+                name: url.to_uppercase(),
+                suggested_dependencies: deps
+                    .iter()
+                    .map(|d| ModSpecification::new(d.to_string()))
+                    .collect(),
+                ..mod_info(url, url)
+            },
+        )
+    }
+
+    #[test]
+    fn test_dep_graph() {
+        // a -> b -> c -> b, d -> d, e has a missing dependency
+        let mods = [
+            info("a", &["b"]),
+            info("b", &["c"]),
+            info("c", &["b"]),
+            info("d", &["d"]),
+            info("e", &["missing"]),
+        ]
+        .into_iter()
+        .collect();
+        let graph = DepGraph::new(&[ModSpecification::new("a".to_string())], &mods);
+
+        assert_eq!(graph.nodes.len(), 5);
+        assert!(graph.nodes[0].requested);
+        assert!(!graph.nodes[1].requested);
+        assert_eq!(graph.cycles, vec![vec![1, 2], vec![3]]);
+        assert_eq!(
+            graph
+                .edges
+                .iter()
+                .map(|e| (e.from, e.to, e.in_cycle))
+                .collect::<Vec<_>>(),
+            vec![(0, 1, false), (1, 2, true), (2, 1, true), (3, 3, true)]
+        );
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph dependencies {\n"));
+        assert!(dot.contains("  n0 [label=\"A\", tooltip=\"a\", style=bold];\n"));
+        assert!(dot.contains("  n1 -> n2 [color=red];\n"));
+        assert!(dot.contains("  n0 -> n1;\n"));
+    }
+
+    #[test]
+    fn test_dep_graph_versions() {
+        // a depends on a pinned b which was also resolved on its own, c on any version of d
+        let mods = [
+            info("a", &["b@1"]),
+            info("b", &[]),
+            info("b@1", &[]),
+            info("c", &["d"]),
+            info("d@>=2", &[]),
+        ]
+        .into_iter()
+        .collect();
+        let requested = ["a", "b", "c", "d@>=2"].map(|url| ModSpecification::new(url.to_string()));
+        let graph = DepGraph::new(&requested, &mods);
+
+        let urls = graph
+            .nodes
+            .iter()
+            .map(|n| n.url.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(urls, ["a", "b", "b@1", "c", "d@>=2"]);
+        assert_eq!(
+            graph
+                .edges
+                .iter()
+                .map(|e| (e.from, e.to))
+                .collect::<Vec<_>>(),
+            vec![(0, 1), (3, 4)]
+        );
+    }
+}
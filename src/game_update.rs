@@ -0,0 +1,124 @@
+use std::io::BufReader;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+use fs_err as fs;
+use mint_lib::DRGInstallation;
+use serde::{Deserialize, Serialize};
+
+/// The game pak as it was when mods were last installed. The mods pak contains patched copies of
+/// game assets and an asset registry taken from the game pak, so after a game update it is stale
+/// and mods may break until they are installed again.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PakBaseline {
+    pub size: u64,
+    /// Modification time in seconds since the Unix epoch.
+    pub modified: u64,
+    /// SHA-256 of the pak. Hashing several GB takes a while so it is only recorded if enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+}
+
+impl PakBaseline {
+    pub fn record(path: &Path, hash: bool) -> std::io::Result<Self> {
+        let (size, modified) = size_and_modified(path)?;
+        Ok(Self {
+            size,
+            modified,
+            sha256: if hash { Some(hash_file(path)?) } else { None },
+        })
+    }
+
+    /// Whether the pak at `path` differs from the baseline. Only the size and modification time
+    /// are compared, unless a hash was recorded. It then decides whether a pak which was only
+    /// touched, e.g. by Steam verifying the game files, changed.
+    pub fn changed(&self, path: &Path) -> std::io::Result<bool> {
+        let (size, modified) = size_and_modified(path)?;
+        if size != self.size {
+            return Ok(true);
+        }
+        if modified == self.modified {
+            return Ok(false);
+        }
+        match &self.sha256 {
+            Some(sha256) => Ok(hash_file(path)? != *sha256),
+            None => Ok(true),
+        }
+    }
+}
+
+/// Whether the game of `pak_path` was updated since mods were installed with `baseline`. Without
+/// installed mods or a baseline there is nothing to compare against.
+pub fn game_updated(pak_path: &Path, baseline: Option<&PakBaseline>) -> std::io::Result<bool> {
+    let Some(baseline) = baseline else {
+        return Ok(false);
+    };
+    if !DRGInstallation::from_pak_path(pak_path).is_ok_and(|i| i.mods_pak().exists()) {
+        return Ok(false);
+    }
+    baseline.changed(pak_path)
+}
+
+fn size_and_modified(path: &Path) -> std::io::Result<(u64, u64)> {
+    let metadata = fs::metadata(path)?;
+    let modified = metadata
+        .modified()?
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    Ok((metadata.len(), modified))
+}
+
+fn hash_file(path: &Path) -> std::io::Result<String> {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut BufReader::new(fs::File::open(path)?), &mut hasher)?;
+    Ok(hex::encode(hasher.finalize()))
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, SystemTime};
+
+    use super::*;
+
+    fn touch(path: &Path, secs: u64) {
+        std::fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_pak_baseline() {
+        let dir = tempfile::tempdir().unwrap();
+        let pak = dir.path().join("FSD-WindowsNoEditor.pak");
+        fs::write(&pak, b"game").unwrap();
+        touch(&pak, 1000);
+
+        let baseline = PakBaseline::record(&pak, false).unwrap();
+        assert_eq!(baseline.modified, 1000);
+        assert!(!baseline.changed(&pak).unwrap());
+
+        // without a hash any change of the modification time counts
+        touch(&pak, 2000);
+        assert!(baseline.changed(&pak).unwrap());
+
+        let hashed = PakBaseline::record(&pak, true).unwrap();
+        touch(&pak, 3000);
+        assert!(!hashed.changed(&pak).unwrap());
+        fs::write(&pak, b"tame").unwrap();
+        touch(&pak, 3000);
+        assert!(hashed.changed(&pak).unwrap());
+
+        fs::write(&pak, b"updated game").unwrap();
+        assert!(baseline.changed(&pak).unwrap());
+        assert!(hashed.changed(&pak).unwrap());
+
+        fs::remove_file(&pak).unwrap();
+        assert!(baseline.changed(&pak).is_err());
+    }
+}
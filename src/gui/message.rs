@@ -1,6 +1,6 @@
 use std::collections::BTreeSet;
 use std::ops::DerefMut;
-use std::time::SystemTime;
+use std::time::{Instant, SystemTime};
 use std::{collections::HashMap, sync::Arc};
 
 use snafu::prelude::*;
@@ -13,12 +13,18 @@ use tracing::*;
 use super::SelfUpdateProgress;
 use super::{
     request_counter::{RequestCounter, RequestID},
-    App, SpecFetchProgress, WindowProviderParameters,
+    App, DiskUsage, FailedBatch, SpecFetchProgress, WindowOutputLocked, WindowProviderParameters,
 };
+use crate::asset_tree::AssetTree;
+use crate::check_links::{check_availability, LinkReport};
+use crate::game_update::PakBaseline;
 use crate::gui::LastAction;
 use crate::integrate::*;
+use crate::integrate_hooks::IntegrateHooks;
+use crate::mod_diff::ModDiff;
 use crate::mod_lints::{LintId, LintReport};
-use crate::state::{ModData_v0_1_0 as ModData, ModOrGroup};
+use crate::smoke_test::{run_smoke_test, SmokeTestOutcome};
+use crate::state::{unix_time_now, ModData_v0_1_0 as ModData, ModOrGroup};
 use crate::*;
 use crate::{
     providers::{FetchProgress, ModInfo, ModStore},
@@ -27,12 +33,15 @@ use crate::{
 use mint_lib::error::GenericError;
 use mint_lib::mod_info::MetaConfig;
 use mint_lib::update::GitHubRelease;
+use mint_lib::DRGInstallation;
 
 #[derive(Debug)]
 pub struct MessageHandle<S> {
     pub rid: RequestID,
     pub handle: JoinHandle<()>,
     pub state: S,
+    /// When the operation was started, or when the user last chose to keep waiting on it.
+    pub started: Instant,
 }
 
 #[derive(Debug)]
@@ -41,8 +50,16 @@ pub enum Message {
     Integrate(Integrate),
     FetchModProgress(FetchModProgress),
     UpdateCache(UpdateCache),
+    RefreshMetadata(RefreshMetadata),
+    CheckAvailability(CheckAvailability),
+    SmokeTest(SmokeTest),
     CheckUpdates(CheckUpdates),
     LintMods(LintMods),
+    ValidatePakPath(ValidatePakPath),
+    ComputeDiskUsage(ComputeDiskUsage),
+    ClearCache(ClearCache),
+    DiffModVersions(DiffModVersions),
+    CollectAssets(CollectAssets),
     SelfUpdate(SelfUpdate),
     FetchSelfUpdateProgress(FetchSelfUpdateProgress),
 }
@@ -54,8 +71,16 @@ impl Message {
             Self::Integrate(msg) => msg.receive(app),
             Self::FetchModProgress(msg) => msg.receive(app),
             Self::UpdateCache(msg) => msg.receive(app),
+            Self::RefreshMetadata(msg) => msg.receive(app),
+            Self::CheckAvailability(msg) => msg.receive(app),
+            Self::SmokeTest(msg) => msg.receive(app),
             Self::CheckUpdates(msg) => msg.receive(app),
             Self::LintMods(msg) => msg.receive(app),
+            Self::ValidatePakPath(msg) => msg.receive(app),
+            Self::ComputeDiskUsage(msg) => msg.receive(app),
+            Self::ClearCache(msg) => msg.receive(app),
+            Self::DiffModVersions(msg) => msg.receive(app),
+            Self::CollectAssets(msg) => msg.receive(app),
             Self::SelfUpdate(msg) => msg.receive(app),
             Self::FetchSelfUpdateProgress(msg) => msg.receive(app),
         }
@@ -66,8 +91,14 @@ impl Message {
 pub struct ResolveMods {
     rid: RequestID,
     specs: Vec<ModSpecification>,
-    result: Result<HashMap<ModSpecification, ModInfo>, ProviderError>,
+    resolved: HashMap<ModSpecification, ModInfo>,
+    /// Specs which could not be resolved and the first error encountered.
+    failed: Vec<ModSpecification>,
+    error: Option<ProviderError>,
     is_dependency: bool,
+    /// Name of the mod whose dependencies are added because it was enabled, see
+    /// [`ResolveMods::send_auto_dependencies`].
+    dependent: Option<String>,
 }
 
 impl ResolveMods {
@@ -76,18 +107,42 @@ impl ResolveMods {
         ctx: &egui::Context,
         specs: Vec<ModSpecification>,
         is_dependency: bool,
+    ) {
+        Self::send_inner(app, ctx, specs, is_dependency, None);
+    }
+
+    /// Add or enable the missing dependencies of `dependent`, which was just enabled in a
+    /// profile that adds dependencies automatically. What was added is summarized afterwards.
+    pub fn send_auto_dependencies(
+        app: &mut App,
+        ctx: &egui::Context,
+        specs: Vec<ModSpecification>,
+        dependent: String,
+    ) {
+        Self::send_inner(app, ctx, specs, true, Some(dependent));
+    }
+
+    fn send_inner(
+        app: &mut App,
+        ctx: &egui::Context,
+        specs: Vec<ModSpecification>,
+        is_dependency: bool,
+        dependent: Option<String>,
     ) {
         let rid = app.request_counter.next();
         let store = app.state.store.clone();
         let ctx = ctx.clone();
         let tx = app.tx.clone();
         let handle = tokio::spawn(async move {
-            let result = store.resolve_mods(&specs, false).await;
+            let (resolved, failed, error) = store.resolve_mods_lenient(&specs, false).await;
             tx.send(Message::ResolveMods(Self {
                 rid,
                 specs,
-                result,
+                resolved,
+                failed,
+                error,
                 is_dependency,
+                dependent,
             }))
             .await
             .unwrap();
@@ -98,76 +153,133 @@ impl ResolveMods {
             rid,
             handle,
             state: (),
+            started: Instant::now(),
         });
     }
 
     fn receive(self, app: &mut App) {
         if Some(self.rid) == app.resolve_mod_rid.as_ref().map(|r| r.rid) {
-            match self.result {
-                Ok(resolved_mods) => {
-                    let primary_mods = self
-                        .specs
-                        .into_iter()
-                        .collect::<HashSet<ModSpecification>>();
-                    for (resolved_spec, info) in resolved_mods {
-                        let is_dep = self.is_dependency || !primary_mods.contains(&resolved_spec);
-                        let add = if is_dep {
-                            // if mod is a dependency then check if there is a disabled
-                            // mod that satisfies the dependency and enable it. if it
-                            // is not a dependency then assume the user explicitly
-                            // wants to add a specific mod version.
-                            let active_profile = app.state.mod_data.active_profile.clone();
-                            !app.state.mod_data.any_mod_mut(
-                                &active_profile,
-                                |mc, mod_group_enabled| {
-                                    if mc.spec.satisfies_dependency(&resolved_spec) {
-                                        mc.enabled = true;
-                                        if let Some(mod_group_enabled) = mod_group_enabled {
-                                            *mod_group_enabled = true;
-                                        }
-                                        true
-                                    } else {
-                                        false
+            // mods which resolved are added even if others in the batch failed
+            let primary_mods = self
+                .specs
+                .iter()
+                .cloned()
+                .collect::<HashSet<ModSpecification>>();
+            // names of the mods which were added or enabled as dependencies
+            let mut added_deps = vec![];
+            for (resolved_spec, info) in self.resolved {
+                let is_dep = self.is_dependency || !primary_mods.contains(&resolved_spec);
+                let add = if is_dep {
+                    // if mod is a dependency then check if there is a disabled
+                    // mod that satisfies the dependency and enable it. if it
+                    // is not a dependency then assume the user explicitly
+                    // wants to add a specific mod version.
+                    let active_profile = app.state.mod_data.active_profile.clone();
+                    let mut enabled = false;
+                    let found =
+                        app.state
+                            .mod_data
+                            .any_mod_mut(&active_profile, |mc, mod_group_enabled| {
+                                if mc.spec.satisfies_dependency(&resolved_spec) {
+                                    enabled = !mc.enabled;
+                                    mc.enabled = true;
+                                    if let Some(mod_group_enabled) = mod_group_enabled {
+                                        enabled |= !*mod_group_enabled;
+                                        *mod_group_enabled = true;
                                     }
-                                },
-                            )
-                        } else {
-                            true
-                        };
-
-                        if add {
-                            let ModData {
-                                active_profile,
-                                profiles,
-                                ..
-                            } = app.state.mod_data.deref_mut().deref_mut();
-
-                            profiles.get_mut(active_profile).unwrap().mods.insert(
-                                0,
-                                ModOrGroup::Individual(ModConfig {
-                                    spec: info.spec.clone(),
-                                    required: info.suggested_require,
-                                    enabled: true,
-                                    priority: 0,
-                                }),
-                            );
-                        }
+                                    true
+                                } else {
+                                    false
+                                }
+                            });
+                    if enabled {
+                        added_deps.push(info.name.clone());
+                    }
+                    !found
+                } else {
+                    true
+                };
+
+                if add {
+                    if is_dep {
+                        added_deps.push(info.name.clone());
                     }
+                    let ModData {
+                        active_profile,
+                        profiles,
+                        ..
+                    } = app.state.mod_data.deref_mut().deref_mut();
+
+                    profiles.get_mut(active_profile).unwrap().mods.insert(
+                        0,
+                        ModOrGroup::Individual(ModConfig {
+                            spec: info.spec.clone(),
+                            required: info.suggested_require,
+                            enabled: true,
+                            priority: 0,
+                            display_name: None,
+                            added: Some(unix_time_now()),
+                            frozen_from: None,
+                            priority_overrides: vec![],
+                        }),
+                    );
+                }
+            }
+            app.state.mod_data.save().unwrap();
+
+            match self.error {
+                None if let Some(dependent) = &self.dependent => {
+                    added_deps.sort();
+                    app.last_action = Some(LastAction::success(if added_deps.is_empty() {
+                        format!("dependencies of {dependent} are already enabled")
+                    } else {
+                        format!(
+                            "added dependencies of {dependent}: {}",
+                            added_deps.join(", ")
+                        )
+                    }));
+                }
+                None => {
                     app.resolve_mod.clear();
-                    app.state.mod_data.save().unwrap();
+                    app.failed_batch = None;
                     app.last_action = Some(LastAction::success(
                         "mods successfully resolved".to_string(),
                     ));
                 }
-                Err(ProviderError::NoProvider { url: _, factory }) => {
+                Some(ProviderError::NoProvider { url: _, factory }) => {
                     app.window_provider_parameters =
                         Some(WindowProviderParameters::new(factory, &app.state));
                     app.last_action = Some(LastAction::failure("no provider".to_string()));
                 }
-                Err(e) => {
+                Some(e) => {
                     error!("{}", e);
                     app.problematic_mod_id = e.opt_mod_id();
-                    app.last_action = Some(LastAction::failure(e.to_string()));
+                    app.last_action = Some(
+                        LastAction::failure(if self.failed.len() < self.specs.len() {
+                            format!(
+                                "{} of {} mods failed to resolve: {e}",
+                                self.failed.len(),
+                                self.specs.len()
+                            )
+                        } else {
+                            e.to_string()
+                        })
+                        .with_error(e)
+                        .with_context("request", self.rid)
+                        .with_mod_id(app.problematic_mod_id)
+                        .with_context(
+                            "failed mods",
+                            self.failed
+                                .iter()
+                                .map(|spec| spec.url.as_str())
+                                .collect::<Vec<_>>()
+                                .join(", "),
+                        ),
+                    );
+                    app.failed_batch = Some(FailedBatch::Resolve {
+                        specs: self.failed,
+                        is_dependency: self.is_dependency,
+                    });
                 }
             }
             app.resolve_mod_rid = None;
@@ -178,41 +290,80 @@ impl ResolveMods {
 #[derive(Debug)]
 pub struct Integrate {
     rid: RequestID,
-    result: Result<(), IntegrationError>,
+    install_hash: String,
+    mods: Vec<ModSpecification>,
+    /// The installed mods pak and the game pak it was built from.
+    result: Result<(PathBuf, Option<PakBaseline>), IntegrationError>,
+}
+
+/// The mods to install and how, for [`Integrate::send`].
+#[derive(Debug)]
+pub struct IntegrateRequest {
+    pub mods: Vec<ModSpecification>,
+    pub fsd_pak: PathBuf,
+    pub config: MetaConfig,
+    pub pak_key: Option<PakKey>,
+    pub install_hook: bool,
+    /// Directory to write the integrate log to, if any.
+    pub log_dir: Option<PathBuf>,
+    pub auto_downgrade: bool,
+    pub options: IntegrateOptions,
+    pub hooks: IntegrateHooks,
+    pub hash_game_pak: bool,
 }
 
 impl Integrate {
     pub fn send(
         rc: &mut RequestCounter,
         store: Arc<ModStore>,
-        mods: Vec<ModSpecification>,
-        fsd_pak: PathBuf,
-        config: MetaConfig,
+        request: IntegrateRequest,
+        last_install_hash: Option<String>,
         tx: Sender<Message>,
         ctx: egui::Context,
     ) -> MessageHandle<HashMap<ModSpecification, SpecFetchProgress>> {
         let rid = rc.next();
+        let install_hash = crate::install_hash(&request.fsd_pak, &request.mods);
+        let quick = last_install_hash.as_ref() == Some(&install_hash);
         MessageHandle {
             rid,
             handle: tokio::task::spawn(async move {
+                let mods = request.mods.clone();
                 let res =
-                    integrate_async(store, ctx.clone(), mods, fsd_pak, config, rid, tx.clone())
-                        .await;
-                tx.send(Message::Integrate(Integrate { rid, result: res }))
-                    .await
-                    .unwrap();
+                    integrate_async(store, ctx.clone(), request, quick, rid, tx.clone()).await;
+                tx.send(Message::Integrate(Integrate {
+                    rid,
+                    install_hash,
+                    mods,
+                    result: res,
+                }))
+                .await
+                .unwrap();
                 ctx.request_repaint();
             }),
             state: Default::default(),
+            started: Instant::now(),
         }
     }
 
     fn receive(self, app: &mut App) {
         if Some(self.rid) == app.integrate_rid.as_ref().map(|r| r.rid) {
             match self.result {
-                Ok(()) => {
+                Ok((path, baseline)) => {
                     info!("integration complete");
-                    app.last_action = Some(LastAction::success("integration complete".to_string()));
+                    app.state.config.last_install_hash = Some(self.install_hash);
+                    app.state.config.game_pak_baseline = baseline;
+                    app.state.config.save().unwrap();
+                    app.state.record_usage(Some(&self.mods));
+                    app.game_updated = false;
+                    app.failed_batch = None;
+                    app.hook_problem = None;
+                    if app.state.config.open_pak_location_after_install
+                        && let Some(dir) = path.parent()
+                    {
+                        opener::open(dir).ok();
+                    }
+                    app.last_action = Some(LastAction::installed(path));
+                    app.smoke_test_pending = app.state.config.smoke_test_after_install;
                 }
                 Err(ref e)
                     if let IntegrationError::ProviderError { ref source } = e
@@ -222,10 +373,23 @@ impl Integrate {
                         Some(WindowProviderParameters::new(factory, &app.state));
                     app.last_action = Some(LastAction::failure("no provider".to_string()));
                 }
+                Err(IntegrationError::OutputLocked { path, .. }) => {
+                    error!("{} is in use or read-only", path.display());
+                    app.last_action = Some(LastAction::failure(
+                        "Failed to write mods, close the game and try again".to_string(),
+                    ));
+                    app.failed_batch = Some(FailedBatch::Integrate);
+                    app.output_locked_window = Some(WindowOutputLocked { path });
+                }
                 Err(e) => {
                     error!("{}", e);
                     app.problematic_mod_id = e.opt_mod_id();
-                    app.last_action = Some(LastAction::failure(e.to_string()));
+                    app.last_action = Some(
+                        LastAction::error(e)
+                            .with_context("request", self.rid)
+                            .with_mod_id(app.problematic_mod_id),
+                    );
+                    app.failed_batch = Some(FailedBatch::Integrate);
                 }
             }
             app.integrate_rid = None;
@@ -272,6 +436,7 @@ impl UpdateCache {
             rid,
             handle,
             state: (),
+            started: Instant::now(),
         });
     }
 
@@ -292,7 +457,158 @@ impl UpdateCache {
                 Err(e) => {
                     error!("{}", e);
                     app.problematic_mod_id = e.opt_mod_id();
-                    app.last_action = Some(LastAction::failure(e.to_string()));
+                    app.last_action = Some(
+                        LastAction::error(e)
+                            .with_context("request", self.rid)
+                            .with_mod_id(app.problematic_mod_id),
+                    );
+                }
+            }
+            app.update_rid = None;
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct RefreshMetadata {
+    rid: RequestID,
+    result: Result<HashMap<ModSpecification, ModInfo>, ProviderError>,
+}
+
+impl RefreshMetadata {
+    pub fn send(app: &mut App, ctx: &egui::Context) {
+        let rid = app.request_counter.next();
+        let tx = app.tx.clone();
+        let ctx = ctx.clone();
+        let store = app.state.store.clone();
+
+        let mut mods = vec![];
+        app.state
+            .mod_data
+            .for_each_mod(&app.state.mod_data.active_profile, |mc| {
+                mods.push(mc.spec.clone());
+            });
+
+        let handle = tokio::spawn(async move {
+            let res = store.refresh_metadata(&mods).await;
+            tx.send(Message::RefreshMetadata(RefreshMetadata {
+                rid,
+                result: res,
+            }))
+            .await
+            .unwrap();
+            ctx.request_repaint();
+        });
+        app.last_action = None;
+        app.update_rid = Some(MessageHandle {
+            rid,
+            handle,
+            state: (),
+            started: Instant::now(),
+        });
+    }
+
+    fn receive(self, app: &mut App) {
+        if Some(self.rid) == app.update_rid.as_ref().map(|r| r.rid) {
+            match self.result {
+                Ok(mods) => {
+                    info!("metadata refresh complete");
+                    app.last_action = Some(LastAction::success(format!(
+                        "refreshed metadata for {} mod(s)",
+                        mods.len()
+                    )));
+                }
+                Err(ProviderError::NoProvider { url: _, factory }) => {
+                    app.window_provider_parameters =
+                        Some(WindowProviderParameters::new(factory, &app.state));
+                    app.last_action = Some(LastAction::failure("no provider".to_string()));
+                }
+                Err(e) => {
+                    error!("{}", e);
+                    app.problematic_mod_id = e.opt_mod_id();
+                    app.last_action = Some(
+                        LastAction::error(e)
+                            .with_context("request", self.rid)
+                            .with_mod_id(app.problematic_mod_id),
+                    );
+                }
+            }
+            app.update_rid = None;
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct CheckAvailability {
+    rid: RequestID,
+    result: Result<Vec<LinkReport>, ProviderError>,
+}
+
+impl CheckAvailability {
+    pub fn send(app: &mut App, ctx: &egui::Context) {
+        let rid = app.request_counter.next();
+        let tx = app.tx.clone();
+        let ctx = ctx.clone();
+        let store = app.state.store.clone();
+
+        let mut mods = vec![];
+        app.state
+            .mod_data
+            .for_each_mod(&app.state.mod_data.active_profile, |mc| {
+                if !mods.contains(&mc.spec) {
+                    mods.push(mc.spec.clone());
+                }
+            });
+
+        let handle = tokio::spawn(async move {
+            let res = check_availability(&store, &mods).await;
+            tx.send(Message::CheckAvailability(CheckAvailability {
+                rid,
+                result: res,
+            }))
+            .await
+            .unwrap();
+            ctx.request_repaint();
+        });
+        app.last_action = None;
+        app.update_rid = Some(MessageHandle {
+            rid,
+            handle,
+            state: (),
+            started: Instant::now(),
+        });
+    }
+
+    fn receive(self, app: &mut App) {
+        if Some(self.rid) == app.update_rid.as_ref().map(|r| r.rid) {
+            match self.result {
+                Ok(reports) => {
+                    let total = reports.len();
+                    app.unavailable_mods = reports
+                        .into_iter()
+                        .filter_map(|report| Some((report.spec, report.result.err()?)))
+                        .collect();
+                    info!(
+                        "availability check complete, {} unavailable",
+                        app.unavailable_mods.len()
+                    );
+                    app.last_action = Some(if app.unavailable_mods.is_empty() {
+                        LastAction::success(format!("all {total} mod(s) are available"))
+                    } else {
+                        LastAction::failure(format!(
+                            "{} of {total} mod(s) are unavailable",
+                            app.unavailable_mods.len()
+                        ))
+                    });
+                }
+                Err(ProviderError::NoProvider { url: _, factory }) => {
+                    app.window_provider_parameters =
+                        Some(WindowProviderParameters::new(factory, &app.state));
+                    app.last_action = Some(LastAction::failure("no provider".to_string()));
+                }
+                Err(e) => {
+                    error!("{}", e);
+                    app.last_action = Some(LastAction::error(e).with_context("request", self.rid));
                 }
             }
             app.update_rid = None;
@@ -300,6 +616,64 @@ impl UpdateCache {
     }
 }
 
+#[derive(Debug)]
+pub struct SmokeTest {
+    rid: RequestID,
+    result: std::io::Result<SmokeTestOutcome>,
+}
+
+impl SmokeTest {
+    pub fn send(
+        app: &mut App,
+        ctx: &egui::Context,
+        installation: DRGInstallation,
+        command: Vec<String>,
+    ) {
+        let rid = app.request_counter.next();
+        let tx = app.tx.clone();
+        let ctx = ctx.clone();
+        let timeout = app.state.config.smoke_test_timeout();
+
+        let handle = tokio::task::spawn_blocking(move || {
+            let res = run_smoke_test(&installation, &command, timeout);
+            tx.blocking_send(Message::SmokeTest(SmokeTest { rid, result: res }))
+                .unwrap();
+            ctx.request_repaint();
+        });
+        app.smoke_test_rid = Some(MessageHandle {
+            rid,
+            handle,
+            state: (),
+            started: Instant::now(),
+        });
+    }
+
+    fn receive(self, app: &mut App) {
+        if Some(self.rid) == app.smoke_test_rid.as_ref().map(|r| r.rid) {
+            match self.result {
+                Ok(outcome @ SmokeTestOutcome::Passed { .. }) => {
+                    info!("smoke test passed: {outcome}");
+                    app.last_action =
+                        Some(LastAction::success(format!("Smoke test passed, {outcome}")));
+                }
+                Ok(outcome) => {
+                    warn!("smoke test failed: {outcome}");
+                    app.last_action = Some(LastAction::failure(format!(
+                        "Smoke test failed, {outcome}. The installed mods may crash the game, see mint_hook.log next to the game executable"
+                    )));
+                }
+                Err(e) => {
+                    error!("smoke test could not run: {e}");
+                    app.last_action = Some(
+                        LastAction::failure(format!("Smoke test could not run: {e}")).with_error(e),
+                    );
+                }
+            }
+            app.smoke_test_rid = None;
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct CheckUpdates {
     rid: RequestID,
@@ -325,6 +699,7 @@ impl CheckUpdates {
             rid,
             handle,
             state: (),
+            started: Instant::now(),
         });
     }
 
@@ -355,28 +730,50 @@ impl CheckUpdates {
 async fn integrate_async(
     store: Arc<ModStore>,
     ctx: egui::Context,
-    mod_specs: Vec<ModSpecification>,
-    fsd_pak: PathBuf,
-    config: MetaConfig,
+    request: IntegrateRequest,
+    quick: bool,
     rid: RequestID,
     message_tx: Sender<Message>,
-) -> Result<(), IntegrationError> {
+) -> Result<(PathBuf, Option<PakBaseline>), IntegrationError> {
+    let IntegrateRequest {
+        mods: mod_specs,
+        fsd_pak,
+        config,
+        pak_key,
+        install_hook,
+        log_dir,
+        auto_downgrade,
+        options,
+        hooks,
+        hash_game_pak,
+    } = request;
     let update = false;
+    // cancelling aborts this task which drops the guard, stopping the merge as well
+    let cancel = CancelToken::default();
+    let _cancel_on_drop = cancel.drop_guard();
 
-    let mods = store.resolve_mods(&mod_specs, update).await?;
+    if !hooks.is_empty() {
+        let (hooks, fsd_pak) = (hooks.clone(), fsd_pak.clone());
+        tokio::task::spawn_blocking(move || hooks.run_pre(&fsd_pak)).await??;
+    }
 
-    let to_integrate = mod_specs
-        .iter()
-        .map(|u| mods[u].clone())
-        .collect::<Vec<_>>();
+    // nothing changed since the last install so the cached mod info can be used as is
+    let mods = match quick
+        .then(|| crate::cached_mod_info(&store, &mod_specs))
+        .flatten()
+    {
+        Some(mods) => {
+            info!("mods unchanged since last install, skipped resolution");
+            mods
+        }
+        None => store.resolve_mods(&mod_specs, update).await?,
+    };
+
+    let to_integrate = mod_specs.iter().map(|u| (u, &mods[u])).collect::<Vec<_>>();
     let res_map: HashMap<ModResolution, ModSpecification> = mods
         .iter()
         .map(|(spec, info)| (info.resolution.clone(), spec.clone()))
         .collect();
-    let urls = to_integrate
-        .iter()
-        .map(|m| &m.resolution)
-        .collect::<Vec<_>>();
 
     let (tx, mut rx) = mpsc::channel::<FetchProgress>(10);
 
@@ -396,51 +793,111 @@ async fn integrate_async(
         }
     });
 
-    let paths = store.fetch_mods_ordered(&urls, update, Some(tx)).await?;
+    let mods = if auto_downgrade {
+        store
+            .fetch_mods_with_fallback(&to_integrate, update, Some(tx), pak_key.as_ref())
+            .await?
+    } else {
+        let urls = to_integrate
+            .iter()
+            .map(|(spec, m)| (*spec, &m.resolution))
+            .collect::<Vec<_>>();
+        let paths = store.fetch_mods_ordered(&urls, update, Some(tx)).await?;
+        to_integrate
+            .into_iter()
+            .map(|(_, m)| m.clone())
+            .zip(paths)
+            .collect()
+    };
 
-    tokio::task::spawn_blocking(|| {
-        crate::integrate::integrate(
-            fsd_pak,
+    tokio::task::spawn_blocking(move || {
+        let path = crate::integrate::integrate(
+            &fsd_pak,
             config,
-            to_integrate.into_iter().zip(paths).collect(),
-        )
+            pak_key.as_ref(),
+            mods,
+            log_dir.as_deref(),
+            options,
+            Some(&cancel),
+        )?;
+        if install_hook {
+            crate::integrate::install_hook(&fsd_pak)?;
+        } else {
+            warn!(
+                "skipped deploying the hook DLL, mint's in-game features will not work without it"
+            );
+        }
+        hooks.run_post(&fsd_pak);
+        let baseline = PakBaseline::record(&fsd_pak, hash_game_pak)
+            .inspect_err(|e| warn!("failed to record the game pak for detecting updates: {e}"))
+            .ok();
+        Ok::<_, IntegrationError>((path, baseline))
     })
-    .await??;
-
-    Ok(())
+    .await?
 }
 
 #[derive(Debug)]
 pub struct LintMods {
     rid: RequestID,
+    /// Profile the mods were linted for.
+    profile: String,
     result: Result<LintReport, IntegrationError>,
 }
 
+/// The mods to lint and how, for [`LintMods::send`].
+#[derive(Debug)]
+pub struct LintRequest {
+    /// Profile the mods are linted for.
+    pub profile: String,
+    pub mods: Vec<ModSpecification>,
+    pub enabled_lints: BTreeSet<LintId>,
+    pub game_pak_path: Option<PathBuf>,
+    pub pak_key: Option<PakKey>,
+    pub all_archive_paks: bool,
+    pub cache_dir: PathBuf,
+}
+
 impl LintMods {
     pub fn send(
         rc: &mut RequestCounter,
         store: Arc<ModStore>,
-        mods: Vec<ModSpecification>,
-        enabled_lints: BTreeSet<LintId>,
-        game_pak_path: Option<PathBuf>,
+        request: LintRequest,
         tx: Sender<Message>,
         ctx: egui::Context,
     ) -> MessageHandle<()> {
         let rid = rc.next();
+        let LintRequest {
+            profile,
+            mods,
+            enabled_lints,
+            game_pak_path,
+            pak_key,
+            all_archive_paks,
+            cache_dir,
+        } = request;
 
         let handle = tokio::task::spawn(async move {
             let paths_res =
-                resolve_async_ordered(store, ctx.clone(), mods.clone(), rid, tx.clone()).await;
+                resolve_async_ordered(store.clone(), ctx.clone(), mods.clone(), rid, tx.clone())
+                    .await;
+            let modio_tags = mods
+                .iter()
+                .filter_map(|m| Some((m.clone(), store.get_mod_info(m)?.modio_tags?)))
+                .collect();
             let mod_path_pairs_res =
                 paths_res.map(|paths| mods.into_iter().zip(paths).collect::<Vec<_>>());
 
             let report_res = match mod_path_pairs_res {
                 Ok(pairs) => tokio::task::spawn_blocking(move || {
-                    crate::mod_lints::run_lints(
-                        &enabled_lints,
+                    let lint_ctxt = crate::mod_lints::LintCtxt::init(
                         pairs.into_iter().collect(),
                         game_pak_path,
-                    )
+                        pak_key,
+                    )?
+                    .all_archive_paks(all_archive_paks)
+                    .cache_dir(Some(cache_dir))
+                    .modio_tags(modio_tags);
+                    crate::mod_lints::run_lints_in(&enabled_lints, lint_ctxt)
                 })
                 .await
                 .unwrap()
@@ -450,6 +907,7 @@ impl LintMods {
 
             tx.send(Message::LintMods(LintMods {
                 rid,
+                profile,
                 result: report_res,
             }))
             .await
@@ -461,6 +919,7 @@ impl LintMods {
             rid,
             handle,
             state: Default::default(),
+            started: Instant::now(),
         }
     }
 
@@ -469,7 +928,13 @@ impl LintMods {
             match self.result {
                 Ok(report) => {
                     info!("lint mod report complete");
-                    app.lint_report = Some(report);
+                    if let Some(conflicting_mods) = &report.conflicting_mods
+                        && let Some(profile) = app.state.mod_data.profiles.get_mut(&self.profile)
+                        && profile.prune_acknowledged_conflicts(conflicting_mods)
+                    {
+                        app.state.mod_data.save().unwrap();
+                    }
+                    app.lint_report = Some((self.profile, report));
                     app.last_action =
                         Some(LastAction::success("lint mod report complete".to_string()));
                 }
@@ -484,7 +949,11 @@ impl LintMods {
                 Err(e) => {
                     error!("{}", e);
                     app.problematic_mod_id = e.opt_mod_id();
-                    app.last_action = Some(LastAction::failure(e.to_string()));
+                    app.last_action = Some(
+                        LastAction::error(e)
+                            .with_context("request", self.rid)
+                            .with_mod_id(app.problematic_mod_id),
+                    );
                 }
             }
             app.integrate_rid = None;
@@ -503,18 +972,14 @@ async fn resolve_async_ordered(
 
     let mods = store.resolve_mods(&mod_specs, update).await?;
 
-    let to_integrate = mod_specs
-        .iter()
-        .map(|u| mods[u].clone())
-        .collect::<Vec<_>>();
     let res_map: HashMap<ModResolution, ModSpecification> = mods
         .iter()
         .map(|(spec, info)| (info.resolution.clone(), spec.clone()))
         .collect();
-    let urls = to_integrate
+    let urls = mod_specs
         .iter()
-        .map(|m| &m.resolution)
-        .collect::<Vec<&ModResolution>>();
+        .map(|spec| (spec, &mods[spec].resolution))
+        .collect::<Vec<_>>();
 
     let (tx, mut rx) = mpsc::channel::<FetchProgress>(10);
 
@@ -537,6 +1002,317 @@ async fn resolve_async_ordered(
     Ok(store.fetch_mods_ordered(&urls, update, Some(tx)).await?)
 }
 
+#[derive(Debug)]
+pub struct ValidatePakPath {
+    rid: RequestID,
+    result: Result<(), MintError>,
+}
+
+impl ValidatePakPath {
+    pub fn send(
+        rc: &mut RequestCounter,
+        path: String,
+        skip_validation: bool,
+        tx: Sender<Message>,
+        ctx: egui::Context,
+    ) -> MessageHandle<()> {
+        let rid = rc.next();
+        MessageHandle {
+            rid,
+            handle: tokio::task::spawn(async move {
+                let result =
+                    tokio::task::spawn_blocking(move || validate_drg_pak(path, skip_validation))
+                        .await
+                        .unwrap();
+                tx.send(Message::ValidatePakPath(Self { rid, result }))
+                    .await
+                    .unwrap();
+                ctx.request_repaint();
+            }),
+            state: (),
+            started: Instant::now(),
+        }
+    }
+
+    fn receive(self, app: &mut App) {
+        if let Some(window) = &mut app.settings_window
+            && Some(self.rid) == window.drg_pak_path_check_rid.as_ref().map(|r| r.rid)
+        {
+            window.drg_pak_path_check_rid = None;
+            match self.result {
+                Ok(()) => {
+                    window.drg_pak_path_valid = true;
+                    window.drg_pak_path_err = None;
+                }
+                Err(e) => {
+                    window.drg_pak_path_valid = false;
+                    window.drg_pak_path_err = Some(e.to_string());
+                }
+            }
+        }
+    }
+}
+
+/// Total size of all files below `path`. Entries which cannot be read are skipped.
+fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(t) if t.is_dir() => dir_size(&entry.path()),
+            Ok(_) => entry.metadata().map(|m| m.len()).unwrap_or(0),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+#[derive(Debug)]
+pub struct ComputeDiskUsage {
+    rid: RequestID,
+    result: DiskUsage,
+}
+
+impl ComputeDiskUsage {
+    pub fn send(
+        rc: &mut RequestCounter,
+        dirs: [PathBuf; 4],
+        tx: Sender<Message>,
+        ctx: egui::Context,
+    ) -> MessageHandle<()> {
+        let rid = rc.next();
+        MessageHandle {
+            rid,
+            handle: tokio::task::spawn(async move {
+                let result = tokio::task::spawn_blocking(move || {
+                    let [config, cache, blobs, data] = dirs.each_ref().map(|d| dir_size(d));
+                    DiskUsage {
+                        config,
+                        cache,
+                        blobs,
+                        data,
+                    }
+                })
+                .await
+                .unwrap();
+                tx.send(Message::ComputeDiskUsage(Self { rid, result }))
+                    .await
+                    .unwrap();
+                ctx.request_repaint();
+            }),
+            state: (),
+            started: Instant::now(),
+        }
+    }
+
+    fn receive(self, app: &mut App) {
+        if let Some(window) = &mut app.settings_window
+            && Some(self.rid) == window.disk_usage_rid.as_ref().map(|r| r.rid)
+        {
+            window.disk_usage_rid = None;
+            window.disk_usage = Some(self.result);
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ClearCache {
+    rid: RequestID,
+    result: Result<(), ProviderError>,
+}
+
+impl ClearCache {
+    pub fn send(
+        rc: &mut RequestCounter,
+        store: Arc<ModStore>,
+        tx: Sender<Message>,
+        ctx: egui::Context,
+    ) -> MessageHandle<()> {
+        let rid = rc.next();
+        MessageHandle {
+            rid,
+            handle: tokio::task::spawn(async move {
+                let result = tokio::task::spawn_blocking(move || store.clear_blob_cache())
+                    .await
+                    .unwrap();
+                tx.send(Message::ClearCache(Self { rid, result }))
+                    .await
+                    .unwrap();
+                ctx.request_repaint();
+            }),
+            state: (),
+            started: Instant::now(),
+        }
+    }
+
+    fn receive(self, app: &mut App) {
+        if let Some(window) = &mut app.settings_window
+            && Some(self.rid) == window.clear_cache_rid.as_ref().map(|r| r.rid)
+        {
+            window.clear_cache_rid = None;
+            // sizes are stale now, recompute them
+            window.disk_usage = None;
+            match self.result {
+                Ok(()) => {
+                    app.last_action = Some(LastAction::success("cleared cache".to_string()));
+                }
+                Err(e) => {
+                    error!("{}", e);
+                    app.last_action = Some(LastAction::error(e).with_context("request", self.rid));
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct DiffModVersions {
+    rid: RequestID,
+    versions: (ModSpecification, ModSpecification),
+    result: Result<ModDiff, IntegrationError>,
+}
+
+impl DiffModVersions {
+    pub fn send(
+        rc: &mut RequestCounter,
+        store: Arc<ModStore>,
+        versions: (ModSpecification, ModSpecification),
+        pak_key: Option<PakKey>,
+        tx: Sender<Message>,
+        ctx: egui::Context,
+    ) -> MessageHandle<()> {
+        let rid = rc.next();
+        MessageHandle {
+            rid,
+            handle: tokio::task::spawn(async move {
+                let result = diff_mod_versions_async(store, &versions, pak_key).await;
+                tx.send(Message::DiffModVersions(Self {
+                    rid,
+                    versions,
+                    result,
+                }))
+                .await
+                .unwrap();
+                ctx.request_repaint();
+            }),
+            state: (),
+            started: Instant::now(),
+        }
+    }
+
+    fn receive(self, app: &mut App) {
+        if let Some(window) = &mut app.update_diff_window
+            && Some(self.rid) == window.rid.as_ref().map(|r| r.rid)
+        {
+            window.rid = None;
+            match self.result {
+                Ok(diff) => {
+                    app.update_diffs.insert(self.versions, diff);
+                }
+                Err(e) => {
+                    error!("{}", e);
+                    window.err = Some(e.to_string());
+                }
+            }
+        }
+    }
+}
+
+async fn diff_mod_versions_async(
+    store: Arc<ModStore>,
+    (current, new): &(ModSpecification, ModSpecification),
+    pak_key: Option<PakKey>,
+) -> Result<ModDiff, IntegrationError> {
+    let mods = store
+        .resolve_mods(&[current.clone(), new.clone()], false)
+        .await?;
+    let mut paths = store
+        .fetch_mods_ordered(
+            &[
+                (current, &mods[current].resolution),
+                (new, &mods[new].resolution),
+            ],
+            false,
+            None,
+        )
+        .await?;
+    let (new, current) = (paths.pop().unwrap(), paths.pop().unwrap());
+
+    tokio::task::spawn_blocking(move || crate::mod_diff::diff_paks(current, new, pak_key.as_ref()))
+        .await?
+}
+
+#[derive(Debug)]
+pub struct CollectAssets {
+    rid: RequestID,
+    result: Result<AssetTree, IntegrationError>,
+}
+
+impl CollectAssets {
+    /// Build the merged asset tree of `mods`, which are in integration order, the way
+    /// integrating them with `options` would lay them out.
+    pub fn send(
+        rc: &mut RequestCounter,
+        store: Arc<ModStore>,
+        mods: Vec<ModSpecification>,
+        pak_key: Option<PakKey>,
+        options: IntegrateOptions,
+        tx: Sender<Message>,
+        ctx: egui::Context,
+    ) -> MessageHandle<()> {
+        let rid = rc.next();
+        MessageHandle {
+            rid,
+            handle: tokio::task::spawn(async move {
+                let result = collect_assets_async(store, mods, pak_key, options).await;
+                tx.send(Message::CollectAssets(Self { rid, result }))
+                    .await
+                    .unwrap();
+                ctx.request_repaint();
+            }),
+            state: (),
+            started: Instant::now(),
+        }
+    }
+
+    fn receive(self, app: &mut App) {
+        if let Some(window) = &mut app.asset_tree_window
+            && Some(self.rid) == window.rid.as_ref().map(|r| r.rid)
+        {
+            window.rid = None;
+            match self.result {
+                Ok(tree) => window.tree = Some(tree),
+                Err(e) => {
+                    error!("{}", e);
+                    window.err = Some(e.to_string());
+                }
+            }
+        }
+    }
+}
+
+async fn collect_assets_async(
+    store: Arc<ModStore>,
+    mods: Vec<ModSpecification>,
+    pak_key: Option<PakKey>,
+    options: IntegrateOptions,
+) -> Result<AssetTree, IntegrationError> {
+    let infos = store.resolve_mods(&mods, false).await?;
+    let resolutions = mods
+        .iter()
+        .map(|spec| (spec, &infos[spec].resolution))
+        .collect::<Vec<_>>();
+    let paths = store.fetch_mods_ordered(&resolutions, false, None).await?;
+    let mods = mods
+        .iter()
+        .map(|spec| infos[spec].clone())
+        .zip(paths)
+        .collect::<Vec<_>>();
+
+    tokio::task::spawn_blocking(move || plan(&mods, pak_key.as_ref(), &options)).await?
+}
+
 #[derive(Debug)]
 pub struct SelfUpdate {
     rid: RequestID,
@@ -560,6 +1336,7 @@ impl SelfUpdate {
                 ctx.request_repaint();
             }),
             state: SelfUpdateProgress::Pending,
+            started: Instant::now(),
         }
     }
 
@@ -575,7 +1352,8 @@ impl SelfUpdate {
                     error!("self update failed");
                     error!("{:#?}", e);
                     app.self_update_rid = None;
-                    app.last_action = Some(LastAction::failure("self update failed".to_string()));
+                    app.last_action =
+                        Some(LastAction::failure("self update failed".to_string()).with_error(e));
                 }
             }
             app.integrate_rid = None;
@@ -1,6 +1,7 @@
 mod find_string;
 mod message;
 mod named_combobox;
+mod notifications;
 mod request_counter;
 mod toggle_switch;
 
@@ -13,7 +14,7 @@ use std::time::{Duration, Instant, SystemTime};
 use std::{
     collections::{HashMap, HashSet},
     ops::DerefMut,
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 
 use eframe::egui::{Button, CollapsingHeader, RichText};
@@ -28,26 +29,41 @@ use itertools::Itertools as _;
 use mint_lib::error::ResultExt as _;
 use mint_lib::mod_info::{ModioTags, RequiredStatus};
 use mint_lib::update::GitHubRelease;
+use mint_lib::DRGInstallation;
 use strum::{EnumIter, IntoEnumIterator};
 use tokio::{
     sync::mpsc::{self, Receiver, Sender},
     task::JoinHandle,
 };
-use tracing::{debug, trace};
+use tracing::{debug, trace, warn};
 
+use crate::allowlist::Allowlist;
+use crate::asset_tree::AssetTree;
+use crate::check_links::LinkProblem;
+use crate::game_update::game_updated;
 use crate::gui::find_string::searchable_text;
-use crate::mod_lints::{LintId, LintReport, SplitAssetPair};
+use crate::gui::notifications::{Notification, NotificationAction};
+use crate::incompatible::Incompatibilities;
+use crate::launch_args::{join_args, launch_command, split_args};
+use crate::mod_diff::ModDiff;
+use crate::mod_lints::{conflict_pairs, LintId, LintReport, SplitAssetPair};
+use crate::other_paks::{find_other_paks, OtherPak};
 use crate::providers::ProviderError;
-use crate::state::SortingConfig;
+use crate::smoke_test::DEFAULT_SMOKE_TEST_TIMEOUT_SECS;
+use crate::state::{ConflictStrategy, FilterConfig, SortingConfig, DEFAULT_OPERATION_TIMEOUT_SECS};
+use crate::stats::UsageStats;
 use crate::Dirs;
 use crate::{
-    integrate::uninstall,
-    is_drg_pak,
+    integrate::{check_hook, reinstall_hook, uninstall, HookProblem, IntegrateOptions},
+    modpack::Modpack,
     providers::{
-        ApprovalStatus, FetchProgress, ModInfo, ModSpecification, ModStore, ProviderFactory,
+        format_count, modio::validate_url, parse_spec, ApprovalStatus, FetchProgress, ModInfo,
+        ModSpecification, ModStore, ProviderFactory,
     },
-    state::{ModConfig, ModData_v0_1_0 as ModData, ModOrGroup, ModProfile, State},
-    MintError,
+    state::{
+        ModConfig, ModData_v0_1_0 as ModData, ModOrGroup, ModProfile, PriorityOverride, State,
+    },
+    validate_drg_pak, MintError, PakKey,
 };
 use message::MessageHandle;
 use request_counter::{RequestCounter, RequestID};
@@ -111,6 +127,7 @@ pub enum SortBy {
     Provider,
     RequiredStatus,
     ApprovalCategory,
+    DateAdded,
 }
 
 impl SortBy {
@@ -122,6 +139,61 @@ impl SortBy {
             SortBy::Provider => "Provider",
             SortBy::RequiredStatus => "Is Required",
             SortBy::ApprovalCategory => "Approval",
+            SortBy::DateAdded => "Date Added",
+        }
+    }
+}
+
+#[derive(
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Debug,
+    EnumIter,
+    Clone,
+    Copy,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum TagFilter {
+    Verified,
+    Approved,
+    Sandbox,
+    RequiredByAll,
+    QoL,
+    Gameplay,
+    Audio,
+    Visual,
+    Framework,
+}
+
+impl TagFilter {
+    fn as_str(&self) -> &'static str {
+        match self {
+            TagFilter::Verified => "Verified",
+            TagFilter::Approved => "Approved",
+            TagFilter::Sandbox => "Sandbox",
+            TagFilter::RequiredByAll => "RequiredByAll",
+            TagFilter::QoL => "QoL",
+            TagFilter::Gameplay => "Gameplay",
+            TagFilter::Audio => "Audio",
+            TagFilter::Visual => "Visual",
+            TagFilter::Framework => "Framework",
+        }
+    }
+
+    pub fn matches(&self, tags: &ModioTags) -> bool {
+        match self {
+            TagFilter::Verified => tags.approval_status == ApprovalStatus::Verified,
+            TagFilter::Approved => tags.approval_status == ApprovalStatus::Approved,
+            TagFilter::Sandbox => tags.approval_status == ApprovalStatus::Sandbox,
+            TagFilter::RequiredByAll => tags.required_status == RequiredStatus::RequiredByAll,
+            TagFilter::QoL => tags.qol,
+            TagFilter::Gameplay => tags.gameplay,
+            TagFilter::Audio => tags.audio,
+            TagFilter::Visual => tags.visual,
+            TagFilter::Framework => tags.framework,
         }
     }
 }
@@ -152,14 +224,86 @@ pub struct App {
     open_profiles: HashSet<String>,
     lint_rid: Option<MessageHandle<()>>,
     lint_report_window: Option<WindowLintReport>,
-    lint_report: Option<LintReport>,
+    /// The last lint report and the profile it was made for.
+    lint_report: Option<(String, LintReport)>,
     lints_toggle_window: Option<WindowLintsToggle>,
+    favorites_window: Option<WindowFavorites>,
+    allowlist_window: Option<WindowAllowlist>,
+    output_locked_window: Option<WindowOutputLocked>,
+    update_diff_window: Option<WindowUpdateDiff>,
+    asset_tree_window: Option<WindowAssetTree>,
+    description_window: Option<WindowDescription>,
+    rename_mod: Option<RenameMod>,
+    priority_overrides_window: Option<WindowPriorityOverrides>,
+    failed_batch: Option<FailedBatch>,
+    /// File changes between versions of a mod, keyed by (current, new) version.
+    update_diffs: HashMap<(ModSpecification, ModSpecification), ModDiff>,
     lint_options: LintOptions,
     cache: CommonMarkCache,
     needs_restart: bool,
     self_update_rid: Option<MessageHandle<SelfUpdateProgress>>,
     original_exe_path: Option<PathBuf>,
     problematic_mod_id: Option<u32>,
+    /// Mods found to be unavailable by the last availability check, with why.
+    unavailable_mods: HashMap<ModSpecification, (LinkProblem, String)>,
+    /// Last operation the watchdog logged diagnostics for, to only log them once.
+    watchdog_logged: Option<RequestID>,
+    /// Profile the current provider credentials were selected for.
+    credentials_profile: String,
+    /// Known incompatible mod combinations, reloaded before every install.
+    incompatibilities: Incompatibilities,
+    /// Mods were uninstalled to launch the game in safe mode and have not been restored yet.
+    safe_mode: bool,
+    /// Row number typed so far to toggle a mod with space.
+    row_number_input: String,
+    /// Indices into the active profile's mods in the order they were last shown.
+    row_order: Vec<usize>,
+    /// Problem with the deployed hook DLL found when launching the game.
+    hook_problem: Option<HookProblem>,
+    /// The game pak changed since mods were installed, see [`crate::game_update`].
+    game_updated: bool,
+    /// Patch paks of other tools found in the game's Paks directory on the last install.
+    other_paks: Vec<OtherPak>,
+    /// Mods were just installed and the game should be launched to check that it still starts.
+    smoke_test_pending: bool,
+    smoke_test_rid: Option<MessageHandle<()>>,
+    notifications_window: bool,
+    /// Notifications dismissed this session, by their text so they come back if it changes.
+    dismissed_notifications: HashSet<String>,
+    /// Usage stats as they were when the stats window was opened.
+    stats_window: Option<UsageStats>,
+}
+
+/// Work which failed in the last batch operation and can be retried on its own.
+enum FailedBatch {
+    /// Mods which could not be resolved while adding them.
+    Resolve {
+        specs: Vec<ModSpecification>,
+        is_dependency: bool,
+    },
+    /// Installing failed. Mods fetched before the failure are cached so only the rest are
+    /// fetched again.
+    Integrate,
+}
+
+/// Long running operations watched for stalls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter)]
+enum Operation {
+    Integrate,
+    UpdateCache,
+    ResolveMods,
+    Lint,
+}
+
+impl Operation {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Operation::Integrate => "Installing mods",
+            Operation::UpdateCache => "Updating cache",
+            Operation::ResolveMods => "Resolving mods",
+            Operation::Lint => "Linting mods",
+        }
+    }
 }
 
 #[derive(Default)]
@@ -174,24 +318,84 @@ struct LintOptions {
     non_asset_files: bool,
     split_asset_pairs: bool,
     unmodified_game_assets: bool,
+    overlapping_mount_points: bool,
+    mint_artifacts: bool,
+    untagged_gameplay: bool,
 }
 
 struct LastAction {
     timestamp: Instant,
     status: LastActionStatus,
+    /// Pak written by a successful install.
+    installed_pak: Option<PathBuf>,
+    /// Error a failure was caused by, kept so its whole chain of sources can be copied.
+    error: Option<Box<dyn std::error::Error>>,
+    /// Additional lines for the copied error details, e.g. the request the error happened in.
+    context: Vec<(&'static str, String)>,
 }
 impl LastAction {
     fn success(msg: String) -> Self {
         Self {
             timestamp: Instant::now(),
             status: LastActionStatus::Success(msg),
+            installed_pak: None,
+            error: None,
+            context: vec![],
         }
     }
     fn failure(msg: String) -> Self {
         Self {
             timestamp: Instant::now(),
             status: LastActionStatus::Failure(msg),
+            installed_pak: None,
+            error: None,
+            context: vec![],
+        }
+    }
+    fn installed(path: PathBuf) -> Self {
+        Self {
+            timestamp: Instant::now(),
+            status: LastActionStatus::Success(format!("integration complete: {}", path.display())),
+            installed_pak: Some(path),
+            error: None,
+            context: vec![],
+        }
+    }
+    /// Failure showing `error` which also keeps it so its details can be copied.
+    fn error(error: impl std::error::Error + 'static) -> Self {
+        Self::failure(error.to_string()).with_error(error)
+    }
+    fn with_error(mut self, error: impl std::error::Error + 'static) -> Self {
+        self.error = Some(Box::new(error));
+        self
+    }
+    fn with_context(mut self, key: &'static str, value: impl ToString) -> Self {
+        self.context.push((key, value.to_string()));
+        self
+    }
+    fn with_mod_id(self, mod_id: Option<u32>) -> Self {
+        match mod_id {
+            Some(mod_id) => self.with_context("mod.io mod id", mod_id),
+            None => self,
+        }
+    }
+    /// Everything known about a failure as plain text for pasting into bug reports.
+    fn error_details(&self) -> Option<String> {
+        let LastActionStatus::Failure(msg) = &self.status else {
+            return None;
+        };
+        let error = self.error.as_ref()?;
+        let mut details = format!("{msg}\n\nerror: {error}\n");
+        let mut source = error.source();
+        while let Some(e) = source {
+            details.push_str(&format!("caused by: {e}\n"));
+            source = e.source();
         }
+        details.push_str(&format!("\nmint version: {}\n", env!("CARGO_PKG_VERSION")));
+        for (key, value) in &self.context {
+            details.push_str(&format!("{key}: {value}\n"));
+        }
+        Some(details)
     }
     fn timeago(&self) -> String {
         let duration = Instant::now().duration_since(self.timestamp);
@@ -218,9 +422,12 @@ impl App {
         args: Option<Vec<String>>,
     ) -> Result<Self, MintError> {
         let (tx, rx) = mpsc::channel(10);
-        let state = State::init(dirs)?;
+        let mut state = State::init(dirs)?;
+        state.apply_startup_profile();
+        let incompatibilities = load_incompatibilities(&state);
+        let credentials_profile = state.mod_data.active_profile.clone();
 
-        Ok(Self {
+        let mut app = Self {
             args,
             tx,
             rx,
@@ -246,17 +453,132 @@ impl App {
             lint_report_window: None,
             lint_report: None,
             lints_toggle_window: None,
+            favorites_window: None,
+            allowlist_window: None,
+            output_locked_window: None,
+            update_diff_window: None,
+            asset_tree_window: None,
+            description_window: None,
+            rename_mod: None,
+            priority_overrides_window: None,
+            failed_batch: None,
+            update_diffs: Default::default(),
+            watchdog_logged: None,
+            credentials_profile,
             lint_options: LintOptions::default(),
             cache: Default::default(),
             needs_restart: false,
             self_update_rid: None,
             original_exe_path: None,
             problematic_mod_id: None,
-        })
+            unavailable_mods: HashMap::new(),
+            incompatibilities,
+            safe_mode: false,
+            row_number_input: String::new(),
+            row_order: vec![],
+            hook_problem: None,
+            game_updated: false,
+            other_paks: vec![],
+            smoke_test_pending: false,
+            smoke_test_rid: None,
+            notifications_window: false,
+            dismissed_notifications: Default::default(),
+            stats_window: None,
+        };
+        app.check_game_updated();
+        Ok(app)
+    }
+
+    /// Warn about enabled mods of a profile which are known not to work together.
+    fn ui_incompatible(&self, ui: &mut Ui, profile: &str) {
+        if self.incompatibilities.is_empty() {
+            return;
+        }
+        let mut specs = vec![];
+        self.state.mod_data.for_each_enabled_mod(profile, |mc| {
+            specs.push(mc.spec.clone());
+        });
+        for found in self
+            .incompatibilities
+            .check_specs(&self.state.store, &specs)
+        {
+            ui.colored_label(
+                ui.visuals().warn_fg_color,
+                format!(
+                    "\u{26A0} Known incompatible: {}",
+                    found.describe(&self.state.store)
+                ),
+            );
+        }
+    }
+
+    /// Explain that config files which could not be read on startup were reset.
+    fn ui_recovered(&mut self, ui: &mut Ui) {
+        if self.state.recovered.is_empty() {
+            return;
+        }
+        for recovered in &self.state.recovered {
+            ui.colored_label(
+                ui.visuals().warn_fg_color,
+                format!(
+                    "\u{26A0} {} could not be read and was reset to defaults. The old file was moved to {}.",
+                    recovered.path.display(),
+                    recovered.backup.display(),
+                ),
+            )
+            .on_hover_text(&recovered.error);
+        }
+        ui.horizontal(|ui| {
+            if let Some(dir) = self.state.recovered[0].backup.parent()
+                && ui.button("📂 Open config folder").clicked()
+            {
+                opener::open(dir).ok();
+            }
+            if ui.button("Dismiss").clicked() {
+                self.state.recovered.clear();
+            }
+        });
+        ui.separator();
+    }
+
+    /// Explain how to get started if `profile` does not contain any mods yet. Returns whether
+    /// anything was shown in place of the mod list.
+    fn ui_empty_state(&mut self, ui: &mut Ui, profile: &str) -> bool {
+        if self
+            .state
+            .mod_data
+            .profiles
+            .get(profile)
+            .is_none_or(|p| !p.mods.is_empty())
+        {
+            return false;
+        }
+
+        ui.vertical_centered(|ui| {
+            ui.add_space(32.);
+            ui.heading("No mods in this profile yet");
+            ui.add_space(8.);
+            ui.label("Paste a mod.io URL or the path of a local .pak or .zip into \"Add mod...\" above and press enter.");
+            ui.label("Mod files can also be dragged onto this window, and mods copied elsewhere can be pasted anywhere with Ctrl+V.");
+            ui.add_space(8.);
+            if self.state.config.drg_pak_path.is_none() {
+                ui.colored_label(
+                    ui.visuals().warn_fg_color,
+                    "DRG install not found. Set the path to FSD-WindowsNoEditor.pak (FSD-WinGDK.pak for the Microsoft Store version) in the settings before installing mods.",
+                );
+            }
+            ui.label("Adding mod.io mods requires a mod.io OAuth token, which is asked for when adding the first one.");
+            ui.add_space(8.);
+            if ui.button("Open settings").clicked() {
+                self.settings_window = Some(WindowSettings::new(&self.state));
+            }
+        });
+        true
     }
 
     fn ui_profile(&mut self, ui: &mut Ui, profile: &str) {
         let sorting_config = self.get_sorting_config();
+        let filter_config = self.get_filter_config();
 
         let ModData {
             profiles, groups, ..
@@ -267,24 +589,51 @@ impl App {
             scroll_to_match: bool,
             btn_remove: Option<usize>,
             add_deps: Option<Vec<ModSpecification>>,
+            toggle_favorite: Option<ModSpecification>,
+            /// (current, newest) versions of a mod to show the changes between.
+            show_update_diff: Option<(ModSpecification, ModSpecification)>,
+            /// Profile indices of the rows drawn so far.
+            row_order: Vec<usize>,
+            /// Mod to leave out when installing the profile again.
+            remove_installed: Option<ModSpecification>,
+            show_description: Option<ModSpecification>,
+            /// Mod to start renaming and its current name.
+            rename: Option<(ModSpecification, String)>,
+            /// Mod to edit the priority overrides of and its current overrides.
+            edit_priority_overrides: Option<(ModSpecification, Vec<PriorityOverride>)>,
+            /// Name and missing dependencies of a mod which was just enabled, to add them right
+            /// away.
+            auto_add_deps: Option<(String, Vec<ModSpecification>)>,
         }
         let mut ctx = Ctx {
             needs_save: false,
             scroll_to_match: self.scroll_to_match,
             btn_remove: None,
             add_deps: None,
+            toggle_favorite: None,
+            show_update_diff: None,
+            row_order: vec![],
+            remove_installed: None,
+            show_description: None,
+            rename: None,
+            edit_priority_overrides: None,
+            auto_add_deps: None,
         };
 
         let ui_profile = |ui: &mut Ui, profile: &mut ModProfile| {
+            let auto_add_dependencies = profile.auto_add_dependencies;
+            // keyed by (profile row, index within group)
             let enabled_specs = profile
                 .mods
                 .iter()
                 .enumerate()
                 .flat_map(|(i, m)| -> Box<dyn Iterator<Item = _>> {
                     match m {
-                        ModOrGroup::Individual(mc) => {
-                            Box::new(mc.enabled.then_some((Some(i), mc.spec.clone())).into_iter())
-                        }
+                        ModOrGroup::Individual(mc) => Box::new(
+                            mc.enabled
+                                .then_some(((i, None), mc.spec.clone()))
+                                .into_iter(),
+                        ),
                         ModOrGroup::Group {
                             group_name,
                             enabled,
@@ -293,15 +642,30 @@ impl App {
                                 .then(|| groups.get(group_name))
                                 .flatten()
                                 .into_iter()
-                                .flat_map(|g| {
-                                    g.mods
-                                        .iter()
-                                        .filter_map(|m| m.enabled.then_some((None, m.spec.clone())))
+                                .flat_map(move |g| {
+                                    g.mods.iter().enumerate().filter_map(move |(j, m)| {
+                                        m.enabled.then_some(((i, Some(j)), m.spec.clone()))
+                                    })
                                 }),
                         ),
                     }
                 })
                 .collect::<Vec<_>>();
+            let enabled_infos = enabled_specs
+                .iter()
+                .filter_map(|(_, spec)| self.state.store.get_mod_info(spec))
+                .collect::<Vec<_>>();
+            let mod_matches_filter = |mc: &ModConfig| {
+                filter_config.matches(self.state.store.get_mod_info(&mc.spec).as_ref())
+            };
+            // groups are listed while filtering as long as one of their mods passes the filter
+            let filtered_groups = groups
+                .iter()
+                .filter(|(_, group)| {
+                    filter_config.is_empty() || group.mods.iter().any(&mod_matches_filter)
+                })
+                .map(|(name, _)| name.clone())
+                .collect::<HashSet<_>>();
 
             let ui_mod_tags = |ctx: &mut Ctx, ui: &mut Ui, info: &ModInfo| {
                 if let Some(ModioTags {
@@ -414,13 +778,35 @@ impl App {
                     if *framework {
                         mk_searchable_modio_tag("Framework", ui, None, None);
                     }
+                } else if info.provider == "modio" {
+                    ui.add_enabled(false, egui::Button::new("Unknown").small())
+                        .on_disabled_hover_text(
+                            "mod.io metadata unavailable, tags and approval status are unknown",
+                        );
+                }
+                if let Some(stats) = &info.modio_stats {
+                    let mut badge = format!("⬇ {}", format_count(stats.downloads));
+                    if let Some(percent) = stats.rating_percent() {
+                        badge.push_str(&format!(" 👍 {percent}%"));
+                    }
+                    ui.add_enabled(
+                        false,
+                        egui::Button::new(badge).small().stroke(egui::Stroke::NONE),
+                    )
+                    .on_disabled_hover_text(format!(
+                        "{} downloads, {} subscribers\n{} positive and {} negative ratings on mod.io",
+                        stats.downloads,
+                        stats.subscribers,
+                        stats.ratings_positive,
+                        stats.ratings_negative,
+                    ));
                 }
             };
 
             let mut ui_mod = |ctx: &mut Ctx,
                               ui: &mut Ui,
-                              _group: Option<&str>,
                               row_index: usize,
+                              group_index: Option<usize>,
                               mc: &mut ModConfig| {
                 if !mc.enabled {
                     let vis = ui.visuals_mut();
@@ -428,12 +814,14 @@ impl App {
                     vis.hyperlink_color = vis.text_color();
                 }
 
+                let mut just_enabled = false;
                 if ui
                     .add(toggle_switch(&mut mc.enabled))
                     .on_hover_text_at_pointer("Enabled?")
                     .changed()
                 {
                     ctx.needs_save = true;
+                    just_enabled = mc.enabled;
                 }
 
                 /*
@@ -446,6 +834,7 @@ impl App {
                 */
 
                 let info = self.state.store.get_mod_info(&mc.spec);
+                let compact = self.state.config.compact_mod_list;
 
                 if let Some(ref info) = info
                     && let Some(modio_id) = info.modio_id
@@ -456,6 +845,13 @@ impl App {
                     ui.add_enabled(false, icon);
                 }
 
+                if let Some((problem, error)) = self.unavailable_mods.get(&mc.spec) {
+                    let icon = egui::Button::new(RichText::new("❌").color(Color32::WHITE))
+                        .fill(Color32::RED);
+                    ui.add_enabled(false, icon)
+                        .on_disabled_hover_text(format!("Unavailable ({problem}): {error}"));
+                }
+
                 if mc.enabled {
                     if let Some(req) = &self.integrate_rid {
                         match req.state.get(&mc.spec) {
@@ -477,93 +873,132 @@ impl App {
                 }
 
                 if let Some(info) = &info {
-                    egui::ComboBox::from_id_salt(row_index)
-                        .selected_text(
-                            self.state
-                                .store
-                                .get_version_name(&mc.spec)
-                                .unwrap_or_default(),
-                        )
-                        .show_ui(ui, |ui| {
-                            ui.selectable_value(
-                                &mut mc.spec.url,
-                                info.spec.url.to_string(),
+                    if !compact {
+                        egui::ComboBox::from_id_salt((row_index, group_index))
+                            .selected_text(
                                 self.state
                                     .store
-                                    .get_version_name(&info.spec)
+                                    .get_version_name(&mc.spec)
                                     .unwrap_or_default(),
-                            );
-                            for version in info.versions.iter().rev() {
+                            )
+                            .show_ui(ui, |ui| {
                                 ui.selectable_value(
                                     &mut mc.spec.url,
-                                    version.url.to_string(),
+                                    info.spec.url.to_string(),
                                     self.state
                                         .store
-                                        .get_version_name(version)
+                                        .get_version_name(&info.spec)
                                         .unwrap_or_default(),
                                 );
+                                for version in info.versions.iter().rev() {
+                                    ui.selectable_value(
+                                        &mut mc.spec.url,
+                                        version.url.to_string(),
+                                        self.state
+                                            .store
+                                            .get_version_name(version)
+                                            .unwrap_or_default(),
+                                    );
+                                }
+                            });
+
+                        if let Some(newest) = info.versions.last()
+                            && *newest != mc.spec
+                            && info.versions.contains(&mc.spec)
+                            && ui
+                                .button(RichText::new("\u{2B06}").color(ui.visuals().warn_fg_color))
+                                .on_hover_text_at_pointer("update available\nshow changes")
+                                .clicked()
+                        {
+                            ctx.show_update_diff = Some((mc.spec.clone(), newest.clone()));
+                        }
+
+                        ui.scope(|ui| {
+                            ui.style_mut().spacing.interact_size.x = 30.;
+                            let dark = ui.visuals().dark_mode;
+                            match mc.priority.cmp(&0) {
+                                std::cmp::Ordering::Less => {
+                                    ui.visuals_mut().override_text_color = Some(if dark {
+                                        Color32::LIGHT_RED
+                                    } else {
+                                        Color32::DARK_RED
+                                    });
+                                }
+                                std::cmp::Ordering::Greater => {
+                                    ui.visuals_mut().override_text_color = Some(if dark {
+                                        Color32::LIGHT_GREEN
+                                    } else {
+                                        Color32::DARK_GREEN
+                                    });
+                                }
+                                _ => {}
                             }
+                            ui.add(
+                                egui::DragValue::new(&mut mc.priority)
+                                    .custom_formatter(|n, _| {
+                                        if n == 0. {
+                                            "-".to_string()
+                                        } else {
+                                            format!("{n}")
+                                        }
+                                    })
+                                    .speed(0.05)
+                                    .range(RangeInclusive::new(-999, 999)),
+                            )
+                            .on_hover_text_at_pointer(match mc.priority_overrides.len() {
+                                0 => "Load Priority\nIn case of asset conflict, mods with higher priority take precedent.\nCan have duplicate values.".to_string(),
+                                n => format!("Load Priority\nIn case of asset conflict, mods with higher priority take precedent.\nCan have duplicate values.\n{n} priority override(s) apply to some files instead."),
+                            });
                         });
 
-                    ui.scope(|ui| {
-                        ui.style_mut().spacing.interact_size.x = 30.;
-                        let dark = ui.visuals().dark_mode;
-                        match mc.priority.cmp(&0) {
-                            std::cmp::Ordering::Less => {
-                                ui.visuals_mut().override_text_color = Some(if dark {
-                                    Color32::LIGHT_RED
-                                } else {
-                                    Color32::DARK_RED
-                                });
-                            }
-                            std::cmp::Ordering::Greater => {
-                                ui.visuals_mut().override_text_color = Some(if dark {
-                                    Color32::LIGHT_GREEN
-                                } else {
-                                    Color32::DARK_GREEN
-                                });
-                            }
-                            _ => {}
+                        if ui
+                            .button("📋")
+                            .on_hover_text_at_pointer("copy URL")
+                            .clicked()
+                        {
+                            ui.output_mut(|o| o.copied_text = mc.spec.url.to_string());
                         }
-                        ui.add(
-                            egui::DragValue::new(&mut mc.priority)
-                                .custom_formatter(|n, _| {
-                                    if n == 0. {
-                                        "-".to_string()
-                                    } else {
-                                        format!("{n}")
-                                    }
-                                })
-                                .speed(0.05)
-                                .range(RangeInclusive::new(-999, 999)),
-                        )
-                        .on_hover_text_at_pointer(
-                            "Load Priority\nIn case of asset conflict, mods with higher priority take precedent.\nCan have duplicate values.",
-                        );
-                    });
 
-                    if ui
-                        .button("📋")
-                        .on_hover_text_at_pointer("copy URL")
-                        .clicked()
-                    {
-                        ui.output_mut(|o| o.copied_text = mc.spec.url.to_string());
+                        let is_favorite = self.state.config.favorites.contains(&info.spec);
+                        if ui
+                            .button(if is_favorite { "★" } else { "☆" })
+                            .on_hover_text_at_pointer(if is_favorite {
+                                "remove from favorites"
+                            } else {
+                                "add to favorites"
+                            })
+                            .clicked()
+                        {
+                            ctx.toggle_favorite = Some(info.spec.clone());
+                        }
                     }
 
                     if mc.enabled {
-                        let is_duplicate = enabled_specs.iter().any(|(i, spec)| {
-                            Some(row_index) != *i && info.spec.satisfies_dependency(spec)
-                        });
-                        if is_duplicate
-                            && ui
-                                .button(
-                                    egui::RichText::new("\u{26A0}")
-                                        .color(ui.visuals().warn_fg_color),
+                        let entry = (row_index, group_index);
+                        let is_duplicate = enabled_specs.iter().any(|(e, _)| *e == entry)
+                            && enabled_specs.iter().any(|(e, spec)| {
+                                *e != entry && info.spec.satisfies_dependency(spec)
+                            });
+                        if is_duplicate {
+                            let button = ui.button(
+                                egui::RichText::new("\u{26A0}").color(ui.visuals().warn_fg_color),
+                            );
+                            if group_index.is_none() {
+                                if button
+                                    .on_hover_text_at_pointer("remove duplicate")
+                                    .clicked()
+                                {
+                                    ctx.btn_remove = Some(row_index);
+                                }
+                            } else if button
+                                .on_hover_text_at_pointer(
+                                    "disable duplicate\nthis affects the group in every profile",
                                 )
-                                .on_hover_text_at_pointer("remove duplicate")
                                 .clicked()
-                        {
-                            ctx.btn_remove = Some(row_index);
+                            {
+                                mc.enabled = false;
+                                ctx.needs_save = true;
+                            }
                         }
 
                         let missing_deps = info
@@ -574,6 +1009,13 @@ impl App {
                             })
                             .collect::<Vec<_>>();
 
+                        if just_enabled && auto_add_dependencies && !missing_deps.is_empty() {
+                            ctx.auto_add_deps = Some((
+                                mc.name(Some(info)).to_string(),
+                                missing_deps.iter().copied().cloned().collect(),
+                            ));
+                        }
+
                         if !missing_deps.is_empty() {
                             let mut msg = "Add missing dependencies:".to_string();
                             for dep in &missing_deps {
@@ -616,7 +1058,7 @@ impl App {
                             }
                             ui.add(img);
                         }
-                        "http" => {
+                        "http" | "http-index" => {
                             ui.label("🌐");
                         }
                         "file" => {
@@ -625,22 +1067,94 @@ impl App {
                         _ => unimplemented!("unimplemented provider kind"),
                     }
 
-                    let search = searchable_text(&info.name, &self.search_string, {
-                        TextFormat {
-                            color: ui.visuals().hyperlink_color,
-                            ..Default::default()
+                    if let Some(rename) = self.rename_mod.as_mut().filter(|r| r.spec == mc.spec) {
+                        let res = ui.add(
+                            egui::TextEdit::singleline(&mut rename.name)
+                                .hint_text(&info.name)
+                                .desired_width(200.0),
+                        );
+                        if std::mem::take(&mut rename.focus) {
+                            res.request_focus();
                         }
-                    });
+                        if res.lost_focus() {
+                            if !ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                                let name = rename.name.trim();
+                                mc.display_name = (!name.is_empty() && name != info.name)
+                                    .then(|| name.to_string());
+                                ctx.needs_save = true;
+                            }
+                            self.rename_mod = None;
+                        }
+                        return;
+                    }
 
-                    let res = ui.hyperlink_to(search.job, &mc.spec.url);
-                    if search.is_match && self.scroll_to_match {
+                    let format = TextFormat {
+                        color: ui.visuals().hyperlink_color,
+                        ..Default::default()
+                    };
+                    let mut search =
+                        searchable_text(mc.name(Some(info)), &self.search_string, format.clone());
+                    // only the name is searched, the version is appended after highlighting
+                    if self.state.config.show_mod_versions
+                        && let Some(version) = self.state.store.get_version_name(&mc.spec)
+                    {
+                        search.job.append(&format!(" ({version})"), 0.0, format);
+                    }
+
+                    let mut res = ui.hyperlink_to(search.job, &mc.spec.url);
+                    if mc.display_name.is_some() {
+                        res = res.on_hover_text(format!("Renamed from {}", info.name));
+                    }
+                    if self.scroll_to_match
+                        && !self.search_string.is_empty()
+                        && mc.matches_search(Some(info), &self.search_string.to_lowercase())
+                    {
                         res.scroll_to_me(None);
                         ctx.scroll_to_match = false;
                     }
-
-                    ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
-                        ui_mod_tags(ctx, ui, info);
+                    if !compact
+                        && ui
+                            .small_button("ℹ")
+                            .on_hover_text_at_pointer("Show description")
+                            .clicked()
+                    {
+                        ctx.show_description = Some(mc.spec.clone());
+                    }
+                    res.context_menu(|ui| {
+                        if ui
+                            .button("Rename")
+                            .on_hover_text("Show a different name for this mod.\nClear the name to use the original one again.")
+                            .clicked()
+                        {
+                            ctx.rename = Some((mc.spec.clone(), mc.name(Some(info)).to_string()));
+                            ui.close_menu();
+                        }
+                        if ui
+                            .button("Priority overrides")
+                            .on_hover_text("Use a different priority for some of this mod's files, e.g. to let it win conflicts over textures but not blueprints.")
+                            .clicked()
+                        {
+                            ctx.edit_priority_overrides =
+                                Some((mc.spec.clone(), mc.priority_overrides.clone()));
+                            ui.close_menu();
+                        }
+                        if mc.enabled
+                            && self.state.config.drg_pak_path.is_some()
+                            && ui
+                                .button("Remove from installed mods")
+                                .on_hover_text("Install the profile again without this mod.\nThe mod stays in the profile.")
+                                .clicked()
+                        {
+                            ctx.remove_installed = Some(mc.spec.clone());
+                            ui.close_menu();
+                        }
                     });
+
+                    if !compact {
+                        ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                            ui_mod_tags(ctx, ui, info);
+                        });
+                    }
                 } else {
                     if ui
                         .button("📋")
@@ -650,7 +1164,12 @@ impl App {
                         ui.output_mut(|o| o.copied_text = mc.spec.url.to_string());
                     }
 
-                    let search = searchable_text(&mc.spec.url, &self.search_string, {
+                    ui.label(RichText::new("❓").color(ui.visuals().warn_fg_color))
+                        .on_hover_text_at_pointer(
+                            "Mod metadata unavailable\nIt will be fetched the next time the mod is resolved or the cache is updated.",
+                        );
+
+                    let search = searchable_text(mc.name(None), &self.search_string, {
                         TextFormat {
                             color: ui.visuals().hyperlink_color,
                             ..Default::default()
@@ -667,21 +1186,46 @@ impl App {
 
             let mut ui_item =
                 |ctx: &mut Ctx, ui: &mut Ui, mc: &mut ModOrGroup, row_index: usize| {
+                    ctx.row_order.push(row_index);
+                    ui.label(
+                        RichText::new(format!("{:>3}", ctx.row_order.len()))
+                            .monospace()
+                            .weak(),
+                    )
+                    .on_hover_text_at_pointer("Type this number and press space to toggle the mod");
+
+                    let dependents = match mc {
+                        ModOrGroup::Individual(mc) if mc.enabled => {
+                            crate::dependents(&enabled_infos, &mc.spec)
+                        }
+                        _ => vec![],
+                    };
                     ui.scope(|ui| {
                         ui.visuals_mut().widgets.hovered.weak_bg_fill = colors::DARK_RED;
                         ui.visuals_mut().widgets.active.weak_bg_fill = colors::DARKER_RED;
-                        if ui
-                            .add(Button::new(" 🗑 "))
-                            .on_hover_text_at_pointer("Delete mod")
-                            .clicked()
-                        {
+                        let (button, hover) = if dependents.is_empty() {
+                            (Button::new(" 🗑 "), "Delete mod".to_string())
+                        } else {
+                            let mut msg = "Delete mod\n\u{26A0} required by:".to_string();
+                            for info in &dependents {
+                                msg.push('\n');
+                                msg.push_str(&info.name);
+                            }
+                            (
+                                Button::new(
+                                    egui::RichText::new(" 🗑 ").color(ui.visuals().warn_fg_color),
+                                ),
+                                msg,
+                            )
+                        };
+                        if ui.add(button).on_hover_text_at_pointer(hover).clicked() {
                             ctx.btn_remove = Some(row_index);
                         };
                     });
 
                     match mc {
                         ModOrGroup::Individual(mc) => {
-                            ui_mod(ctx, ui, None, row_index, mc);
+                            ui_mod(ctx, ui, row_index, None, mc);
                         }
                         ModOrGroup::Group {
                             ref group_name,
@@ -701,29 +1245,43 @@ impl App {
                                     .mods
                                     .iter_mut()
                                     .enumerate()
+                                    .filter(|(_, m)| mod_matches_filter(&**m))
                                 {
-                                    ui.horizontal(|ui| ui_mod(ctx, ui, Some(group_name), index, m));
+                                    ui.horizontal(|ui| ui_mod(ctx, ui, row_index, Some(index), m));
                                 }
                             });
                         }
                     }
                 };
 
-            if let Some(sorting_config) = sorting_config {
-                let comp = sort_mods(sorting_config);
+            if sorting_config.is_some() || !filter_config.is_empty() {
+                ui.label(
+                    RichText::new("Clear the sorting and filters to reorder mods by dragging them")
+                        .weak(),
+                );
+                let comp = sorting_config.map(sort_mods);
                 profile
                     .mods
                     .iter_mut()
                     .map(|m| {
                         // fetch ModInfo up front because doing it in the comparator is slow
-                        let ModOrGroup::Individual(mc) = m else {
-                            unimplemented!("Item is not Individual \n{:?}", m);
+                        let info = match &*m {
+                            ModOrGroup::Individual(mc) => self.state.store.get_mod_info(&mc.spec),
+                            ModOrGroup::Group { .. } => None,
                         };
-                        let info = self.state.store.get_mod_info(&mc.spec);
                         (m, info)
                     })
                     .enumerate()
-                    .sorted_by(|a, b| comp((a.1 .0, a.1 .1.as_ref()), (b.1 .0, b.1 .1.as_ref())))
+                    .filter(|(_, (m, info))| match m {
+                        ModOrGroup::Individual(_) => filter_config.matches(info.as_ref()),
+                        ModOrGroup::Group { group_name, .. } => {
+                            filtered_groups.contains(group_name)
+                        }
+                    })
+                    .sorted_by(|a, b| match &comp {
+                        Some(comp) => comp((a.1 .0, a.1 .1.as_ref()), (b.1 .0, b.1 .1.as_ref())),
+                        None => Ordering::Equal,
+                    })
                     .enumerate()
                     .for_each(|(visual_index, (store_index, item))| {
                         let mut frame = egui::Frame::none();
@@ -784,7 +1342,50 @@ impl App {
             self.problematic_mod_id = None;
         }
 
+        if let Some((dependent, deps)) = ctx.auto_add_deps {
+            message::ResolveMods::send_auto_dependencies(self, ui.ctx(), deps, dependent);
+        }
+
+        if let Some(spec) = ctx.toggle_favorite {
+            self.toggle_favorite(spec);
+        }
+
+        if let Some(versions) = ctx.show_update_diff {
+            self.open_update_diff(ui.ctx(), versions);
+        }
+
+        if let Some(spec) = ctx.show_description {
+            self.description_window = Some(WindowDescription { spec });
+        }
+
+        if let Some(spec) = ctx.remove_installed {
+            self.install_mods_except(ui.ctx(), &[spec]);
+        }
+
+        if let Some((spec, name)) = ctx.rename {
+            self.rename_mod = Some(RenameMod {
+                spec,
+                name,
+                focus: true,
+            });
+        }
+
+        if let Some((spec, overrides)) = ctx.edit_priority_overrides {
+            self.priority_overrides_window = Some(WindowPriorityOverrides {
+                profile: profile.to_string(),
+                spec,
+                rows: overrides
+                    .into_iter()
+                    .map(|o| (o.pattern, o.priority))
+                    .collect(),
+                error: None,
+            });
+        }
+
         self.scroll_to_match = ctx.scroll_to_match;
+        if profile == self.state.mod_data.active_profile {
+            self.row_order = ctx.row_order;
+        }
 
         if ctx.needs_save {
             self.state.mod_data.save().unwrap();
@@ -792,35 +1393,591 @@ impl App {
     }
 
     fn parse_mods(&self) -> Vec<ModSpecification> {
-        self.resolve_mod
-            .lines()
-            .map(|l| l.trim())
-            .filter(|l| !l.is_empty())
-            .map(|l| ModSpecification::new(l.to_string()))
-            .collect()
+        parse_mods(&self.resolve_mod)
     }
 
-    fn build_mod_string(mods: &Vec<ModConfig>) -> String {
-        let mut string = String::new();
-        for m in mods {
-            if m.enabled {
-                string.push_str(&m.spec.url);
-                string.push('\n');
+    fn export_active_profile(&mut self) {
+        let profile = self.state.mod_data.active_profile.clone();
+        let Some(path) = rfd::FileDialog::new()
+            .set_file_name(format!("{profile}.profile.json"))
+            .add_filter("Profile", &["json"])
+            .save_file()
+        else {
+            return;
+        };
+        let result = Modpack::export_profile(&self.state.mod_data, &profile)
+            .and_then(|modpack| modpack.write(&path))
+            .map_err(|e| e.to_string());
+        self.last_action = Some(match result {
+            Ok(()) => LastAction::success(format!(
+                "Exported profile {profile:?} to {}",
+                path.display()
+            )),
+            Err(e) => LastAction::failure(format!("Failed to export profile: {e}")),
+        });
+    }
+
+    fn import_profile(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("Profile", &["json", "toml"])
+            .pick_file()
+        else {
+            return;
+        };
+        let result = Modpack::read(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|modpack| {
+                self.state
+                    .import_profile(modpack, None)
+                    .map_err(|e| e.to_string())
+            });
+        self.last_action = Some(match result {
+            Ok(name) => {
+                self.state.mod_data.active_profile = name.clone();
+                self.state.mod_data.save().unwrap();
+                LastAction::success(format!("Imported profile {name:?}"))
             }
+            Err(e) => LastAction::failure(format!("Failed to import profile: {e}")),
+        });
+    }
+
+    /// Problems with the shape of the mod.io links in the add box, see [`validate_url`].
+    fn url_format_errors(&self) -> Vec<String> {
+        self.parse_mods()
+            .iter()
+            .filter_map(|spec| validate_url(&spec.url).err())
+            .map(|e| e.to_string())
+            .collect()
+    }
+
+    /// Resolve and add the mods in the add box, unless some of them are malformed mod.io links
+    /// which are left in the box to be fixed.
+    fn resolve_added_mods(&mut self, ctx: &egui::Context) {
+        if self.url_format_errors().is_empty() {
+            message::ResolveMods::send(self, ctx, self.parse_mods(), false);
         }
-        string
     }
 
-    fn show_update_window(&mut self, ctx: &egui::Context) {
-        if let (Some(update), Some(update_time)) =
-            (self.available_update.as_ref(), self.show_update_time)
+    /// Editable notes and options of the profile, shown above its mods.
+    fn ui_profile_description(&mut self, ui: &mut Ui, profile: &str) {
+        let Some(p) = self.state.mod_data.profiles.get_mut(profile) else {
+            return;
+        };
+        let res = ui.add(
+            egui::TextEdit::multiline(&mut p.description)
+                .desired_rows(1)
+                .desired_width(f32::INFINITY)
+                .hint_text("Describe this profile, e.g. hardcore co-op build"),
+        );
+        if res.changed() {
+            self.state.mod_data.save().unwrap();
+        }
+        if ui
+            .checkbox(
+                &mut p.auto_add_dependencies,
+                "Add dependencies when enabling a mod",
+            )
+            .on_hover_text(
+                "Add or enable the dependencies a mod suggests as soon as it is enabled, instead of only warning about them",
+            )
+            .changed()
         {
-            let now = SystemTime::now();
-            let wait_time = Duration::from_secs(10);
-            egui::Area::new("available-update-overlay".into())
-                .movable(false)
-                .fixed_pos(Pos2::ZERO)
-                .order(egui::Order::Background)
+            self.state.mod_data.save().unwrap();
+        }
+    }
+
+    /// Enable or disable the mod or group shown in row `number` (1-based) of the active profile.
+    fn toggle_row(&mut self, number: usize) {
+        let Some(index) = number
+            .checked_sub(1)
+            .and_then(|i| self.row_order.get(i))
+            .copied()
+        else {
+            return;
+        };
+        let active_profile = self.state.mod_data.active_profile.clone();
+        let Some(profile) = self.state.mod_data.profiles.get_mut(&active_profile) else {
+            return;
+        };
+        match profile.mods.get_mut(index) {
+            Some(ModOrGroup::Individual(mc)) => mc.enabled = !mc.enabled,
+            Some(ModOrGroup::Group { enabled, .. }) => *enabled = !*enabled,
+            None => return,
+        }
+        self.state.mod_data.save().unwrap();
+    }
+
+    fn open_update_diff(
+        &mut self,
+        ctx: &egui::Context,
+        versions: (ModSpecification, ModSpecification),
+    ) {
+        // comparisons are kept for the session so reopening is instant
+        let rid = (!self.update_diffs.contains_key(&versions)).then(|| {
+            message::DiffModVersions::send(
+                &mut self.request_counter,
+                self.state.store.clone(),
+                versions.clone(),
+                self.state.config.pak_key.clone(),
+                self.tx.clone(),
+                ctx.clone(),
+            )
+        });
+        self.update_diff_window = Some(WindowUpdateDiff {
+            versions,
+            rid,
+            err: None,
+        });
+    }
+
+    fn open_asset_tree(&mut self, ctx: &egui::Context) {
+        let active_profile = &self.state.mod_data.active_profile;
+        let mods = self.state.integration_order(active_profile);
+        let mut options: IntegrateOptions = self.state.config.deref().into();
+        let (rid, err) = match self.state.mod_priorities(active_profile, &mods) {
+            Ok(priorities) => {
+                options.priorities = priorities;
+                let rid = message::CollectAssets::send(
+                    &mut self.request_counter,
+                    self.state.store.clone(),
+                    mods,
+                    self.state.config.pak_key.clone(),
+                    options,
+                    self.tx.clone(),
+                    ctx.clone(),
+                );
+                (Some(rid), None)
+            }
+            Err(e) => (None, Some(e.to_string())),
+        };
+        self.asset_tree_window = Some(WindowAssetTree {
+            rid,
+            tree: None,
+            err,
+            filter: String::new(),
+            overrides_only: false,
+            shown: None,
+        });
+    }
+
+    fn install_mods(&mut self, ctx: &egui::Context) {
+        self.install_mods_except(ctx, &[]);
+    }
+
+    /// Install the active profile without the mods matching `except`, which stay in the profile.
+    fn install_mods_except(&mut self, ctx: &egui::Context, except: &[ModSpecification]) {
+        let active_profile = self.state.mod_data.active_profile.clone();
+        let mut mods = self.state.integration_order(&active_profile);
+        if !except.is_empty() {
+            mods = crate::without_mods(&mods, except).0;
+        }
+
+        self.incompatibilities = load_incompatibilities(&self.state);
+        for found in self.incompatibilities.check_specs(&self.state.store, &mods) {
+            warn!(
+                "installing known incompatible mods: {}",
+                found.describe(&self.state.store)
+            );
+        }
+
+        let pak_path = self.state.config.drg_pak_path.as_ref().unwrap();
+        self.other_paks =
+            match DRGInstallation::from_pak_path(pak_path).map(|i| find_other_paks(&i)) {
+                Ok(Ok(paks)) => paks,
+                Ok(Err(e)) => {
+                    warn!("failed to look for other paks: {e}");
+                    vec![]
+                }
+                Err(_) => vec![],
+            };
+        for pak in &self.other_paks {
+            warn!("other pak in the game's Paks directory: {pak}");
+        }
+
+        let mut options: IntegrateOptions = self.state.config.deref().into();
+        options.priorities = match self.state.mod_priorities(&active_profile, &mods) {
+            Ok(priorities) => priorities,
+            Err(e) => {
+                self.last_action = Some(LastAction::failure(format!("Failed to install: {e}")));
+                return;
+            }
+        };
+
+        self.last_action = None;
+        self.safe_mode = false;
+        let request = message::IntegrateRequest {
+            mods,
+            fsd_pak: self.state.config.drg_pak_path.as_ref().unwrap().clone(),
+            config: self.state.config.deref().into(),
+            pak_key: self.state.config.pak_key.clone(),
+            install_hook: !self.state.config.skip_hook,
+            log_dir: self
+                .state
+                .config
+                .verbose_integrate
+                .then(|| self.state.dirs.data_dir.clone()),
+            auto_downgrade: self.state.config.auto_downgrade,
+            options,
+            hooks: self.state.config.integrate_hooks(&active_profile),
+            hash_game_pak: self.state.config.hash_game_pak,
+        };
+        self.integrate_rid = Some(message::Integrate::send(
+            &mut self.request_counter,
+            self.state.store.clone(),
+            request,
+            self.state.config.last_install_hash.clone(),
+            self.tx.clone(),
+            ctx.clone(),
+        ));
+        self.problematic_mod_id = None;
+    }
+
+    fn uninstall_mods(&self, pak_path: &Path) -> Result<(), snafu::Whatever> {
+        let mut mods = HashSet::default();
+        self.state
+            .mod_data
+            .for_each_enabled_mod(&self.state.mod_data.active_profile, |mc| {
+                if let Some(modio_id) = self
+                    .state
+                    .store
+                    .get_mod_info(&mc.spec)
+                    .and_then(|i| i.modio_id)
+                {
+                    mods.insert(modio_id);
+                }
+            });
+        uninstall(pak_path, mods, !self.state.config.skip_hook)
+    }
+
+    /// Look for a broken hook DLL, which makes the game silently start without mods. Only done if
+    /// mods are installed and mint manages the hook.
+    fn check_hook_before_launch(&mut self) {
+        self.hook_problem = None;
+        let Some(pak_path) = &self.state.config.drg_pak_path else {
+            return;
+        };
+        if self.state.config.skip_hook
+            || !DRGInstallation::from_pak_path(pak_path).is_ok_and(|i| i.mods_pak().exists())
+        {
+            return;
+        }
+        match check_hook(pak_path) {
+            Ok(problem) => self.hook_problem = problem,
+            Err(e) => warn!("failed to check hook DLL: {e}"),
+        }
+        if let Some(problem) = self.hook_problem {
+            warn!("{problem}");
+            self.last_action = Some(LastAction::failure(format!(
+                "The {problem}, mods may not load. Reinstall the hook or install mods again."
+            )));
+        }
+    }
+
+    /// Deploy the hook DLL again without installing mods and check that it is fine afterwards.
+    fn reinstall_hook(&mut self) {
+        let Some(pak_path) = &self.state.config.drg_pak_path else {
+            return;
+        };
+        self.last_action = Some(match reinstall_hook(pak_path) {
+            Ok(None) => {
+                self.hook_problem = None;
+                LastAction::success("Reinstalled hook DLL".to_string())
+            }
+            Ok(Some(problem)) => {
+                self.hook_problem = Some(problem);
+                LastAction::failure(format!("Reinstalled hook DLL, but the {problem}"))
+            }
+            Err(e) => LastAction::failure(format!("Failed to reinstall hook: {e}")).with_error(e),
+        });
+    }
+
+    /// Active warnings for the notification center which were not dismissed.
+    fn notifications(&self) -> Vec<Notification> {
+        let store = &self.state.store;
+        let mut enabled = vec![];
+        self.state
+            .mod_data
+            .for_each_enabled_mod(&self.state.mod_data.active_profile, |mc| {
+                enabled.push((mc.clone(), store.get_mod_info(&mc.spec)))
+            });
+        let is_enabled = |spec: &ModSpecification| enabled.iter().any(|(mc, _)| mc.spec == *spec);
+        let mod_name =
+            |spec: &ModSpecification| match enabled.iter().find(|(mc, _)| mc.spec == *spec) {
+                Some((mc, info)) => mc.name(info.as_ref()).to_string(),
+                None => spec.url.clone(),
+            };
+
+        let mut notifications = vec![];
+        for (mc, info) in &enabled {
+            let name = mc.name(info.as_ref()).to_string();
+            if let Some(info) = info {
+                let deps = info
+                    .suggested_dependencies
+                    .iter()
+                    .filter(|d| !enabled.iter().any(|(e, _)| e.spec.satisfies_dependency(d)))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                if !deps.is_empty() {
+                    notifications.push(Notification::MissingDependencies {
+                        name: name.clone(),
+                        deps,
+                    });
+                }
+                if let Some(newest) = info.versions.last()
+                    && *newest != mc.spec
+                    && info.versions.contains(&mc.spec)
+                {
+                    notifications.push(Notification::ModUpdate {
+                        name: name.clone(),
+                        versions: (mc.spec.clone(), newest.clone()),
+                    });
+                }
+            }
+            if let Some((problem, error)) = self.unavailable_mods.get(&mc.spec) {
+                notifications.push(Notification::Unavailable {
+                    name,
+                    problem: *problem,
+                    error: error.clone(),
+                });
+            }
+        }
+
+        if let Some((_, report)) = self
+            .lint_report
+            .as_ref()
+            .filter(|(profile, _)| *profile == self.state.mod_data.active_profile)
+            && let Some(conflicting_mods) = &report.conflicting_mods
+        {
+            let profile = self.state.mod_data.get_active_profile();
+            let unacknowledged = conflicting_mods
+                .iter()
+                .filter(|(path, mods)| !profile.is_conflict_acknowledged(path, *mods));
+            for ((a, b), files) in conflict_pairs(unacknowledged) {
+                if is_enabled(&a) && is_enabled(&b) {
+                    notifications.push(Notification::Conflict {
+                        mods: (mod_name(&a), mod_name(&b)),
+                        files: files.len(),
+                    });
+                }
+            }
+        }
+
+        if let Some(problem) = self.hook_problem {
+            notifications.push(Notification::Hook(problem));
+        }
+        if self.game_updated {
+            notifications.push(Notification::GameUpdated);
+        }
+        notifications.extend(self.other_paks.iter().cloned().map(Notification::OtherPak));
+        if let Some(release) = &self.available_update {
+            notifications.push(Notification::MintUpdate {
+                tag: release.tag_name.clone(),
+                url: release.html_url.clone(),
+            });
+        }
+
+        notifications.retain(|n| !self.dismissed_notifications.contains(&n.to_string()));
+        notifications
+    }
+
+    fn run_notification_action(&mut self, ctx: &egui::Context, action: NotificationAction) {
+        match action {
+            NotificationAction::AddDependencies(deps) => {
+                message::ResolveMods::send(self, ctx, deps, true);
+            }
+            NotificationAction::ShowConflicts => {
+                self.lint_report_window = Some(WindowLintReport::default());
+            }
+            NotificationAction::ShowChanges(versions) => self.open_update_diff(ctx, versions),
+            NotificationAction::ReinstallHook => self.reinstall_hook(),
+            NotificationAction::Install => self.install_mods(ctx),
+            NotificationAction::OpenPaksDirectory => {
+                if let Some(Ok(installation)) = self
+                    .state
+                    .config
+                    .drg_pak_path
+                    .as_ref()
+                    .map(DRGInstallation::from_pak_path)
+                {
+                    opener::open(installation.paks_path()).ok();
+                }
+            }
+            NotificationAction::OpenUrl(url) => ctx.output_mut(|o| {
+                o.open_url = Some(egui::output::OpenUrl { url, new_tab: true });
+            }),
+        }
+    }
+
+    fn show_notifications(&mut self, ctx: &egui::Context) {
+        if !self.notifications_window {
+            return;
+        }
+        let notifications = self.notifications();
+        let mut open = true;
+        let mut action = None;
+        let mut jump_to = None;
+        let mut dismiss = None;
+        egui::Window::new("Notifications")
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                if notifications.is_empty() {
+                    ui.label("Nothing to report");
+                }
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    for notification in &notifications {
+                        ui.horizontal(|ui| {
+                            if ui.button("✖").on_hover_text("Dismiss").clicked() {
+                                dismiss = Some(notification.to_string());
+                            }
+                            if let Some(name) = notification.mod_name()
+                                && ui.button("Jump to mod").clicked()
+                            {
+                                jump_to = Some(name.to_string());
+                            }
+                            if let Some((label, notification_action)) = notification.action()
+                                && ui.button(label).clicked()
+                            {
+                                action = Some(notification_action);
+                            }
+                            ui.colored_label(ui.visuals().warn_fg_color, notification.to_string());
+                        });
+                    }
+                });
+                if !self.dismissed_notifications.is_empty() && ui.button("Show dismissed").clicked()
+                {
+                    self.dismissed_notifications.clear();
+                }
+            });
+
+        if let Some(text) = dismiss {
+            self.dismissed_notifications.insert(text);
+        }
+        if let Some(name) = jump_to {
+            self.search_string = name;
+            self.scroll_to_match = true;
+        }
+        if let Some(action) = action {
+            self.run_notification_action(ctx, action);
+        }
+        if !open {
+            self.notifications_window = false;
+        }
+    }
+
+    /// Look for a game update since mods were installed, which leaves them built against the
+    /// previous version of the game.
+    fn check_game_updated(&mut self) {
+        let Some(pak_path) = &self.state.config.drg_pak_path else {
+            return;
+        };
+        self.game_updated =
+            match game_updated(pak_path, self.state.config.game_pak_baseline.as_ref()) {
+                Ok(updated) => updated,
+                Err(e) => {
+                    warn!("failed to check for game updates: {e}");
+                    false
+                }
+            };
+        if self.game_updated {
+            warn!("the game was updated since mods were installed");
+            self.last_action = Some(LastAction::failure(
+                "DRG was updated since mods were installed, install them again".to_string(),
+            ));
+        }
+    }
+
+    /// Launch the game to check that it starts with the mods which were just installed, see
+    /// [`crate::smoke_test`].
+    fn start_smoke_test(&mut self, ctx: &egui::Context) {
+        let skipped = |reason: &str| {
+            warn!("smoke test skipped: {reason}");
+            Some(LastAction::failure(format!(
+                "Mods installed, but the smoke test was skipped: {reason}"
+            )))
+        };
+        let Some(args) = &self.args else {
+            self.last_action = skipped("mint has to be launched through Steam to start the game");
+            return;
+        };
+        if self.state.config.skip_hook {
+            self.last_action = skipped("it needs the hook, which is not deployed");
+            return;
+        }
+        let Some(installation) = self
+            .state
+            .config
+            .drg_pak_path
+            .as_ref()
+            .and_then(|pak_path| DRGInstallation::from_pak_path(pak_path).ok())
+        else {
+            self.last_action = skipped("the game could not be found");
+            return;
+        };
+        let command = launch_command(args, &self.state.config.launch_args);
+        message::SmokeTest::send(self, ctx, installation, command);
+    }
+
+    fn lint_active_profile(&mut self, ctx: &egui::Context, lints: BTreeSet<LintId>) {
+        let mut mods = Vec::new();
+        self.state
+            .mod_data
+            .for_each_enabled_mod(&self.state.mod_data.active_profile, |mc| {
+                mods.push(mc.spec.clone());
+            });
+
+        self.lint_report = None;
+        let request = message::LintRequest {
+            profile: self.state.mod_data.active_profile.clone(),
+            mods,
+            enabled_lints: lints,
+            game_pak_path: self.state.config.drg_pak_path.clone(),
+            pak_key: self.state.config.pak_key.clone(),
+            all_archive_paks: self.state.config.all_archive_paks,
+            cache_dir: self.state.dirs.cache_dir.clone(),
+        };
+        self.lint_rid = Some(message::LintMods::send(
+            &mut self.request_counter,
+            self.state.store.clone(),
+            request,
+            self.tx.clone(),
+            ctx.clone(),
+        ));
+        self.problematic_mod_id = None;
+        self.lint_report_window = Some(WindowLintReport::default());
+    }
+
+    fn toggle_favorite(&mut self, spec: ModSpecification) {
+        let favorites = &mut self.state.config.favorites;
+        if let Some(i) = favorites.iter().position(|f| *f == spec) {
+            favorites.remove(i);
+        } else {
+            favorites.push(spec);
+        }
+        self.state.config.save().unwrap();
+    }
+
+    fn build_mod_string(mods: &Vec<ModConfig>) -> String {
+        let mut string = String::new();
+        for m in mods {
+            if m.enabled {
+                string.push_str(&m.spec.url);
+                string.push('\n');
+            }
+        }
+        string
+    }
+
+    fn show_update_window(&mut self, ctx: &egui::Context) {
+        if let (Some(update), Some(update_time)) =
+            (self.available_update.as_ref(), self.show_update_time)
+        {
+            let now = SystemTime::now();
+            let wait_time = Duration::from_secs(10);
+            egui::Area::new("available-update-overlay".into())
+                .movable(false)
+                .fixed_pos(Pos2::ZERO)
+                .order(egui::Order::Background)
                 .show(ctx, |ui| {
                     egui::Frame::none()
                         .fill(Color32::from_rgba_unmultiplied(0, 0, 0, 127))
@@ -905,13 +2062,16 @@ impl App {
         while let Ok((rid, res)) = window.rx.try_recv() {
             if window.check_rid.as_ref().is_some_and(|r| rid == r.0) {
                 match res {
+                    Ok(()) if window.check_is_test => window.test_passed = true,
                     Ok(()) => {
                         let window = self.window_provider_parameters.take().unwrap();
+                        let name = match window.credentials_name.trim() {
+                            "" => "default".to_string(),
+                            name => name.to_string(),
+                        };
                         self.state
-                            .config
-                            .provider_parameters
-                            .insert(window.factory.id.to_string(), window.parameters);
-                        self.state.config.save().unwrap();
+                            .save_credentials(window.factory.id, name, window.parameters)
+                            .unwrap();
                         return;
                     }
                     Err(e) => {
@@ -924,12 +2084,23 @@ impl App {
 
         let mut open = true;
         let mut check = false;
+        let mut test = false;
         egui::Window::new(format!("Configure {} provider", window.factory.id))
             .open(&mut open)
             .resizable(false)
             .show(ctx, |ui| {
                 ui.add_enabled_ui(window.check_rid.is_none(), |ui| {
                     egui::Grid::new("grid").num_columns(2).show(ui, |ui| {
+                        ui.label("Name").on_hover_text(
+                            "Save under different names to switch between multiple accounts",
+                        );
+                        ui.add(
+                            egui::TextEdit::singleline(&mut window.credentials_name)
+                                .hint_text("default")
+                                .desired_width(200.0),
+                        );
+                        ui.end_row();
+
                         for p in window.factory.parameters {
                             if let Some(link) = p.link {
                                 ui.hyperlink_to(p.name, link).on_hover_text(p.description);
@@ -943,6 +2114,9 @@ impl App {
                                 .password(true)
                                 .desired_width(200.0),
                             );
+                            if res.changed() {
+                                window.test_passed = false;
+                            }
                             if is_committed(&res) {
                                 check = true;
                             }
@@ -954,19 +2128,30 @@ impl App {
                         if ui.button("Save").clicked() {
                             check = true;
                         }
+                        if ui
+                            .button("Test")
+                            .on_hover_text("Check the parameters without saving them")
+                            .clicked()
+                        {
+                            test = true;
+                        }
                         if window.check_rid.is_some() {
                             ui.spinner();
                         }
                         if let Some(error) = &window.check_error {
                             ui.colored_label(ui.visuals().error_fg_color, error);
+                        } else if window.test_passed {
+                            ui.colored_label(Color32::GREEN, "✔ Parameters are valid");
                         }
                     });
                 });
             });
         if !open {
             self.window_provider_parameters = None;
-        } else if check {
+        } else if check || test {
             window.check_error = None;
+            window.test_passed = false;
+            window.check_is_test = !check;
             let tx = window.tx.clone();
             let ctx = ctx.clone();
             let rid = self.request_counter.next();
@@ -974,7 +2159,12 @@ impl App {
             let params = window.parameters.clone();
             let factory = window.factory;
             let handle = tokio::task::spawn(async move {
-                let res = store.add_provider_checked(factory, &params).await;
+                // a test leaves the provider in use and the saved parameters untouched
+                let res = if check {
+                    store.add_provider_checked(factory, &params).await
+                } else {
+                    ModStore::check_provider(factory, &params).await
+                };
                 tx.send((rid, res)).await.unwrap();
                 ctx.request_repaint();
             });
@@ -982,6 +2172,78 @@ impl App {
         }
     }
 
+    /// Quick switching between saved credentials, globally and for the active profile.
+    fn ui_provider_credentials(
+        ui: &mut Ui,
+        state: &mut State,
+        last_action: &mut Option<LastAction>,
+        factory: &'static ProviderFactory,
+    ) {
+        let names = state
+            .config
+            .provider_credentials
+            .get(factory.id)
+            .map(|c| c.keys().cloned().collect::<Vec<_>>())
+            .unwrap_or_default();
+        if names.len() < 2 {
+            return;
+        }
+
+        let mut global = state.config.active_credentials.get(factory.id).cloned();
+        egui::ComboBox::from_id_salt(("credentials-global", factory.id))
+            .selected_text(global.as_deref().unwrap_or("-"))
+            .show_ui(ui, |ui| {
+                for name in &names {
+                    ui.selectable_value(&mut global, Some(name.clone()), name);
+                }
+            })
+            .response
+            .on_hover_text("Credentials used by default");
+
+        let profile = state.mod_data.get_active_profile();
+        let mut for_profile = profile.credentials.get(factory.id).cloned();
+        egui::ComboBox::from_id_salt(("credentials-profile", factory.id))
+            .selected_text(for_profile.as_deref().unwrap_or("Same as default"))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut for_profile, None, "Same as default");
+                for name in &names {
+                    ui.selectable_value(&mut for_profile, Some(name.clone()), name);
+                }
+            })
+            .response
+            .on_hover_text(format!(
+                "Credentials used while profile \"{}\" is active",
+                state.mod_data.active_profile
+            ));
+
+        let mut changed = false;
+        if global != state.config.active_credentials.get(factory.id).cloned()
+            && let Some(global) = global
+        {
+            state
+                .config
+                .active_credentials
+                .insert(factory.id.to_string(), global);
+            state.config.save().unwrap();
+            changed = true;
+        }
+        let profile = state.mod_data.get_active_profile_mut();
+        if for_profile != profile.credentials.get(factory.id).cloned() {
+            match for_profile {
+                Some(name) => profile.credentials.insert(factory.id.to_string(), name),
+                None => profile.credentials.remove(factory.id),
+            };
+            state.mod_data.save().unwrap();
+            changed = true;
+        }
+        if changed && let Err(e) = state.apply_credentials() {
+            *last_action = Some(
+                LastAction::failure(format!("failed to switch provider credentials: {e}"))
+                    .with_error(e),
+            );
+        }
+    }
+
     fn show_profile_windows(&mut self, ctx: &egui::Context) {
         let mut to_remove = vec![];
         for profile in &self.open_profiles.clone() {
@@ -1002,6 +2264,39 @@ impl App {
 
     fn show_settings(&mut self, ctx: &egui::Context) {
         if let Some(window) = &mut self.settings_window {
+            if let Some(edited) = window.drg_pak_path_edited {
+                let elapsed = edited.elapsed();
+                if elapsed >= DRG_PAK_PATH_CHECK_DELAY {
+                    window.drg_pak_path_edited = None;
+                    window.drg_pak_path_check_rid = Some(message::ValidatePakPath::send(
+                        &mut self.request_counter,
+                        window.drg_pak_path.clone(),
+                        self.state.config.skip_pak_validation,
+                        self.tx.clone(),
+                        ctx.clone(),
+                    ));
+                } else {
+                    ctx.request_repaint_after(DRG_PAK_PATH_CHECK_DELAY - elapsed);
+                }
+            }
+
+            if window.disk_usage.is_none()
+                && window.disk_usage_rid.is_none()
+                && window.clear_cache_rid.is_none()
+            {
+                window.disk_usage_rid = Some(message::ComputeDiskUsage::send(
+                    &mut self.request_counter,
+                    [
+                        self.state.dirs.config_dir.clone(),
+                        self.state.dirs.cache_dir.clone(),
+                        self.state.store.blob_cache_path().to_path_buf(),
+                        self.state.dirs.data_dir.clone(),
+                    ],
+                    self.tx.clone(),
+                    ctx.clone(),
+                ));
+            }
+
             let mut open = true;
             let mut try_save = false;
             egui::Window::new("Settings")
@@ -1029,6 +2324,8 @@ impl App {
                             );
                             if res.changed() {
                                 window.drg_pak_path_err = None;
+                                window.drg_pak_path_valid = false;
+                                window.drg_pak_path_edited = Some(Instant::now());
                             }
                             if is_committed(&res) {
                                 try_save = true;
@@ -1040,82 +2337,418 @@ impl App {
                                 {
                                     window.drg_pak_path = fsd_pak.to_string_lossy().to_string();
                                     window.drg_pak_path_err = None;
+                                    window.drg_pak_path_valid = false;
+                                    window.drg_pak_path_edited = Some(Instant::now());
                                 }
                             }
+                            if window.drg_pak_path_check_rid.is_some()
+                                || window.drg_pak_path_edited.is_some()
+                            {
+                                ui.spinner();
+                            } else if self.state.config.skip_pak_validation {
+                                ui.colored_label(ui.visuals().warn_fg_color, "⚠")
+                                    .on_hover_text("Not validated");
+                            } else if window.drg_pak_path_valid {
+                                ui.colored_label(Color32::GREEN, "✔")
+                                    .on_hover_text("Valid DRG pak");
+                            } else if let Some(error) = &window.drg_pak_path_err {
+                                ui.colored_label(ui.visuals().error_fg_color, "❌")
+                                    .on_hover_text(error);
+                            }
                         });
                         ui.end_row();
 
-                        let config_dir = &self.state.dirs.config_dir;
-                        ui.label("Config directory:");
-                        if ui.link(config_dir.display().to_string()).clicked() {
-                            opener::open(config_dir).ok();
-                        }
+                        ui.label("Skip DRG pak validation:").on_hover_cursor(egui::CursorIcon::Help).on_hover_text("Use the DRG pak without checking that it contains FSD/FSD.uproject, for pre-modified game paks or total conversions.\nOnly enable this if your install is nonstandard, installing mods into anything else than the game may fail or break it.");
+                        ui.horizontal(|ui| {
+                            let config = &mut self.state.config;
+                            if ui.add(toggle_switch(&mut config.skip_pak_validation)).changed() {
+                                config.save().unwrap();
+                                window.drg_pak_path_err = None;
+                                window.drg_pak_path_valid = false;
+                                window.drg_pak_path_edited = Some(Instant::now());
+                            }
+                            if config.skip_pak_validation {
+                                ui.colored_label(
+                                    ui.visuals().warn_fg_color,
+                                    "any file is accepted as the DRG pak",
+                                );
+                            }
+                        });
                         ui.end_row();
 
-                        let cache_dir = &self.state.dirs.cache_dir;
-                        ui.label("Cache directory:");
-                        if ui.link(cache_dir.display().to_string()).clicked() {
-                            opener::open(cache_dir).ok();
+                        ui.label("Pak AES key:").on_hover_cursor(egui::CursorIcon::Help).on_hover_text("Key used to read mods with encrypted paks. Leave empty if none of your mods are encrypted.");
+                        let res = ui.add(
+                            egui::TextEdit::singleline(&mut window.pak_key)
+                                .hint_text("0x...")
+                                .desired_width(200.0),
+                        );
+                        if res.changed() {
+                            window.pak_key_err = None;
                         }
-                        ui.end_row();
-
-                        let data_dir = &self.state.dirs.data_dir;
-                        ui.label("Data directory:");
-                        if ui.link(data_dir.display().to_string()).clicked() {
-                            opener::open(data_dir).ok();
+                        if is_committed(&res) {
+                            try_save = true;
                         }
                         ui.end_row();
 
-                        ui.label("GUI theme:");
-                        ui.horizontal(|ui| {
-                            ui.horizontal(|ui| {
-                                let config = &mut self.state.config;
+                        let usage = window.disk_usage;
+                        let show_size = |ui: &mut Ui, size: Option<u64>| match size {
+                            Some(size) => {
+                                ui.colored_label(size_color(ui, size), format_size(size));
+                            }
+                            None => {
+                                ui.spinner();
+                            }
+                        };
 
-                                let old_theme = GuiTheme::into_egui_theme(config.gui_theme);
-                                let mut theme = old_theme;
-                                theme.radio_buttons(ui);
-                                if theme != old_theme {
-                                    ui.memory_mut(|m| m.options.theme_preference = theme);
-                                    config.gui_theme = GuiTheme::from_egui_theme(theme);
-                                    config.save().unwrap();
-                                }
-                            });
+                        let config_dir = &self.state.dirs.config_dir;
+                        ui.label("Config directory:");
+                        ui.horizontal(|ui| {
+                            if ui.link(config_dir.display().to_string()).clicked() {
+                                opener::open(config_dir).ok();
+                            }
+                            show_size(ui, usage.map(|u| u.config));
                         });
                         ui.end_row();
 
-                        ui.label("Mod providers:");
-                        ui.end_row();
+                        let cache_dir = &self.state.dirs.cache_dir;
+                        ui.label("Cache directory:");
+                        ui.horizontal(|ui| {
+                            if ui.link(cache_dir.display().to_string()).clicked() {
+                                opener::open(cache_dir).ok();
+                            }
+                            show_size(ui, usage.map(|u| u.cache));
+                        });
+                        ui.end_row();
+
+                        ui.label("Downloaded mods:").on_hover_cursor(egui::CursorIcon::Help).on_hover_text("Mods are kept in the cache so reinstalling does not download them again. Clearing it is safe, mods are downloaded again when needed.");
+                        ui.horizontal(|ui| {
+                            show_size(ui, usage.map(|u| u.blobs));
+                            let busy = window.clear_cache_rid.is_some() || self.integrate_rid.is_some();
+                            if ui
+                                .add_enabled(!busy, egui::Button::new("clear cache"))
+                                .on_disabled_hover_text("Cannot clear the cache while mods are being installed")
+                                .clicked()
+                            {
+                                window.clear_cache_rid = Some(message::ClearCache::send(
+                                    &mut self.request_counter,
+                                    self.state.store.clone(),
+                                    self.tx.clone(),
+                                    ctx.clone(),
+                                ));
+                            }
+                            if let Some(usage) = usage
+                                && usage.cache >= LARGE_CACHE_SIZE
+                            {
+                                ui.colored_label(ui.visuals().warn_fg_color, "\u{26A0}")
+                                    .on_hover_text(format!(
+                                        "The cache is larger than {}, consider clearing it",
+                                        format_size(LARGE_CACHE_SIZE)
+                                    ));
+                            }
+                        });
+                        ui.end_row();
+
+                        let data_dir = &self.state.dirs.data_dir;
+                        ui.label("Data directory:");
+                        ui.horizontal(|ui| {
+                            if ui.link(data_dir.display().to_string()).clicked() {
+                                opener::open(data_dir).ok();
+                            }
+                            show_size(ui, usage.map(|u| u.data));
+                        });
+                        ui.end_row();
+
+                        ui.label("Startup profile:");
+                        let config = &mut self.state.config;
+                        let mut startup_profile = config.startup_profile.clone();
+                        egui::ComboBox::from_id_salt("startup-profile")
+                            .selected_text(startup_profile.as_deref().unwrap_or("Last active"))
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut startup_profile, None, "Last active");
+                                for profile in self.state.mod_data.ordered_profiles() {
+                                    ui.selectable_value(
+                                        &mut startup_profile,
+                                        Some(profile.clone()),
+                                        profile,
+                                    );
+                                }
+                            });
+                        if startup_profile != config.startup_profile {
+                            config.startup_profile = startup_profile;
+                            config.save().unwrap();
+                        }
+                        ui.end_row();
+
+                        ui.label("Operation timeout:").on_hover_cursor(egui::CursorIcon::Help).on_hover_text("Offer to cancel installs, cache updates and lints which run longer than this. 0 disables the prompt.");
+                        let config = &mut self.state.config;
+                        let mut timeout = config
+                            .operation_timeout_secs
+                            .unwrap_or(DEFAULT_OPERATION_TIMEOUT_SECS);
+                        if ui
+                            .add(
+                                egui::DragValue::new(&mut timeout)
+                                    .range(0..=3600)
+                                    .suffix("s"),
+                            )
+                            .changed()
+                        {
+                            config.operation_timeout_secs = Some(timeout);
+                            config.save().unwrap();
+                        }
+                        ui.end_row();
+
+                        ui.label("Extra launch arguments:").on_hover_cursor(egui::CursorIcon::Help).on_hover_text("Arguments appended to the game's command line when launching it from mint, e.g. -nosteam or -ResX=1920 -ResY=1080.\nQuote arguments containing spaces with \" or '.");
+                        let res = ui.add(
+                            egui::TextEdit::singleline(&mut window.launch_args)
+                                .hint_text("-nosteam")
+                                .desired_width(200.0),
+                        );
+                        if res.changed() {
+                            window.launch_args_err = None;
+                        }
+                        if is_committed(&res) {
+                            try_save = true;
+                        }
+                        ui.end_row();
+
+                        ui.label("Export pak to:").on_hover_cursor(egui::CursorIcon::Help).on_hover_text("After every install also copy mods_P.pak to this file or directory, e.g. to share it with others. Leave empty to only install to the game.");
+                        ui.horizontal(|ui| {
+                            let res = ui.add(
+                                egui::TextEdit::singleline(&mut window.export_pak_path)
+                                    .hint_text("not exported")
+                                    .desired_width(200.0),
+                            );
+                            if is_committed(&res) {
+                                try_save = true;
+                            }
+                            if ui.button("browse").clicked()
+                                && let Some(path) = rfd::FileDialog::new()
+                                    .add_filter("Pak", &["pak"])
+                                    .set_file_name("mods_P.pak")
+                                    .save_file()
+                            {
+                                window.export_pak_path = path.to_string_lossy().to_string();
+                            }
+                        });
+                        ui.end_row();
+
+                        ui.label("Skip hook deployment:").on_hover_cursor(egui::CursorIcon::Help).on_hover_text("Only write the mods pak on install and leave the hook DLL alone, e.g. when it is managed separately or conflicts with another proxy DLL. Uninstalling then leaves it alone as well.\nmint's in-game features such as the escape menu mod list will not work without the hook.");
+                        ui.horizontal(|ui| {
+                            let config = &mut self.state.config;
+                            if ui.add(toggle_switch(&mut config.skip_hook)).changed() {
+                                config.save().unwrap();
+                            }
+                            if config.skip_hook {
+                                ui.colored_label(
+                                    ui.visuals().warn_fg_color,
+                                    "in-game features are disabled without the hook",
+                                );
+                            }
+                            if ui
+                                .add_enabled(
+                                    config.drg_pak_path.is_some(),
+                                    egui::Button::new("Reinstall hook"),
+                                )
+                                .on_hover_text("Deploy the hook DLL bundled with this version of mint again without installing mods, e.g. after an antivirus removed it and mods stopped loading")
+                                .clicked()
+                            {
+                                self.reinstall_hook();
+                            }
+                        });
+                        ui.end_row();
+
+                        ui.label("Smoke test after install:").on_hover_cursor(egui::CursorIcon::Help).on_hover_text("After installing mods, launch the game to check that it still starts with them, then exit it again. The game is exited as soon as mint's integration loaded or the timeout passed.\nNeeds the hook and mint to be launched through Steam.");
+                        ui.horizontal(|ui| {
+                            let config = &mut self.state.config;
+                            if ui
+                                .add(toggle_switch(&mut config.smoke_test_after_install))
+                                .changed()
+                            {
+                                config.save().unwrap();
+                            }
+                            let mut timeout = config
+                                .smoke_test_timeout_secs
+                                .unwrap_or(DEFAULT_SMOKE_TEST_TIMEOUT_SECS);
+                            if ui
+                                .add_enabled(
+                                    config.smoke_test_after_install,
+                                    egui::DragValue::new(&mut timeout)
+                                        .range(30..=1800)
+                                        .suffix("s"),
+                                )
+                                .on_hover_text("Time the game gets to start")
+                                .changed()
+                            {
+                                config.smoke_test_timeout_secs = Some(timeout);
+                                config.save().unwrap();
+                            }
+                        });
+                        ui.end_row();
+
+                        ui.label("Log integration details:").on_hover_cursor(egui::CursorIcon::Help).on_hover_text("Write integrate.log and integrate.json to the data directory on install, listing the files every mod contributed and which mod won each conflict.");
+                        let config = &mut self.state.config;
+                        if ui.add(toggle_switch(&mut config.verbose_integrate)).changed() {
+                            config.save().unwrap();
+                        }
+                        ui.end_row();
+
+                        ui.label("Hash game pak:").on_hover_cursor(egui::CursorIcon::Help).on_hover_text("Record a hash of the game pak on install to tell game updates apart from the pak only being touched, e.g. by Steam verifying the game files. Hashing the pak makes installs take a few seconds longer.");
+                        let config = &mut self.state.config;
+                        if ui.add(toggle_switch(&mut config.hash_game_pak)).changed() {
+                            config.save().unwrap();
+                        }
+                        ui.end_row();
+
+                        ui.label("Fall back to older mod versions:").on_hover_cursor(egui::CursorIcon::Help).on_hover_text("If the newest version of a mod fails to download or its pak is unusable, install the newest older version which works instead of failing the install. Pinned mods are never downgraded.");
+                        let config = &mut self.state.config;
+                        if ui.add(toggle_switch(&mut config.auto_downgrade)).changed() {
+                            config.save().unwrap();
+                        }
+                        ui.end_row();
+
+                        ui.label("Use all paks of archives:").on_hover_cursor(egui::CursorIcon::Help).on_hover_text("Install and lint every pak of mods distributed as archives with multiple paks, in the order the game would load them, instead of only the first one.");
+                        let config = &mut self.state.config;
+                        if ui.add(toggle_switch(&mut config.all_archive_paks)).changed() {
+                            config.save().unwrap();
+                        }
+                        ui.end_row();
+
+                        ui.label("Resolve conflicts by:").on_hover_cursor(egui::CursorIcon::Help).on_hover_text("Which mod a file is taken from when several enabled mods provide it. The mod with the highest priority always wins, \"Approval\" only decides between mods with equal priority: Verified before Approved before Sandbox mods.");
+                        let config = &mut self.state.config;
+                        let old_strategy = config.conflict_strategy;
+                        ui.horizontal(|ui| {
+                            ui.radio_value(
+                                &mut config.conflict_strategy,
+                                ConflictStrategy::Priority,
+                                "Priority",
+                            );
+                            ui.radio_value(
+                                &mut config.conflict_strategy,
+                                ConflictStrategy::Approval,
+                                "Approval",
+                            );
+                        });
+                        if config.conflict_strategy != old_strategy {
+                            config.save().unwrap();
+                        }
+                        ui.end_row();
+
+                        ui.label("Skip split assets:").on_hover_cursor(egui::CursorIcon::Help).on_hover_text("Leave out .uasset and .uexp files whose other half is missing from the mod when installing, instead of combining them with the other half of the game's asset. Each skipped file is logged.");
+                        let config = &mut self.state.config;
+                        if ui.add(toggle_switch(&mut config.skip_split_assets)).changed() {
+                            config.save().unwrap();
+                        }
+                        ui.end_row();
+
+                        ui.label("Embed mod list:").on_hover_cursor(egui::CursorIcon::Help).on_hover_text("Write mint_mods.txt listing the names, URLs and versions of the installed mods into mods_P.pak, so anyone inspecting the pak can see what it contains without mint.");
+                        let config = &mut self.state.config;
+                        if ui.add(toggle_switch(&mut config.embed_mod_list)).changed() {
+                            config.save().unwrap();
+                        }
+                        ui.end_row();
+
+                        ui.label("Collect usage stats:").on_hover_cursor(egui::CursorIcon::Help).on_hover_text("Count installs, the mods they included and how many mods were downloaded or taken from the cache. The stats are only kept in the data directory for you to look at and are never sent anywhere.");
+                        ui.horizontal(|ui| {
+                            let config = &mut self.state.config;
+                            if ui.add(toggle_switch(&mut config.collect_stats)).changed() {
+                                config.save().unwrap();
+                            }
+                            if ui.button("Show stats").clicked() {
+                                self.stats_window =
+                                    Some(UsageStats::read(&self.state.dirs.data_dir));
+                            }
+                        });
+                        ui.end_row();
+
+                        ui.label("Open pak location after install:");
+                        let config = &mut self.state.config;
+                        if ui
+                            .add(toggle_switch(&mut config.open_pak_location_after_install))
+                            .changed()
+                        {
+                            config.save().unwrap();
+                        }
+                        ui.end_row();
+
+                        ui.label("GUI theme:");
+                        ui.horizontal(|ui| {
+                            ui.horizontal(|ui| {
+                                let config = &mut self.state.config;
+
+                                let old_theme = GuiTheme::into_egui_theme(config.gui_theme);
+                                let mut theme = old_theme;
+                                theme.radio_buttons(ui);
+                                if theme != old_theme {
+                                    ui.memory_mut(|m| m.options.theme_preference = theme);
+                                    config.gui_theme = GuiTheme::from_egui_theme(theme);
+                                    config.save().unwrap();
+                                }
+                            });
+                        });
+                        ui.end_row();
+
+                        ui.label("Mod providers:");
+                        ui.end_row();
 
                         for provider_factory in ModStore::get_provider_factories() {
                             ui.label(provider_factory.id);
-                            if ui.add_enabled(!provider_factory.parameters.is_empty(), egui::Button::new("⚙"))
-                                    .on_hover_text(format!("Open \"{}\" settings", provider_factory.id))
-                                    .clicked() {
-                                self.window_provider_parameters = Some(
-                                    WindowProviderParameters::new(provider_factory, &self.state),
+                            ui.horizontal(|ui| {
+                                if ui.add_enabled(!provider_factory.parameters.is_empty(), egui::Button::new("⚙"))
+                                        .on_hover_text(format!("Open \"{}\" settings", provider_factory.id))
+                                        .clicked() {
+                                    self.window_provider_parameters = Some(
+                                        WindowProviderParameters::new(provider_factory, &self.state),
+                                    );
+                                }
+                                Self::ui_provider_credentials(
+                                    ui,
+                                    &mut self.state,
+                                    &mut self.last_action,
+                                    provider_factory,
                                 );
-                            }
+                            });
                             ui.end_row();
                         }
                     });
 
                     ui.with_layout(egui::Layout::right_to_left(Align::TOP), |ui| {
-                        if ui.add_enabled(window.drg_pak_path_err.is_none(), egui::Button::new("save")).clicked() {
+                        if ui.add_enabled(window.drg_pak_path_err.is_none() && window.pak_key_err.is_none() && window.launch_args_err.is_none(), egui::Button::new("save")).clicked() {
                             try_save = true;
                         }
                         if let Some(error) = &window.drg_pak_path_err {
                             ui.colored_label(ui.visuals().error_fg_color, error);
                         }
+                        if let Some(error) = &window.pak_key_err {
+                            ui.colored_label(ui.visuals().error_fg_color, error);
+                        }
+                        if let Some(error) = &window.launch_args_err {
+                            ui.colored_label(ui.visuals().error_fg_color, error);
+                        }
                     });
 
                 });
             if try_save {
-                if let Err(e) = is_drg_pak(&window.drg_pak_path) {
+                let pak_key = Some(window.pak_key.trim())
+                    .filter(|k| !k.is_empty())
+                    .map(str::parse::<PakKey>)
+                    .transpose();
+                let launch_args = split_args(&window.launch_args);
+                if let Err(e) =
+                    validate_drg_pak(&window.drg_pak_path, self.state.config.skip_pak_validation)
+                {
                     window.drg_pak_path_err = Some(e.to_string());
+                } else if let Err(e) = &pak_key {
+                    window.pak_key_err = Some(e.to_string());
+                } else if let Err(e) = &launch_args {
+                    window.launch_args_err = Some(e.to_string());
                 } else {
-                    self.state.config.drg_pak_path = Some(PathBuf::from(
-                        self.settings_window.take().unwrap().drg_pak_path,
-                    ));
+                    let window = self.settings_window.take().unwrap();
+                    self.state.config.drg_pak_path = Some(PathBuf::from(window.drg_pak_path));
+                    self.state.config.pak_key = pak_key.unwrap();
+                    self.state.config.launch_args = launch_args.unwrap();
+                    self.state.config.export_pak_path = Some(window.export_pak_path.trim())
+                        .filter(|p| !p.is_empty())
+                        .map(PathBuf::from);
                     self.state.config.save().unwrap();
                 }
             } else if !open {
@@ -1178,95 +2811,702 @@ impl App {
                             ui.add(toggle_switch(&mut self.lint_options.split_asset_pairs));
                             ui.end_row();
 
-                            ui.label("Mods containing unmodified game assets");
-                            ui.add_enabled(
-                                self.state.config.drg_pak_path.is_some(),
-                                toggle_switch(&mut self.lint_options.unmodified_game_assets),
-                            )
-                            .on_disabled_hover_text(
-                                "This lint requires DRG pak path to be specified",
+                            ui.label("Mods with overlapping mount points");
+                            ui.add(toggle_switch(
+                                &mut self.lint_options.overlapping_mount_points,
+                            ));
+                            ui.end_row();
+
+                            ui.label("Mods containing mint's hook or its files");
+                            ui.add(toggle_switch(&mut self.lint_options.mint_artifacts));
+                            ui.end_row();
+
+                            ui.label("Gameplay mods not tagged as required or verified")
+                                .on_hover_cursor(egui::CursorIcon::Help)
+                                .on_hover_text("Advisory: guesses from asset names whether a mod changes gameplay, e.g. enemy or weapon blueprints. Such mods usually have to be installed by every player, so check with the mod's author or page whether it works when only some players have it.");
+                            ui.add(toggle_switch(&mut self.lint_options.untagged_gameplay));
+                            ui.end_row();
+
+                            ui.label("Mods containing unmodified game assets");
+                            ui.add_enabled(
+                                self.state.config.drg_pak_path.is_some(),
+                                toggle_switch(&mut self.lint_options.unmodified_game_assets),
+                            )
+                            .on_disabled_hover_text(
+                                "This lint requires DRG pak path to be specified",
+                            );
+                            ui.end_row();
+                        });
+                    });
+
+                    ui.horizontal(|ui| {
+                        if ui.button("Cancel").clicked() {
+                            self.lints_toggle_window = None;
+                        }
+
+                        if ui
+                            .add_enabled(
+                                self.check_updates_rid.is_none()
+                                    && self.integrate_rid.is_none()
+                                    && self.lint_rid.is_none(),
+                                egui::Button::new("Generate report"),
+                            )
+                            .clicked()
+                        {
+                            let lint_options = BTreeMap::from([
+                                (
+                                    LintId::ARCHIVE_WITH_MULTIPLE_PAKS,
+                                    self.lint_options.archive_with_multiple_paks,
+                                ),
+                                (
+                                    LintId::ARCHIVE_WITH_ONLY_NON_PAK_FILES,
+                                    self.lint_options.archive_with_only_non_pak_files,
+                                ),
+                                (
+                                    LintId::ASSET_REGISTRY_BIN,
+                                    self.lint_options.asset_register_bin,
+                                ),
+                                (LintId::CONFLICTING, self.lint_options.conflicting),
+                                (LintId::EMPTY_ARCHIVE, self.lint_options.empty_archive),
+                                (
+                                    LintId::OUTDATED_PAK_VERSION,
+                                    self.lint_options.outdated_pak_version,
+                                ),
+                                (LintId::SHADER_FILES, self.lint_options.shader_files),
+                                (LintId::NON_ASSET_FILES, self.lint_options.non_asset_files),
+                                (
+                                    LintId::SPLIT_ASSET_PAIRS,
+                                    self.lint_options.split_asset_pairs,
+                                ),
+                                (
+                                    LintId::UNMODIFIED_GAME_ASSETS,
+                                    self.lint_options.unmodified_game_assets,
+                                ),
+                                (
+                                    LintId::OVERLAPPING_MOUNT_POINTS,
+                                    self.lint_options.overlapping_mount_points,
+                                ),
+                                (LintId::MINT_ARTIFACTS, self.lint_options.mint_artifacts),
+                                (
+                                    LintId::UNTAGGED_GAMEPLAY,
+                                    self.lint_options.untagged_gameplay,
+                                ),
+                            ]);
+
+                            trace!(?lint_options);
+
+                            self.lint_active_profile(
+                                ctx,
+                                BTreeSet::from_iter(
+                                    lint_options
+                                        .into_iter()
+                                        .filter_map(|(lint, enabled)| enabled.then_some(lint)),
+                                ),
+                            );
+                        }
+                    });
+                });
+
+            if !open {
+                self.lints_toggle_window = None;
+            }
+        }
+    }
+
+    fn operation_started(&self, op: Operation) -> Option<(RequestID, Instant)> {
+        match op {
+            Operation::Integrate => self.integrate_rid.as_ref().map(|h| (h.rid, h.started)),
+            Operation::UpdateCache => self.update_rid.as_ref().map(|h| (h.rid, h.started)),
+            Operation::ResolveMods => self.resolve_mod_rid.as_ref().map(|h| (h.rid, h.started)),
+            Operation::Lint => self.lint_rid.as_ref().map(|h| (h.rid, h.started)),
+        }
+    }
+
+    /// Abort the operation if `cancel`, otherwise restart its timer.
+    fn resolve_stalled_operation(&mut self, op: Operation, cancel: bool) {
+        macro_rules! handle {
+            ($field:ident) => {
+                if cancel {
+                    if let Some(h) = self.$field.take() {
+                        h.handle.abort();
+                    }
+                } else if let Some(h) = &mut self.$field {
+                    h.started = Instant::now();
+                }
+            };
+        }
+        match op {
+            Operation::Integrate => handle!(integrate_rid),
+            Operation::UpdateCache => handle!(update_rid),
+            Operation::ResolveMods => handle!(resolve_mod_rid),
+            Operation::Lint => handle!(lint_rid),
+        }
+    }
+
+    fn show_watchdog(&mut self, ctx: &egui::Context) {
+        let Some(timeout) = self.state.config.operation_timeout() else {
+            return;
+        };
+
+        let Some((op, rid, elapsed)) = Operation::iter().find_map(|op| {
+            self.operation_started(op)
+                .map(|(rid, started)| (op, rid, started.elapsed()))
+                .filter(|(_, _, elapsed)| *elapsed >= timeout)
+        }) else {
+            // make sure the prompt shows up even if nothing else triggers a repaint
+            if let Some(remaining) = Operation::iter()
+                .filter_map(|op| self.operation_started(op))
+                .map(|(_, started)| timeout.saturating_sub(started.elapsed()))
+                .min()
+            {
+                ctx.request_repaint_after(remaining);
+            }
+            return;
+        };
+
+        if self.watchdog_logged != Some(rid) {
+            self.watchdog_logged = Some(rid);
+            warn!(
+                "{} has not completed after {}s",
+                op.as_str(),
+                elapsed.as_secs()
+            );
+            if op == Operation::Integrate
+                && let Some(handle) = &self.integrate_rid
+            {
+                for (spec, progress) in &handle.state {
+                    warn!("  {}: {:?}", spec.url, progress);
+                }
+            }
+        }
+
+        let mut cancel = None;
+        egui::Window::new("Operation is taking a long time")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "{} has been running for {}s. This may be caused by a stalled network connection.",
+                    op.as_str(),
+                    elapsed.as_secs()
+                ));
+                ui.horizontal(|ui| {
+                    if ui.button("Cancel").clicked() {
+                        cancel = Some(true);
+                    }
+                    if ui.button("Keep waiting").clicked() {
+                        cancel = Some(false);
+                    }
+                });
+            });
+
+        if let Some(cancel) = cancel {
+            self.resolve_stalled_operation(op, cancel);
+            if cancel {
+                self.last_action = Some(LastAction::failure(format!(
+                    "{} cancelled after {}s",
+                    op.as_str(),
+                    elapsed.as_secs()
+                )));
+            }
+        }
+    }
+
+    fn show_update_diff(&mut self, ctx: &egui::Context) {
+        let Some(window) = &self.update_diff_window else {
+            return;
+        };
+        let (current, new) = &window.versions;
+        let store = &self.state.store;
+        let name = store
+            .get_mod_info(current)
+            .map(|info| info.name)
+            .unwrap_or_else(|| current.url.clone());
+        let version_name = |spec: &ModSpecification| {
+            store
+                .get_version_name(spec)
+                .unwrap_or_else(|| spec.url.clone())
+        };
+
+        let mut open = true;
+        egui::Window::new(format!("Update: {name}"))
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                if window.rid.is_some() {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label("Fetching both versions...");
+                    });
+                    return;
+                }
+                if let Some(err) = &window.err {
+                    ui.colored_label(ui.visuals().error_fg_color, err);
+                    return;
+                }
+                let Some(diff) = self.update_diffs.get(&window.versions) else {
+                    return;
+                };
+                if diff.is_empty() {
+                    ui.label(format!("No files changed ({} unchanged)", diff.unchanged));
+                    return;
+                }
+                ui.label(format!(
+                    "{} added, {} removed, {} changed, {} unchanged",
+                    diff.added.len(),
+                    diff.removed.len(),
+                    diff.changed.len(),
+                    diff.unchanged
+                ));
+                ui.separator();
+
+                let removed_color = ui.visuals().error_fg_color;
+                let changed_color = ui.visuals().warn_fg_color;
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    ui.columns(2, |columns| {
+                        columns[0].strong(format!("Current: {}", version_name(current)));
+                        for path in &diff.removed {
+                            columns[0].colored_label(removed_color, format!("- {path}"));
+                        }
+                        for path in &diff.changed {
+                            columns[0].colored_label(changed_color, format!("~ {path}"));
+                        }
+                        columns[1].strong(format!("New: {}", version_name(new)));
+                        for path in &diff.added {
+                            columns[1].colored_label(Color32::GREEN, format!("+ {path}"));
+                        }
+                        for path in &diff.changed {
+                            columns[1].colored_label(changed_color, format!("~ {path}"));
+                        }
+                    });
+                });
+            });
+
+        if !open {
+            self.update_diff_window = None;
+        }
+    }
+
+    fn show_asset_tree(&mut self, ctx: &egui::Context) {
+        let Some(window) = &mut self.asset_tree_window else {
+            return;
+        };
+        let store = &self.state.store;
+        let mod_name = |spec: &ModSpecification| {
+            store
+                .get_mod_info(spec)
+                .map(|info| info.name)
+                .unwrap_or_else(|| spec.url.clone())
+        };
+
+        let mut open = true;
+        egui::Window::new("Merged assets")
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                if window.rid.is_some() {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label("Reading mods...");
+                    });
+                    return;
+                }
+                if let Some(err) = &window.err {
+                    ui.colored_label(ui.visuals().error_fg_color, err);
+                    return;
+                }
+                let Some(tree) = &window.tree else {
+                    return;
+                };
+                ui.label(format!(
+                    "{} files, {} provided by more than one mod",
+                    tree.file_count(),
+                    tree.override_count()
+                ))
+                .on_hover_text("Files as installing the active profile would write them, each taken from the enabled mod with the highest priority providing it");
+                let mut changed = false;
+                ui.horizontal(|ui| {
+                    ui.label("Filter:");
+                    changed |= ui.text_edit_singleline(&mut window.filter).changed();
+                    changed |= ui
+                        .checkbox(&mut window.overrides_only, "Only overridden files")
+                        .changed();
+                });
+                if changed || window.shown.is_none() {
+                    window.shown = Some(tree.filtered(&window.filter, window.overrides_only));
+                }
+                ui.separator();
+
+                let shown = window.shown.as_ref().unwrap();
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    if shown.is_empty() {
+                        ui.label("No matching files");
+                    }
+                    show_asset_dir(ui, shown, "", &mod_name);
+                });
+            });
+
+        if !open {
+            self.asset_tree_window = None;
+        }
+    }
+
+    fn show_stats(&mut self, ctx: &egui::Context) {
+        let Some(stats) = &self.stats_window else {
+            return;
+        };
+
+        let mut open = true;
+        let mut reset = false;
+        egui::Window::new("Stats")
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                if !self.state.config.collect_stats {
+                    ui.label("Collecting stats is disabled in the settings.");
+                }
+                egui::Grid::new("stats-grid").num_columns(2).show(ui, |ui| {
+                    ui.label("Installs:");
+                    ui.label(stats.integrates.to_string());
+                    ui.end_row();
+
+                    ui.label("Mods downloaded:");
+                    ui.label(format!(
+                        "{} ({})",
+                        stats.downloads,
+                        format_size(stats.fetched_bytes)
+                    ));
+                    ui.end_row();
+
+                    ui.label("Taken from cache:");
+                    ui.label(match stats.cache_hit_rate() {
+                        Some(rate) => format!("{} ({:.0}%)", stats.cache_hits, rate * 100.),
+                        None => stats.cache_hits.to_string(),
+                    });
+                    ui.end_row();
+                });
+
+                let most_used = stats.most_used(10);
+                if !most_used.is_empty() {
+                    ui.separator();
+                    ui.label("Most installed mods:");
+                    egui::Grid::new("stats-mods-grid")
+                        .num_columns(2)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for (url, count) in most_used {
+                                let spec = ModSpecification::new(url.to_string());
+                                let name = self
+                                    .state
+                                    .store
+                                    .get_mod_info(&spec)
+                                    .map(|info| info.name)
+                                    .unwrap_or_else(|| url.to_string());
+                                ui.label(name).on_hover_text(url);
+                                ui.label(count.to_string());
+                                ui.end_row();
+                            }
+                        });
+                }
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    reset = ui.button("Reset stats").clicked();
+                    ui.label("Stats are kept in the data directory and never sent anywhere.");
+                });
+            });
+
+        if reset {
+            match UsageStats::reset(&self.state.dirs.data_dir) {
+                Ok(()) => self.stats_window = Some(UsageStats::default()),
+                Err(e) => {
+                    self.last_action =
+                        Some(LastAction::failure(format!("Failed to reset stats: {e}")))
+                }
+            }
+        }
+        if !open {
+            self.stats_window = None;
+        }
+    }
+
+    fn show_description(&mut self, ctx: &egui::Context) {
+        let Some(window) = &self.description_window else {
+            return;
+        };
+        let spec = &window.spec;
+        let info = self.state.store.get_mod_info(spec);
+        let name = info
+            .as_ref()
+            .map(|info| info.name.clone())
+            .unwrap_or_else(|| spec.url.clone());
+
+        let mut open = true;
+        egui::Window::new(name)
+            .id(egui::Id::new("mod_description"))
+            .open(&mut open)
+            .resizable(true)
+            .default_width(512.0)
+            .show(ctx, |ui| {
+                match info.as_ref().and_then(|info| info.description.as_ref()) {
+                    Some(description) => {
+                        egui::ScrollArea::vertical()
+                            .max_height(400.0)
+                            .show(ui, |ui| {
+                                CommonMarkViewer::new().max_image_width(Some(512)).show(
+                                    ui,
+                                    &mut self.cache,
+                                    description,
+                                );
+                            });
+                    }
+                    None => {
+                        ui.label("No description available.");
+                    }
+                }
+                ui.separator();
+                match info.as_ref().map(|info| info.provider) {
+                    Some("file") => {
+                        ui.label(&spec.url);
+                    }
+                    _ => {
+                        ui.hyperlink(&spec.url);
+                    }
+                }
+            });
+        if !open {
+            self.description_window = None;
+        }
+    }
+
+    fn show_priority_overrides(&mut self, ctx: &egui::Context) {
+        let Some(window) = &mut self.priority_overrides_window else {
+            return;
+        };
+        let name = self
+            .state
+            .store
+            .get_mod_info(&window.spec)
+            .map(|info| info.name)
+            .unwrap_or_else(|| window.spec.url.clone());
+
+        let mut open = true;
+        let mut save = false;
+        egui::Window::new(format!("Priority overrides: {name}"))
+            .id(egui::Id::new("priority_overrides"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label("Files matching a pattern use its priority instead of the mod's.\nPatterns are paths like FSD/Content/Textures, `*` matches anything.\nThe first matching pattern applies.");
+                let mut remove = None;
+                egui::Grid::new("priority-overrides-grid")
+                    .num_columns(3)
+                    .show(ui, |ui| {
+                        for (i, (pattern, priority)) in window.rows.iter_mut().enumerate() {
+                            ui.add(
+                                egui::TextEdit::singleline(pattern)
+                                    .hint_text("FSD/Content/Textures/*")
+                                    .desired_width(300.0),
                             );
+                            ui.add(
+                                egui::DragValue::new(priority)
+                                    .speed(0.05)
+                                    .range(RangeInclusive::new(-999, 999)),
+                            );
+                            if ui.button("🗑").on_hover_text("Remove").clicked() {
+                                remove = Some(i);
+                            }
                             ui.end_row();
-                        });
+                        }
                     });
+                if let Some(i) = remove {
+                    window.rows.remove(i);
+                }
+                if ui.button("➕ Add override").clicked() {
+                    window.rows.push((String::new(), 0));
+                }
+                if let Some(error) = &window.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+                ui.separator();
+                save = ui.button("Save").clicked();
+            });
 
-                    ui.horizontal(|ui| {
-                        if ui.button("Cancel").clicked() {
-                            self.lints_toggle_window = None;
+        if save {
+            let overrides = window
+                .rows
+                .iter()
+                .filter(|(pattern, _)| !pattern.trim().is_empty())
+                .map(|(pattern, priority)| PriorityOverride::new(pattern, *priority))
+                .collect::<Result<Vec<_>, _>>();
+            match overrides {
+                Ok(overrides) => {
+                    let spec = window.spec.clone();
+                    let profile = window.profile.clone();
+                    self.state.mod_data.for_each_mod_mut(&profile, |mc| {
+                        if mc.spec == spec {
+                            mc.priority_overrides = overrides.clone();
                         }
+                    });
+                    self.state.mod_data.save().unwrap();
+                    self.priority_overrides_window = None;
+                }
+                Err(e) => window.error = Some(e.to_string()),
+            }
+        } else if !open {
+            self.priority_overrides_window = None;
+        }
+    }
 
-                        if ui
-                            .add_enabled(
-                                self.check_updates_rid.is_none()
-                                    && self.integrate_rid.is_none()
-                                    && self.lint_rid.is_none(),
-                                egui::Button::new("Generate report"),
-                            )
-                            .clicked()
-                        {
-                            let lint_options = BTreeMap::from([
-                                (
-                                    LintId::ARCHIVE_WITH_MULTIPLE_PAKS,
-                                    self.lint_options.archive_with_multiple_paks,
-                                ),
-                                (
-                                    LintId::ARCHIVE_WITH_ONLY_NON_PAK_FILES,
-                                    self.lint_options.archive_with_only_non_pak_files,
-                                ),
-                                (
-                                    LintId::ASSET_REGISTRY_BIN,
-                                    self.lint_options.asset_register_bin,
-                                ),
-                                (LintId::CONFLICTING, self.lint_options.conflicting),
-                                (LintId::EMPTY_ARCHIVE, self.lint_options.empty_archive),
-                                (
-                                    LintId::OUTDATED_PAK_VERSION,
-                                    self.lint_options.outdated_pak_version,
-                                ),
-                                (LintId::SHADER_FILES, self.lint_options.shader_files),
-                                (LintId::NON_ASSET_FILES, self.lint_options.non_asset_files),
-                                (
-                                    LintId::SPLIT_ASSET_PAIRS,
-                                    self.lint_options.split_asset_pairs,
-                                ),
-                                (
-                                    LintId::UNMODIFIED_GAME_ASSETS,
-                                    self.lint_options.unmodified_game_assets,
-                                ),
-                            ]);
+    fn show_favorites(&mut self, ctx: &egui::Context) {
+        if self.favorites_window.is_some() {
+            let mut open = true;
+            let mut add = None;
+            let mut remove = None;
 
-                            trace!(?lint_options);
+            egui::Window::new("Favorites")
+                .open(&mut open)
+                .resizable(true)
+                .show(ctx, |ui| {
+                    if self.state.config.favorites.is_empty() {
+                        ui.label("No favorites yet. Use ☆ next to a mod to add it.");
+                        return;
+                    }
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        egui::Grid::new("favorites-grid")
+                            .num_columns(3)
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for spec in &self.state.config.favorites {
+                                    let name = self
+                                        .state
+                                        .store
+                                        .get_mod_info(spec)
+                                        .map(|info| info.name)
+                                        .unwrap_or_else(|| spec.url.clone());
+                                    ui.label(name).on_hover_text(&spec.url);
+                                    if ui
+                                        .add_enabled(
+                                            self.resolve_mod_rid.is_none(),
+                                            egui::Button::new("➕"),
+                                        )
+                                        .on_hover_text("Add to active profile")
+                                        .clicked()
+                                    {
+                                        add = Some(spec.clone());
+                                    }
+                                    if ui.button("🗑").on_hover_text("Remove").clicked() {
+                                        remove = Some(spec.clone());
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                    });
+                });
 
-                            let mut mods = Vec::new();
-                            self.state.mod_data.for_each_enabled_mod(
-                                &self.state.mod_data.active_profile,
-                                |mc| {
-                                    mods.push(mc.spec.clone());
-                                },
-                            );
+            if let Some(spec) = add {
+                message::ResolveMods::send(self, ctx, vec![spec], false);
+            }
+            if let Some(spec) = remove {
+                self.toggle_favorite(spec);
+            }
+            if !open {
+                self.favorites_window = None;
+            }
+        }
+    }
 
-                            self.lint_report = None;
-                            self.lint_rid = Some(message::LintMods::send(
-                                &mut self.request_counter,
-                                self.state.store.clone(),
-                                mods,
-                                BTreeSet::from_iter(
-                                    lint_options
-                                        .into_iter()
-                                        .filter_map(|(lint, enabled)| enabled.then_some(lint)),
-                                ),
-                                self.state.config.drg_pak_path.clone(),
-                                self.tx.clone(),
-                                ctx.clone(),
-                            ));
-                            self.problematic_mod_id = None;
-                            self.lint_report_window = Some(WindowLintReport);
+    fn show_output_locked(&mut self, ctx: &egui::Context) {
+        if let Some(window) = &self.output_locked_window {
+            let mut open = true;
+            let mut retry = false;
+            let mut cancel = false;
+
+            egui::Window::new("Unable to install mods")
+                .open(&mut open)
+                .collapsible(false)
+                .resizable(false)
+                .anchor(Align2::CENTER_CENTER, Vec2::ZERO)
+                .show(ctx, |ui| {
+                    ui.label(format!("{} is in use or read-only.", window.path.display()));
+                    ui.label("Close the game if it is running, then try again.");
+                    ui.horizontal(|ui| {
+                        retry = ui
+                            .add_enabled(self.integrate_rid.is_none(), egui::Button::new("Retry"))
+                            .clicked();
+                        cancel = ui.button("Cancel").clicked();
+                    });
+                });
+
+            if retry {
+                self.output_locked_window = None;
+                self.failed_batch = None;
+                self.install_mods(ctx);
+            } else if cancel || !open {
+                self.output_locked_window = None;
+            }
+        }
+    }
+
+    fn show_allowlist(&mut self, ctx: &egui::Context) {
+        if let Some(window) = &self.allowlist_window {
+            let mut open = true;
+            let mut remove = false;
+
+            let active_profile = self.state.mod_data.active_profile.clone();
+            let mut disallowed = vec![];
+            self.state
+                .mod_data
+                .for_each_enabled_mod(&active_profile, |mc| {
+                    let info = self.state.store.get_mod_info(&mc.spec);
+                    let modio_id = info.as_ref().and_then(|i| i.modio_id);
+                    if !window.allowlist.allows(&mc.spec, modio_id) {
+                        disallowed.push((mc.spec.clone(), info));
+                    }
+                });
+
+            egui::Window::new("Allowlist")
+                .open(&mut open)
+                .resizable(true)
+                .show(ctx, |ui| {
+                    ui.label(format!("Checking against {}", window.path.display()));
+                    if disallowed.is_empty() {
+                        ui.label("All enabled mods of the profile are on the allowlist.");
+                        return;
+                    }
+                    ui.label(
+                        egui::RichText::new(format!(
+                            "{} enabled mods are not on the allowlist:",
+                            disallowed.len()
+                        ))
+                        .color(ui.visuals().warn_fg_color),
+                    );
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        for (spec, info) in &disallowed {
+                            match info {
+                                Some(info) => ui.label(&info.name).on_hover_text(&spec.url),
+                                None => ui.label(&spec.url),
+                            };
                         }
                     });
+                    if ui
+                        .button("Remove from profile")
+                        .on_hover_text("Mods which are part of a group are disabled in the group, which affects the group in every profile")
+                        .clicked()
+                    {
+                        remove = true;
+                    }
                 });
 
+            if remove {
+                self.state.mod_data.remove_mods(&active_profile, |mc| {
+                    disallowed.iter().any(|(spec, _)| *spec == mc.spec)
+                });
+                self.state.mod_data.save().unwrap();
+            }
             if !open {
-                self.lints_toggle_window = None;
+                self.allowlist_window = None;
             }
         }
     }
@@ -1274,12 +3514,41 @@ impl App {
     fn show_lint_report(&mut self, ctx: &egui::Context) {
         if self.lint_report_window.is_some() {
             let mut open = true;
+            let mut acknowledge_changes: Vec<(String, Option<Vec<ModSpecification>>)> = vec![];
+            let heatmap_selection = self
+                .lint_report_window
+                .as_ref()
+                .and_then(|w| w.heatmap_selection.clone());
+            let mut heatmap_click = None;
+            let mut group_by_mod = self
+                .lint_report_window
+                .as_ref()
+                .is_some_and(|w| w.group_by_mod);
 
             egui::Window::new("Lint results")
                 .open(&mut open)
                 .resizable(true)
                 .show(ctx, |ui| {
-                    if let Some(report) = &self.lint_report {
+                    if let Some((linted_profile, report)) = &self.lint_report {
+                        // acknowledgements are those of the linted profile, which may no longer
+                        // be the active one
+                        let no_profile = ModProfile::default();
+                        let profile = self
+                            .state
+                            .mod_data
+                            .profiles
+                            .get(linted_profile)
+                            .unwrap_or(&no_profile);
+                        let loaded_paks = if self.state.config.all_archive_paks {
+                            "all `.pak`s will be loaded in load order"
+                        } else {
+                            "only the first encountered `.pak` will be loaded"
+                        };
+                        ui.horizontal(|ui| {
+                            ui.label("Group by:");
+                            ui.selectable_value(&mut group_by_mod, false, "Lint");
+                            ui.selectable_value(&mut group_by_mod, true, "Mod");
+                        });
                         let scroll_height =
                             (ui.available_height() - 30.0).clamp(0.0, f32::INFINITY);
                         egui::ScrollArea::vertical()
@@ -1287,15 +3556,82 @@ impl App {
                             .show(ui, |ui| {
                                 const AMBER: Color32 = Color32::from_rgb(255, 191, 0);
 
+                                if group_by_mod {
+                                    let mut by_mod = report.by_mod();
+                                    // acknowledged conflicts are hidden like in the warnings grouped by lint
+                                    for findings in by_mod.values_mut() {
+                                        let Some(paths) = findings.get_mut(&LintId::CONFLICTING) else {
+                                            continue;
+                                        };
+                                        paths.retain(|path| {
+                                            !report
+                                                .conflicting_mods
+                                                .as_ref()
+                                                .and_then(|c| c.get(path))
+                                                .is_some_and(|mods| profile.is_conflict_acknowledged(path, mods))
+                                        });
+                                        if paths.is_empty() {
+                                            findings.remove(&LintId::CONFLICTING);
+                                        }
+                                    }
+                                    by_mod.retain(|_, findings| !findings.is_empty());
+
+                                    if by_mod.is_empty() {
+                                        ui.label("No findings");
+                                    }
+                                    for (spec, findings) in &by_mod {
+                                        let name = self
+                                            .state
+                                            .store
+                                            .get_mod_info(spec)
+                                            .map(|info| info.name)
+                                            .unwrap_or_else(|| spec.url.clone());
+                                        let header = if findings.keys().all(LintId::is_advisory) {
+                                            RichText::new(format!("ℹ {name}"))
+                                        } else {
+                                            RichText::new(format!("⚠ {name}")).color(AMBER)
+                                        };
+                                        CollapsingHeader::new(header)
+                                            .id_salt(spec)
+                                            .default_open(by_mod.len() == 1)
+                                            .show(ui, |ui| {
+                                                ui.label(&spec.url);
+                                                for (lint, details) in findings {
+                                                    if details.is_empty() {
+                                                        ui.label(lint_title(*lint));
+                                                        continue;
+                                                    }
+                                                    CollapsingHeader::new(format!(
+                                                        "{} ({})",
+                                                        lint_title(*lint),
+                                                        details.len()
+                                                    ))
+                                                    .id_salt((spec, lint.name()))
+                                                    .show(ui, |ui| {
+                                                        for detail in details {
+                                                            ui.label(detail);
+                                                        }
+                                                    });
+                                                }
+                                            });
+                                    }
+                                    return;
+                                }
+
                                 if let Some(conflicting_mods) = &report.conflicting_mods {
-                                    if !conflicting_mods.is_empty() {
+                                    let (acknowledged, unacknowledged): (Vec<_>, Vec<_>) =
+                                        conflicting_mods.iter().partition(|(path, mods)| {
+                                            profile.is_conflict_acknowledged(path, *mods)
+                                        });
+
+                                    if !unacknowledged.is_empty() {
                                         CollapsingHeader::new(
                                             RichText::new("⚠ Mods(s) with conflicting asset modifications detected")
                                                 .color(AMBER),
                                         )
                                         .default_open(true)
                                         .show(ui, |ui| {
-                                            conflicting_mods.iter().for_each(|(path, mods)| {
+                                            unacknowledged.iter().for_each(|(path, mods)| {
                                                 CollapsingHeader::new(
                                                     RichText::new(format!(
                                                         "⚠ Conflicting modification of asset `{}`",
@@ -1309,9 +3645,132 @@ impl App {
                                                         mods.iter().for_each(|mod_spec| {
                                                             ui.label(&mod_spec.url);
                                                         });
+                                                        if ui
+                                                            .button("Acknowledge")
+                                                            .on_hover_text("Hide this conflict until the mods involved change")
+                                                            .clicked()
+                                                        {
+                                                            acknowledge_changes.push((
+                                                                path.to_string(),
+                                                                Some(mods.iter().cloned().collect()),
+                                                            ));
+                                                        }
                                                     },
                                                 );
                                             });
+
+                                            let pairs = conflict_pairs(unacknowledged.iter().copied());
+                                            let mods = pairs
+                                                .keys()
+                                                .flat_map(|(a, b)| [a, b])
+                                                .collect::<BTreeSet<_>>()
+                                                .into_iter()
+                                                .collect::<Vec<_>>();
+                                            let max = pairs.values().map(BTreeSet::len).max().unwrap_or(1);
+                                            let name = |spec: &ModSpecification| {
+                                                self.state
+                                                    .store
+                                                    .get_mod_info(spec)
+                                                    .map(|info| info.name)
+                                                    .unwrap_or_else(|| spec.url.clone())
+                                            };
+                                            CollapsingHeader::new("Conflict heatmap")
+                                                .default_open(mods.len() > 2)
+                                                .show(ui, |ui| {
+                                                    egui::Grid::new("conflict_heatmap")
+                                                        .spacing([2.0, 2.0])
+                                                        .show(ui, |ui| {
+                                                            ui.label("");
+                                                            for (i, &spec) in mods.iter().enumerate() {
+                                                                ui.label((i + 1).to_string())
+                                                                    .on_hover_text(name(spec));
+                                                            }
+                                                            ui.end_row();
+
+                                                            for (i, &a) in mods.iter().enumerate() {
+                                                                ui.label(format!("{}. {}", i + 1, name(a)))
+                                                                    .on_hover_text(&a.url);
+                                                                for &b in &mods {
+                                                                    let pair = if a < b {
+                                                                        (a.clone(), b.clone())
+                                                                    } else {
+                                                                        (b.clone(), a.clone())
+                                                                    };
+                                                                    let Some(paths) = pairs.get(&pair) else {
+                                                                        ui.label("");
+                                                                        continue;
+                                                                    };
+                                                                    // stronger color for pairs sharing more assets
+                                                                    let alpha = 64.0 + 191.0 * paths.len() as f32 / max as f32;
+                                                                    let cell = Button::new(
+                                                                        RichText::new(paths.len().to_string())
+                                                                            .color(Color32::BLACK),
+                                                                    )
+                                                                    .fill(Color32::from_rgba_unmultiplied(255, 191, 0, alpha as u8))
+                                                                    .selected(heatmap_selection.as_ref() == Some(&pair));
+                                                                    if ui
+                                                                        .add(cell)
+                                                                        .on_hover_text(format!(
+                                                                            "{} and {} both modify {} asset(s)",
+                                                                            name(a),
+                                                                            name(b),
+                                                                            paths.len()
+                                                                        ))
+                                                                        .clicked()
+                                                                    {
+                                                                        heatmap_click = Some(pair);
+                                                                    }
+                                                                }
+                                                                ui.end_row();
+                                                            }
+                                                        });
+
+                                                    if let Some(((a, b), paths)) = heatmap_selection
+                                                        .as_ref()
+                                                        .and_then(|pair| pairs.get_key_value(pair))
+                                                    {
+                                                        ui.separator();
+                                                        ui.label(format!(
+                                                            "Assets modified by both {} and {}:",
+                                                            name(a),
+                                                            name(b)
+                                                        ));
+                                                        for path in paths {
+                                                            ui.label(path);
+                                                        }
+                                                    }
+                                                });
+                                        });
+                                    }
+
+                                    if !acknowledged.is_empty() {
+                                        CollapsingHeader::new(
+                                            RichText::new(format!(
+                                                "Acknowledged conflicts ({})",
+                                                acknowledged.len()
+                                            ))
+                                            .color(Color32::GRAY),
+                                        )
+                                        .default_open(false)
+                                        .show(ui, |ui| {
+                                            acknowledged.iter().for_each(|(path, mods)| {
+                                                CollapsingHeader::new(
+                                                    RichText::new(format!("`{}`", path))
+                                                        .color(Color32::GRAY),
+                                                )
+                                                .show(ui, |ui| {
+                                                    mods.iter().for_each(|mod_spec| {
+                                                        ui.label(
+                                                            RichText::new(&mod_spec.url)
+                                                                .color(Color32::GRAY),
+                                                        );
+                                                    });
+                                                    if ui.button("Unacknowledge").clicked() {
+                                                        acknowledge_changes
+                                                            .push((path.to_string(), None));
+                                                    }
+                                                });
+                                            });
                                         });
                                     }
                                 }
@@ -1457,7 +3916,7 @@ impl App {
                                         .show(ui, |ui| {
                                             archive_with_multiple_paks_mods.iter().for_each(|r#mod| {
                                                 ui.label(RichText::new(format!(
-                                                    "⚠ {} contains multiple `.pak`s, only the first encountered `.pak` will be loaded",
+                                                    "⚠ {} contains multiple `.pak`s, {loaded_paks}",
                                                     r#mod.url
                                                 ))
                                                 .color(AMBER));
@@ -1466,20 +3925,114 @@ impl App {
                                     }
                                 }
 
-                                if let Some(non_asset_file_mods) = &report.non_asset_file_mods {
-                                    if !non_asset_file_mods.is_empty() {
+                                if let Some(non_asset_file_mods) = &report.non_asset_file_mods {
+                                    if !non_asset_file_mods.is_empty() {
+                                        CollapsingHeader::new(
+                                            RichText::new(
+                                                "⚠ Mod(s) with non-asset files detected",
+                                            )
+                                            .color(AMBER),
+                                        )
+                                        .default_open(true)
+                                        .show(ui, |ui| {
+                                            non_asset_file_mods.iter().for_each(|(r#mod, files)| {
+                                                CollapsingHeader::new(
+                                                    RichText::new(format!(
+                                                        "⚠ {} includes non-asset files",
+                                                        r#mod.url
+                                                    ))
+                                                    .color(AMBER),
+                                                )
+                                                .show(ui, |ui| {
+                                                    files.iter().for_each(|file| {
+                                                        ui.label(file);
+                                                    });
+                                                });
+                                            });
+                                        });
+                                    }
+                                }
+
+                                if let Some(split_asset_pairs_mods) = &report.split_asset_pairs_mods {
+                                    if !split_asset_pairs_mods.is_empty() {
+                                        CollapsingHeader::new(
+                                            RichText::new(
+                                                "⚠ Mod(s) with split {uexp, uasset} pairs detected",
+                                            )
+                                            .color(AMBER),
+                                        )
+                                        .default_open(true)
+                                        .show(ui, |ui| {
+                                            split_asset_pairs_mods.iter().for_each(|(r#mod, files)| {
+                                                CollapsingHeader::new(
+                                                    RichText::new(format!(
+                                                        "⚠ {} includes split {{uexp, uasset}} pairs",
+                                                        r#mod.url
+                                                    ))
+                                                    .color(AMBER),
+                                                )
+                                                .show(ui, |ui| {
+                                                    files.iter().for_each(|(file, kind)| {
+                                                        match kind {
+                                                            SplitAssetPair::MissingUasset => {
+                                                                ui.label(format!("`{file}` missing matching .uasset file"));
+                                                            },
+                                                            SplitAssetPair::MissingUexp => {
+                                                                ui.label(format!("`{file}` missing matching .uexp file"));
+                                                            }
+                                                        }
+                                                    });
+                                                });
+                                            });
+                                        });
+                                    }
+                                }
+
+                                if let Some(overlapping_mount_points_mods) = &report.overlapping_mount_points_mods {
+                                    if !overlapping_mount_points_mods.is_empty() {
+                                        CollapsingHeader::new(
+                                            RichText::new(
+                                                "⚠ Mod(s) with overlapping mount points detected",
+                                            )
+                                            .color(AMBER),
+                                        )
+                                        .default_open(true)
+                                        .show(ui, |ui| {
+                                            overlapping_mount_points_mods.iter().for_each(|((broad, narrow), overlap)| {
+                                                CollapsingHeader::new(
+                                                    RichText::new(format!(
+                                                        "⚠ {} (mounted at `{}`) overlaps {} (mounted at `{}`)",
+                                                        broad.url,
+                                                        overlap.broad_mount_point,
+                                                        narrow.url,
+                                                        overlap.narrow_mount_point,
+                                                    ))
+                                                    .color(AMBER),
+                                                )
+                                                .show(ui, |ui| {
+                                                    overlap.shadowed_files.iter().for_each(|file| {
+                                                        ui.label(file);
+                                                    });
+                                                });
+                                            });
+                                        });
+                                    }
+                                }
+
+                                if let Some(mint_artifact_mods) = &report.mint_artifact_mods {
+                                    if !mint_artifact_mods.is_empty() {
                                         CollapsingHeader::new(
                                             RichText::new(
-                                                "⚠ Mod(s) with non-asset files detected",
+                                                "⚠ Mod(s) containing mint's hook or its files detected",
                                             )
                                             .color(AMBER),
                                         )
                                         .default_open(true)
                                         .show(ui, |ui| {
-                                            non_asset_file_mods.iter().for_each(|(r#mod, files)| {
+                                            mint_artifact_mods.iter().for_each(|(r#mod, files)| {
                                                 CollapsingHeader::new(
                                                     RichText::new(format!(
-                                                        "⚠ {} includes non-asset files",
+                                                        "⚠ {} includes mint's hook or its files",
                                                         r#mod.url
                                                     ))
                                                     .color(AMBER),
@@ -1494,34 +4047,22 @@ impl App {
                                     }
                                 }
 
-                                if let Some(split_asset_pairs_mods) = &report.split_asset_pairs_mods {
-                                    if !split_asset_pairs_mods.is_empty() {
+                                if let Some(untagged_gameplay_mods) = &report.untagged_gameplay_mods {
+                                    if !untagged_gameplay_mods.is_empty() {
                                         CollapsingHeader::new(
-                                            RichText::new(
-                                                "⚠ Mod(s) with split {uexp, uasset} pairs detected",
-                                            )
-                                            .color(AMBER),
+                                            "ℹ Gameplay mod(s) not tagged as required or verified detected",
                                         )
                                         .default_open(true)
                                         .show(ui, |ui| {
-                                            split_asset_pairs_mods.iter().for_each(|(r#mod, files)| {
-                                                CollapsingHeader::new(
-                                                    RichText::new(format!(
-                                                        "⚠ {} includes split {{uexp, uasset}} pairs",
-                                                        r#mod.url
-                                                    ))
-                                                    .color(AMBER),
-                                                )
+                                            ui.label("These mods look like they change gameplay but do not declare whether every player needs them. Check that they work in your lobbies.");
+                                            untagged_gameplay_mods.iter().for_each(|(r#mod, files)| {
+                                                CollapsingHeader::new(format!(
+                                                    "ℹ {} includes gameplay assets",
+                                                    r#mod.url
+                                                ))
                                                 .show(ui, |ui| {
-                                                    files.iter().for_each(|(file, kind)| {
-                                                        match kind {
-                                                            SplitAssetPair::MissingUasset => {
-                                                                ui.label(format!("`{file}` missing matching .uasset file"));
-                                                            },
-                                                            SplitAssetPair::MissingUexp => {
-                                                                ui.label(format!("`{file}` missing matching .uexp file"));
-                                                            }
-                                                        }
+                                                    files.iter().for_each(|file| {
+                                                        ui.label(file);
                                                     });
                                                 });
                                             });
@@ -1563,6 +4104,30 @@ impl App {
                     }
                 });
 
+            if !acknowledge_changes.is_empty()
+                && let Some((linted_profile, _)) = &self.lint_report
+                && let Some(profile) = self.state.mod_data.profiles.get_mut(linted_profile)
+            {
+                for (path, mods) in acknowledge_changes {
+                    match mods {
+                        Some(mods) => profile.acknowledge_conflict(&path, &mods),
+                        None => profile.unacknowledge_conflict(&path),
+                    }
+                }
+                self.state.mod_data.save().unwrap();
+            }
+
+            // clicking the selected cell again hides its assets
+            if let Some(pair) = heatmap_click
+                && let Some(window) = &mut self.lint_report_window
+            {
+                window.heatmap_selection =
+                    (window.heatmap_selection.as_ref() != Some(&pair)).then_some(pair);
+            }
+            if let Some(window) = &mut self.lint_report_window {
+                window.group_by_mod = group_by_mod;
+            }
+
             if !open {
                 self.lint_report_window = None;
                 self.lint_rid = None;
@@ -1570,6 +4135,15 @@ impl App {
         }
     }
 
+    fn get_filter_config(&self) -> FilterConfig {
+        self.state.config.filter_config.clone().unwrap_or_default()
+    }
+
+    fn update_filter_config(&mut self, filter_config: FilterConfig) {
+        self.state.config.filter_config = (!filter_config.is_empty()).then_some(filter_config);
+        self.state.config.save().unwrap();
+    }
+
     fn get_sorting_config(&self) -> Option<SortingConfig> {
         self.state.config.sorting_config.clone()
     }
@@ -1586,17 +4160,16 @@ impl App {
 type ModListEntry<'a> = (&'a ModOrGroup, Option<&'a ModInfo>);
 fn sort_mods(config: SortingConfig) -> impl Fn(ModListEntry, ModListEntry) -> Ordering {
     move |(a, info_a), (b, info_b)| {
-        if matches!(a, ModOrGroup::Group { .. }) || matches!(b, ModOrGroup::Group { .. }) {
-            unimplemented!("Groups in sorting not implemented");
-        }
-
-        let ModOrGroup::Individual(mc_a) = a else {
-            debug!("Item is not Individual \n{:?}", a);
-            return Ordering::Equal;
-        };
-        let ModOrGroup::Individual(mc_b) = b else {
-            debug!("Item is not Individual \n{:?}", b);
-            return Ordering::Equal;
+        let (ModOrGroup::Individual(mc_a), ModOrGroup::Individual(mc_b)) = (a, b) else {
+            // groups come after the mods, by name
+            return match (a, b) {
+                (
+                    ModOrGroup::Group { group_name: a, .. },
+                    ModOrGroup::Group { group_name: b, .. },
+                ) => a.cmp(b),
+                (ModOrGroup::Group { .. }, _) => Ordering::Greater,
+                _ => Ordering::Less,
+            };
         };
 
         fn map_cmp<V, M, F>(a: &V, b: &V, map: F) -> Ordering
@@ -1608,16 +4181,25 @@ fn sort_mods(config: SortingConfig) -> impl Fn(ModListEntry, ModListEntry) -> Or
         }
 
         let name_order = map_cmp(&(mc_a, info_a), &(mc_b, info_b), |(mc, info)| {
-            (info.map(|i| i.name.to_lowercase()), &mc.spec.url)
+            (
+                (mc.display_name.is_some() || info.is_some()).then(|| mc.name(info).to_lowercase()),
+                &mc.spec.url,
+            )
         });
         let provider_order = map_cmp(&info_a, &info_b, |info| info.map(|i| i.provider));
+        // mods without mod.io metadata are grouped into an "unknown" bucket after all known
+        // categories rather than being mixed in with them
         let approval_order = map_cmp(&info_a, &info_b, |info| {
-            info.and_then(|i| i.modio_tags.as_ref())
-                .map(|t| t.approval_status)
+            let status = info
+                .and_then(|i| i.modio_tags.as_ref())
+                .map(|t| t.approval_status);
+            (status.is_none(), status)
         });
         let required_order = map_cmp(&info_a, &info_b, |info| {
-            info.and_then(|i| i.modio_tags.as_ref())
-                .map(|t| std::cmp::Reverse(t.required_status))
+            let status = info
+                .and_then(|i| i.modio_tags.as_ref())
+                .map(|t| std::cmp::Reverse(t.required_status));
+            (status.is_none(), status)
         });
         let mut order = match config.sort_category {
             SortBy::Enabled => mc_b.enabled.cmp(&mc_a.enabled),
@@ -1626,6 +4208,8 @@ fn sort_mods(config: SortingConfig) -> impl Fn(ModListEntry, ModListEntry) -> Or
             SortBy::Provider => provider_order,
             SortBy::RequiredStatus => required_order,
             SortBy::ApprovalCategory => approval_order,
+            // mods added before dates were recorded count as the oldest
+            SortBy::DateAdded => mc_a.added.cmp(&mc_b.added),
         };
 
         if config.is_ascending {
@@ -1642,8 +4226,13 @@ struct WindowProviderParameters {
     tx: Sender<(RequestID, Result<(), ProviderError>)>,
     rx: Receiver<(RequestID, Result<(), ProviderError>)>,
     check_rid: Option<(RequestID, JoinHandle<()>)>,
+    /// Whether the running check only tests the parameters rather than saving them.
+    check_is_test: bool,
     check_error: Option<String>,
+    /// The last test succeeded and the parameters have not been edited since.
+    test_passed: bool,
     factory: &'static ProviderFactory,
+    credentials_name: String,
     parameters: HashMap<String, String>,
 }
 
@@ -1654,7 +4243,13 @@ impl WindowProviderParameters {
             tx,
             rx,
             check_rid: None,
+            check_is_test: false,
             check_error: None,
+            test_passed: false,
+            credentials_name: state
+                .selected_credentials(factory.id)
+                .unwrap_or_default()
+                .to_string(),
             parameters: state
                 .config
                 .provider_parameters
@@ -1666,9 +4261,82 @@ impl WindowProviderParameters {
     }
 }
 
+/// How long to wait after the DRG pak path was last edited before validating it.
+const DRG_PAK_PATH_CHECK_DELAY: Duration = Duration::from_millis(400);
+
 struct WindowSettings {
     drg_pak_path: String,
     drg_pak_path_err: Option<String>,
+    /// Whether the current `drg_pak_path` passed validation.
+    drg_pak_path_valid: bool,
+    /// When the pak path was last edited, if it has not been validated since.
+    drg_pak_path_edited: Option<Instant>,
+    drg_pak_path_check_rid: Option<MessageHandle<()>>,
+    pak_key: String,
+    pak_key_err: Option<String>,
+    launch_args: String,
+    launch_args_err: Option<String>,
+    export_pak_path: String,
+    disk_usage: Option<DiskUsage>,
+    disk_usage_rid: Option<MessageHandle<()>>,
+    clear_cache_rid: Option<MessageHandle<()>>,
+}
+
+/// Sizes in bytes of the directories mint stores files in.
+#[derive(Debug, Clone, Copy)]
+pub struct DiskUsage {
+    config: u64,
+    cache: u64,
+    /// Downloaded mods, part of `cache`.
+    blobs: u64,
+    data: u64,
+}
+
+/// Cache size above which clearing it is suggested.
+const LARGE_CACHE_SIZE: u64 = 5 * 1024 * 1024 * 1024;
+
+fn launch_game(args: Vec<String>) {
+    std::thread::spawn(move || {
+        let mut iter = args.iter();
+        std::process::Command::new(iter.next().unwrap())
+            .args(iter)
+            .spawn()
+            .unwrap()
+            .wait()
+            .unwrap();
+    });
+}
+
+/// The list is optional and only used for warnings so a broken one is logged and ignored.
+fn load_incompatibilities(state: &State) -> Incompatibilities {
+    Incompatibilities::load(&state.dirs.config_dir).unwrap_or_else(|e| {
+        warn!("failed to read known incompatible mods: {e}");
+        Default::default()
+    })
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+fn size_color(ui: &Ui, bytes: u64) -> Color32 {
+    if bytes >= LARGE_CACHE_SIZE {
+        ui.visuals().error_fg_color
+    } else if bytes >= LARGE_CACHE_SIZE / 5 {
+        ui.visuals().warn_fg_color
+    } else {
+        Color32::GREEN
+    }
 }
 
 impl WindowSettings {
@@ -1682,14 +4350,141 @@ impl WindowSettings {
         Self {
             drg_pak_path: path,
             drg_pak_path_err: None,
+            drg_pak_path_valid: false,
+            drg_pak_path_edited: Some(Instant::now()),
+            drg_pak_path_check_rid: None,
+            pak_key: state
+                .config
+                .pak_key
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_default(),
+            pak_key_err: None,
+            launch_args: join_args(&state.config.launch_args),
+            launch_args_err: None,
+            export_pak_path: state
+                .config
+                .export_pak_path
+                .as_ref()
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            disk_usage: None,
+            disk_usage_rid: None,
+            clear_cache_rid: None,
         }
     }
 }
 
-struct WindowLintReport;
+#[derive(Default)]
+struct WindowLintReport {
+    /// Pair of mods whose shared assets are listed below the conflict heatmap.
+    heatmap_selection: Option<(ModSpecification, ModSpecification)>,
+    /// Show the findings grouped by mod instead of by lint.
+    group_by_mod: bool,
+}
+
+/// Title of a lint in the lint report when its findings are grouped by mod.
+fn lint_title(id: LintId) -> &'static str {
+    match id {
+        LintId::CONFLICTING => "Conflicting files",
+        LintId::ASSET_REGISTRY_BIN => "Contains AssetRegistry.bin",
+        LintId::SHADER_FILES => "Contains shader files",
+        LintId::OUTDATED_PAK_VERSION => "Outdated pak version",
+        LintId::EMPTY_ARCHIVE => "Empty archive",
+        LintId::ARCHIVE_WITH_ONLY_NON_PAK_FILES => "Archive with only non-pak files",
+        LintId::ARCHIVE_WITH_MULTIPLE_PAKS => "Archive with multiple paks",
+        LintId::NON_ASSET_FILES => "Contains non-asset files",
+        LintId::SPLIT_ASSET_PAIRS => "Split {uexp, uasset} pairs",
+        LintId::UNMODIFIED_GAME_ASSETS => "Contains unmodified game assets",
+        LintId::OVERLAPPING_MOUNT_POINTS => "Overlapping mount points",
+        LintId::MINT_ARTIFACTS => "Contains mint's hook or its files",
+        LintId::UNTAGGED_GAMEPLAY => "Gameplay assets without required or verified tag",
+        _ => id.name(),
+    }
+}
 
 struct WindowLintsToggle;
 
+struct WindowFavorites;
+
+/// Installing failed because the mods pak or hook DLL could not be written.
+struct WindowOutputLocked {
+    path: PathBuf,
+}
+
+struct WindowAllowlist {
+    path: PathBuf,
+    allowlist: Allowlist,
+}
+
+struct WindowDescription {
+    spec: ModSpecification,
+}
+
+/// Priority overrides of a mod being edited, see [`PriorityOverride`].
+struct WindowPriorityOverrides {
+    profile: String,
+    spec: ModSpecification,
+    /// (pattern, priority) as entered, only checked when saving.
+    rows: Vec<(String, i32)>,
+    error: Option<String>,
+}
+
+/// Display name of a mod being edited in place of its name in the mod list.
+struct RenameMod {
+    spec: ModSpecification,
+    name: String,
+    /// Whether the text field still has to take focus.
+    focus: bool,
+}
+
+struct WindowAssetTree {
+    rid: Option<MessageHandle<()>>,
+    tree: Option<AssetTree>,
+    err: Option<String>,
+    filter: String,
+    overrides_only: bool,
+    /// [`Self::tree`] with the filter applied, recomputed when it changes.
+    shown: Option<AssetTree>,
+}
+
+/// Folders of `dir` as collapsible headers followed by its files annotated with the mod they are
+/// taken from. `path` is the path of `dir` and keeps the headers of equally named folders apart.
+fn show_asset_dir(
+    ui: &mut egui::Ui,
+    dir: &AssetTree,
+    path: &str,
+    mod_name: &dyn Fn(&ModSpecification) -> String,
+) {
+    for (name, sub_dir) in &dir.dirs {
+        let sub_path = format!("{path}{name}/");
+        CollapsingHeader::new(format!("{name}/ ({})", sub_dir.file_count()))
+            .id_salt(&sub_path)
+            .show(ui, |ui| show_asset_dir(ui, sub_dir, &sub_path, mod_name));
+    }
+    for (name, leaf) in &dir.files {
+        ui.horizontal(|ui| {
+            ui.label(name);
+            let winner = mod_name(&leaf.winner);
+            if leaf.overridden.is_empty() {
+                ui.weak(winner);
+            } else {
+                ui.colored_label(ui.visuals().warn_fg_color, winner)
+                    .on_hover_text(format!(
+                        "Also provided by: {}",
+                        leaf.overridden.iter().map(mod_name).join(", ")
+                    ));
+            }
+        });
+    }
+}
+
+struct WindowUpdateDiff {
+    versions: (ModSpecification, ModSpecification),
+    rid: Option<MessageHandle<()>>,
+    err: Option<String>,
+}
+
 impl eframe::App for App {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         if self.needs_restart
@@ -1721,6 +4516,20 @@ impl eframe::App for App {
         while let Ok(msg) = self.rx.try_recv() {
             msg.handle(self);
         }
+        if std::mem::take(&mut self.smoke_test_pending) {
+            self.start_smoke_test(ctx);
+        }
+
+        // profiles can select their own provider credentials
+        if self.credentials_profile != self.state.mod_data.active_profile {
+            self.credentials_profile = self.state.mod_data.active_profile.clone();
+            if let Err(e) = self.state.apply_credentials() {
+                self.last_action = Some(
+                    LastAction::failure(format!("failed to switch provider credentials: {e}"))
+                        .with_error(e),
+                );
+            }
+        }
 
         // begin draw
 
@@ -1729,40 +4538,127 @@ impl eframe::App for App {
         self.show_profile_windows(ctx);
         self.show_settings(ctx);
         self.show_lints_toggle(ctx);
+        self.show_favorites(ctx);
+        self.show_allowlist(ctx);
+        self.show_output_locked(ctx);
+        self.show_update_diff(ctx);
+        self.show_asset_tree(ctx);
+        self.show_notifications(ctx);
+        self.show_stats(ctx);
+        self.show_description(ctx);
+        self.show_priority_overrides(ctx);
         self.show_lint_report(ctx);
+        self.show_watchdog(ctx);
 
         egui::TopBottomPanel::bottom("bottom_panel").show(ctx, |ui| {
             ui.with_layout(egui::Layout::right_to_left(Align::TOP), |ui| {
+                if self.smoke_test_rid.is_some() {
+                    ui.spinner();
+                    ui.label("Checking that the game starts...");
+                }
                 ui.add_enabled_ui(
                     self.integrate_rid.is_none()
                         && self.update_rid.is_none()
                         && self.lint_rid.is_none()
                         && self.self_update_rid.is_none()
+                        && self.smoke_test_rid.is_none()
                         && self.state.config.drg_pak_path.is_some(),
                     |ui| {
-                        if let Some(args) = &self.args {
+                        if let Some(args) = self
+                            .args
+                            .as_ref()
+                            .map(|args| launch_command(args, &self.state.config.launch_args))
+                        {
                             if ui
                                 .button("Launch game")
                                 .on_hover_ui(|ui| {
-                                    for arg in args {
+                                    for arg in &args {
                                         ui.label(arg);
                                     }
                                 })
                                 .clicked()
                             {
-                                let args = args.clone();
-                                std::thread::spawn(move || {
-                                    let mut iter = args.iter();
-                                    std::process::Command::new(iter.next().unwrap())
-                                        .args(iter)
-                                        .spawn()
-                                        .unwrap()
-                                        .wait()
-                                        .unwrap();
+                                self.check_hook_before_launch();
+                                self.check_game_updated();
+                                launch_game(args.clone());
+                            }
+
+                            if ui
+                                .button("Launch safe mode")
+                                .on_hover_text("Uninstall mods and launch the game without them for troubleshooting.\nThey stay uninstalled until restored with \"Restore mods\".")
+                                .clicked()
+                                && let Some(pak_path) = self.state.config.drg_pak_path.clone()
+                            {
+                                debug!("launching safe mode: pak_path = {}", pak_path.display());
+                                self.last_action = Some(match self.uninstall_mods(&pak_path) {
+                                    Ok(()) => {
+                                        self.safe_mode = true;
+                                        launch_game(args);
+                                        LastAction::success(
+                                            "Launched without mods, restore them when done"
+                                                .to_string(),
+                                        )
+                                    }
+                                    Err(e) => LastAction::failure(format!(
+                                        "Failed to uninstall mods for safe mode: {e}"
+                                    ))
+                                    .with_error(e),
                                 });
                             }
                         }
 
+                        if let Some(problem) = self.hook_problem
+                            && ui
+                                .button(
+                                    RichText::new("Reinstall hook")
+                                        .color(ui.visuals().warn_fg_color),
+                                )
+                                .on_hover_text(format!(
+                                    "The {problem}, mods may not load.\nDeploy the hook DLL bundled with this version of mint again."
+                                ))
+                                .clicked()
+                        {
+                            self.reinstall_hook();
+                        }
+
+                        if self.game_updated
+                            && ui
+                                .button(
+                                    RichText::new("Reinstall mods")
+                                        .color(ui.visuals().warn_fg_color),
+                                )
+                                .on_hover_text("DRG was updated since mods were installed, which can break them.\nInstall the active profile again against the current version of the game.")
+                                .clicked()
+                        {
+                            self.install_mods(ctx);
+                        }
+
+                        if !self.other_paks.is_empty() {
+                            ui.colored_label(
+                                ui.visuals().warn_fg_color,
+                                format!("⚠ {} other pak(s)", self.other_paks.len()),
+                            )
+                            .on_hover_text(format!(
+                                "Paks of other tools or installed by hand are loaded together with the installed mods:\n{}",
+                                self.other_paks
+                                    .iter()
+                                    .map(ToString::to_string)
+                                    .collect::<Vec<_>>()
+                                    .join("\n")
+                            ));
+                        }
+
+                        if self.safe_mode
+                            && ui
+                                .button("Restore mods")
+                                .on_hover_text(
+                                    "Install the active profile again after launching in safe mode",
+                                )
+                                .clicked()
+                        {
+                            self.install_mods(ctx);
+                        }
+
                         ui.add_enabled_ui(self.state.config.drg_pak_path.is_some(), |ui| {
                             let mut button = ui.button("Install mods");
                             if self.state.config.drg_pak_path.is_none() {
@@ -1772,32 +4668,7 @@ impl eframe::App for App {
                             }
 
                             if button.clicked() {
-                                let mut mod_configs = Vec::new();
-                                let mut mods = Vec::new();
-                                let active_profile = self.state.mod_data.active_profile.clone();
-                                self.state
-                                    .mod_data
-                                    .for_each_enabled_mod(&active_profile, |mc| {
-                                        mod_configs.push(mc.clone());
-                                    });
-
-                                mod_configs.sort_by_key(|k| -k.priority);
-
-                                for config in mod_configs {
-                                    mods.push(config.spec.clone());
-                                }
-
-                                self.last_action = None;
-                                self.integrate_rid = Some(message::Integrate::send(
-                                    &mut self.request_counter,
-                                    self.state.store.clone(),
-                                    mods,
-                                    self.state.config.drg_pak_path.as_ref().unwrap().clone(),
-                                    self.state.config.deref().into(),
-                                    self.tx.clone(),
-                                    ctx.clone(),
-                                ));
-                                self.problematic_mod_id = None;
+                                self.install_mods(ctx);
                             }
                         });
 
@@ -1810,36 +4681,52 @@ impl eframe::App for App {
                             }
                             if button.clicked() {
                                 self.last_action = None;
-                                if let Some(pak_path) = &self.state.config.drg_pak_path {
-                                    let mut mods = HashSet::default();
-                                    let active_profile = self.state.mod_data.active_profile.clone();
-                                    self.state.mod_data.for_each_enabled_mod(
-                                        &active_profile,
-                                        |mc| {
-                                            if let Some(modio_id) = self
-                                                .state
-                                                .store
-                                                .get_mod_info(&mc.spec)
-                                                .and_then(|i| i.modio_id)
-                                            {
-                                                mods.insert(modio_id);
-                                            }
-                                        },
-                                    );
-
+                                if let Some(pak_path) = self.state.config.drg_pak_path.clone() {
                                     debug!("uninstalling mods: pak_path = {}", pak_path.display());
-                                    self.last_action = Some(match uninstall(pak_path, mods) {
+                                    self.safe_mode = false;
+                                    self.last_action = Some(match self.uninstall_mods(&pak_path) {
                                         Ok(()) => LastAction::success(
                                             "Successfully uninstalled mods".to_string(),
                                         ),
                                         Err(e) => LastAction::failure(format!(
                                             "Failed to uninstall mods: {e}"
-                                        )),
+                                        ))
+                                        .with_error(e),
                                     })
                                 }
                             }
                         });
 
+                        if let Some(failed) = &self.failed_batch {
+                            let hover = match failed {
+                                FailedBatch::Resolve { specs, .. } => format!(
+                                    "Retry resolving the mods which failed:\n{}",
+                                    specs
+                                        .iter()
+                                        .map(|s| s.url.as_str())
+                                        .collect::<Vec<_>>()
+                                        .join("\n")
+                                ),
+                                FailedBatch::Integrate => "Retry installing. Mods which were already downloaded are not fetched again.".to_string(),
+                            };
+                            if ui
+                                .add_enabled(
+                                    self.resolve_mod_rid.is_none(),
+                                    egui::Button::new("Retry failed"),
+                                )
+                                .on_hover_text(hover)
+                                .clicked()
+                            {
+                                match self.failed_batch.take().unwrap() {
+                                    FailedBatch::Resolve {
+                                        specs,
+                                        is_dependency,
+                                    } => message::ResolveMods::send(self, ctx, specs, is_dependency),
+                                    FailedBatch::Integrate => self.install_mods(ctx),
+                                }
+                            }
+                        }
+
                         if ui
                             .button("Update cache")
                             .on_hover_text(
@@ -1850,6 +4737,28 @@ impl eframe::App for App {
                             message::UpdateCache::send(self);
                             self.problematic_mod_id = None;
                         }
+
+                        if ui
+                            .button("Refresh metadata")
+                            .on_hover_text(
+                                "Re-fetches names, tags and approval status for all mods in the profile without re-downloading them",
+                            )
+                            .clicked()
+                        {
+                            message::RefreshMetadata::send(self, ui.ctx());
+                            self.problematic_mod_id = None;
+                        }
+
+                        if ui
+                            .button("Check availability")
+                            .on_hover_text(
+                                "Checks whether all mods in the profile still exist and can be downloaded",
+                            )
+                            .clicked()
+                        {
+                            message::CheckAvailability::send(self, ui.ctx());
+                            self.problematic_mod_id = None;
+                        }
                     },
                 );
                 if self.integrate_rid.is_some() {
@@ -1871,6 +4780,66 @@ impl eframe::App for App {
                 {
                     self.lints_toggle_window = Some(WindowLintsToggle);
                 }
+                if ui
+                    .add_enabled(
+                        self.integrate_rid.is_none() && self.lint_rid.is_none(),
+                        egui::Button::new("Check conflicts"),
+                    )
+                    .on_hover_text("Quickly check whether any enabled mods modify the same files")
+                    .clicked()
+                {
+                    self.lint_active_profile(ctx, BTreeSet::from([LintId::CONFLICTING]));
+                }
+                if ui
+                    .add_enabled(
+                        self.integrate_rid.is_none() && self.asset_tree_window.is_none(),
+                        egui::Button::new("Merged assets"),
+                    )
+                    .on_hover_text("Browse the files installing the current profile would produce and which mod each is taken from")
+                    .clicked()
+                {
+                    self.open_asset_tree(ctx);
+                }
+                if ui
+                    .button("Allowlist")
+                    .on_hover_text("Check the current profile against a server allowlist")
+                    .clicked()
+                {
+                    if let Some(path) = rfd::FileDialog::new().pick_file() {
+                        match Allowlist::read(&path) {
+                            Ok(allowlist) => {
+                                self.allowlist_window = Some(WindowAllowlist { path, allowlist })
+                            }
+                            Err(e) => {
+                                self.last_action = Some(
+                                    LastAction::failure(format!("Failed to read allowlist: {e}"))
+                                        .with_error(e),
+                                )
+                            }
+                        }
+                    }
+                }
+                if ui
+                    .button("★")
+                    .on_hover_text("Browse favorite mods")
+                    .clicked()
+                {
+                    self.favorites_window = Some(WindowFavorites);
+                }
+                let notification_count = self.notifications().len();
+                let bell = if notification_count == 0 {
+                    RichText::new("🔔")
+                } else {
+                    RichText::new(format!("🔔 {notification_count}"))
+                        .color(ui.visuals().warn_fg_color)
+                };
+                if ui
+                    .button(bell)
+                    .on_hover_text("Show warnings about the current profile and installation")
+                    .clicked()
+                {
+                    self.notifications_window = !self.notifications_window;
+                }
                 if ui.button("⚙").on_hover_text("Open settings").clicked() {
                     self.settings_window = Some(WindowSettings::new(&self.state));
                 }
@@ -1913,6 +4882,23 @@ impl eframe::App for App {
                         };
                         ui.ctx().request_repaint(); // for continuously updating time
                         ui.label(format!("({}): {}", last_action.timeago(), msg));
+                        if let Some(details) = last_action.error_details()
+                            && ui
+                                .button("📋")
+                                .on_hover_text("Copy error details for bug reports")
+                                .clicked()
+                        {
+                            ui.output_mut(|o| o.copied_text = details);
+                        }
+                        if let Some(path) = &last_action.installed_pak
+                            && let Some(dir) = path.parent()
+                            && ui
+                                .button("📂")
+                                .on_hover_text("Show installed pak in file manager")
+                                .clicked()
+                        {
+                            opener::open(dir).ok();
+                        }
                     }
                 });
             });
@@ -1922,9 +4908,21 @@ impl eframe::App for App {
             {
                 ui.disable();
             }
+            self.ui_recovered(ui);
             // profile selection
 
+            let mut export_profile = false;
+            let mut import_profile = false;
             let buttons = |ui: &mut Ui, mod_data: &mut ModData| {
+                export_profile = ui
+                    .button("📤")
+                    .on_hover_text_at_pointer("Export profile to a file to share it")
+                    .clicked();
+                import_profile = ui
+                    .button("📥")
+                    .on_hover_text_at_pointer("Import profile from a file")
+                    .clicked();
+
                 if ui
                     .button("📋")
                     .on_hover_text_at_pointer("Copy profile mods")
@@ -1959,6 +4957,12 @@ impl eframe::App for App {
             ) {
                 self.state.mod_data.save().unwrap();
             }
+            if export_profile {
+                self.export_active_profile();
+            }
+            if import_profile {
+                self.import_profile();
+            }
 
             ui.separator();
 
@@ -1987,11 +4991,15 @@ impl eframe::App for App {
                             .hint_text("Add mod..."),
                     );
                     if is_committed(&resolve) {
-                        message::ResolveMods::send(self, ctx, self.parse_mods(), false);
+                        self.resolve_added_mods(ctx);
                         self.problematic_mod_id = None;
                     }
                 });
             });
+            // format errors are shown while typing, errors resolving the mods once they are sent
+            for error in self.url_format_errors() {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
 
             let profile = self.state.mod_data.active_profile.clone();
 
@@ -2023,16 +5031,33 @@ impl eframe::App for App {
                     self.update_sorting_config(sort_category, is_ascending);
                 }
 
+                ui.add_space(16.);
+                let config = &mut self.state.config;
+                if ui
+                    .selectable_label(config.compact_mod_list, "Compact")
+                    .on_hover_text("Only show the toggle, name and provider of each mod")
+                    .clicked()
+                {
+                    config.compact_mod_list = !config.compact_mod_list;
+                    config.save().unwrap();
+                }
+                if ui
+                    .selectable_label(config.show_mod_versions, "Versions")
+                    .on_hover_text("Show the version of each mod after its name")
+                    .clicked()
+                {
+                    config.show_mod_versions = !config.show_mod_versions;
+                    config.save().unwrap();
+                }
+
                 ui.add_space(16.);
                 // TODO: actually implement mod groups.
                 let search_string = &mut self.search_string;
                 let lower = search_string.to_lowercase();
+                let filter_config = self.state.config.filter_config.clone().unwrap_or_default();
                 let any_matches = self.state.mod_data.any_mod(&profile, |mc, _| {
-                    self.state
-                        .store
-                        .get_mod_info(&mc.spec)
-                        .map(|i| i.name.to_lowercase().contains(&lower))
-                        .unwrap_or(false)
+                    let info = self.state.store.get_mod_info(&mc.spec);
+                    filter_config.matches(info.as_ref()) && mc.matches_search(info.as_ref(), &lower)
                 });
 
                 let mut text_edit = egui::TextEdit::singleline(search_string).hint_text("Search");
@@ -2060,9 +5085,64 @@ impl eframe::App for App {
                     self.focus_search = false;
                 }
             });
+
+            ui.horizontal(|ui| {
+                ui.label("Filter: ");
+
+                let mut filter_config = self.get_filter_config();
+                let mut changed = false;
+
+                egui::ComboBox::from_id_salt("provider-filter")
+                    .selected_text(filter_config.provider.as_deref().unwrap_or("Any provider"))
+                    .show_ui(ui, |ui| {
+                        changed |= ui
+                            .selectable_value(&mut filter_config.provider, None, "Any provider")
+                            .changed();
+                        for factory in ModStore::get_provider_factories() {
+                            changed |= ui
+                                .selectable_value(
+                                    &mut filter_config.provider,
+                                    Some(factory.id.to_string()),
+                                    factory.id,
+                                )
+                                .changed();
+                        }
+                    });
+
+                for tag in TagFilter::iter() {
+                    let selected = filter_config.tags.contains(&tag);
+                    if ui.selectable_label(selected, tag.as_str()).clicked() {
+                        if selected {
+                            filter_config.tags.remove(&tag);
+                        } else {
+                            filter_config.tags.insert(tag);
+                        }
+                        changed = true;
+                    }
+                }
+
+                if !filter_config.is_empty() && ui.button("Clear").clicked() {
+                    filter_config = FilterConfig::default();
+                    changed = true;
+                }
+
+                if changed {
+                    self.update_filter_config(filter_config);
+                }
+            });
             ui.add_space(4.);
 
-            self.ui_profile(ui, &profile);
+            self.ui_profile_description(ui, &profile);
+            self.ui_incompatible(ui, &profile);
+            if !self.row_number_input.is_empty() {
+                ui.label(format!(
+                    "Press space to toggle mod {}, escape to cancel",
+                    self.row_number_input
+                ));
+            }
+            if !self.ui_empty_state(ui, &profile) {
+                self.ui_profile(ui, &profile);
+            }
 
             // must access memory outside of input lock to prevent deadlock
             let is_anything_focused = ctx.memory(|m| m.focused().is_some());
@@ -2083,7 +5163,7 @@ impl eframe::App for App {
                     }
 
                     self.resolve_mod = mods.trim().to_string();
-                    message::ResolveMods::send(self, ctx, self.parse_mods(), false);
+                    self.resolve_added_mods(ctx);
                     self.problematic_mod_id = None;
                 }
                 for e in &i.events {
@@ -2095,16 +5175,35 @@ impl eframe::App for App {
                                 && !is_anything_focused
                             {
                                 self.resolve_mod = s.trim().to_string();
-                                message::ResolveMods::send(self, ctx, self.parse_mods(), false);
+                                self.resolve_added_mods(ctx);
                             }
                         }
-                        egui::Event::Text(text) => {
-                            if !is_anything_focused {
+                        egui::Event::Text(text) if !is_anything_focused => {
+                            if text.chars().all(|c| c.is_ascii_digit()) {
+                                self.row_number_input.push_str(text);
+                            } else if text == " " && !self.row_number_input.is_empty() {
+                                if self.integrate_rid.is_none()
+                                    && self.update_rid.is_none()
+                                    && self.lint_rid.is_none()
+                                    && let Ok(number) = self.row_number_input.parse()
+                                {
+                                    self.toggle_row(number);
+                                }
+                                self.row_number_input.clear();
+                            } else {
+                                self.row_number_input.clear();
                                 self.search_string = text.to_string();
                                 self.scroll_to_match = true;
                                 self.focus_search = true;
                             }
                         }
+                        egui::Event::Key {
+                            key: egui::Key::Escape,
+                            pressed: true,
+                            ..
+                        } => {
+                            self.row_number_input.clear();
+                        }
                         _ => {}
                     }
                 }
@@ -2113,6 +5212,15 @@ impl eframe::App for App {
     }
 }
 
+/// Parse a newline separated list of mod URLs or paths, ignoring blank lines.
+pub fn parse_mods(text: &str) -> Vec<ModSpecification> {
+    text.lines()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty())
+        .map(parse_spec)
+        .collect()
+}
+
 fn is_committed(res: &egui::Response) -> bool {
     res.lost_focus() && res.ctx.input(|i| i.key_pressed(egui::Key::Enter))
 }
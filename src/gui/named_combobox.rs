@@ -1,3 +1,5 @@
+use std::collections::BTreeSet;
+
 use super::{colors, custom_popup_above_or_below_widget, is_committed};
 
 use crate::state::{ModData_v0_1_0 as ModData, ModProfile_v0_1_0 as ModProfile};
@@ -26,7 +28,57 @@ pub trait NamedEntries<E> {
     fn remove_selected(&mut self);
     fn rename_selected(&mut self, new_name: String);
     fn duplicate_selected(&mut self, new_name: String);
+    /// Move the selected entry `offset` places in the list. Returns whether it moved.
+    fn move_selected(&mut self, offset: isize) -> bool;
     fn entries<'s>(&'s mut self) -> Box<dyn Iterator<Item = (&'s String, &'s E)> + 's>;
+    fn selected_mut(&mut self) -> &mut E;
+}
+
+/// Entries which can be labeled to narrow down the dropdown.
+pub trait Tagged {
+    fn tags(&self) -> &BTreeSet<String>;
+    fn tags_mut(&mut self) -> &mut BTreeSet<String>;
+    /// Shown when hovering the entry in the dropdown.
+    fn description(&self) -> &str;
+}
+
+impl Tagged for ModProfile {
+    fn tags(&self) -> &BTreeSet<String> {
+        &self.tags
+    }
+    fn tags_mut(&mut self) -> &mut BTreeSet<String> {
+        &mut self.tags
+    }
+    fn description(&self) -> &str {
+        &self.description
+    }
+}
+
+fn format_tags(tags: &BTreeSet<String>) -> String {
+    tags.iter()
+        .map(|t| format!("#{t}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn parse_tags(text: &str) -> BTreeSet<String> {
+    text.split([',', ' '])
+        .map(|t| t.trim().trim_start_matches('#').to_lowercase())
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+/// Words starting with `#` have to match one of the tags, any other word has to be part of the
+/// name. An empty filter matches everything.
+fn matches_filter(filter: &str, name: &str, tags: &BTreeSet<String>) -> bool {
+    let name = name.to_lowercase();
+    filter.split_whitespace().all(|word| {
+        let word = word.to_lowercase();
+        match word.strip_prefix('#') {
+            Some(tag) => tags.contains(tag),
+            None => name.contains(&word),
+        }
+    })
 }
 
 impl NamedEntries<ModProfile> for ModData {
@@ -52,6 +104,7 @@ impl NamedEntries<ModProfile> for ModData {
     fn rename_selected(&mut self, new_name: String) {
         let tmp = self.profiles.remove(&self.active_profile).unwrap();
         self.profiles.insert(new_name.clone(), tmp);
+        self.rename_in_profile_order(&self.active_profile.clone(), &new_name);
         self.active_profile = new_name;
     }
     fn duplicate_selected(&mut self, new_name: String) {
@@ -59,8 +112,19 @@ impl NamedEntries<ModProfile> for ModData {
         self.profiles.insert(new_name.clone(), new);
         self.active_profile = new_name;
     }
+    fn move_selected(&mut self, offset: isize) -> bool {
+        self.move_profile(&self.active_profile.clone(), offset)
+    }
     fn entries<'s>(&'s mut self) -> Box<dyn Iterator<Item = (&'s String, &'s ModProfile)> + 's> {
-        Box::new(self.profiles.iter())
+        let this = &*self;
+        Box::new(
+            this.ordered_profiles()
+                .into_iter()
+                .map(move |name| (name, &this.profiles[name])),
+        )
+    }
+    fn selected_mut(&mut self) -> &mut ModProfile {
+        self.get_active_profile_mut()
     }
 }
 
@@ -73,6 +137,7 @@ pub(crate) fn ui<E, N>(
 ) -> bool
 where
     N: NamedEntries<E>,
+    E: Tagged,
 {
     let mut modified = false;
     ui.push_id(name, |ui| {
@@ -83,6 +148,8 @@ where
 
             ui.with_layout(egui::Layout::right_to_left(egui::Align::TOP), |ui| {
                 mk_duplicate(ui, name, entries, &mut modified);
+                mk_tags(ui, name, entries, &mut modified);
+                mk_move(ui, name, entries, &mut modified);
 
                 if let Some(additional_ui) = additional_ui {
                     additional_ui(ui, entries);
@@ -202,21 +269,144 @@ where
     );
 }
 
+fn mk_move<E, N>(ui: &mut egui::Ui, name: &str, entries: &mut N, modified: &mut bool)
+where
+    N: NamedEntries<E>,
+{
+    ui.add_enabled_ui(entries.len() > 1, |ui| {
+        // laid out right to left
+        if ui
+            .button("⏷")
+            .on_hover_text_at_pointer(format!("Move {name} down"))
+            .clicked()
+        {
+            *modified |= entries.move_selected(1);
+        }
+        if ui
+            .button("⏶")
+            .on_hover_text_at_pointer(format!("Move {name} up"))
+            .clicked()
+        {
+            *modified |= entries.move_selected(-1);
+        }
+    });
+}
+
+fn mk_tags<E, N>(ui: &mut egui::Ui, name: &str, entries: &mut N, modified: &mut bool)
+where
+    N: NamedEntries<E>,
+    E: Tagged,
+{
+    let tags = entries.selected_mut().tags();
+    let response = ui.button("🏷").on_hover_text_at_pointer(if tags.is_empty() {
+        format!("Tag {name}")
+    } else {
+        format!("Edit tags: {}", format_tags(tags))
+    });
+    let popup_id = ui.make_persistent_id(format!("tags-{name}"));
+    if response.clicked() {
+        ui.memory_mut(|mem| mem.open_popup(popup_id));
+    }
+
+    let data_id = popup_id.with("data");
+    let mut popup: NamePopup = ui.data(|data| data.get_temp(data_id)).unwrap_or_default();
+    popup.buffer_needs_prefill_and_focus = custom_popup_above_or_below_widget(
+        ui,
+        popup_id,
+        &response,
+        egui::AboveOrBelow::Below,
+        |ui| {
+            ui.set_min_width(200.0);
+            ui.vertical(|ui| {
+                if popup.buffer_needs_prefill_and_focus {
+                    popup.buffer = format_tags(entries.selected_mut().tags());
+                }
+
+                let res = ui.add(
+                    egui::TextEdit::singleline(&mut popup.buffer)
+                        .hint_text("Enter tags, e.g. #coop #testing"),
+                );
+                if popup.buffer_needs_prefill_and_focus {
+                    res.request_focus();
+                }
+
+                ui.horizontal(|ui| {
+                    if ui.button("Cancel").clicked() {
+                        ui.memory_mut(|mem| mem.close_popup());
+                    }
+                    if ui.button("OK").clicked() || is_committed(&res) {
+                        ui.memory_mut(|mem| mem.close_popup());
+                        *entries.selected_mut().tags_mut() = parse_tags(&popup.buffer);
+                        *modified = true;
+                    }
+                });
+            });
+        },
+    )
+    .is_none();
+
+    ui.data_mut(|data| data.insert_temp(data_id, popup));
+}
+
 fn mk_dropdown<E, N>(ui: &mut egui::Ui, name: &str, entries: &mut N, modified: &mut bool)
 where
     N: NamedEntries<E>,
+    E: Tagged,
 {
     let mut selected = entries.selected_name().to_owned();
 
+    let filter_id = ui.make_persistent_id(format!("filter-{name}"));
+    let mut filter: String = ui.data(|data| data.get_temp(filter_id)).unwrap_or_default();
+
     egui::ComboBox::from_id_salt(format!("dropdown-{name}"))
         .width(ui.available_width())
         .selected_text(selected.clone())
         .show_ui(ui, |ui| {
-            entries.entries().for_each(|(k, _)| {
-                ui.selectable_value(&mut selected, k.to_owned(), k);
-            })
+            let all_tags = entries
+                .entries()
+                .flat_map(|(_, e)| e.tags().iter().cloned())
+                .collect::<BTreeSet<_>>();
+            // without any tags the plain list is shown, there is nothing to filter by
+            if !all_tags.is_empty() {
+                ui.add(egui::TextEdit::singleline(&mut filter).hint_text("Filter, e.g. #coop"));
+                ui.horizontal_wrapped(|ui| {
+                    for tag in &all_tags {
+                        let token = format!("#{tag}");
+                        let active = filter.split_whitespace().any(|w| w == token);
+                        if ui.selectable_label(active, &token).clicked() {
+                            filter = if active {
+                                filter
+                                    .split_whitespace()
+                                    .filter(|w| *w != token)
+                                    .collect::<Vec<_>>()
+                                    .join(" ")
+                            } else {
+                                format!("{filter} {token}").trim().to_string()
+                            };
+                        }
+                    }
+                });
+                ui.separator();
+            }
+
+            entries
+                .entries()
+                .filter(|(k, e)| matches_filter(&filter, k, e.tags()))
+                .for_each(|(k, e)| {
+                    let label = if e.tags().is_empty() {
+                        k.to_owned()
+                    } else {
+                        format!("{k}  {}", format_tags(e.tags()))
+                    };
+                    let res = ui.selectable_value(&mut selected, k.to_owned(), label);
+                    if !e.description().is_empty() {
+                        res.on_hover_text(e.description());
+                    }
+                })
         });
 
+    ui.data_mut(|data| data.insert_temp(filter_id, filter));
+
     if selected != entries.selected_name() {
         entries.select(selected);
         *modified = true;
@@ -0,0 +1,119 @@
+use std::fmt::Display;
+
+use crate::check_links::LinkProblem;
+use crate::integrate::HookProblem;
+use crate::other_paks::OtherPak;
+use crate::providers::ModSpecification;
+
+/// A warning shown in the notification center. Notifications are collected from the same sources
+/// as the warning badges shown across the GUI every frame, so they disappear once their cause is
+/// fixed.
+#[derive(Debug, Clone)]
+pub enum Notification {
+    /// An enabled mod depends on mods which are not enabled.
+    MissingDependencies {
+        name: String,
+        deps: Vec<ModSpecification>,
+    },
+    /// Two enabled mods modify the same files according to the last lint report, and not all of
+    /// them were acknowledged.
+    Conflict {
+        mods: (String, String),
+        files: usize,
+    },
+    /// A newer version of an enabled mod pinned to a version is available.
+    ModUpdate {
+        name: String,
+        versions: (ModSpecification, ModSpecification),
+    },
+    /// An enabled mod was found to be unavailable by the last availability check.
+    Unavailable {
+        name: String,
+        problem: LinkProblem,
+        error: String,
+    },
+    Hook(HookProblem),
+    /// The game was updated since mods were installed.
+    GameUpdated,
+    OtherPak(OtherPak),
+    /// A newer release of mint is available.
+    MintUpdate {
+        tag: String,
+        url: String,
+    },
+}
+
+/// What the button next to a notification does.
+#[derive(Debug, Clone)]
+pub enum NotificationAction {
+    AddDependencies(Vec<ModSpecification>),
+    ShowConflicts,
+    ShowChanges((ModSpecification, ModSpecification)),
+    ReinstallHook,
+    Install,
+    OpenPaksDirectory,
+    OpenUrl(String),
+}
+
+impl Notification {
+    /// Name of the mod the notification is about, to jump to it in the mod list.
+    pub fn mod_name(&self) -> Option<&str> {
+        match self {
+            Self::MissingDependencies { name, .. }
+            | Self::ModUpdate { name, .. }
+            | Self::Unavailable { name, .. } => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Button label and action fixing or showing the cause of the warning, if there is one
+    /// besides jumping to the mod.
+    pub fn action(&self) -> Option<(&'static str, NotificationAction)> {
+        Some(match self {
+            Self::MissingDependencies { deps, .. } => (
+                "Add dependencies",
+                NotificationAction::AddDependencies(deps.clone()),
+            ),
+            Self::Conflict { .. } => ("Show conflicts", NotificationAction::ShowConflicts),
+            Self::ModUpdate { versions, .. } => (
+                "Show changes",
+                NotificationAction::ShowChanges(versions.clone()),
+            ),
+            Self::Unavailable { .. } => return None,
+            Self::Hook(_) => ("Reinstall hook", NotificationAction::ReinstallHook),
+            Self::GameUpdated => ("Reinstall mods", NotificationAction::Install),
+            Self::OtherPak(_) => ("Open Paks folder", NotificationAction::OpenPaksDirectory),
+            Self::MintUpdate { url, .. } => {
+                ("Open release", NotificationAction::OpenUrl(url.clone()))
+            }
+        })
+    }
+}
+
+impl Display for Notification {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingDependencies { name, deps } => write!(
+                f,
+                "{name} is missing dependencies: {}",
+                deps.iter()
+                    .map(|d| d.url.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Self::Conflict { mods, files } => {
+                write!(f, "{} and {} both modify {files} file(s)", mods.0, mods.1)
+            }
+            Self::ModUpdate { name, .. } => write!(f, "An update of {name} is available"),
+            Self::Unavailable {
+                name,
+                problem,
+                error,
+            } => write!(f, "{name} is unavailable ({problem}): {error}"),
+            Self::Hook(problem) => write!(f, "The {problem}, mods may not load"),
+            Self::GameUpdated => write!(f, "DRG was updated since mods were installed"),
+            Self::OtherPak(pak) => write!(f, "Other pak: {pak}"),
+            Self::MintUpdate { tag, .. } => write!(f, "mint {tag} is available"),
+        }
+    }
+}
@@ -15,3 +15,9 @@ impl RequestCounter {
 pub struct RequestID {
     id: u32,
 }
+
+impl std::fmt::Display for RequestID {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.id)
+    }
+}
@@ -0,0 +1,134 @@
+mod server_list;
+mod text;
+mod vortex;
+
+use std::path::Path;
+
+use fs_err as fs;
+use snafu::prelude::*;
+
+use crate::providers::{ModSearchResult, ModSpecification, ModStore, ProviderError};
+
+#[derive(Debug, Snafu)]
+pub enum ImportError {
+    #[snafu(transparent)]
+    IoError { source: std::io::Error },
+    #[snafu(display("mod list does not have a name column"))]
+    MissingNameColumn,
+    #[snafu(transparent)]
+    ProviderError { source: ProviderError },
+}
+
+/// Mod list formats exported by other mod managers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ImportFormat {
+    /// CSV exported from the Vortex mods table.
+    Vortex,
+    /// One mod name or URL per line.
+    Text,
+    /// Mods advertised by a server in the server list.
+    ServerList,
+}
+
+/// Entry of an external mod list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalMod {
+    pub name: String,
+    pub version: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportMatch {
+    Matched(ModSearchResult),
+    /// Several mods could be meant and the user has to pick one.
+    Ambiguous(Vec<ModSearchResult>),
+    Unmatched,
+}
+
+pub fn parse(format: ImportFormat, content: &str) -> Result<Vec<ExternalMod>, ImportError> {
+    match format {
+        ImportFormat::Vortex => vortex::parse(content),
+        ImportFormat::Text => Ok(text::parse(content)),
+        ImportFormat::ServerList => Ok(server_list::parse(content)),
+    }
+}
+
+pub fn read<P: AsRef<Path>>(
+    format: ImportFormat,
+    path: P,
+) -> Result<Vec<ExternalMod>, ImportError> {
+    parse(format, &fs::read_to_string(path)?)
+}
+
+/// Look up every entry by name, keeping entries which are already URLs as they are.
+pub async fn match_mods(
+    store: &ModStore,
+    mods: &[ExternalMod],
+) -> Result<Vec<ImportMatch>, ImportError> {
+    let mut matches = vec![];
+    for m in mods {
+        if m.name.starts_with("https://") || m.name.starts_with("http://") {
+            matches.push(ImportMatch::Matched(ModSearchResult {
+                name: m.name.clone(),
+                spec: ModSpecification::new(m.name.clone()),
+                modio_stats: None,
+            }));
+        } else {
+            matches.push(classify(&m.name, store.search_mods(&m.name).await?));
+        }
+    }
+    Ok(matches)
+}
+
+/// A single mod with exactly the searched name is a match. Anything else the search returned
+/// is only a guess so it needs confirmation.
+fn classify(name: &str, results: Vec<ModSearchResult>) -> ImportMatch {
+    let (mut exact, rest): (Vec<_>, Vec<_>) = results
+        .into_iter()
+        .partition(|r| r.name.trim().eq_ignore_ascii_case(name.trim()));
+    match exact.len() {
+        1 => ImportMatch::Matched(exact.remove(0)),
+        0 if rest.is_empty() => ImportMatch::Unmatched,
+        0 => ImportMatch::Ambiguous(rest),
+        _ => ImportMatch::Ambiguous(exact),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn result(name: &str, url: &str) -> ModSearchResult {
+        ModSearchResult {
+            name: name.to_string(),
+            spec: ModSpecification::new(url.to_string()),
+            modio_stats: None,
+        }
+    }
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(classify("a", vec![]), ImportMatch::Unmatched);
+        assert_eq!(
+            classify(
+                "Better Scout",
+                vec![
+                    result("better scout", "1"),
+                    result("Better Scout Plus", "2")
+                ]
+            ),
+            ImportMatch::Matched(result("better scout", "1"))
+        );
+        assert_eq!(
+            classify(
+                "Scout",
+                vec![result("Better Scout", "1"), result("Scout Plus", "2")]
+            ),
+            ImportMatch::Ambiguous(vec![result("Better Scout", "1"), result("Scout Plus", "2")])
+        );
+        assert_eq!(
+            classify("Scout", vec![result("Scout", "1"), result("scout", "2")]),
+            ImportMatch::Ambiguous(vec![result("Scout", "1"), result("scout", "2")])
+        );
+    }
+}
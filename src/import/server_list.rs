@@ -0,0 +1,71 @@
+use mint_lib::mod_info::Meta;
+use serde::Deserialize;
+
+use super::{text, ExternalMod};
+
+/// Entry of the `Mods` session setting servers advertise in the server list.
+#[derive(Debug, Deserialize)]
+struct JsonMod {
+    name: String,
+}
+
+/// Mods advertised by a server. Accepts the JSON `Mods` session setting, the string mint
+/// advertises on its own, or a plain list of mod names.
+///
+/// Servers running mint advertise a single entry holding every mod, other servers list each mod
+/// the official integration installed separately.
+pub(super) fn parse(content: &str) -> Vec<ExternalMod> {
+    let content = content.trim();
+    match serde_json::from_str::<Vec<JsonMod>>(content) {
+        Ok(mods) => mods
+            .into_iter()
+            .flat_map(|m| parse_mint(&m.name).unwrap_or_else(|| vec![external(m.name)]))
+            .collect(),
+        Err(_) => parse_mint(content).unwrap_or_else(|| text::parse(content)),
+    }
+}
+
+fn parse_mint(s: &str) -> Option<Vec<ExternalMod>> {
+    Meta::parse_server_list_string(s)
+        .map(|mods| mods.into_iter().map(|(_, name)| external(name)).collect())
+}
+
+fn external(name: String) -> ExternalMod {
+    ExternalMod {
+        name,
+        version: None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn names(mods: Vec<ExternalMod>) -> Vec<String> {
+        mods.into_iter().map(|m| m.name).collect()
+    }
+
+    #[test]
+    fn test_parse_server_list() {
+        let mint = "mint;0.2.10;V;Better Scout;S;Sandbox Utilities";
+        assert_eq!(names(parse(mint)), ["Better Scout", "Sandbox Utilities"]);
+        assert_eq!(
+            names(parse(&format!(
+                r#"[{{"name":"{mint}","version":"mint","category":0}}]"#
+            ))),
+            ["Better Scout", "Sandbox Utilities"]
+        );
+        assert_eq!(
+            names(parse(
+                r#"[{"name":"Better Scout","version":"1.2","category":1},
+                    {"name":"More Players","version":"","category":0}]"#
+            )),
+            ["Better Scout", "More Players"]
+        );
+        assert_eq!(names(parse("mint;0.2.10")), Vec::<String>::new());
+        assert_eq!(
+            names(parse("Better Scout\nMore Players\n")),
+            ["Better Scout", "More Players"]
+        );
+    }
+}
@@ -0,0 +1,14 @@
+use super::ExternalMod;
+
+/// One mod per line. Blank lines and lines starting with `#` are ignored.
+pub(super) fn parse(content: &str) -> Vec<ExternalMod> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(|l| ExternalMod {
+            name: l.to_string(),
+            version: None,
+        })
+        .collect()
+}
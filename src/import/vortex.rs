@@ -0,0 +1,87 @@
+use super::{ExternalMod, ImportError, MissingNameColumnSnafu};
+
+use snafu::prelude::*;
+
+/// Split a CSV line, honoring double quoted fields and `""` escapes.
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+/// CSV exported from the Vortex mods table. Only the name and version columns are used.
+pub(super) fn parse(content: &str) -> Result<Vec<ExternalMod>, ImportError> {
+    let mut lines = content
+        .trim_start_matches('\u{feff}')
+        .lines()
+        .filter(|l| !l.trim().is_empty());
+
+    let header = lines.next().map(split_csv_line).unwrap_or_default();
+    let column = |names: &[&str]| {
+        header
+            .iter()
+            .position(|h| names.iter().any(|n| h.trim().eq_ignore_ascii_case(n)))
+    };
+    let name_column = column(&["mod name", "name"]).context(MissingNameColumnSnafu)?;
+    let version_column = column(&["version"]);
+
+    Ok(lines
+        .map(split_csv_line)
+        .filter_map(|fields| {
+            let name = fields.get(name_column)?.trim();
+            (!name.is_empty()).then(|| ExternalMod {
+                name: name.to_string(),
+                version: version_column
+                    .and_then(|i| fields.get(i))
+                    .map(|v| v.trim().to_string())
+                    .filter(|v| !v.is_empty()),
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_vortex_csv() {
+        let csv = "\u{feff}Status,Mod Name,Version,Category\n\
+                   Enabled,Better Scout,1.2,Gameplay\n\
+                   Enabled,\"Sandbox Utilities, Extended\",,\n\
+                   \n\
+                   Disabled,\"The \"\"Best\"\" Mod\",2.0.1,Visual\n";
+        assert_eq!(
+            parse(csv).unwrap(),
+            vec![
+                ExternalMod {
+                    name: "Better Scout".to_string(),
+                    version: Some("1.2".to_string()),
+                },
+                ExternalMod {
+                    name: "Sandbox Utilities, Extended".to_string(),
+                    version: None,
+                },
+                ExternalMod {
+                    name: "The \"Best\" Mod".to_string(),
+                    version: Some("2.0.1".to_string()),
+                },
+            ]
+        );
+        assert!(parse("Status,Version\nEnabled,1.0\n").is_err());
+    }
+}
@@ -0,0 +1,199 @@
+use std::path::Path;
+
+use fs_err as fs;
+use snafu::prelude::*;
+
+use crate::allowlist::Allowlist;
+use crate::providers::{ModSpecification, ModStore};
+
+/// Name of the list of known incompatible mods in the config directory.
+pub const INCOMPATIBLE_MODS_FILE: &str = "incompatible_mods.txt";
+
+#[derive(Debug, Snafu)]
+pub enum IncompatibleError {
+    #[snafu(transparent)]
+    IoError { source: std::io::Error },
+}
+
+/// Mods which are known not to work together, beyond what overlapping files would reveal.
+///
+/// The file lists one combination per line as mods separated by `+`, optionally followed by `: `
+/// and the reason, e.g. `1234 + https://mod.io/g/drg/m/example: both replace the HUD`. Mods are
+/// given like in an allowlist, as numeric mod.io mod IDs or as mod URLs. Blank lines and lines
+/// starting with `#` are ignored.
+#[derive(Debug, Clone, Default)]
+pub struct Incompatibilities {
+    combinations: Vec<Combination>,
+}
+
+#[derive(Debug, Clone)]
+struct Combination {
+    /// Every entry has to match an enabled mod for the combination to apply.
+    mods: Vec<Allowlist>,
+    reason: Option<String>,
+}
+
+/// Enabled mods of a profile making up a known incompatible combination.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Incompatibility {
+    pub mods: Vec<ModSpecification>,
+    pub reason: Option<String>,
+}
+
+impl Incompatibility {
+    pub fn describe(&self, store: &ModStore) -> String {
+        let names = self
+            .mods
+            .iter()
+            .map(|spec| {
+                store
+                    .get_mod_info(spec)
+                    .map(|info| info.name)
+                    .unwrap_or_else(|| spec.url.clone())
+            })
+            .collect::<Vec<_>>()
+            .join(" + ");
+        match &self.reason {
+            Some(reason) => format!("{names}: {reason}"),
+            None => names,
+        }
+    }
+}
+
+impl Incompatibilities {
+    pub fn parse(content: &str) -> Self {
+        let combinations = content
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with('#'))
+            .filter_map(|line| {
+                // URLs contain `://` so only a colon followed by whitespace starts the reason
+                let (mods, reason) = match line.find(": ") {
+                    Some(i) => (&line[..i], Some(line[i + 1..].trim().to_string())),
+                    None => (line, None),
+                };
+                let mods = mods
+                    .split('+')
+                    .map(str::trim)
+                    .filter(|m| !m.is_empty())
+                    .map(Allowlist::parse)
+                    .collect::<Vec<_>>();
+                (mods.len() > 1).then_some(Combination {
+                    mods,
+                    reason: reason.filter(|r| !r.is_empty()),
+                })
+            })
+            .collect();
+        Self { combinations }
+    }
+
+    /// Read the list from the config directory. The list is optional so a missing file is
+    /// treated as empty.
+    pub fn load<P: AsRef<Path>>(config_dir: P) -> Result<Self, IncompatibleError> {
+        let path = config_dir.as_ref().join(INCOMPATIBLE_MODS_FILE);
+        match fs::read_to_string(path) {
+            Ok(content) => Ok(Self::parse(&content)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.combinations.is_empty()
+    }
+
+    /// Find the known incompatible combinations among `mods`. `modio_id` is only known once the
+    /// mod has been resolved.
+    pub fn check<'a>(
+        &self,
+        mods: impl IntoIterator<Item = (&'a ModSpecification, Option<u32>)>,
+    ) -> Vec<Incompatibility> {
+        let mods = mods.into_iter().collect::<Vec<_>>();
+        self.combinations
+            .iter()
+            .filter_map(|combination| {
+                let matched = combination
+                    .mods
+                    .iter()
+                    .map(|entry| {
+                        mods.iter()
+                            .find(|(spec, modio_id)| entry.allows(spec, *modio_id))
+                            .map(|(spec, _)| (*spec).clone())
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                Some(Incompatibility {
+                    mods: matched,
+                    reason: combination.reason.clone(),
+                })
+            })
+            .collect()
+    }
+
+    /// Check mods using whatever is cached about them in `store`.
+    pub fn check_specs(
+        &self,
+        store: &ModStore,
+        specs: &[ModSpecification],
+    ) -> Vec<Incompatibility> {
+        let modio_ids = specs
+            .iter()
+            .map(|spec| store.get_mod_info(spec).and_then(|info| info.modio_id))
+            .collect::<Vec<_>>();
+        self.check(specs.iter().zip(modio_ids))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn spec(url: &str) -> ModSpecification {
+        ModSpecification::new(url.to_string())
+    }
+
+    #[test]
+    fn test_incompatibilities() {
+        let list = Incompatibilities::parse(
+            "# known bad\n\
+             1234 + https://mod.io/g/drg/m/hud-b: both replace the HUD\n\
+             \n\
+             https://example.com/a.zip + https://example.com/b.zip + 99\n\
+             https://example.com/lonely.zip\n",
+        );
+        assert_eq!(list.combinations.len(), 2);
+
+        let hud_a = spec("https://mod.io/g/drg/m/hud-a");
+        let hud_b = spec("https://mod.io/g/drg/m/hud-b#5/6");
+        let a = spec("https://example.com/a.zip");
+        let b = spec("https://example.com/b.zip");
+
+        assert_eq!(
+            list.check([(&hud_a, Some(1234)), (&hud_b, Some(5)), (&a, None)]),
+            vec![Incompatibility {
+                mods: vec![hud_a.clone(), hud_b.clone()],
+                reason: Some("both replace the HUD".to_string()),
+            }]
+        );
+        assert!(list
+            .check([(&hud_a, Some(4321)), (&hud_b, Some(5))])
+            .is_empty());
+        // every mod of a combination has to be enabled
+        assert!(list.check([(&a, None), (&b, None)]).is_empty());
+        assert_eq!(
+            list.check([(&a, None), (&b, None), (&hud_a, Some(99))]),
+            vec![Incompatibility {
+                mods: vec![a, b, hud_a],
+                reason: None,
+            }]
+        );
+        assert!(Incompatibilities::parse("").is_empty());
+    }
+
+    #[test]
+    fn test_load_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(Incompatibilities::load(dir.path()).unwrap().is_empty());
+        fs::write(dir.path().join(INCOMPATIBLE_MODS_FILE), "1 + 2\n").unwrap();
+        assert!(!Incompatibilities::load(dir.path()).unwrap().is_empty());
+    }
+}
@@ -1,13 +1,16 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::io::{BufReader, BufWriter, Cursor, ErrorKind, Read, Seek, Write};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 use fs_err as fs;
 
+use indexmap::IndexSet;
 use repak::PakWriter;
 use serde::Deserialize;
 use snafu::{prelude::*, Whatever};
-use tracing::info;
+use tracing::{info, warn};
 use uasset_utils::asset_registry::{AssetRegistry, Readable as _, Writable as _};
 use uasset_utils::paths::{PakPath, PakPathBuf, PakPathComponentTrait};
 use uasset_utils::splice::{
@@ -16,8 +19,12 @@ use uasset_utils::splice::{
 use unreal_asset::engine_version::EngineVersion;
 use unreal_asset::AssetBuilder;
 
-use crate::mod_lints::LintError;
-use crate::providers::{ModInfo, ProviderError, ReadSeek};
+use crate::asset_tree::AssetTree;
+use crate::integrate_log::IntegrateLog;
+use crate::mod_lints::{collect_all_assets, find_split_asset_pairs, LintError};
+use crate::providers::{ModInfo, ModSpecification, ProviderError, ReadSeek};
+use crate::state::{ModConfig, PriorityOverride};
+use crate::{pak_builder, PakKey};
 use mint_lib::mod_info::{ApprovalStatus, Meta, MetaConfig, MetaMod, SemverVersion};
 use mint_lib::DRGInstallation;
 
@@ -40,17 +47,34 @@ use unreal_asset::{
 /// back to the config so they will be disabled when the game is launched again. Since we have
 /// Modio IDs anyway, with just a little more effort we can make the 'uninstall' button work as an
 /// 'install' button for the official integration. Best anti-feature ever.
+///
+/// `remove_hook` should match whether the hook was deployed on install so a separately managed
+/// proxy DLL is left alone.
 #[tracing::instrument(level = "debug", skip(path_pak))]
-pub fn uninstall<P: AsRef<Path>>(path_pak: P, modio_mods: HashSet<u32>) -> Result<(), Whatever> {
+pub fn uninstall<P: AsRef<Path>>(
+    path_pak: P,
+    modio_mods: HashSet<u32>,
+    remove_hook: bool,
+) -> Result<(), Whatever> {
     let installation = DRGInstallation::from_pak_path(path_pak)
         .whatever_context("failed to get DRG installation")?;
-    let path_mods_pak = installation.paks_path().join("mods_P.pak");
+    let path_mods_pak = installation.mods_pak();
     match fs::remove_file(&path_mods_pak) {
         Ok(()) => Ok(()),
         Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
         Err(e) => Err(e),
     }
     .with_whatever_context(|_| format!("failed to remove {}", path_mods_pak.display()))?;
+    remove_stale_outputs(&path_mods_pak);
+    if remove_hook {
+        uninstall_hook(&installation)?;
+    }
+    uninstall_modio(&installation, modio_mods).ok();
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "hook"), allow(unused_variables))]
+fn uninstall_hook(installation: &DRGInstallation) -> Result<(), Whatever> {
     #[cfg(feature = "hook")]
     {
         let path_hook_dll = installation
@@ -63,10 +87,129 @@ pub fn uninstall<P: AsRef<Path>>(path_pak: P, modio_mods: HashSet<u32>) -> Resul
         }
         .with_whatever_context(|_| format!("failed to remove {}", path_hook_dll.display()))?;
     }
-    uninstall_modio(&installation, modio_mods).ok();
     Ok(())
 }
 
+/// Deploy the hook DLL next to the game executable. Mods still load without it, but mint's
+/// in-game features such as the mod list in the escape menu are unavailable.
+#[cfg_attr(not(feature = "hook"), allow(unused_variables))]
+pub fn install_hook<P: AsRef<Path>>(path_pak: P) -> Result<(), IntegrationError> {
+    #[cfg(feature = "hook")]
+    {
+        let Ok(installation) = DRGInstallation::from_pak_path(&path_pak) else {
+            return Err(IntegrationError::DrgInstallationNotFound {
+                path: path_pak.as_ref().to_path_buf(),
+            });
+        };
+        let path_hook_dll = installation
+            .binaries_directory()
+            .join(installation.installation_type.hook_dll_name());
+        let hook_dll = include_bytes!(env!("CARGO_CDYLIB_FILE_HOOK_hook"));
+        // compare the content rather than the size so a corrupt DLL gets replaced as well
+        if fs::read(&path_hook_dll)
+            .map(|existing| existing != hook_dll)
+            .unwrap_or(true)
+        {
+            fs::write(&path_hook_dll, hook_dll).map_err(|e| output_error(&path_hook_dll, e))?;
+        }
+    }
+    Ok(())
+}
+
+/// Problems with the deployed hook DLL which make the game start without mods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookProblem {
+    Missing,
+    /// Not a valid DLL at all, e.g. truncated by an interrupted write.
+    Corrupt,
+    /// Built for another architecture than the 64-bit game, given as PE machine type.
+    WrongArchitecture {
+        machine: u16,
+    },
+    /// A valid DLL which differs from the one bundled with this version of mint.
+    Mismatch,
+}
+
+impl std::fmt::Display for HookProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Missing => write!(f, "hook DLL is missing"),
+            Self::Corrupt => write!(f, "hook DLL is corrupt"),
+            Self::WrongArchitecture { machine } => {
+                write!(
+                    f,
+                    "hook DLL is built for the wrong architecture ({machine:#06x})"
+                )
+            }
+            Self::Mismatch => write!(f, "hook DLL does not match this version of mint"),
+        }
+    }
+}
+
+/// Check the hook DLL deployed next to the game executable. Whether it is the DLL bundled with
+/// this version of mint can only be verified if mint was built with the hook.
+pub fn check_hook<P: AsRef<Path>>(path_pak: P) -> Result<Option<HookProblem>, IntegrationError> {
+    let Ok(installation) = DRGInstallation::from_pak_path(&path_pak) else {
+        return Err(IntegrationError::DrgInstallationNotFound {
+            path: path_pak.as_ref().to_path_buf(),
+        });
+    };
+    let path_hook_dll = installation
+        .binaries_directory()
+        .join(installation.installation_type.hook_dll_name());
+    match fs::read(&path_hook_dll) {
+        Ok(data) => Ok(check_hook_dll(&data, bundled_hook_dll())),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(Some(HookProblem::Missing)),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Deploy the hook DLL again without touching the mods pak, e.g. after an antivirus removed it,
+/// and check the result. Returns the problem which is left, if any.
+pub fn reinstall_hook<P: AsRef<Path>>(
+    path_pak: P,
+) -> Result<Option<HookProblem>, IntegrationError> {
+    install_hook(&path_pak)?;
+    check_hook(&path_pak)
+}
+
+fn bundled_hook_dll() -> Option<&'static [u8]> {
+    #[cfg(feature = "hook")]
+    {
+        Some(include_bytes!(env!("CARGO_CDYLIB_FILE_HOOK_hook")))
+    }
+    #[cfg(not(feature = "hook"))]
+    {
+        None
+    }
+}
+
+fn check_hook_dll(data: &[u8], bundled: Option<&[u8]>) -> Option<HookProblem> {
+    const IMAGE_FILE_MACHINE_AMD64: u16 = 0x8664;
+    match pe_machine(data) {
+        None => Some(HookProblem::Corrupt),
+        Some(machine) if machine != IMAGE_FILE_MACHINE_AMD64 => {
+            Some(HookProblem::WrongArchitecture { machine })
+        }
+        Some(_) if bundled.is_some_and(|bundled| bundled != data) => Some(HookProblem::Mismatch),
+        Some(_) => None,
+    }
+}
+
+/// Machine type from the COFF header of a PE image.
+fn pe_machine(data: &[u8]) -> Option<u16> {
+    if !data.starts_with(b"MZ") {
+        return None;
+    }
+    let pe_offset = u32::from_le_bytes(data.get(0x3c..0x40)?.try_into().ok()?) as usize;
+    if data.get(pe_offset..pe_offset.checked_add(4)?)? != b"PE\0\0" {
+        return None;
+    }
+    Some(u16::from_le_bytes(
+        data.get(pe_offset + 4..pe_offset + 6)?.try_into().ok()?,
+    ))
+}
+
 #[tracing::instrument(level = "debug")]
 fn uninstall_modio(
     installation: &DRGInstallation,
@@ -158,6 +301,14 @@ pub enum IntegrationError {
     DrgInstallationNotFound { path: PathBuf },
     #[snafu(transparent)]
     IoError { source: std::io::Error },
+    #[snafu(display(
+        "unable to write {}, it is in use or read-only. Close the game before installing mods",
+        path.display()
+    ))]
+    OutputLocked {
+        source: std::io::Error,
+        path: PathBuf,
+    },
     #[snafu(transparent)]
     RepakError { source: repak::Error },
     #[snafu(transparent)]
@@ -197,10 +348,14 @@ pub enum IntegrationError {
     JoinError { source: tokio::task::JoinError },
     #[snafu(transparent)]
     LintError { source: LintError },
+    #[snafu(display("{hook} hook failed: {reason}"))]
+    HookFailed { hook: &'static str, reason: String },
     #[snafu(display("self update failed: {source:?}"))]
     SelfUpdateFailed {
         source: Box<dyn std::error::Error + Send + Sync>,
     },
+    #[snafu(display("installation was cancelled"))]
+    Cancelled,
 }
 
 impl IntegrationError {
@@ -216,18 +371,229 @@ impl IntegrationError {
     }
 }
 
+/// How mods are merged into the mods pak, taken from the config.
+#[derive(Debug, Clone, Default)]
+pub struct IntegrateOptions {
+    /// Use every pak of archives containing several instead of only the first.
+    pub all_archive_paks: bool,
+    /// Leave out `.uasset`/`.uexp` files whose other half is missing from the same pak, which
+    /// would otherwise be combined with the other half of the game's asset.
+    pub skip_split_assets: bool,
+    /// Write a plain text list of the installed mods to [`MOD_LIST_PATH`] in the mods pak.
+    pub embed_mod_list: bool,
+    /// Also copy the finished mods pak here, see [`export_pak`].
+    pub export_path: Option<PathBuf>,
+    /// Priority of each of the mods passed to [`integrate`], in the same order. An asset provided
+    /// by several mods is taken from the one with the highest priority for it, the earliest of them
+    /// on ties. All files of an asset, e.g. its `.uasset` and `.uexp`, come from the same mod.
+    /// Mods are integrated in order of their base priority already, so this only matters for mods
+    /// with [`PriorityOverride`]s. Missing entries count as the default priority.
+    pub priorities: Vec<ModPriority>,
+}
+
+/// Priority of a mod when several provide the same file, see [`IntegrateOptions::priorities`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModPriority {
+    pub base: i32,
+    /// Normalized overrides, the first one matching a path applies.
+    pub overrides: Vec<PriorityOverride>,
+}
+
+impl ModPriority {
+    /// Priority for the file at the lowercase `path`.
+    pub fn for_path(&self, path: &str) -> i32 {
+        self.for_asset(&[path])
+    }
+
+    /// Priority for the asset made of the lowercase `files`. The first override matching any of
+    /// them applies, so the halves of a split asset are never taken from different mods.
+    pub fn for_asset(&self, files: &[&str]) -> i32 {
+        self.overrides
+            .iter()
+            .find(|o| files.iter().any(|file| o.matches(file)))
+            .map_or(self.base, |o| o.priority)
+    }
+}
+
+impl From<&ModConfig> for ModPriority {
+    fn from(mc: &ModConfig) -> Self {
+        Self {
+            base: mc.priority,
+            overrides: mc
+                .priority_overrides
+                .iter()
+                .map(PriorityOverride::normalized)
+                .collect(),
+        }
+    }
+}
+
+/// `path` without the extension of its file name, which all files of an asset like its `.uasset`,
+/// `.uexp` and `.ubulk` share.
+pub fn asset_path(path: &str) -> &str {
+    let file_name = path.rfind('/').map_or(0, |i| i + 1);
+    match path[file_name..].rfind('.') {
+        Some(i) => &path[..file_name + i],
+        None => path,
+    }
+}
+
+/// The files [`integrate`] would take from `mods`, each with the mod it is taken from, without
+/// writing anything. Files mint adds or patches itself are not included.
+pub fn plan(
+    mods: &[(ModInfo, PathBuf)],
+    pak_key: Option<&PakKey>,
+    options: &IntegrateOptions,
+) -> Result<AssetTree, IntegrationError> {
+    // read like `integrate` does so split asset halves it skips are left out here as well
+    let mut assets: BTreeMap<String, IndexSet<ModSpecification>> = BTreeMap::new();
+    for (mod_info, path) in mods {
+        for (_, pak) in open_mod_paks(mod_info, path, pak_key, options.all_archive_paks)? {
+            let pak_files = pak_files(mod_info, &pak)?;
+            let split_assets = if options.skip_split_assets {
+                find_split_asset_pairs(pak_files.keys().map(|p| p.as_str()))
+            } else {
+                Default::default()
+            };
+            for normalized in pak_files.keys() {
+                if !split_assets.contains_key(normalized.as_str()) {
+                    assets
+                        .entry(normalized.as_str().to_ascii_lowercase())
+                        .or_default()
+                        .insert(mod_info.spec.clone());
+                }
+            }
+        }
+    }
+    let winners = override_winners(mods, &options.priorities, pak_key, options.all_archive_paks)?;
+    for (path, providers) in &mut assets {
+        if let Some(&winner) = winners.get(asset_path(path))
+            && let Some(index) = providers.get_index_of(&mods[winner].0.spec)
+        {
+            providers.move_index(index, 0);
+        }
+    }
+    Ok(AssetTree::new(assets))
+}
+
+/// Assets which a later mod wins over the first mod providing them because of priority overrides,
+/// by lowercase [`asset_path`], with the index of the winning mod. See
+/// [`IntegrateOptions::priorities`].
+fn override_winners(
+    mods: &[(ModInfo, PathBuf)],
+    priorities: &[ModPriority],
+    pak_key: Option<&PakKey>,
+    all_archive_paks: bool,
+) -> Result<HashMap<String, usize>, IntegrationError> {
+    if priorities.iter().all(|p| p.overrides.is_empty()) {
+        return Ok(HashMap::new());
+    }
+    let specs = mods
+        .iter()
+        .map(|(info, path)| (info.spec.clone(), path.clone()))
+        .collect::<IndexSet<_>>();
+    let indices = mods
+        .iter()
+        .enumerate()
+        .rev()
+        .map(|(i, (info, _))| (&info.spec, i))
+        .collect::<HashMap<_, _>>();
+    // lowercase asset path => (its files, indices of the mods providing any of them)
+    let mut assets: HashMap<String, (Vec<String>, BTreeSet<usize>)> = HashMap::new();
+    for (path, providers) in collect_all_assets(&specs, pak_key, all_archive_paks)? {
+        let (files, asset_providers) = assets.entry(asset_path(&path).to_string()).or_default();
+        asset_providers.extend(providers.iter().map(|spec| indices[spec]));
+        files.push(path);
+    }
+
+    let mut winners = HashMap::new();
+    for (asset, (files, providers)) in assets {
+        let files = files.iter().map(String::as_str).collect::<Vec<_>>();
+        let priority = |index: usize| {
+            priorities
+                .get(index)
+                .map_or(0, |priority| priority.for_asset(&files))
+        };
+        let mut providers = providers.into_iter();
+        let first = providers.next().unwrap();
+        let winner = providers.fold(first, |winner, i| {
+            if priority(i) > priority(winner) {
+                i
+            } else {
+                winner
+            }
+        });
+        if winner != first {
+            winners.insert(asset, winner);
+        }
+    }
+    Ok(winners)
+}
+
+/// Stops a running [`integrate`] before it merges the next mod. The installed mods pak is left as
+/// it was. Downloads are not part of this, an aborted fetch already keeps the finished ones, see
+/// [`ModStore::fetch_mods`](crate::providers::ModStore::fetch_mods).
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Cancel once the returned guard is dropped. Merging runs on the blocking thread pool where
+    /// aborting the task awaiting it has no effect, so the guard is kept in the aborted future.
+    pub fn drop_guard(&self) -> CancelOnDrop {
+        CancelOnDrop(self.clone())
+    }
+}
+
+pub struct CancelOnDrop(CancelToken);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.cancel();
+    }
+}
+
+/// Path of the plain text mod list in the mods pak. It is next to `meta` at the root of the game
+/// directory where the game never looks for assets.
+pub const MOD_LIST_PATH: &str = "mint_mods.txt";
+
+/// Write the mods pak. The hook DLL is deployed separately by [`install_hook`].
+///
+/// Mods are read one after another and each is closed before the next is opened, so besides the
+/// game pak and the output at most one mod file is open at a time regardless of how many mods are
+/// installed. There is therefore no limit on open mod files to configure for systems with a low
+/// limit on open files.
+///
+/// If `log_dir` is set an [`IntegrateLog`] of what every mod contributed is written to it.
+///
+/// Fails with [`IntegrationError::Cancelled`] if `cancel` is cancelled before the output is
+/// complete.
 #[tracing::instrument(skip_all)]
 pub fn integrate<P: AsRef<Path>>(
     path_pak: P,
     config: MetaConfig,
+    pak_key: Option<&PakKey>,
     mods: Vec<(ModInfo, PathBuf)>,
-) -> Result<(), IntegrationError> {
+    log_dir: Option<&Path>,
+    options: IntegrateOptions,
+    cancel: Option<&CancelToken>,
+) -> Result<PathBuf, IntegrationError> {
+    let cancelled = || cancel.is_some_and(CancelToken::is_cancelled);
+
     let Ok(installation) = DRGInstallation::from_pak_path(&path_pak) else {
         return Err(IntegrationError::DrgInstallationNotFound {
             path: path_pak.as_ref().to_path_buf(),
         });
     };
-    let path_mod_pak = installation.paks_path().join("mods_P.pak");
+    let path_mod_pak = installation.mods_pak();
+    check_writable(&path_mod_pak)?;
 
     let mut fsd_pak_reader = BufReader::new(fs::File::open(path_pak.as_ref())?);
     let fsd_pak = repak::PakBuilder::new().reader(&mut fsd_pak_reader)?;
@@ -296,153 +662,163 @@ pub fn integrate<P: AsRef<Path>>(
         }?;
     }
 
-    let mut bundle = ModBundleWriter::new(
-        BufWriter::new(
-            fs::OpenOptions::new()
-                .write(true)
-                .create(true)
-                .truncate(true)
-                .open(&path_mod_pak)?,
-        ),
-        &fsd_pak.files(),
-    )?;
-
-    #[cfg(feature = "hook")]
-    {
-        let path_hook_dll = installation
-            .binaries_directory()
-            .join(installation.installation_type.hook_dll_name());
-        let hook_dll = include_bytes!(env!("CARGO_CDYLIB_FILE_HOOK_hook"));
-        if path_hook_dll
-            .metadata()
-            .map(|m| m.len() != hook_dll.len() as u64)
-            .unwrap_or(true)
-        {
-            fs::write(&path_hook_dll, hook_dll)?;
-        }
-    }
+    let output = create_output(&path_mod_pak)?;
+    let mut bundle = ModBundleWriter::new(BufWriter::new(output.as_file()), &fsd_pak.files())?;
 
     let mut init_spacerig_assets = HashSet::new();
     let mut init_cave_assets = HashSet::new();
 
-    let mut added_paths = HashSet::new();
-
-    for (mod_info, path) in &mods {
-        let raw_mod_file = fs::File::open(path).with_context(|_| CtxtIoSnafu {
-            mod_info: mod_info.clone(),
-        })?;
-        let mut buf = get_pak_from_data(Box::new(BufReader::new(raw_mod_file))).map_err(|e| {
-            if let IntegrationError::IoError { source } = e {
-                IntegrationError::CtxtIoError {
-                    source,
-                    mod_info: mod_info.clone(),
-                }
+    // lowercase path => index of the mod it was taken from
+    let mut added_paths = HashMap::new();
+    let mut log = log_dir.map(|_| IntegrateLog::default());
+    let override_winners = override_winners(
+        &mods,
+        &options.priorities,
+        pak_key,
+        options.all_archive_paks,
+    )?;
+    // (winner, loser, path) of files an override made a later mod win, logged once the winner is
+    // part of the log
+    let mut overridden = IndexSet::new();
+
+    for (mod_index, (mod_info, path)) in mods.iter().enumerate() {
+        ensure!(!cancelled(), CancelledSnafu);
+        if let Some(log) = &mut log {
+            log.add_mod(mod_info);
+        }
+        for (mut buf, pak) in open_mod_paks(mod_info, path, pak_key, options.all_archive_paks)? {
+            let pak_files = pak_files(mod_info, &pak)?;
+            let split_assets = if options.skip_split_assets {
+                find_split_asset_pairs(pak_files.keys().map(|p| p.as_str()))
             } else {
-                e
-            }
-        })?;
-        let pak = repak::PakBuilder::new()
-            .reader(&mut buf)
-            .with_context(|_| CtxtRepakSnafu {
-                mod_info: mod_info.clone(),
-            })?;
-
-        let mount = PakPath::new(pak.mount_point());
-
-        let pak_files = pak
-            .files()
-            .into_iter()
-            .map(|p| -> Result<_, IntegrationError> {
-                let j = mount.join(&p);
-                Ok((
-                    j.strip_prefix("../../../")
-                        .map_err(|_| IntegrationError::ModfileInvalidPrefix {
-                            mod_info: mod_info.clone(),
-                            modfile_path: j.to_string(),
-                        })?
-                        .to_path_buf(),
-                    p,
-                ))
-            })
-            .collect::<Result<HashMap<_, _>, _>>()?;
-
-        for (normalized, pak_path) in &pak_files {
-            match normalized.extension() {
-                Some("uasset" | "umap")
-                    if pak_files.contains_key(&normalized.with_extension("uexp")) =>
+                Default::default()
+            };
+
+            for (normalized, pak_path) in &pak_files {
+                // the asset is taken from another mod, so is its registry entry
+                if let Some(&winner) =
+                    override_winners.get(asset_path(&normalized.as_str().to_ascii_lowercase()))
+                    && winner != mod_index
                 {
-                    let uasset = pak
-                        .get(pak_path, &mut buf)
-                        .with_context(|_| CtxtRepakSnafu {
-                            mod_info: mod_info.clone(),
-                        })?;
-
-                    let uexp = pak
-                        .get(
-                            PakPath::new(pak_path).with_extension("uexp").as_str(),
-                            &mut buf,
-                        )
-                        .with_context(|_| CtxtRepakSnafu {
-                            mod_info: mod_info.clone(),
-                        })?;
-
-                    let asset = AssetBuilder::new(Cursor::new(uasset), EngineVersion::VER_UE4_27)
-                        .bulk(Cursor::new(uexp))
-                        .skip_data(true)
-                        .build()?;
-                    asset_registry
-                        .populate(normalized.with_extension("").as_str(), &asset)
-                        .map_err(|e| IntegrationError::CtxtGenericError {
-                            source: e.into(),
-                            mod_info: mod_info.clone(),
-                        })?;
+                    continue;
+                }
+                match normalized.extension() {
+                    Some("uasset" | "umap")
+                        if pak_files.contains_key(&normalized.with_extension("uexp")) =>
+                    {
+                        let uasset =
+                            pak.get(pak_path, &mut buf)
+                                .with_context(|_| CtxtRepakSnafu {
+                                    mod_info: mod_info.clone(),
+                                })?;
+
+                        let uexp = pak
+                            .get(
+                                PakPath::new(pak_path).with_extension("uexp").as_str(),
+                                &mut buf,
+                            )
+                            .with_context(|_| CtxtRepakSnafu {
+                                mod_info: mod_info.clone(),
+                            })?;
+
+                        let asset =
+                            AssetBuilder::new(Cursor::new(uasset), EngineVersion::VER_UE4_27)
+                                .bulk(Cursor::new(uexp))
+                                .skip_data(true)
+                                .build()?;
+                        asset_registry
+                            .populate(normalized.with_extension("").as_str(), &asset)
+                            .map_err(|e| IntegrationError::CtxtGenericError {
+                                source: e.into(),
+                                mod_info: mod_info.clone(),
+                            })?;
+                    }
+                    _ => {}
                 }
-                _ => {}
-            }
-        }
-
-        for (normalized, pak_path) in pak_files {
-            let lowercase = normalized.as_str().to_ascii_lowercase();
-            if added_paths.contains(&lowercase) {
-                continue;
             }
 
-            if let Some(filename) = normalized.file_name() {
-                if filename == "AssetRegistry.bin" {
+            for (normalized, pak_path) in pak_files {
+                let lowercase = normalized.as_str().to_ascii_lowercase();
+                if let Some(&winner) = override_winners.get(asset_path(&lowercase))
+                    && winner != mod_index
+                {
+                    overridden.insert((winner, mod_index, normalized.as_str().to_string()));
                     continue;
                 }
-                if normalized.extension() == Some("ushaderbytecode") {
+                if let Some(&winner) = added_paths.get(&lowercase) {
+                    // overridden by a higher priority pak of the same mod
+                    if winner != mod_index
+                        && let Some(log) = &mut log
+                    {
+                        log.conflict(winner, mod_index, normalized.as_str());
+                    }
                     continue;
                 }
-                let lower = filename.to_lowercase();
-                if lower == "initspacerig.uasset" {
-                    init_spacerig_assets.insert(format_soft_class(&normalized));
+                if split_assets.contains_key(normalized.as_str()) {
+                    warn!(
+                        "skipped {} of {}, the other half of the asset is missing",
+                        normalized.as_str(),
+                        mod_info.name
+                    );
+                    if let Some(log) = &mut log {
+                        log.mods[mod_index]
+                            .stripped
+                            .push(normalized.as_str().to_string());
+                    }
+                    continue;
                 }
-                if lower == "initcave.uasset" {
-                    init_cave_assets.insert(format_soft_class(&normalized));
+
+                if let Some(filename) = normalized.file_name() {
+                    if filename == "AssetRegistry.bin"
+                        || normalized.extension() == Some("ushaderbytecode")
+                    {
+                        if let Some(log) = &mut log {
+                            log.mods[mod_index]
+                                .stripped
+                                .push(normalized.as_str().to_string());
+                        }
+                        continue;
+                    }
+                    let lower = filename.to_lowercase();
+                    if lower == "initspacerig.uasset" {
+                        init_spacerig_assets.insert(format_soft_class(&normalized));
+                    }
+                    if lower == "initcave.uasset" {
+                        init_cave_assets.insert(format_soft_class(&normalized));
+                    }
                 }
-            }
 
-            let file_data = pak
-                .get(&pak_path, &mut buf)
-                .with_context(|_| CtxtRepakSnafu {
-                    mod_info: mod_info.clone(),
-                })?;
-            if let Some(raw) = normalized
-                .as_str()
-                .strip_suffix(".uasset")
-                .and_then(|path| deferred_assets.get_mut(path))
-            {
-                raw.uasset = Some(file_data);
-            } else if let Some(raw) = normalized
-                .as_str()
-                .strip_suffix(".uexp")
-                .and_then(|path| deferred_assets.get_mut(path))
-            {
-                raw.uexp = Some(file_data);
-            } else {
-                bundle.write_file(&file_data, normalized.as_str())?;
-                added_paths.insert(lowercase);
+                let file_data = pak
+                    .get(&pak_path, &mut buf)
+                    .with_context(|_| CtxtRepakSnafu {
+                        mod_info: mod_info.clone(),
+                    })?;
+                let entry = log.as_mut().map(|log| &mut log.mods[mod_index]);
+                if let Some(raw) = normalized
+                    .as_str()
+                    .strip_suffix(".uasset")
+                    .and_then(|path| deferred_assets.get_mut(path))
+                {
+                    raw.uasset = Some(file_data);
+                    if let Some(entry) = entry {
+                        entry.patched.push(normalized.as_str().to_string());
+                    }
+                } else if let Some(raw) = normalized
+                    .as_str()
+                    .strip_suffix(".uexp")
+                    .and_then(|path| deferred_assets.get_mut(path))
+                {
+                    raw.uexp = Some(file_data);
+                    if let Some(entry) = entry {
+                        entry.patched.push(normalized.as_str().to_string());
+                    }
+                } else {
+                    bundle.write_file(&file_data, normalized.as_str())?;
+                    added_paths.insert(lowercase, mod_index);
+                    if let Some(entry) = entry {
+                        entry.written.push(normalized.as_str().to_string());
+                    }
+                }
             }
         }
     }
@@ -477,6 +853,13 @@ pub fn integrate<P: AsRef<Path>>(
     }
 
     bundle.write_meta(config, &mods)?;
+    if options.embed_mod_list {
+        if added_paths.contains_key(MOD_LIST_PATH) {
+            warn!("a mod contains {MOD_LIST_PATH}, skipped writing the mod list");
+        } else {
+            bundle.write_file(mod_list(&mods).as_bytes(), MOD_LIST_PATH)?;
+        }
+    }
 
     let mut buf = vec![];
     asset_registry
@@ -485,6 +868,8 @@ pub fn integrate<P: AsRef<Path>>(
     bundle.write_file(&buf, ar_path)?;
 
     bundle.finish()?;
+    ensure!(!cancelled(), CancelledSnafu);
+    persist_output(output, &path_mod_pak)?;
 
     info!(
         "{} mods installed to {}",
@@ -492,9 +877,193 @@ pub fn integrate<P: AsRef<Path>>(
         path_mod_pak.display()
     );
 
+    if let Some(export_path) = &options.export_path {
+        let exported = export_pak(&path_mod_pak, export_path)?;
+        info!("mods pak exported to {}", exported.display());
+    }
+
+    if let (Some(mut log), Some(log_dir)) = (log, log_dir) {
+        for (winner, loser, path) in overridden {
+            log.conflict(winner, loser, &path);
+        }
+        log.write(log_dir)?;
+        info!("integrate log written to {}", log_dir.display());
+    }
+
+    Ok(path_mod_pak)
+}
+
+/// Files of a pak of `mod_info` by their path relative to the game directory, with the path to
+/// read them from the pak with.
+fn pak_files(
+    mod_info: &ModInfo,
+    pak: &repak::PakReader,
+) -> Result<HashMap<PakPathBuf, String>, IntegrationError> {
+    let mount = PakPath::new(pak.mount_point());
+    pak.files()
+        .into_iter()
+        .map(|p| -> Result<_, IntegrationError> {
+            let j = mount.join(&p);
+            Ok((
+                j.strip_prefix("../../../")
+                    .map_err(|_| IntegrationError::ModfileInvalidPrefix {
+                        mod_info: mod_info.clone(),
+                        modfile_path: j.to_string(),
+                    })?
+                    .to_path_buf(),
+                p,
+            ))
+        })
+        .collect()
+}
+
+/// Open the paks of the mod at `path` for [`integrate`], decrypting them with `pak_key` if they
+/// are encrypted. The highest priority pak comes first since the first pak to add a path wins.
+fn open_mod_paks(
+    mod_info: &ModInfo,
+    path: &Path,
+    pak_key: Option<&PakKey>,
+    all_archive_paks: bool,
+) -> Result<Vec<(Box<dyn ReadSeek>, repak::PakReader)>, IntegrationError> {
+    let raw_mod_file = fs::File::open(path).with_context(|_| CtxtIoSnafu {
+        mod_info: mod_info.clone(),
+    })?;
+    let paks = if all_archive_paks {
+        get_all_paks_from_data(Box::new(BufReader::new(raw_mod_file)))
+    } else {
+        get_pak_from_data(Box::new(BufReader::new(raw_mod_file))).map(|pak| vec![pak])
+    }
+    .map_err(|e| {
+        if let IntegrationError::IoError { source } = e {
+            IntegrationError::CtxtIoError {
+                source,
+                mod_info: mod_info.clone(),
+            }
+        } else {
+            e
+        }
+    })?;
+
+    paks.into_iter()
+        .rev()
+        .map(|mut buf| {
+            let pak = pak_builder(pak_key)
+                .reader(&mut buf)
+                .with_context(|_| CtxtRepakSnafu {
+                    mod_info: mod_info.clone(),
+                })?;
+            Ok((buf, pak))
+        })
+        .collect()
+}
+
+/// Copy the installed mods pak to `export_path`, e.g. to share it, so it does not have to be
+/// merged a second time. If `export_path` is a directory the pak keeps its name. Returns the path
+/// of the copy.
+pub fn export_pak(path_mod_pak: &Path, export_path: &Path) -> Result<PathBuf, IntegrationError> {
+    let target = if export_path.is_dir() {
+        export_path.join(path_mod_pak.file_name().expect("mods pak has a file name"))
+    } else {
+        export_path.to_path_buf()
+    };
+    ensure!(
+        target != path_mod_pak,
+        GenericSnafu {
+            msg: "the export path is the installed mods pak itself".to_string()
+        }
+    );
+    fs::copy(path_mod_pak, &target)?;
+    Ok(target)
+}
+
+/// OS error codes of a file being locked by another process or on a read-only file system.
+#[cfg(windows)]
+const LOCKED_OS_ERRORS: &[i32] = &[
+    32, // ERROR_SHARING_VIOLATION
+    33, // ERROR_LOCK_VIOLATION
+];
+#[cfg(not(windows))]
+const LOCKED_OS_ERRORS: &[i32] = &[
+    26, // ETXTBSY
+    30, // EROFS
+];
+
+/// Whether writing a file failed because it is in use, usually by the running game, or read-only.
+fn is_locked(e: &std::io::Error) -> bool {
+    // fs_err wraps the OS error so look through it for the code
+    let os_error = e.raw_os_error().or_else(|| {
+        e.get_ref()
+            .and_then(|inner| inner.source())
+            .and_then(|source| source.downcast_ref::<std::io::Error>())
+            .and_then(|source| source.raw_os_error())
+    });
+    e.kind() == ErrorKind::PermissionDenied
+        || os_error.is_some_and(|code| LOCKED_OS_ERRORS.contains(&code))
+}
+
+fn output_error(path: &Path, source: std::io::Error) -> IntegrationError {
+    if is_locked(&source) {
+        IntegrationError::OutputLocked {
+            source,
+            path: path.to_path_buf(),
+        }
+    } else {
+        IntegrationError::IoError { source }
+    }
+}
+
+/// Names of temporary files the mods pak is written to start with this. They are not paks so the
+/// game ignores them even if one is left behind by mint being killed.
+const OUTPUT_TEMP_PREFIX: &str = ".mods_P";
+
+/// Create a temporary file next to `path` to write the new output to. The existing output is
+/// only replaced by [`persist_output`] once the new one is complete, so an interrupted or failed
+/// install leaves the previous one intact. The temporary file is deleted if it is dropped
+/// instead.
+fn create_output(path: &Path) -> Result<tempfile::NamedTempFile, IntegrationError> {
+    let dir = path.parent().expect("output is inside the game directory");
+    let mut builder = tempfile::Builder::new();
+    builder.prefix(OUTPUT_TEMP_PREFIX);
+    // temporary files are only readable by the owner by default
+    #[cfg(unix)]
+    builder.permissions(std::os::unix::fs::PermissionsExt::from_mode(0o644));
+    builder.tempfile_in(dir).map_err(|e| output_error(path, e))
+}
+
+/// Atomically replace `path` with the completely written `output`.
+fn persist_output(output: tempfile::NamedTempFile, path: &Path) -> Result<(), IntegrationError> {
+    output
+        .persist(path)
+        .map_err(|e| output_error(path, e.error))?;
     Ok(())
 }
 
+/// Remove temporary outputs left behind next to `path` by an install which was killed.
+fn remove_stale_outputs(path: &Path) {
+    let Some(Ok(entries)) = path.parent().map(fs::read_dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        if entry
+            .file_name()
+            .to_string_lossy()
+            .starts_with(OUTPUT_TEMP_PREFIX)
+            && let Err(e) = fs::remove_file(entry.path())
+        {
+            warn!("failed to remove stale output: {e}");
+        }
+    }
+}
+
+/// Fail before doing any work if an existing output file cannot be replaced.
+fn check_writable(path: &Path) -> Result<(), IntegrationError> {
+    match fs::OpenOptions::new().append(true).open(path) {
+        Ok(_) => Ok(()),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(output_error(path, e)),
+    }
+}
+
 fn collect_dir_files(dir: &'static include_dir::Dir, collect: &mut HashMap<String, &[u8]>) {
     for entry in dir.entries() {
         match entry {
@@ -628,11 +1197,32 @@ impl<W: Write + Seek> ModBundleWriter<W> {
     }
 
     fn finish(self) -> Result<(), IntegrationError> {
-        self.pak_writer.write_index()?;
+        // flush explicitly as errors are lost if the buffered writer is flushed on drop
+        self.pak_writer.write_index()?.flush()?;
         Ok(())
     }
 }
 
+/// Human readable list of `mods` for [`MOD_LIST_PATH`].
+fn mod_list(mods: &[(ModInfo, PathBuf)]) -> String {
+    let mut list = format!(
+        "Mods installed by mint {}, highest priority first:\n",
+        env!("CARGO_PKG_VERSION")
+    );
+    for (i, (info, _)) in mods.iter().enumerate() {
+        list.push_str(&format!("\n{}. {}\n", i + 1, info.name));
+        list.push_str(&format!("   url: {}\n", info.spec.url));
+        list.push_str(&format!(
+            "   version: {}\n",
+            info.resolution.get_resolvable_url_or_name()
+        ));
+        if info.suggested_require {
+            list.push_str("   required by all players\n");
+        }
+    }
+    list
+}
+
 #[derive(Debug, Default)]
 struct Dir {
     name: String,
@@ -673,6 +1263,55 @@ pub(crate) fn get_pak_from_data(
     }
 }
 
+/// Order in which the game loads the paks of a directory: by file name, with `_P` patch paks
+/// after all others. Files of paks loaded later take precedence.
+pub fn pak_load_order_key(path: &Path) -> (bool, String) {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    let is_patch = name.strip_suffix(".pak").unwrap_or(&name).ends_with("_p");
+    (is_patch, name)
+}
+
+/// Every pak of a mod in load order, see [`pak_load_order_key`]. A mod which is a pak itself is
+/// returned as is.
+pub(crate) fn get_all_paks_from_data(
+    mut data: Box<dyn ReadSeek>,
+) -> Result<Vec<Box<dyn ReadSeek>>, IntegrationError> {
+    let Ok(mut archive) = zip::ZipArchive::new(&mut data) else {
+        data.rewind()?;
+        return Ok(vec![data]);
+    };
+    let mut paks = vec![];
+    for i in 0..archive.len() {
+        let mut file = archive
+            .by_index(i)
+            .map_err(|_| IntegrationError::GenericError {
+                msg: "failed to extract file in zip archive".to_string(),
+            })?;
+        if let Some(p) = file.enclosed_name()
+            && file.is_file()
+            && p.extension() == Some(std::ffi::OsStr::new("pak"))
+        {
+            let mut buf = vec![];
+            file.read_to_end(&mut buf)?;
+            paks.push((
+                pak_load_order_key(&p),
+                Box::new(Cursor::new(buf)) as Box<dyn ReadSeek>,
+            ));
+        }
+    }
+    ensure!(
+        !paks.is_empty(),
+        GenericSnafu {
+            msg: "zip archive does not contain pak",
+        }
+    );
+    paks.sort_by(|(a, _), (b, _)| a.cmp(b));
+    Ok(paks.into_iter().map(|(_, pak)| pak).collect())
+}
+
 type ImportChain<'a> = Vec<Import<'a>>;
 
 struct Import<'a> {
@@ -1265,3 +1904,474 @@ fn patch_server_list_entry<C: Seek + Read>(asset: &mut Asset<C>) -> Result<(), I
 
     Ok(())
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::providers::{ModResolution, ModSpecification};
+
+    #[test]
+    fn test_mod_list() {
+        let info = |name: &str, url: &str, resolution: &str, required| ModInfo {
+            provider: "test",
+            name: name.to_string(),
+            spec: ModSpecification::new(url.to_string()),
+            versions: vec![],
+            resolution: ModResolution::resolvable(resolution.to_string().into()),
+            suggested_require: required,
+            suggested_dependencies: vec![],
+            modio_tags: None,
+            modio_id: None,
+            modio_stats: None,
+            description: None,
+        };
+        let mods = [
+            info(
+                "Example",
+                "https://mod.io/g/drg/m/example",
+                "https://mod.io/g/drg/m/example#1/2",
+                true,
+            ),
+            info("Local", "/mods/local.pak", "/mods/local.pak", false),
+        ]
+        .map(|info| (info, PathBuf::new()));
+
+        let list = mod_list(&mods);
+        assert!(list.starts_with("Mods installed by mint "));
+        assert!(list.contains(
+            "\n1. Example\n   url: https://mod.io/g/drg/m/example\n   version: https://mod.io/g/drg/m/example#1/2\n   required by all players\n"
+        ));
+        assert!(
+            list.ends_with("\n2. Local\n   url: /mods/local.pak\n   version: /mods/local.pak\n")
+        );
+    }
+
+    #[test]
+    fn test_mod_priority_for_path() {
+        let mc = ModConfig {
+            spec: ModSpecification::new("https://mod.io/g/drg/m/example".into()),
+            required: false,
+            enabled: true,
+            priority: 10,
+            display_name: None,
+            added: None,
+            frozen_from: None,
+            priority_overrides: vec![
+                PriorityOverride::new("FSD/Content/Textures", -5).unwrap(),
+                PriorityOverride {
+                    pattern: "\\FSD\\Content\\*.uexp".into(),
+                    priority: 20,
+                },
+            ],
+        };
+        let priority = ModPriority::from(&mc);
+        assert_eq!(priority.base, 10);
+        assert_eq!(priority.overrides[1].pattern, "fsd/content/*.uexp");
+
+        assert_eq!(priority.for_path("fsd/content/textures/rock.uasset"), -5);
+        // the first matching override applies
+        assert_eq!(priority.for_path("fsd/content/textures/rock.uexp"), -5);
+        assert_eq!(priority.for_path("fsd/content/ui/menu.uexp"), 20);
+        assert_eq!(priority.for_path("fsd/content/ui/menu.uasset"), 10);
+        assert_eq!(priority.for_path("fsd/content/texturesextra/a.uasset"), 10);
+        assert_eq!(ModPriority::default().for_path("fsd/content/a.uasset"), 0);
+
+        // an override matching one half of an asset applies to all of it
+        let menu = ["fsd/content/ui/menu.uasset", "fsd/content/ui/menu.uexp"];
+        assert_eq!(priority.for_asset(&menu), 20);
+        assert_eq!(priority.for_asset(&menu[..1]), 10);
+    }
+
+    #[test]
+    fn test_asset_path() {
+        assert_eq!(
+            asset_path("fsd/content/ui/menu.uasset"),
+            "fsd/content/ui/menu"
+        );
+        assert_eq!(
+            asset_path("fsd/content/ui/menu.uexp"),
+            "fsd/content/ui/menu"
+        );
+        assert_eq!(
+            asset_path("fsd/content/maps/cave.umap"),
+            "fsd/content/maps/cave"
+        );
+        assert_eq!(
+            asset_path("fsd/content.dir/readme"),
+            "fsd/content.dir/readme"
+        );
+    }
+
+    /// Write a pak named `name` containing `files` to `dir`, each file holding the name.
+    fn write_test_mod(dir: &Path, name: &str, files: &[&str]) -> (ModInfo, PathBuf) {
+        let mut writer = repak::PakBuilder::new().writer(
+            Cursor::new(vec![]),
+            repak::Version::V11,
+            "../../../".to_string(),
+            None,
+        );
+        for file in files {
+            writer.write_file(file, name.as_bytes()).unwrap();
+        }
+        let path = dir.join(format!("{name}.pak"));
+        fs::write(&path, writer.write_index().unwrap().into_inner()).unwrap();
+        (test_mod_info(name, &path), path)
+    }
+
+    fn test_mod_info(name: &str, path: &Path) -> ModInfo {
+        let url = path.to_string_lossy().to_string();
+        ModInfo {
+            provider: "test",
+            name: name.to_string(),
+            spec: ModSpecification::new(url.clone()),
+            versions: vec![],
+            resolution: ModResolution::resolvable(url.into()),
+            suggested_require: false,
+            suggested_dependencies: vec![],
+            modio_tags: None,
+            modio_id: None,
+            modio_stats: None,
+            description: None,
+        }
+    }
+
+    #[test]
+    fn test_plan_skips_split_assets() {
+        let dir = tempfile::tempdir().unwrap();
+        let mods = [write_test_mod(
+            dir.path(),
+            "a",
+            &[
+                "FSD/Content/UI/Menu.uasset",
+                "FSD/Content/UI/Menu.uexp",
+                "FSD/Content/UI/Split.uasset",
+            ],
+        )];
+        let paths = |skip_split_assets| {
+            let options = IntegrateOptions {
+                skip_split_assets,
+                ..Default::default()
+            };
+            plan(&mods, None, &options)
+                .unwrap()
+                .paths()
+                .into_iter()
+                .map(|(path, _)| path)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            paths(false),
+            [
+                "fsd/content/ui/menu.uasset",
+                "fsd/content/ui/menu.uexp",
+                "fsd/content/ui/split.uasset",
+            ]
+        );
+        assert_eq!(
+            paths(true),
+            ["fsd/content/ui/menu.uasset", "fsd/content/ui/menu.uexp"]
+        );
+    }
+
+    /// Run the test `name` again in a child process limited to 16 file descriptors, which holding
+    /// 32 mods open at once exceeds. Returns whether this is the child, which runs the test.
+    #[cfg(unix)]
+    fn in_fd_limited_child(name: &str) -> bool {
+        const CHILD_ENV: &str = "MINT_TEST_FD_LIMIT_CHILD";
+        if std::env::var_os(CHILD_ENV).is_some() {
+            return true;
+        }
+        let output = std::process::Command::new("sh")
+            .arg("-c")
+            .arg("ulimit -n 16 && exec \"$0\" \"$1\" --exact --include-ignored --test-threads 1")
+            .arg(std::env::current_exe().unwrap())
+            .arg(name)
+            .env(CHILD_ENV, "1")
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stdout)
+        );
+        false
+    }
+
+    /// 32 mods which all have the same files, with a priority override so the extra pass over the
+    /// mods runs as well.
+    fn many_test_mods(dir: &Path, files: &[&str]) -> (Vec<(ModInfo, PathBuf)>, IntegrateOptions) {
+        let mods = (0..32)
+            .map(|i| write_test_mod(dir, &format!("mod{i}"), files))
+            .collect();
+        let options = IntegrateOptions {
+            priorities: vec![ModPriority {
+                base: 0,
+                overrides: vec![PriorityOverride::new("FSD/Content/*", 1).unwrap()],
+            }],
+            ..Default::default()
+        };
+        (mods, options)
+    }
+
+    /// Mods are read one at a time, see [`integrate`], so installing many of them does not run
+    /// into low limits on open files and there is no limit on open paks to configure.
+    #[cfg(unix)]
+    #[test]
+    fn test_plan_reads_mods_one_at_a_time() {
+        if !in_fd_limited_child("integrate::test::test_plan_reads_mods_one_at_a_time") {
+            return;
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let (mods, options) = many_test_mods(
+            dir.path(),
+            &["FSD/Content/UI/Menu.uasset", "FSD/Content/UI/Menu.uexp"],
+        );
+        let layout = plan(&mods, None, &options).unwrap();
+        assert_eq!(layout.file_count(), 2);
+    }
+
+    /// Like [`test_plan_reads_mods_one_at_a_time`] for [`integrate`] itself, which needs the game
+    /// pak. It is linked into a temporary game directory so the mods pak is written there.
+    #[cfg(unix)]
+    #[test]
+    #[ignore = "needs the game pak in MINT_FSD_PAK"]
+    fn test_integrate_reads_mods_one_at_a_time() {
+        if !in_fd_limited_child("integrate::test::test_integrate_reads_mods_one_at_a_time") {
+            return;
+        }
+        let game_pak = std::env::var_os("MINT_FSD_PAK").expect("MINT_FSD_PAK is not set");
+        let dir = tempfile::tempdir().unwrap();
+        let paks = dir.path().join("FSD/Content/Paks");
+        fs::create_dir_all(&paks).unwrap();
+        let pak = paks.join("FSD-WindowsNoEditor.pak");
+        std::os::unix::fs::symlink(game_pak, &pak).unwrap();
+
+        // files which are copied as they are, assets would have to be valid to be merged
+        let (mods, options) = many_test_mods(dir.path(), &["FSD/Content/Movies/Intro.bk2"]);
+        let mods_pak = integrate(&pak, MetaConfig {}, None, mods, None, options, None).unwrap();
+        assert_eq!(
+            mods_pak,
+            fs::canonicalize(&paks).unwrap().join("mods_P.pak")
+        );
+    }
+
+    #[test]
+    fn test_override_winners() {
+        let dir = tempfile::tempdir().unwrap();
+        let files = [
+            "FSD/Content/Textures/Rock.uasset",
+            "FSD/Content/UI/Menu.uasset",
+            "FSD/Content/UI/Menu.uexp",
+        ];
+        let mods = ["a", "b"].map(|name| write_test_mod(dir.path(), name, &files));
+
+        // without overrides the first mod wins everything as before
+        let plain = [ModPriority::default(), ModPriority::default()];
+        assert!(override_winners(&mods, &plain, None, false)
+            .unwrap()
+            .is_empty());
+
+        // the first mod gives up its textures but keeps the rest
+        let scoped = [
+            ModPriority {
+                base: 0,
+                overrides: vec![PriorityOverride::new("FSD/Content/Textures", -1).unwrap()],
+            },
+            ModPriority::default(),
+        ];
+        let winners = override_winners(&mods, &scoped, None, false).unwrap();
+        assert_eq!(
+            winners,
+            HashMap::from([("fsd/content/textures/rock".to_string(), 1)])
+        );
+
+        // matching only the .uexp still moves the .uasset along with it
+        let split = [
+            ModPriority::default(),
+            ModPriority {
+                base: 0,
+                overrides: vec![PriorityOverride::new("FSD/Content/*.uexp", 1).unwrap()],
+            },
+        ];
+        let winners = override_winners(&mods, &split, None, false).unwrap();
+        assert_eq!(
+            winners,
+            HashMap::from([("fsd/content/ui/menu".to_string(), 1)])
+        );
+        let options = IntegrateOptions {
+            priorities: split.to_vec(),
+            ..Default::default()
+        };
+        let layout = plan(&mods, None, &options).unwrap();
+        assert_eq!(
+            layout
+                .paths()
+                .into_iter()
+                .map(|(path, leaf)| (path, leaf.winner.url.clone()))
+                .collect::<Vec<_>>(),
+            [
+                ("fsd/content/textures/rock.uasset", &mods[0].0.spec.url),
+                ("fsd/content/ui/menu.uasset", &mods[1].0.spec.url),
+                ("fsd/content/ui/menu.uexp", &mods[1].0.spec.url),
+            ]
+            .map(|(path, url)| (path.to_string(), url.clone()))
+        );
+
+        // ties go to the earlier mod
+        let tied = [
+            ModPriority::default(),
+            ModPriority {
+                base: 0,
+                overrides: vec![PriorityOverride::new("FSD/Content/*", 0).unwrap()],
+            },
+        ];
+        assert!(override_winners(&mods, &tied, None, false)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_open_encrypted_mod_paks() {
+        let path = Path::new("test_assets/lints/encrypted.pak");
+        let info = test_mod_info("encrypted", path);
+
+        // the index is encrypted so not even the file list can be read without the key
+        assert!(matches!(
+            open_mod_paks(&info, path, None, false),
+            Err(IntegrationError::CtxtRepakError { .. })
+        ));
+
+        let key = "0x000102030405060708090A0B0C0D0E0F101112131415161718191A1B1C1D1E1F"
+            .parse::<PakKey>()
+            .unwrap();
+        let mut paks = open_mod_paks(&info, path, Some(&key), false).unwrap();
+        assert_eq!(paks.len(), 1);
+        let (buf, pak) = &mut paks[0];
+        let mut files = pak.files();
+        files.sort();
+        assert_eq!(
+            files,
+            [
+                "FSD/Content/A.uexp",
+                "FSD/Content/AssetRegistry.bin",
+                "FSD/Content/B.uexp",
+                "FSD/Content/C.ushaderbytecode",
+            ]
+        );
+        assert_eq!(pak.get("FSD/Content/A.uexp", buf).unwrap(), b"a\n");
+    }
+
+    #[test]
+    fn test_output_replaced_only_when_complete() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mods_P.pak");
+        fs::write(&path, b"previous install").unwrap();
+        let files = || {
+            fs::read_dir(dir.path())
+                .unwrap()
+                .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        // an error while writing returns before persisting, dropping the partial output
+        let mut output = create_output(&path).unwrap();
+        output.write_all(b"partial").unwrap();
+        drop(output);
+        assert_eq!(fs::read(&path).unwrap(), b"previous install");
+        assert_eq!(files(), ["mods_P.pak"]);
+
+        let mut output = create_output(&path).unwrap();
+        output.write_all(b"new install").unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"previous install");
+        persist_output(output, &path).unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"new install");
+        assert_eq!(files(), ["mods_P.pak"]);
+
+        // left behind by a killed install
+        let stale = create_output(&path)
+            .unwrap()
+            .into_temp_path()
+            .keep()
+            .unwrap();
+        assert!(stale.exists());
+        remove_stale_outputs(&path);
+        assert!(!stale.exists());
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn test_export_pak() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mods_P.pak");
+        fs::write(&path, b"install").unwrap();
+        let export_dir = dir.path().join("export");
+        fs::create_dir(&export_dir).unwrap();
+
+        let exported = export_pak(&path, &export_dir.join("shared.pak")).unwrap();
+        assert_eq!(exported, export_dir.join("shared.pak"));
+        assert_eq!(fs::read(&exported).unwrap(), b"install");
+
+        // directories keep the name of the mods pak
+        let exported = export_pak(&path, &export_dir).unwrap();
+        assert_eq!(exported, export_dir.join("mods_P.pak"));
+        assert_eq!(fs::read(&exported).unwrap(), b"install");
+
+        assert!(export_pak(&path, dir.path()).is_err());
+        assert_eq!(fs::read(&path).unwrap(), b"install");
+    }
+
+    fn pe(machine: u16) -> Vec<u8> {
+        let mut data = vec![0; 0x90];
+        data[..2].copy_from_slice(b"MZ");
+        data[0x3c..0x40].copy_from_slice(&0x80u32.to_le_bytes());
+        data[0x80..0x84].copy_from_slice(b"PE\0\0");
+        data[0x84..0x86].copy_from_slice(&machine.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn test_check_hook_dll() {
+        let x64 = pe(0x8664);
+        assert_eq!(check_hook_dll(&x64, None), None);
+        assert_eq!(check_hook_dll(&x64, Some(&x64)), None);
+        assert_eq!(
+            check_hook_dll(&x64, Some(&pe(0x8664)[..0x88])),
+            Some(HookProblem::Mismatch)
+        );
+        assert_eq!(
+            check_hook_dll(&pe(0x14c), None),
+            Some(HookProblem::WrongArchitecture { machine: 0x14c })
+        );
+        assert_eq!(
+            check_hook_dll(&x64[..0x82], None),
+            Some(HookProblem::Corrupt)
+        );
+        assert_eq!(check_hook_dll(b"", None), Some(HookProblem::Corrupt));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlinked_paks_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let real = dir.path().join("real_paks");
+        let content = dir.path().join("FSD/Content");
+        fs::create_dir_all(&real).unwrap();
+        fs::create_dir_all(&content).unwrap();
+        std::os::unix::fs::symlink(&real, content.join("Paks")).unwrap();
+        // the temporary directory itself may be behind a link
+        let real = fs::canonicalize(&real).unwrap();
+
+        let pak = content.join("Paks/FSD-WindowsNoEditor.pak");
+        let installation = DRGInstallation::from_pak_path(&pak).unwrap();
+        assert_eq!(installation.paks_path(), real);
+        assert_eq!(installation.mods_pak(), real.join("mods_P.pak"));
+        assert_eq!(
+            installation.main_pak(),
+            real.join("FSD-WindowsNoEditor.pak")
+        );
+
+        fs::write(real.join("mods_P.pak"), b"").unwrap();
+        uninstall(&pak, HashSet::new(), false).unwrap();
+        assert!(!real.join("mods_P.pak").exists());
+    }
+}
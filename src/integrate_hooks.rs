@@ -0,0 +1,131 @@
+use std::path::Path;
+use std::process::Command;
+
+use mint_lib::DRGInstallation;
+use tracing::{info, warn};
+
+use crate::integrate::IntegrationError;
+
+/// Commands run before and after installing mods, e.g. to back up saves or notify a server. They
+/// are only ever taken from the user's own config, never from shared profiles or installers.
+///
+/// Placeholders in the templates are replaced before the command is run through the system shell
+/// (`sh -c`, or `cmd /C` on Windows). Values are inserted as is, so quote them in the template:
+///
+/// - `{pak}`: the mods pak which is written
+/// - `{game_pak}`: the game pak
+/// - `{profile}`: the installed profile, empty when installing a list of mods
+#[derive(Debug, Clone, Default)]
+pub struct IntegrateHooks {
+    pub pre_integrate: Option<String>,
+    pub post_integrate: Option<String>,
+    pub profile: String,
+}
+
+impl IntegrateHooks {
+    pub fn is_empty(&self) -> bool {
+        self.pre_integrate.is_none() && self.post_integrate.is_none()
+    }
+
+    /// Run the pre-integrate hook. If it fails the install must be aborted.
+    pub fn run_pre(&self, game_pak: &Path) -> Result<(), IntegrationError> {
+        match &self.pre_integrate {
+            Some(template) => run_hook("pre_integrate", &self.expand(template, game_pak)?),
+            None => Ok(()),
+        }
+    }
+
+    /// Run the post-integrate hook. The mods are installed at this point so a failure is only
+    /// logged.
+    pub fn run_post(&self, game_pak: &Path) {
+        if let Some(template) = &self.post_integrate
+            && let Err(e) = self
+                .expand(template, game_pak)
+                .and_then(|command| run_hook("post_integrate", &command))
+        {
+            warn!("{e}");
+        }
+    }
+
+    fn expand(&self, template: &str, game_pak: &Path) -> Result<String, IntegrationError> {
+        let installation = DRGInstallation::from_pak_path(game_pak).map_err(|_| {
+            IntegrationError::DrgInstallationNotFound {
+                path: game_pak.to_path_buf(),
+            }
+        })?;
+        Ok(expand(
+            template,
+            &installation.mods_pak(),
+            game_pak,
+            &self.profile,
+        ))
+    }
+}
+
+fn expand(template: &str, pak: &Path, game_pak: &Path, profile: &str) -> String {
+    template
+        .replace("{pak}", &pak.to_string_lossy())
+        .replace("{game_pak}", &game_pak.to_string_lossy())
+        .replace("{profile}", profile)
+}
+
+fn run_hook(hook: &'static str, command: &str) -> Result<(), IntegrationError> {
+    info!("running {hook} hook: {command}");
+    #[cfg(windows)]
+    let output = Command::new("cmd").arg("/C").arg(command).output();
+    #[cfg(not(windows))]
+    let output = Command::new("sh").arg("-c").arg(command).output();
+    let output = output.map_err(|e| IntegrationError::HookFailed {
+        hook,
+        reason: e.to_string(),
+    })?;
+
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        info!("{hook}: {line}");
+    }
+    for line in String::from_utf8_lossy(&output.stderr).lines() {
+        warn!("{hook}: {line}");
+    }
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(IntegrationError::HookFailed {
+            hook,
+            reason: output.status.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_expand() {
+        assert_eq!(
+            expand(
+                "backup \"{profile}\" \"{pak}\" {game_pak} {unknown}",
+                Path::new("/game/mods_P.pak"),
+                Path::new("/game/FSD.pak"),
+                "default",
+            ),
+            "backup \"default\" \"/game/mods_P.pak\" /game/FSD.pak {unknown}"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_hook() {
+        assert!(run_hook("pre_integrate", "echo ok").is_ok());
+        let err = run_hook("pre_integrate", "exit 3").unwrap_err();
+        assert!(matches!(
+            err,
+            IntegrationError::HookFailed {
+                hook: "pre_integrate",
+                ..
+            }
+        ));
+        assert!(err.to_string().contains("3"));
+    }
+}
@@ -0,0 +1,130 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::Path;
+
+use fs_err as fs;
+use serde::Serialize;
+
+use crate::providers::ModInfo;
+
+/// What every mod contributed to the mods pak, for diagnosing mods which misbehave once merged.
+///
+/// Mods are listed in integration order. When several mods provide the same file the earliest one
+/// wins.
+#[derive(Debug, Default, Serialize)]
+pub struct IntegrateLog {
+    pub mods: Vec<ModLog>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct ModLog {
+    pub name: String,
+    pub url: String,
+    /// Files written to the mods pak.
+    pub written: Vec<String>,
+    /// Files taken from this mod which later mods also provided, with those mods.
+    pub won: BTreeMap<String, Vec<String>>,
+    /// Files which were taken from an earlier mod instead, with that mod.
+    pub lost: BTreeMap<String, String>,
+    /// Files which are never written to the mods pak, such as shader bytecode.
+    pub stripped: Vec<String>,
+    /// Game assets mint patches which this mod replaced. The last mod providing one wins.
+    pub patched: Vec<String>,
+}
+
+impl IntegrateLog {
+    pub fn add_mod(&mut self, info: &ModInfo) -> &mut ModLog {
+        self.mods.push(ModLog {
+            name: info.name.clone(),
+            url: info.spec.url.clone(),
+            ..Default::default()
+        });
+        self.mods.last_mut().unwrap()
+    }
+
+    /// Record that mod `loser` also provided `path` which was already taken from mod `winner`.
+    pub fn conflict(&mut self, winner: usize, loser: usize, path: &str) {
+        let winner_name = self.mods[winner].name.clone();
+        let loser_name = self.mods[loser].name.clone();
+        self.mods[winner]
+            .won
+            .entry(path.to_string())
+            .or_default()
+            .push(loser_name);
+        self.mods[loser].lost.insert(path.to_string(), winner_name);
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for m in &self.mods {
+            writeln!(text, "{} ({})", m.name, m.url).unwrap();
+            for path in &m.written {
+                match m.won.get(path) {
+                    Some(losers) => {
+                        writeln!(text, "  + {path} (also in {})", losers.join(", ")).unwrap()
+                    }
+                    None => writeln!(text, "  + {path}").unwrap(),
+                }
+            }
+            for (path, winner) in &m.lost {
+                writeln!(text, "  - {path} (taken from {winner})").unwrap();
+            }
+            for path in &m.stripped {
+                writeln!(text, "  x {path} (stripped)").unwrap();
+            }
+            for path in &m.patched {
+                writeln!(text, "  ~ {path} (patched)").unwrap();
+            }
+        }
+        text
+    }
+
+    /// Write the log as `integrate.log` and `integrate.json` to `dir`.
+    pub fn write(&self, dir: &Path) -> std::io::Result<()> {
+        fs::write(dir.join("integrate.log"), self.to_text())?;
+        fs::write(dir.join("integrate.json"), serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn mod_log(name: &str) -> ModLog {
+        ModLog {
+            name: name.to_string(),
+            url: format!("https://example.com/{name}"),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_integrate_log() {
+        let mut log = IntegrateLog {
+            mods: vec![mod_log("A"), mod_log("B"), mod_log("C")],
+        };
+        log.mods[0].written.push("fsd/content/a.uasset".to_string());
+        log.mods[1]
+            .stripped
+            .push("fsd/content/a.ushaderbytecode".to_string());
+        log.conflict(0, 1, "fsd/content/a.uasset");
+        log.conflict(0, 2, "fsd/content/a.uasset");
+
+        assert_eq!(
+            log.mods[0].won["fsd/content/a.uasset"],
+            ["B".to_string(), "C".to_string()]
+        );
+        assert_eq!(log.mods[2].lost["fsd/content/a.uasset"], "A");
+        assert_eq!(
+            log.to_text(),
+            "A (https://example.com/A)\n  \
+             + fsd/content/a.uasset (also in B, C)\n\
+             B (https://example.com/B)\n  \
+             - fsd/content/a.uasset (taken from A)\n  \
+             x fsd/content/a.ushaderbytecode (stripped)\n\
+             C (https://example.com/C)\n  \
+             - fsd/content/a.uasset (taken from A)\n"
+        );
+    }
+}
@@ -0,0 +1,105 @@
+use snafu::prelude::*;
+
+#[derive(Debug, Snafu, PartialEq, Eq)]
+#[snafu(display("unterminated {quote} quote in launch arguments"))]
+pub struct UnterminatedQuote {
+    quote: char,
+}
+
+/// Split user supplied launch arguments at whitespace. Arguments containing whitespace can be
+/// quoted with `'` or `"`. Inside double quotes `\"` and `\\` are escapes, anywhere else
+/// backslashes are kept as is so Windows paths can be written without escaping them.
+pub fn split_args(s: &str) -> Result<Vec<String>, UnterminatedQuote> {
+    let mut args = vec![];
+    let mut arg: Option<String> = None;
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => args.extend(arg.take()),
+            '\'' | '"' => {
+                let arg = arg.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        None => return UnterminatedQuoteSnafu { quote: c }.fail(),
+                        Some(q) if q == c => break,
+                        Some('\\') if c == '"' && matches!(chars.peek(), Some('"' | '\\')) => {
+                            arg.push(chars.next().unwrap())
+                        }
+                        Some(other) => arg.push(other),
+                    }
+                }
+            }
+            c => arg.get_or_insert_with(String::new).push(c),
+        }
+    }
+    args.extend(arg);
+    Ok(args)
+}
+
+/// Inverse of [`split_args`], quoting only the arguments which need it.
+pub fn join_args<S: AsRef<str>>(args: &[S]) -> String {
+    args.iter()
+        .map(|arg| {
+            let arg = arg.as_ref();
+            if !arg.is_empty()
+                && !arg.contains(|c: char| c.is_whitespace() || c == '"' || c == '\'')
+            {
+                arg.to_string()
+            } else {
+                format!("\"{}\"", arg.replace('\\', "\\\\").replace('"', "\\\""))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The command to launch the game with: the command the game was started with by Steam (see
+/// `mint launch`) followed by the user's extra arguments.
+pub fn launch_command(args: &[String], extra_args: &[String]) -> Vec<String> {
+    args.iter().chain(extra_args).cloned().collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_split_args() {
+        assert_eq!(split_args("").unwrap(), Vec::<String>::new());
+        assert_eq!(
+            split_args("  -nosteam   -ResX=1920 -ResY=1080 ").unwrap(),
+            ["-nosteam", "-ResX=1920", "-ResY=1080"]
+        );
+        assert_eq!(
+            split_args(r#"-log="C:\Program Files\log.txt" 'a "b"' "c \"d\" \\" e\f"#).unwrap(),
+            [
+                r"-log=C:\Program Files\log.txt",
+                r#"a "b""#,
+                r#"c "d" \"#,
+                r"e\f",
+            ]
+        );
+        assert_eq!(split_args(r#""" ''"#).unwrap(), ["", ""]);
+        assert_eq!(
+            split_args(r#"-a "unterminated"#),
+            Err(UnterminatedQuote { quote: '"' })
+        );
+    }
+
+    #[test]
+    fn test_join_args() {
+        let args = [
+            "-nosteam",
+            r"C:\Program Files\FSD.exe",
+            r#"say "hi" \"#,
+            "it's",
+            "",
+        ];
+        let joined = join_args(&args);
+        assert_eq!(
+            joined,
+            r#"-nosteam "C:\\Program Files\\FSD.exe" "say \"hi\" \\" "it's" """#
+        );
+        assert_eq!(split_args(&joined).unwrap(), args);
+    }
+}
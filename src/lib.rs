@@ -1,24 +1,47 @@
 #![feature(let_chains)]
 #![feature(if_let_guard)]
 
+pub mod allowlist;
+pub mod asset_tree;
+pub mod bisect;
+pub mod check_links;
+pub mod dep_graph;
+pub mod game_update;
 pub mod gui;
+pub mod import;
+pub mod incompatible;
 pub mod integrate;
+pub mod integrate_hooks;
+pub mod integrate_log;
+pub mod launch_args;
+pub mod mod_diff;
 pub mod mod_lints;
+pub mod modpack;
+pub mod other_paks;
 pub mod providers;
+pub mod smoke_test;
+pub mod snapshot;
 pub mod state;
+pub mod stats;
 
 use std::ops::Deref;
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     path::{Path, PathBuf},
 };
 
+use asset_tree::AssetTree;
 use directories::ProjectDirs;
 use fs_err as fs;
-use integrate::IntegrationError;
-use providers::{ModResolution, ModSpecification, ProviderError, ProviderFactory};
+use integrate::{IntegrateOptions, IntegrationError, ModPriority};
+use providers::{
+    FetchProgress, ModInfo, ModResolution, ModSpecification, ModStore, ModVersion, ProviderError,
+    ProviderFactory,
+};
+use serde::{Deserialize, Serialize};
 use snafu::prelude::*;
 use state::{State, StateError};
+use tokio::sync::mpsc::Sender;
 use tracing::*;
 
 #[derive(Debug, Snafu)]
@@ -37,8 +60,14 @@ pub enum MintError {
     },
     #[snafu(transparent)]
     StateError { source: StateError },
+    #[snafu(transparent)]
+    ModpackError { source: modpack::ModpackError },
+    #[snafu(transparent)]
+    ImportError { source: import::ImportError },
     #[snafu(display("invalid DRG pak path: {path}"))]
     InvalidDrgPak { path: String },
+    #[snafu(display("invalid pak AES key: expected 64 hex characters"))]
+    InvalidPakKey,
 }
 
 #[derive(Debug)]
@@ -101,12 +130,255 @@ pub fn is_drg_pak<P: AsRef<Path>>(path: P) -> Result<(), MintError> {
     Ok(())
 }
 
+/// [`is_drg_pak`] unless `skip` is set for nonstandard installs such as a pre-modified game pak or
+/// a total conversion, which do not contain `FSD/FSD.uproject`.
+pub fn validate_drg_pak<P: AsRef<Path>>(path: P, skip: bool) -> Result<(), MintError> {
+    if skip {
+        warn!(
+            "DRG pak validation is SKIPPED, {} is used as the game pak without checking it. Installing mods may fail or break the game.",
+            path.as_ref().display()
+        );
+        return Ok(());
+    }
+    is_drg_pak(path)
+}
+
+/// AES-256 key used to read mod paks with an encrypted index or encrypted entries.
+#[derive(Clone, PartialEq, Eq)]
+pub struct PakKey([u8; 32]);
+
+impl PakKey {
+    fn cipher(&self) -> aes::Aes256 {
+        use aes::cipher::KeyInit;
+        aes::Aes256::new(&self.0.into())
+    }
+}
+
+impl std::str::FromStr for PakKey {
+    type Err = MintError;
+
+    /// Parse a key from hex, with or without a leading `0x`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let s = s
+            .strip_prefix("0x")
+            .or_else(|| s.strip_prefix("0X"))
+            .unwrap_or(s);
+        let mut bytes = [0; 32];
+        hex::decode_to_slice(s, &mut bytes)
+            .ok()
+            .context(InvalidPakKeySnafu)?;
+        Ok(Self(bytes))
+    }
+}
+
+impl std::fmt::Display for PakKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "0x{}", hex::encode_upper(self.0))
+    }
+}
+
+impl std::fmt::Debug for PakKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("PakKey(..)")
+    }
+}
+
+impl Serialize for PakKey {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for PakKey {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Create a [`repak::PakBuilder`] which is able to read paks encrypted with `key`, if any.
+///
+/// Compressed entries need no configuration here: repak picks the decompressor by the method the
+/// pak declares. Zlib, Gzip and Zstd come from repak's `compression` feature and Oodle from the
+/// `oodle_platform_dependent` feature, so a pak using another method fails to read.
+pub fn pak_builder(key: Option<&PakKey>) -> repak::PakBuilder {
+    let builder = repak::PakBuilder::new();
+    match key {
+        Some(key) => builder.key(key.cipher()),
+        None => builder,
+    }
+}
+
+/// Hash identifying an install of `mod_specs` into `game_path`, used to detect whether anything
+/// changed since the last install.
+pub fn install_hash<P: AsRef<Path>>(game_path: P, mod_specs: &[ModSpecification]) -> String {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    hasher.update(game_path.as_ref().to_string_lossy().as_bytes());
+    for spec in mod_specs {
+        hasher.update([0]);
+        hasher.update(spec.url.as_bytes());
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// Mod info for `mod_specs` from the provider cache alone, or `None` if any of them has not been
+/// resolved before.
+pub fn cached_mod_info(
+    store: &ModStore,
+    mod_specs: &[ModSpecification],
+) -> Option<HashMap<ModSpecification, ModInfo>> {
+    mod_specs
+        .iter()
+        .map(|spec| Some((spec.clone(), store.get_mod_info(spec)?)))
+        .collect()
+}
+
+/// Mods among `infos` which depend on `spec`, not counting `spec` itself.
+pub fn dependents<'a>(
+    infos: impl IntoIterator<Item = &'a ModInfo>,
+    spec: &ModSpecification,
+) -> Vec<&'a ModInfo> {
+    infos
+        .into_iter()
+        .filter(|info| !info.spec.satisfies_dependency(spec) && info.depends_on(spec))
+        .collect()
+}
+
+/// `mod_specs` without the mods matching any of `remove`, to install a profile without some of
+/// its mods. Also returns the entries of `remove` which did not match any mod.
+pub fn without_mods<'a>(
+    mod_specs: &[ModSpecification],
+    remove: &'a [ModSpecification],
+) -> (Vec<ModSpecification>, Vec<&'a ModSpecification>) {
+    let remaining = mod_specs
+        .iter()
+        .filter(|spec| !remove.iter().any(|r| spec.satisfies_dependency(r)))
+        .cloned()
+        .collect();
+    let unmatched = remove
+        .iter()
+        .filter(|r| !mod_specs.iter().any(|spec| spec.satisfies_dependency(r)))
+        .collect();
+    (remaining, unmatched)
+}
+
+/// Outcome of integrating mods, see [`resolve_unordered_and_integrate`].
+#[derive(Debug)]
+pub enum Integrated {
+    /// The mods pak was written to this path.
+    Installed(PathBuf),
+    /// Nothing was written because this was a dry run.
+    DryRun {
+        /// The fetched mods in integration order with the files they were fetched to.
+        mods: Vec<(ModInfo, PathBuf)>,
+        /// The files the mods pak would contain, see [`integrate::plan`].
+        layout: AssetTree,
+    },
+}
+
+/// Integrate `mod_specs` straight from the cache, skipping resolution. Returns `None` if the
+/// cache is not warm enough, in which case the full [`resolve_unordered_and_integrate`] is needed.
+pub async fn quick_integrate<P: AsRef<Path>>(
+    game_path: P,
+    state: &State,
+    mod_specs: &[ModSpecification],
+    priorities: &[ModPriority],
+    dry_run: bool,
+    tx: Option<Sender<FetchProgress>>,
+) -> Result<Option<Integrated>, IntegrationError> {
+    let Some(mods) = cached_mod_info(&state.store, mod_specs) else {
+        return Ok(None);
+    };
+    let to_integrate = mod_specs
+        .iter()
+        .map(|u| (u.clone(), mods[u].clone()))
+        .collect::<Vec<_>>();
+    fetch_and_integrate(
+        game_path,
+        state,
+        to_integrate,
+        priorities,
+        false,
+        dry_run,
+        tx,
+    )
+    .await
+    .map(Some)
+}
+
+/// Fetch and integrate the mods, or with `dry_run` only work out what integrating them would
+/// write without touching the game directory.
+async fn fetch_and_integrate<P: AsRef<Path>>(
+    game_path: P,
+    state: &State,
+    to_integrate: Vec<(ModSpecification, ModInfo)>,
+    priorities: &[ModPriority],
+    update: bool,
+    dry_run: bool,
+    tx: Option<Sender<FetchProgress>>,
+) -> Result<Integrated, IntegrationError> {
+    info!("fetching mods...");
+    let mods = if state.config.auto_downgrade {
+        let pairs = to_integrate
+            .iter()
+            .map(|(spec, info)| (spec, info))
+            .collect::<Vec<_>>();
+        state
+            .store
+            .fetch_mods_with_fallback(&pairs, update, tx, state.config.pak_key.as_ref())
+            .await?
+    } else {
+        let urls = to_integrate
+            .iter()
+            .map(|(spec, m)| (spec, &m.resolution))
+            .collect::<Vec<_>>();
+        let paths = state.store.fetch_mods(&urls, update, tx).await?;
+        to_integrate
+            .into_iter()
+            .map(|(_, m)| m)
+            .zip(paths)
+            .collect()
+    };
+
+    let mut options: IntegrateOptions = state.config.deref().into();
+    options.priorities = priorities.to_vec();
+    if dry_run {
+        let layout = integrate::plan(&mods, state.config.pak_key.as_ref(), &options)?;
+        return Ok(Integrated::DryRun { mods, layout });
+    }
+    let path = integrate::integrate(
+        &game_path,
+        state.config.deref().into(),
+        state.config.pak_key.as_ref(),
+        mods,
+        state
+            .config
+            .verbose_integrate
+            .then_some(state.dirs.data_dir.as_path()),
+        options,
+        None,
+    )?;
+    if state.config.skip_hook {
+        warn!("skipped deploying the hook DLL, mint's in-game features will not work without it");
+    } else {
+        integrate::install_hook(&game_path)?;
+    }
+    Ok(Integrated::Installed(path))
+}
+
 pub async fn resolve_unordered_and_integrate<P: AsRef<Path>>(
     game_path: P,
     state: &State,
     mod_specs: &[ModSpecification],
+    priorities: &[ModPriority],
     update: bool,
-) -> Result<(), IntegrationError> {
+    dry_run: bool,
+    tx: Option<Sender<FetchProgress>>,
+) -> Result<Integrated, IntegrationError> {
     let mods = state.store.resolve_mods(mod_specs, update).await?;
 
     let mods_set = mod_specs
@@ -133,28 +405,26 @@ pub async fn resolve_unordered_and_integrate<P: AsRef<Path>>(
 
     let to_integrate = mod_specs
         .iter()
-        .map(|u| mods[u].clone())
+        .map(|u| (u.clone(), mods[u].clone()))
         .collect::<Vec<_>>();
-    let urls = to_integrate
-        .iter()
-        .map(|m| &m.resolution)
-        .collect::<Vec<_>>();
-
-    info!("fetching mods...");
-    let paths = state.store.fetch_mods(&urls, update, None).await?;
-
-    integrate::integrate(
+    fetch_and_integrate(
         game_path,
-        state.config.deref().into(),
-        to_integrate.into_iter().zip(paths).collect(),
+        state,
+        to_integrate,
+        priorities,
+        update,
+        dry_run,
+        tx,
     )
+    .await
 }
 
 async fn resolve_into_urls(
     state: &State,
     mod_specs: &[ModSpecification],
-) -> Result<Vec<ModResolution>, MintError> {
-    let mods = state.store.resolve_mods(mod_specs, false).await?;
+    update: bool,
+) -> Result<Vec<(ModSpecification, ModResolution)>, MintError> {
+    let mods = state.store.resolve_mods(mod_specs, update).await?;
 
     let mods_set = mod_specs
         .iter()
@@ -180,8 +450,7 @@ async fn resolve_into_urls(
 
     let urls = mod_specs
         .iter()
-        .map(|u| mods[u].clone())
-        .map(|m| m.resolution)
+        .map(|u| (u.clone(), mods[u].resolution.clone()))
         .collect::<Vec<_>>();
 
     Ok(urls)
@@ -190,11 +459,19 @@ async fn resolve_into_urls(
 pub async fn resolve_ordered(
     state: &State,
     mod_specs: &[ModSpecification],
+    tx: Option<Sender<FetchProgress>>,
 ) -> Result<Vec<PathBuf>, MintError> {
-    let urls = resolve_into_urls(state, mod_specs).await?;
+    let urls = resolve_into_urls(state, mod_specs, false).await?;
     Ok(state
         .store
-        .fetch_mods(&urls.iter().collect::<Vec<_>>(), false, None)
+        .fetch_mods(
+            &urls
+                .iter()
+                .map(|(spec, res)| (spec, res))
+                .collect::<Vec<_>>(),
+            false,
+            tx,
+        )
         .await?)
 }
 
@@ -202,16 +479,29 @@ pub async fn resolve_unordered_and_integrate_with_provider_init<P, F>(
     game_path: P,
     state: &mut State,
     mod_specs: &[ModSpecification],
+    priorities: &[ModPriority],
     update: bool,
+    dry_run: bool,
+    tx: Option<Sender<FetchProgress>>,
     init: F,
-) -> Result<(), MintError>
+) -> Result<Integrated, MintError>
 where
     P: AsRef<Path>,
     F: Fn(&mut State, String, &ProviderFactory) -> Result<(), MintError>,
 {
     loop {
-        match resolve_unordered_and_integrate(&game_path, state, mod_specs, update).await {
-            Ok(()) => return Ok(()),
+        match resolve_unordered_and_integrate(
+            &game_path,
+            state,
+            mod_specs,
+            priorities,
+            update,
+            dry_run,
+            tx.clone(),
+        )
+        .await
+        {
+            Ok(integrated) => return Ok(integrated),
             Err(ref e)
                 if let IntegrationError::ProviderError { ref source } = e
                     && let ProviderError::NoProvider { ref url, factory } = source =>
@@ -223,17 +513,59 @@ where
     }
 }
 
+/// Resolve mod info for `mod_specs` and their dependencies without fetching any mods.
+pub async fn resolve_with_provider_init<F>(
+    state: &mut State,
+    mod_specs: &[ModSpecification],
+    init: F,
+) -> Result<HashMap<ModSpecification, ModInfo>, MintError>
+where
+    F: Fn(&mut State, String, &ProviderFactory) -> Result<(), MintError>,
+{
+    loop {
+        match state.store.resolve_mods(mod_specs, false).await {
+            Ok(mods) => return Ok(mods),
+            Err(ProviderError::NoProvider { ref url, factory }) => {
+                init(state, url.clone(), factory)?
+            }
+            Err(e) => Err(e)?,
+        }
+    }
+}
+
+/// List every version of the mod `spec` refers to, oldest first.
+pub async fn list_versions_with_provider_init<F>(
+    state: &mut State,
+    spec: &ModSpecification,
+    update: bool,
+    init: F,
+) -> Result<Vec<ModVersion>, MintError>
+where
+    F: Fn(&mut State, String, &ProviderFactory) -> Result<(), MintError>,
+{
+    loop {
+        match state.store.list_versions(spec, update).await {
+            Ok(versions) => return Ok(versions),
+            Err(ProviderError::NoProvider { ref url, factory }) => {
+                init(state, url.clone(), factory)?
+            }
+            Err(e) => Err(e)?,
+        }
+    }
+}
+
 #[allow(clippy::needless_pass_by_ref_mut)]
 pub async fn resolve_ordered_with_provider_init<F>(
     state: &mut State,
     mod_specs: &[ModSpecification],
+    tx: Option<Sender<FetchProgress>>,
     init: F,
 ) -> Result<Vec<PathBuf>, MintError>
 where
     F: Fn(&mut State, String, &ProviderFactory) -> Result<(), MintError>,
 {
     loop {
-        match resolve_ordered(state, mod_specs).await {
+        match resolve_ordered(state, mod_specs, tx.clone()).await {
             Ok(mod_paths) => return Ok(mod_paths),
             Err(ref e)
                 if let MintError::IntegrationError { ref source } = e
@@ -246,3 +578,78 @@ where
         }
     }
 }
+
+/// Resolve `mod_specs` and fetch them into the cache without integrating them, so they can be
+/// installed later without network access. The returned paths are in the order of `mod_specs`.
+pub async fn prefetch_with_provider_init<F>(
+    state: &mut State,
+    mod_specs: &[ModSpecification],
+    update: bool,
+    tx: Option<Sender<FetchProgress>>,
+    init: F,
+) -> Result<Vec<PathBuf>, MintError>
+where
+    F: Fn(&mut State, String, &ProviderFactory) -> Result<(), MintError>,
+{
+    loop {
+        let fetched = match resolve_into_urls(state, mod_specs, update).await {
+            Ok(urls) => state
+                .store
+                .fetch_mods_ordered(
+                    &urls
+                        .iter()
+                        .map(|(spec, res)| (spec, res))
+                        .collect::<Vec<_>>(),
+                    update,
+                    tx.clone(),
+                )
+                .await
+                .map_err(MintError::from),
+            Err(e) => Err(e),
+        };
+        match fetched {
+            Ok(mod_paths) => return Ok(mod_paths),
+            Err(MintError::ProviderError {
+                source: ProviderError::NoProvider { url, factory },
+            }) => init(state, url, factory)?,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{without_mods, ModSpecification, PakKey};
+
+    #[test]
+    fn test_without_mods() {
+        let spec = |url: &str| ModSpecification::new(url.to_string());
+        let specs = [
+            spec("https://mod.io/g/drg/m/a#1/2"),
+            spec("https://mod.io/g/drg/m/b"),
+            spec("https://example.com/c.zip"),
+        ];
+        let remove = [
+            spec("https://mod.io/g/drg/m/a"),
+            spec("https://example.com/c.zip"),
+            spec("https://mod.io/g/drg/m/missing"),
+        ];
+        let (remaining, unmatched) = without_mods(&specs, &remove);
+        assert_eq!(remaining, vec![spec("https://mod.io/g/drg/m/b")]);
+        assert_eq!(unmatched, vec![&remove[2]]);
+    }
+
+    #[test]
+    fn test_parse_pak_key() {
+        let hex = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+        let key: PakKey = hex.parse().unwrap();
+        assert_eq!(key, format!("0x{hex}").parse().unwrap());
+        assert_eq!(key, format!(" 0X{} ", hex.to_uppercase()).parse().unwrap());
+        assert_eq!(key.to_string().parse::<PakKey>().unwrap(), key);
+
+        assert!("".parse::<PakKey>().is_err());
+        assert!("0x0123".parse::<PakKey>().is_err());
+        assert!(format!("{hex}00").parse::<PakKey>().is_err());
+        assert!(hex.replace('a', "g").parse::<PakKey>().is_err());
+    }
+}
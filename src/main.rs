@@ -1,17 +1,42 @@
-use std::collections::BTreeSet;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
 
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use clap::{Parser, Subcommand};
-use tracing::{debug, info};
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use tokio::sync::mpsc::{self, Sender};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
 
-use mint::mod_lints::{run_lints, LintId};
-use mint::providers::ProviderFactory;
-use mint::{gui::gui, providers::ModSpecification, state::State};
+use mint::allowlist::Allowlist;
+use mint::check_links::{
+    check_availability_with_provider_init, check_links_with_provider_init, parse_links,
+};
+use mint::dep_graph::DepGraph;
+use mint::game_update::{game_updated, PakBaseline};
+use mint::import::{self, ImportFormat, ImportMatch};
+use mint::incompatible::{Incompatibilities, INCOMPATIBLE_MODS_FILE};
+use mint::mod_lints::{find_conflicts, run_lints_in, LintBaseline, LintCtxt, LintId, LintSummary};
+use mint::other_paks::find_other_paks;
+use mint::providers::{parse_spec, FetchProgress, ModStore, ProviderFactory};
+use mint::{
+    bisect::Bisection,
+    gui::{gui, parse_mods},
+    integrate::{check_hook, install_hook, reinstall_hook, uninstall, HookProblem},
+    modpack::Modpack,
+    providers::ModSpecification,
+    snapshot::Snapshot,
+    state::{unix_time_now, ModConfig, ModOrGroup, ModProfile, State},
+};
 use mint::{
-    resolve_ordered_with_provider_init, resolve_unordered_and_integrate_with_provider_init, Dirs,
-    MintError,
+    dependents, install_hash, list_versions_with_provider_init, prefetch_with_provider_init,
+    quick_integrate, resolve_ordered_with_provider_init,
+    resolve_unordered_and_integrate_with_provider_init, resolve_with_provider_init,
+    validate_drg_pak, without_mods, Dirs, Integrated, MintError,
 };
+use mint_lib::save::ModIntegrationSave;
+use mint_lib::DRGInstallation;
 
 /// Command line integration tool.
 #[derive(Parser, Debug)]
@@ -27,6 +52,15 @@ struct ActionIntegrate {
     #[arg(short, long)]
     update: bool,
 
+    /// Write integrate.log and integrate.json to the data directory, listing the files every
+    /// mod contributed and which mod won each conflict.
+    #[arg(long)]
+    verbose_integrate: bool,
+
+    /// Also copy the installed mods pak to this file or directory, e.g. to share it.
+    #[arg(long)]
+    export: Option<PathBuf>,
+
     /// Paths of mods to integrate
     ///
     /// Can be a file path or URL to a .pak or .zip file or a URL to a mod on https://mod.io/g/drg
@@ -36,6 +70,16 @@ struct ActionIntegrate {
     ///     https://example.org/some-online-mod-repository/public-mod.zip
     #[arg(short, long, num_args=0.., verbatim_doc_comment)]
     mods: Vec<String>,
+
+    /// Read a newline separated list of mods from the clipboard in addition to any specified
+    /// with --mods.
+    #[arg(long)]
+    from_clipboard: bool,
+
+    /// Resolve and fetch the mods and print what would be installed without writing anything to
+    /// the game directory, e.g. to check a mod list in CI. Fails if any mod does not resolve.
+    #[arg(long, conflicts_with = "export")]
+    dry_run: bool,
 }
 
 /// Integrate a profile
@@ -52,16 +96,312 @@ struct ActionIntegrateProfile {
     #[arg(short, long)]
     update: bool,
 
+    /// Write integrate.log and integrate.json to the data directory, listing the files every
+    /// mod contributed and which mod won each conflict.
+    #[arg(long)]
+    verbose_integrate: bool,
+
+    /// Also copy the installed mods pak to this file or directory, e.g. to share it.
+    #[arg(long)]
+    export: Option<PathBuf>,
+
     /// Profile to integrate.
     profile: String,
 }
 
+/// Manage profiles. To integrate a profile use `mint profile <profile>`.
+#[derive(Parser, Debug)]
+struct ActionProfiles {
+    #[command(subcommand)]
+    command: ProfileCommand,
+}
+
+#[derive(Subcommand, Debug)]
+enum ProfileCommand {
+    ExportInstaller(ActionExportInstaller),
+    Install(ActionInstallProfile),
+    Dependents(ActionProfileDependents),
+    Deps(ActionProfileDeps),
+    EnforceAllowlist(ActionEnforceAllowlist),
+    CheckIncompatible(ActionCheckIncompatible),
+    Snapshot(ActionSnapshot),
+    RestoreSnapshot(ActionRestoreSnapshot),
+    Freeze(ActionFreeze),
+    Thaw(ActionThaw),
+    Check(ActionProfileCheck),
+}
+
+/// Check that every mod of a profile is still available, e.g. to replace mods which were removed
+/// from mod.io before they break an install. Prints the status of each mod and fails if any are
+/// unavailable.
+#[derive(Parser, Debug)]
+struct ActionProfileCheck {
+    /// Profile to check.
+    profile: String,
+}
+
+/// Pin every enabled mod of a profile to the exact version it currently resolves to, so updating
+/// mods does not change what the profile installs until it is thawed with `mint profiles thaw`.
+///
+/// Mods which are part of a group are frozen in the group, which affects every profile using it.
+#[derive(Parser, Debug)]
+struct ActionFreeze {
+    /// Profile to freeze.
+    profile: String,
+}
+
+/// Undo `mint profiles freeze`, so the mods of a profile resolve to their newest versions again.
+#[derive(Parser, Debug)]
+struct ActionThaw {
+    /// Profile to thaw.
+    profile: String,
+}
+
+/// Archive the enabled mods of a profile together with a manifest, so the profile can be restored
+/// with `mint profiles restore-snapshot` even after mods were updated or removed upstream.
+///
+/// Every mod is pinned to the version it currently resolves to and stored in the archive with its
+/// SHA-256, which is checked when restoring.
+#[derive(Parser, Debug)]
+struct ActionSnapshot {
+    /// Profile to archive.
+    profile: String,
+
+    /// Archive to write.
+    output: PathBuf,
+}
+
+/// Add a profile from an archive created by `mint profiles snapshot`. The mods are extracted to the
+/// data directory and installed from there instead of being downloaded again.
+#[derive(Parser, Debug)]
+struct ActionRestoreSnapshot {
+    /// Path to FSD-WindowsNoEditor.pak (FSD-WinGDK.pak for Microsoft Store version) located
+    /// inside the "Deep Rock Galactic" installation directory under FSD/Content/Paks. Only
+    /// necessary if it cannot be found automatically.
+    #[arg(short, long)]
+    fsd_pak: Option<PathBuf>,
+
+    /// Name of the profile to create. Defaults to the name of the archived profile.
+    #[arg(short, long)]
+    name: Option<String>,
+
+    /// Install the profile after restoring it.
+    #[arg(short, long)]
+    install: bool,
+
+    /// Archive to restore.
+    snapshot: PathBuf,
+}
+
+/// Check the enabled mods of a profile against the list of known incompatible mod combinations
+/// in `incompatible_mods.txt` in the config directory. Fails if any combination is enabled.
+///
+/// The list has one combination per line as mods separated by `+`, optionally followed by `: `
+/// and the reason. Mods are given as numeric mod.io mod IDs or as mod URLs.
+#[derive(Parser, Debug)]
+struct ActionCheckIncompatible {
+    /// Profile to check. Defaults to the active profile.
+    #[arg(short, long)]
+    profile: Option<String>,
+}
+
+/// Check the enabled mods of a profile against a server allowlist and report any mods which are
+/// not on it.
+///
+/// The allowlist file lists one mod per line, either as a numeric mod.io mod ID or as a mod URL.
+#[derive(Parser, Debug)]
+struct ActionEnforceAllowlist {
+    /// Profile to check. Defaults to the active profile.
+    #[arg(short, long)]
+    profile: Option<String>,
+
+    /// Remove disallowed mods from the profile instead of only reporting them. Mods which are part
+    /// of a group are disabled in the group.
+    #[arg(long)]
+    remove: bool,
+
+    /// Allowlist file to check against.
+    allowlist: PathBuf,
+}
+
+/// List the enabled mods of a profile which depend on a mod.
+#[derive(Parser, Debug)]
+struct ActionProfileDependents {
+    /// Profile to search.
+    profile: String,
+
+    /// Mod to find the dependents of.
+    url: String,
+}
+
+/// Print the dependency graph of the enabled mods of a profile, including dependencies which are
+/// not part of the profile. Mods depending on each other in a cycle are marked.
+#[derive(Parser, Debug)]
+struct ActionProfileDeps {
+    /// Profile to print the dependencies of.
+    profile: String,
+
+    /// Output format of the graph.
+    #[arg(long, value_enum, default_value_t = DepsFormat::Dot)]
+    format: DepsFormat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum DepsFormat {
+    /// Graphviz graph, e.g. for `dot -Tsvg`.
+    Dot,
+    /// JSON object with `nodes`, `edges` and `cycles`.
+    Json,
+}
+
+/// Export a profile to an installer file which another mint can install with `mint profiles
+/// install`.
+///
+/// The installer only references mods by URL: whoever installs it downloads whatever those URLs
+/// point to. Only install installers from people you trust.
+#[derive(Parser, Debug)]
+struct ActionExportInstaller {
+    /// Pin every mod to the version it currently resolves to so the installer reproduces this
+    /// exact install even after mods are updated.
+    #[arg(short, long)]
+    pinned: bool,
+
+    /// File to write the installer to. Defaults to `<profile>.mint.json`, a name ending in `.toml`
+    /// writes TOML instead.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Profile to export.
+    profile: String,
+}
+
+/// Add a profile from an installer file created by `mint profiles export-installer` and integrate
+/// it.
+#[derive(Parser, Debug)]
+struct ActionInstallProfile {
+    /// Path to FSD-WindowsNoEditor.pak (FSD-WinGDK.pak for Microsoft Store version) located
+    /// inside the "Deep Rock Galactic" installation directory under FSD/Content/Paks. Only
+    /// necessary if it cannot be found automatically.
+    #[arg(short, long)]
+    fsd_pak: Option<PathBuf>,
+
+    /// Name of the profile to create. Defaults to the name the profile was exported with.
+    #[arg(short, long)]
+    name: Option<String>,
+
+    /// Installer file to install.
+    installer: PathBuf,
+}
+
+/// Create a profile from the mods configured in game, as stored in `ModIntegration.sav` in the
+/// game's `FSD/Saved/SaveGames` directory. The order and which mods are enabled are kept.
+#[derive(Parser, Debug)]
+struct ActionImportSave {
+    /// Name of the profile to create.
+    #[arg(short, long)]
+    profile: String,
+
+    /// Also mark the save as requesting a sync after importing it.
+    #[arg(long)]
+    request_sync: bool,
+
+    /// Save to import.
+    save: PathBuf,
+}
+
+/// Create a profile from a mod list exported by another mod manager.
+///
+/// Mods are looked up on mod.io by name. When several mods could be meant you are asked which one
+/// to use, or they are skipped if not running interactively.
+#[derive(Parser, Debug)]
+struct ActionImport {
+    /// Format of the mod list.
+    #[arg(short, long, value_enum)]
+    format: ImportFormat,
+
+    /// Name of the profile to create.
+    #[arg(short, long)]
+    name: String,
+
+    /// Mod list to import.
+    file: PathBuf,
+}
+
+/// Create a profile matching the mods a server advertises in the server list and optionally
+/// install it.
+///
+/// The mod list is the server's `Mods` session setting, either as JSON or as the string mint
+/// advertises. Mods are looked up on mod.io by name.
+#[derive(Parser, Debug)]
+struct ActionJoin {
+    /// Path to FSD-WindowsNoEditor.pak (FSD-WinGDK.pak for Microsoft Store version) located
+    /// inside the "Deep Rock Galactic" installation directory under FSD/Content/Paks. Only
+    /// necessary if it cannot be found automatically.
+    #[arg(short, long)]
+    fsd_pak: Option<PathBuf>,
+
+    /// Name of the profile to create. Defaults to the name of the mod list file.
+    #[arg(short, long)]
+    name: Option<String>,
+
+    /// Install the profile after creating it.
+    #[arg(short, long)]
+    install: bool,
+
+    /// Server mod list to match.
+    file: PathBuf,
+}
+
 /// Launch via steam
 #[derive(Parser, Debug)]
 struct ActionLaunch {
     args: Vec<String>,
 }
 
+/// List the files modified by more than one enabled mod of a profile. Much faster than a full
+/// lint.
+#[derive(Parser, Debug)]
+struct ActionConflicts {
+    /// Profile to check.
+    profile: String,
+}
+
+/// Check that a list of mod URLs resolve, e.g. to find dead links in a community mod list.
+/// Nothing is added to the cache. Fails if any link is broken.
+#[derive(Parser, Debug)]
+struct ActionCheckLinks {
+    /// File with one mod URL per line. Blank lines and lines starting with # are skipped.
+    file: PathBuf,
+}
+
+/// Download the mods of a profile into the cache without installing them, e.g. before going
+/// offline.
+#[derive(Parser, Debug)]
+struct ActionPrefetch {
+    /// Profile to fetch.
+    #[arg(required_unless_present = "mods")]
+    profile: Option<String>,
+
+    /// Fetch these mods instead of a profile. Accepts mod URLs and local paths.
+    #[arg(long, num_args = 1.., conflicts_with = "profile")]
+    mods: Vec<String>,
+
+    /// Check for and fetch updates instead of only fetching mods missing from the cache.
+    #[arg(short, long)]
+    update: bool,
+}
+
+/// List every available version of a mod, oldest first, e.g. to pin a mod to one of them.
+#[derive(Parser, Debug)]
+struct ActionVersions {
+    /// Mod URL or local path.
+    url: String,
+
+    /// Fetch the list of versions again instead of using the cached one.
+    #[arg(short, long)]
+    update: bool,
+}
+
 /// Lint the mod bundle that would be created for a profile.
 #[derive(Parser, Debug)]
 struct ActionLint {
@@ -72,15 +412,160 @@ struct ActionLint {
     fsd_pak: Option<PathBuf>,
 
     /// Profile to lint.
+    #[arg(required_unless_present = "mods")]
+    profile: Option<String>,
+
+    /// Lint these mods instead of a profile. Accepts mod URLs and local paths.
+    #[arg(long, num_args = 1.., conflicts_with = "profile")]
+    mods: Vec<String>,
+
+    /// Output format of the lint results.
+    #[arg(long, value_enum, default_value_t = LintFormat::Text)]
+    format: LintFormat,
+
+    /// Fail with exit code 3 if this lint has any findings. Can be repeated, `all` denies every
+    /// lint except advisory ones such as `untagged_gameplay`.
+    #[arg(long, value_name = "LINT")]
+    deny: Vec<String>,
+
+    /// Check every pak of archives containing multiple paks instead of only the first. Enabled by
+    /// default if installs are configured to use all paks.
+    #[arg(long)]
+    all_archive_paks: bool,
+
+    /// Lint every mod again instead of reusing cached results for mods which have not changed.
+    #[arg(long)]
+    no_cache: bool,
+
+    /// Only report findings which are not part of this baseline, and findings of it which were
+    /// fixed. `--deny` only fails on new findings.
+    #[arg(long, value_name = "PATH")]
+    baseline: Option<PathBuf>,
+
+    /// Write the current findings to the `--baseline` file, accepting them for future runs.
+    #[arg(long, requires = "baseline")]
+    write_baseline: bool,
+}
+
+/// Uninstall mods. With --mods only those are removed by installing the profile again without
+/// them, the profile itself is left unchanged.
+#[derive(Parser, Debug)]
+struct ActionUninstall {
+    /// Path to FSD-WindowsNoEditor.pak (FSD-WinGDK.pak for Microsoft Store version) located
+    /// inside the "Deep Rock Galactic" installation directory under FSD/Content/Paks. Only
+    /// necessary if it cannot be found automatically.
+    #[arg(short, long)]
+    fsd_pak: Option<PathBuf>,
+
+    /// Profile which is installed. Defaults to the active profile.
+    #[arg(short, long)]
+    profile: Option<String>,
+
+    /// Only remove these mods from the installed mods. Accepts mod URLs and local paths.
+    #[arg(long, num_args = 1..)]
+    mods: Vec<String>,
+}
+
+/// Write a profile and the groups it uses to an installer to share it, e.g. with a friend who
+/// imports it with `mint import-profile`.
+#[derive(Parser, Debug)]
+struct ActionExportProfile {
+    /// File to write. Defaults to <profile>.profile.json in the current directory.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Profile to export.
+    profile: String,
+}
+
+/// Add a profile from an installer, e.g. one written by `mint export-profile`. Groups which already
+/// exist with different mods are imported under a new name.
+#[derive(Parser, Debug)]
+struct ActionImportProfile {
+    /// Name of the profile to create. Defaults to the name the profile was exported with, with a
+    /// number appended if a profile of that name exists.
+    #[arg(short, long)]
+    name: Option<String>,
+
+    /// File to import.
+    file: PathBuf,
+}
+
+/// Find the mod of a profile which makes the game crash. Half of the remaining suspects are
+/// installed at a time and you are asked whether the game still crashes until one mod is left.
+/// Progress is saved after every answer so an interrupted bisection continues where it left off.
+#[derive(Parser, Debug)]
+struct ActionBisect {
+    /// Path to FSD-WindowsNoEditor.pak (FSD-WinGDK.pak for Microsoft Store version) located
+    /// inside the "Deep Rock Galactic" installation directory under FSD/Content/Paks. Only
+    /// necessary if it cannot be found automatically.
+    #[arg(short, long)]
+    fsd_pak: Option<PathBuf>,
+
+    /// Discard a saved bisection and start over.
+    #[arg(long)]
+    restart: bool,
+
+    /// Profile to bisect.
     profile: String,
 }
 
+/// Check the game installation for problems which keep mods from loading.
+#[derive(Parser, Debug)]
+struct ActionDoctor {
+    /// Path to FSD-WindowsNoEditor.pak (FSD-WinGDK.pak for Microsoft Store version) located
+    /// inside the "Deep Rock Galactic" installation directory under FSD/Content/Paks. Only
+    /// necessary if it cannot be found automatically.
+    #[arg(short, long)]
+    fsd_pak: Option<PathBuf>,
+
+    /// Deploy the hook DLL bundled with this version of mint again.
+    #[arg(long)]
+    reinstall_hook: bool,
+}
+
+/// Deploy the hook DLL bundled with this version of mint again without touching the installed
+/// mods, e.g. after an antivirus or a game update removed it and mods stopped loading.
+#[derive(Parser, Debug)]
+struct ActionInstallHook {
+    /// Path to FSD-WindowsNoEditor.pak (FSD-WinGDK.pak for Microsoft Store version) located
+    /// inside the "Deep Rock Galactic" installation directory under FSD/Content/Paks. Only
+    /// necessary if it cannot be found automatically.
+    #[arg(short, long)]
+    fsd_pak: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum LintFormat {
+    /// Full lint report followed by a summary.
+    Text,
+    /// Summary as a JSON object.
+    Json,
+}
+
+/// Exit code of `mint lint` when a denied lint has findings.
+const LINT_DENIED_EXIT_CODE: i32 = 3;
+
 #[derive(Subcommand, Debug)]
 enum Action {
     Integrate(ActionIntegrate),
     Profile(ActionIntegrateProfile),
+    Profiles(ActionProfiles),
+    Import(ActionImport),
+    ImportSave(ActionImportSave),
+    Join(ActionJoin),
     Launch(ActionLaunch),
     Lint(ActionLint),
+    Conflicts(ActionConflicts),
+    CheckLinks(ActionCheckLinks),
+    Prefetch(ActionPrefetch),
+    Versions(ActionVersions),
+    Doctor(ActionDoctor),
+    InstallHook(ActionInstallHook),
+    Uninstall(ActionUninstall),
+    Bisect(ActionBisect),
+    ExportProfile(ActionExportProfile),
+    ImportProfile(ActionImportProfile),
 }
 
 #[derive(Parser, Debug)]
@@ -92,6 +577,11 @@ struct Args {
     /// Location to store configs and data
     #[arg(long)]
     appdata: Option<PathBuf>,
+
+    /// Use the game pak without checking that it is DRG's, for pre-modified game paks or total
+    /// conversions. Can also be enabled with the skip_pak_validation config in the GUI settings.
+    #[arg(long, global = true)]
+    skip_pak_validation: bool,
 }
 
 fn main() -> Result<()> {
@@ -125,13 +615,52 @@ fn main() -> Result<()> {
 
     debug!(?args);
 
+    let skip_pak_validation = args.skip_pak_validation;
+
     match args.action {
         Some(Action::Integrate(action)) => rt.block_on(async {
-            action_integrate(dirs, action).await?;
+            action_integrate(dirs, action, skip_pak_validation).await?;
             Ok(())
         }),
         Some(Action::Profile(action)) => rt.block_on(async {
-            action_integrate_profile(dirs, action).await?;
+            action_integrate_profile(dirs, action, skip_pak_validation).await?;
+            Ok(())
+        }),
+        Some(Action::Profiles(action)) => rt.block_on(async {
+            match action.command {
+                ProfileCommand::ExportInstaller(action) => {
+                    action_export_installer(dirs, action).await?
+                }
+                ProfileCommand::Install(action) => {
+                    action_install_profile(dirs, action, skip_pak_validation).await?
+                }
+                ProfileCommand::Dependents(action) => {
+                    action_profile_dependents(dirs, action).await?
+                }
+                ProfileCommand::Deps(action) => action_profile_deps(dirs, action).await?,
+                ProfileCommand::EnforceAllowlist(action) => {
+                    action_enforce_allowlist(dirs, action).await?
+                }
+                ProfileCommand::CheckIncompatible(action) => {
+                    action_check_incompatible(dirs, action).await?
+                }
+                ProfileCommand::Snapshot(action) => action_snapshot(dirs, action).await?,
+                ProfileCommand::RestoreSnapshot(action) => {
+                    action_restore_snapshot(dirs, action, skip_pak_validation).await?
+                }
+                ProfileCommand::Freeze(action) => action_freeze(dirs, action).await?,
+                ProfileCommand::Thaw(action) => action_thaw(dirs, action)?,
+                ProfileCommand::Check(action) => action_profile_check(dirs, action).await?,
+            }
+            Ok(())
+        }),
+        Some(Action::Import(action)) => rt.block_on(async {
+            action_import(dirs, action).await?;
+            Ok(())
+        }),
+        Some(Action::ImportSave(action)) => action_import_save(dirs, action),
+        Some(Action::Join(action)) => rt.block_on(async {
+            action_join(dirs, action, skip_pak_validation).await?;
             Ok(())
         }),
         Some(Action::Launch(action)) => {
@@ -141,10 +670,42 @@ fn main() -> Result<()> {
             gui(dirs, Some(action.args))?;
             Ok(())
         }
-        Some(Action::Lint(action)) => rt.block_on(async {
-            action_lint(dirs, action).await?;
+        Some(Action::Lint(action)) => {
+            let passed = rt.block_on(action_lint(dirs, action, skip_pak_validation))?;
+            if !passed {
+                drop(_guard);
+                std::process::exit(LINT_DENIED_EXIT_CODE);
+            }
+            Ok(())
+        }
+        Some(Action::Conflicts(action)) => rt.block_on(async {
+            action_conflicts(dirs, action).await?;
+            Ok(())
+        }),
+        Some(Action::CheckLinks(action)) => rt.block_on(async {
+            action_check_links(dirs, action).await?;
+            Ok(())
+        }),
+        Some(Action::Prefetch(action)) => rt.block_on(async {
+            action_prefetch(dirs, action).await?;
+            Ok(())
+        }),
+        Some(Action::Versions(action)) => rt.block_on(async {
+            action_versions(dirs, action).await?;
             Ok(())
         }),
+        Some(Action::Doctor(action)) => action_doctor(dirs, action, skip_pak_validation),
+        Some(Action::InstallHook(action)) => action_install_hook(dirs, action, skip_pak_validation),
+        Some(Action::Uninstall(action)) => rt.block_on(async {
+            action_uninstall(dirs, action, skip_pak_validation).await?;
+            Ok(())
+        }),
+        Some(Action::Bisect(action)) => rt.block_on(async {
+            action_bisect(dirs, action, skip_pak_validation).await?;
+            Ok(())
+        }),
+        Some(Action::ExportProfile(action)) => action_export_profile(dirs, action),
+        Some(Action::ImportProfile(action)) => action_import_profile(dirs, action),
         None => {
             std::thread::spawn(move || {
                 rt.block_on(std::future::pending::<()>());
@@ -182,70 +743,1403 @@ fn init_provider(
     Ok(state.store.add_provider(factory, params)?)
 }
 
-fn get_pak_path(state: &State, arg: &Option<PathBuf>) -> Result<PathBuf> {
-    arg.as_ref()
-        .or_else(|| state.config.drg_pak_path.as_ref())
-        .cloned()
-        .context("Could not find DRG pak file, please specify manually with the --fsd_pak flag")
+/// Environment variable supplying the game pak path for scripted use without a persisted config.
+const FSD_PAK_ENV: &str = "MINT_FSD_PAK";
+
+/// Check that `path` is DRG's pak, unless disabled in the config or for this run with
+/// `--skip-pak-validation`, which is not saved to the config.
+fn validate_pak(state: &State, path: &Path, skip_pak_validation: bool) -> Result<(), MintError> {
+    validate_drg_pak(
+        path,
+        state.config.skip_pak_validation || skip_pak_validation,
+    )
+}
+
+/// The game pak, in order of precedence from the `--fsd-pak` flag, the [`FSD_PAK_ENV`]
+/// environment variable, the config or an automatically detected installation.
+fn get_pak_path(
+    state: &State,
+    arg: &Option<PathBuf>,
+    skip_pak_validation: bool,
+) -> Result<PathBuf> {
+    if let Some(path) = arg {
+        return Ok(path.clone());
+    }
+    if let Some(path) = std::env::var_os(FSD_PAK_ENV).filter(|p| !p.is_empty()) {
+        let path = PathBuf::from(path);
+        validate_pak(state, &path, skip_pak_validation).with_context(|| {
+            format!(
+                "{FSD_PAK_ENV} is set to {} which is not a DRG pak",
+                path.display()
+            )
+        })?;
+        return Ok(path);
+    }
+    state
+        .config
+        .drg_pak_path
+        .clone()
+        .or_else(|| DRGInstallation::find().as_ref().map(DRGInstallation::main_pak))
+        .with_context(|| {
+            format!("Could not find DRG pak file, please specify manually with the --fsd_pak flag or the {FSD_PAK_ENV} environment variable")
+        })
 }
 
-async fn action_integrate(dirs: Dirs, action: ActionIntegrate) -> Result<()> {
+async fn action_integrate(
+    dirs: Dirs,
+    action: ActionIntegrate,
+    skip_pak_validation: bool,
+) -> Result<()> {
     let mut state = State::init(dirs)?;
-    let game_pak_path = get_pak_path(&state, &action.fsd_pak)?;
+    // a dry run does not touch the game so it also works where the game is not installed, but a
+    // game pak which was given explicitly still has to be one
+    let pak_given =
+        action.fsd_pak.is_some() || std::env::var_os(FSD_PAK_ENV).is_some_and(|p| !p.is_empty());
+    let game_pak_path = match get_pak_path(&state, &action.fsd_pak, skip_pak_validation) {
+        Err(_) if action.dry_run && !pak_given => PathBuf::new(),
+        path => path?,
+    };
+    if action.dry_run
+        && let Some(path) = &action.fsd_pak
+    {
+        validate_pak(&state, path, skip_pak_validation)
+            .with_context(|| format!("{} is not a DRG pak", path.display()))?;
+    }
     debug!(?game_pak_path);
 
-    let mod_specs = action
+    let mut mod_specs = action
         .mods
-        .into_iter()
-        .map(ModSpecification::new)
+        .iter()
+        .map(|m| parse_spec(m))
         .collect::<Vec<_>>();
 
-    resolve_unordered_and_integrate_with_provider_init(
-        game_pak_path,
+    if action.from_clipboard {
+        let text = arboard::Clipboard::new()
+            .and_then(|mut clipboard| clipboard.get_text())
+            .context("failed to read mods from clipboard, is a clipboard available?")?;
+        let clipboard_specs = parse_mods(&text);
+        if clipboard_specs.is_empty() {
+            bail!("clipboard does not contain any mods");
+        }
+        mod_specs.extend(clipboard_specs);
+    }
+
+    if action.dry_run {
+        return dry_run_specs(&mut state, game_pak_path, &mod_specs, action.update).await;
+    }
+    integrate_specs(
         &mut state,
+        game_pak_path,
+        None,
         &mod_specs,
         action.update,
-        init_provider,
+        action.verbose_integrate,
+        action.export,
     )
     .await
-    .map_err(|e| anyhow!("{}", e))
 }
 
-async fn action_integrate_profile(dirs: Dirs, action: ActionIntegrateProfile) -> Result<()> {
-    let mut state = State::init(dirs)?;
-    let game_pak_path = get_pak_path(&state, &action.fsd_pak)?;
-    debug!(?game_pak_path);
+/// Resolve and fetch `mod_specs` like [`integrate_specs`] and print what installing them would
+/// write instead of writing it.
+async fn dry_run_specs(
+    state: &mut State,
+    game_pak_path: PathBuf,
+    mod_specs: &[ModSpecification],
+    update: bool,
+) -> Result<()> {
+    let (progress_tx, progress) = cli_fetch_progress();
+    let integrated = resolve_unordered_and_integrate_with_provider_init(
+        &game_pak_path,
+        state,
+        mod_specs,
+        &[],
+        update,
+        true,
+        Some(progress_tx),
+        init_provider,
+    )
+    .await
+    .map_err(|e| anyhow!("{}", e))?;
+    progress.await?;
+    let Integrated::DryRun { mods, layout } = integrated else {
+        unreachable!("a dry run does not install anything");
+    };
 
-    let mut mods = Vec::new();
-    state.mod_data.for_each_enabled_mod(&action.profile, |mc| {
-        mods.push(mc.spec.clone());
-    });
+    let mib = |bytes: u64| bytes as f64 / (1024. * 1024.);
+    let mut total = 0;
+    println!("mods:");
+    for (info, path) in &mods {
+        let bytes = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        total += bytes;
+        println!(
+            "  {}  {} ({:.1} MiB)",
+            info.name,
+            info.resolution.url.0,
+            mib(bytes)
+        );
+    }
+    println!("total download size: {:.1} MiB", mib(total));
 
-    resolve_unordered_and_integrate_with_provider_init(
-        game_pak_path,
+    let name = |spec: &ModSpecification| {
+        mods.iter()
+            .find(|(info, _)| info.spec == *spec)
+            .map_or_else(|| spec.url.clone(), |(info, _)| info.name.clone())
+    };
+    println!("mods pak layout ({} files):", layout.file_count());
+    for (path, leaf) in layout.paths() {
+        if leaf.overridden.is_empty() {
+            println!("  {path}  {}", name(&leaf.winner));
+        } else {
+            let overridden = leaf.overridden.iter().map(name).collect::<Vec<_>>();
+            println!(
+                "  {path}  {} (overrides {})",
+                name(&leaf.winner),
+                overridden.join(", ")
+            );
+        }
+    }
+    Ok(())
+}
+
+async fn action_integrate_profile(
+    dirs: Dirs,
+    action: ActionIntegrateProfile,
+    skip_pak_validation: bool,
+) -> Result<()> {
+    let profile = action.profile;
+
+    let mut state = State::init(dirs)?;
+    let game_pak_path = get_pak_path(&state, &action.fsd_pak, skip_pak_validation)?;
+    debug!(?game_pak_path);
+
+    let mods = state.integration_order(&profile);
+
+    integrate_specs(
         &mut state,
+        game_pak_path,
+        Some(&profile),
         &mods,
         action.update,
-        init_provider,
+        action.verbose_integrate,
+        action.export,
     )
     .await
-    .map_err(|e| anyhow!("{}", e))
 }
 
-async fn action_lint(dirs: Dirs, action: ActionLint) -> Result<()> {
+async fn action_export_installer(dirs: Dirs, action: ActionExportInstaller) -> Result<()> {
     let mut state = State::init(dirs)?;
-    let game_pak_path = get_pak_path(&state, &action.fsd_pak)?;
-    debug!(?game_pak_path);
 
+    let mut modpack = Modpack::from_profile(&state.mod_data, &action.profile)?;
+
+    // make sure everything resolves so the installer is usable on the other end
+    let resolved = resolve_with_provider_init(&mut state, &modpack.specs(), init_provider)
+        .await
+        .map_err(|e| anyhow!("{}", e))?;
+
+    for m in &modpack.mods {
+        if resolved
+            .get(&m.spec)
+            .is_some_and(|info| info.provider == "file")
+        {
+            warn!(
+                "{} is a local file and will not be available to whoever installs this profile",
+                m.spec.url
+            );
+        }
+    }
+
+    if action.pinned {
+        modpack.pin(&resolved);
+    }
+
+    let output = action
+        .output
+        .unwrap_or_else(|| PathBuf::from(format!("{}.mint.json", action.profile)));
+    modpack.write(&output)?;
+    info!(
+        "exported profile {:?} to {}",
+        action.profile,
+        output.display()
+    );
+
+    Ok(())
+}
+
+async fn action_snapshot(dirs: Dirs, action: ActionSnapshot) -> Result<()> {
+    let mut state = State::init(dirs)?;
+
+    let mut modpack = Modpack::from_profile(&state.mod_data, &action.profile)?;
+    let specs = modpack.specs();
+    let paths = prefetch_with_provider_init(&mut state, &specs, false, None, init_provider)
+        .await
+        .map_err(|e| anyhow!("{}", e))?;
+    let resolved = resolve_with_provider_init(&mut state, &specs, init_provider)
+        .await
+        .map_err(|e| anyhow!("{}", e))?;
+    modpack.pin(&resolved);
+
+    let mods = specs
+        .iter()
+        .map(|spec| resolved[spec].name.clone())
+        .zip(paths)
+        .collect::<Vec<_>>();
+    let snapshot = Snapshot::write(&action.output, modpack, &mods)?;
+    info!(
+        "archived {} mods of profile {:?} to {}",
+        snapshot.blobs.len(),
+        action.profile,
+        action.output.display()
+    );
+
+    Ok(())
+}
+
+async fn action_restore_snapshot(
+    dirs: Dirs,
+    action: ActionRestoreSnapshot,
+    skip_pak_validation: bool,
+) -> Result<()> {
+    let mut state = State::init(dirs)?;
+
+    // extract next to the final location first as the profile name is only known from the manifest
+    let snapshots_dir = state.dirs.data_dir.join("snapshots");
+    std::fs::create_dir_all(&snapshots_dir)?;
+    let extracted = tempfile::tempdir_in(&snapshots_dir)?;
+    let snapshot = Snapshot::restore(&action.snapshot, extracted.path())?;
+    let name = action.name.unwrap_or_else(|| snapshot.modpack.name.clone());
+    if state.mod_data.profiles.contains_key(&name) {
+        bail!("profile {name:?} already exists, choose a different name with --name");
+    }
+    // left over from a profile of the same name which was deleted
+    let mods_dir = snapshots_dir.join(&name);
+    if mods_dir.exists() {
+        std::fs::remove_dir_all(&mods_dir)?;
+    }
+    std::fs::rename(extracted.into_path(), &mods_dir)?;
+
+    let mod_configs = snapshot.to_mod_configs(&mods_dir)?;
+    let mod_count = mod_configs.len();
+    let specs = mod_configs
+        .iter()
+        .filter(|mc| mc.enabled)
+        .map(|mc| mc.spec.clone())
+        .collect::<Vec<_>>();
+    state.mod_data.profiles.insert(
+        name.clone(),
+        ModProfile {
+            mods: mod_configs
+                .into_iter()
+                .map(ModOrGroup::Individual)
+                .collect(),
+            description: snapshot.modpack.description.clone(),
+            ..Default::default()
+        },
+    );
+    state.mod_data.save()?;
+    info!("restored profile {name:?} with {mod_count} mods");
+
+    if action.install {
+        let game_pak_path = get_pak_path(&state, &action.fsd_pak, skip_pak_validation)?;
+        debug!(?game_pak_path);
+        integrate_specs(
+            &mut state,
+            game_pak_path,
+            Some(&name),
+            &specs,
+            false,
+            false,
+            None,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+async fn action_freeze(dirs: Dirs, action: ActionFreeze) -> Result<()> {
+    let mut state = State::init(dirs)?;
+    if !state.mod_data.profiles.contains_key(&action.profile) {
+        bail!("profile {:?} does not exist", action.profile);
+    }
+
+    let mut mods = Vec::new();
+    state.mod_data.for_each_enabled_mod(&action.profile, |mc| {
+        mods.push(mc.spec.clone());
+    });
+    let resolved = resolve_with_provider_init(&mut state, &mods, init_provider)
+        .await
+        .map_err(|e| anyhow!("{}", e))?;
+
+    let frozen = state.mod_data.freeze_profile(&action.profile, &resolved);
+    state.mod_data.save()?;
+    info!("froze {frozen} mods of profile {:?}", action.profile);
+    Ok(())
+}
+
+fn action_thaw(dirs: Dirs, action: ActionThaw) -> Result<()> {
+    let mut state = State::init(dirs)?;
+    if !state.mod_data.profiles.contains_key(&action.profile) {
+        bail!("profile {:?} does not exist", action.profile);
+    }
+
+    let thawed = state.mod_data.thaw_profile(&action.profile);
+    state.mod_data.save()?;
+    info!("thawed {thawed} mods of profile {:?}", action.profile);
+    Ok(())
+}
+
+async fn action_profile_check(dirs: Dirs, action: ActionProfileCheck) -> Result<()> {
+    let mut state = State::init(dirs)?;
+    if !state.mod_data.profiles.contains_key(&action.profile) {
+        bail!("profile {:?} does not exist", action.profile);
+    }
+
+    // disabled mods are checked too so they can be replaced before they are enabled again
     let mut mods = Vec::new();
     state.mod_data.for_each_mod(&action.profile, |mc| {
+        if !mods.contains(&mc.spec) {
+            mods.push(mc.spec.clone());
+        }
+    });
+
+    let reports = check_availability_with_provider_init(&mut state, &mods, init_provider).await?;
+    let mut unavailable = 0;
+    for report in &reports {
+        match &report.result {
+            Ok(name) => println!("{:<14}{} ({name})", "ok", report.spec.url),
+            Err((problem, e)) => {
+                unavailable += 1;
+                println!("{:<14}{}: {e}", problem.to_string(), report.spec.url);
+            }
+        }
+    }
+
+    if unavailable > 0 {
+        bail!(
+            "{unavailable} of {} mods of profile {:?} are unavailable",
+            reports.len(),
+            action.profile
+        );
+    }
+    println!("all {} mods are available", reports.len());
+    Ok(())
+}
+
+async fn action_profile_dependents(dirs: Dirs, action: ActionProfileDependents) -> Result<()> {
+    let mut state = State::init(dirs)?;
+    if !state.mod_data.profiles.contains_key(&action.profile) {
+        bail!("profile {:?} does not exist", action.profile);
+    }
+
+    let mut mods = Vec::new();
+    state.mod_data.for_each_enabled_mod(&action.profile, |mc| {
         mods.push(mc.spec.clone());
     });
+    let resolved = resolve_with_provider_init(&mut state, &mods, init_provider)
+        .await
+        .map_err(|e| anyhow!("{}", e))?;
+
+    let spec = parse_spec(&action.url);
+    let dependents = dependents(mods.iter().filter_map(|m| resolved.get(m)), &spec);
+    if dependents.is_empty() {
+        println!(
+            "no enabled mods of {:?} depend on {}",
+            action.profile, spec.url
+        );
+    }
+    for info in dependents {
+        println!("{} ({})", info.name, info.spec.url);
+    }
+    Ok(())
+}
+
+async fn action_profile_deps(dirs: Dirs, action: ActionProfileDeps) -> Result<()> {
+    let mut state = State::init(dirs)?;
+    if !state.mod_data.profiles.contains_key(&action.profile) {
+        bail!("profile {:?} does not exist", action.profile);
+    }
+
+    let mut mods = Vec::new();
+    state.mod_data.for_each_enabled_mod(&action.profile, |mc| {
+        mods.push(mc.spec.clone());
+    });
+    let resolved = resolve_with_provider_init(&mut state, &mods, init_provider)
+        .await
+        .map_err(|e| anyhow!("{}", e))?;
+
+    let graph = DepGraph::new(&mods, &resolved);
+    for cycle in &graph.cycles {
+        let names = cycle
+            .iter()
+            .map(|i| graph.nodes[*i].name.as_str())
+            .collect::<Vec<_>>();
+        warn!("dependency cycle between {}", names.join(", "));
+    }
+    match action.format {
+        DepsFormat::Dot => print!("{}", graph.to_dot()),
+        DepsFormat::Json => println!("{}", serde_json::to_string_pretty(&graph)?),
+    }
+    Ok(())
+}
+
+async fn action_enforce_allowlist(dirs: Dirs, action: ActionEnforceAllowlist) -> Result<()> {
+    let mut state = State::init(dirs)?;
+    let profile = action
+        .profile
+        .unwrap_or_else(|| state.mod_data.active_profile.clone());
+    if !state.mod_data.profiles.contains_key(&profile) {
+        bail!("profile {:?} does not exist", profile);
+    }
+    let allowlist = Allowlist::read(&action.allowlist)?;
+
+    let mut mods = Vec::new();
+    state.mod_data.for_each_enabled_mod(&profile, |mc| {
+        mods.push(mc.spec.clone());
+    });
+    let resolved = resolve_with_provider_init(&mut state, &mods, init_provider)
+        .await
+        .map_err(|e| anyhow!("{}", e))?;
+
+    let disallowed = mods
+        .into_iter()
+        .filter(|m| !allowlist.allows(m, resolved.get(m).and_then(|info| info.modio_id)))
+        .collect::<Vec<_>>();
+    if disallowed.is_empty() {
+        println!("all enabled mods of {profile:?} are on the allowlist");
+        return Ok(());
+    }
+
+    println!("mods of {profile:?} not on the allowlist:");
+    for spec in &disallowed {
+        match resolved.get(spec) {
+            Some(info) => println!("  {} ({})", info.name, spec.url),
+            None => println!("  {}", spec.url),
+        }
+    }
+    if action.remove {
+        state
+            .mod_data
+            .remove_mods(&profile, |mc| disallowed.contains(&mc.spec));
+        state.mod_data.save()?;
+        info!(
+            "removed {} disallowed mods from {:?}",
+            disallowed.len(),
+            profile
+        );
+    } else {
+        println!("run again with --remove to remove them from the profile");
+    }
+    Ok(())
+}
+
+async fn action_check_incompatible(dirs: Dirs, action: ActionCheckIncompatible) -> Result<()> {
+    let mut state = State::init(dirs)?;
+    let profile = action
+        .profile
+        .unwrap_or_else(|| state.mod_data.active_profile.clone());
+    if !state.mod_data.profiles.contains_key(&profile) {
+        bail!("profile {:?} does not exist", profile);
+    }
+    let incompatibilities = Incompatibilities::load(&state.dirs.config_dir)?;
+    if incompatibilities.is_empty() {
+        println!(
+            "no known incompatible mods listed in {}",
+            state.dirs.config_dir.join(INCOMPATIBLE_MODS_FILE).display()
+        );
+        return Ok(());
+    }
+
+    let mut mods = Vec::new();
+    state.mod_data.for_each_enabled_mod(&profile, |mc| {
+        mods.push(mc.spec.clone());
+    });
+    let resolved = resolve_with_provider_init(&mut state, &mods, init_provider)
+        .await
+        .map_err(|e| anyhow!("{}", e))?;
+
+    let found = incompatibilities.check(
+        mods.iter()
+            .map(|m| (m, resolved.get(m).and_then(|info| info.modio_id))),
+    );
+    if found.is_empty() {
+        println!("no known incompatible mods are enabled in {profile:?}");
+        return Ok(());
+    }
+    for incompatibility in &found {
+        println!("  {}", incompatibility.describe(&state.store));
+    }
+    bail!(
+        "{:?} enables {} known incompatible mod combinations",
+        profile,
+        found.len()
+    );
+}
+
+async fn action_install_profile(
+    dirs: Dirs,
+    action: ActionInstallProfile,
+    skip_pak_validation: bool,
+) -> Result<()> {
+    let mut state = State::init(dirs)?;
+    let game_pak_path = get_pak_path(&state, &action.fsd_pak, skip_pak_validation)?;
+    debug!(?game_pak_path);
+
+    let modpack = Modpack::read(&action.installer)?;
+    let name = action.name.unwrap_or_else(|| modpack.name.clone());
+    if state.mod_data.profiles.contains_key(&name) {
+        bail!("profile {name:?} already exists, choose a different name with --name");
+    }
+
+    state.mod_data.profiles.insert(
+        name.clone(),
+        ModProfile {
+            mods: modpack
+                .to_mod_configs()
+                .into_iter()
+                .map(ModOrGroup::Individual)
+                .collect(),
+            description: modpack.description.clone(),
+            ..Default::default()
+        },
+    );
+    state.mod_data.save()?;
+    info!("added profile {name:?}");
+
+    integrate_specs(
+        &mut state,
+        game_pak_path,
+        Some(&name),
+        &modpack.specs(),
+        false,
+        false,
+        None,
+    )
+    .await
+}
+
+async fn action_import(dirs: Dirs, action: ActionImport) -> Result<()> {
+    let mut state = State::init(dirs)?;
+    let entries = import::read(action.format, &action.file)?;
+    import_profile(&mut state, &action.name, &entries).await?;
+    Ok(())
+}
+
+fn action_import_save(dirs: Dirs, action: ActionImportSave) -> Result<()> {
+    let mut state = State::init(dirs)?;
+    if state.mod_data.profiles.contains_key(&action.profile) {
+        bail!("profile {:?} already exists", action.profile);
+    }
+
+    let mut save = ModIntegrationSave::read(&action.save)
+        .with_context(|| format!("failed to import {}", action.save.display()))?;
+    if save.config.mods.is_empty() {
+        warn!("save does not contain any mods");
+    }
+    let mods = save
+        .config
+        .mods
+        .iter()
+        .map(|m| {
+            ModOrGroup::Individual(ModConfig {
+                spec: parse_spec(&m.url),
+                required: m.required,
+                enabled: m.enabled,
+                priority: 0,
+                display_name: None,
+                added: Some(unix_time_now()),
+                frozen_from: None,
+                priority_overrides: vec![],
+            })
+        })
+        .collect::<Vec<_>>();
+    info!(
+        "imported {} mods into profile {:?}",
+        mods.len(),
+        action.profile
+    );
+    state.mod_data.profiles.insert(
+        action.profile.clone(),
+        ModProfile {
+            mods,
+            ..Default::default()
+        },
+    );
+    state.mod_data.save()?;
+
+    if action.request_sync {
+        save.config.request_sync = true;
+        save.write(&action.save)?;
+        info!("requested a sync in {}", action.save.display());
+    }
+    Ok(())
+}
+
+async fn action_join(dirs: Dirs, action: ActionJoin, skip_pak_validation: bool) -> Result<()> {
+    let mut state = State::init(dirs)?;
+    let name = match action.name {
+        Some(name) => name,
+        None => action
+            .file
+            .file_stem()
+            .context("mod list file has no name, choose a profile name with --name")?
+            .to_string_lossy()
+            .to_string(),
+    };
+
+    let entries = import::read(ImportFormat::ServerList, &action.file)?;
+    if entries.is_empty() {
+        bail!("server does not advertise any mods");
+    }
+    let mods = import_profile(&mut state, &name, &entries).await?;
+
+    if action.install {
+        let game_pak_path = get_pak_path(&state, &action.fsd_pak, skip_pak_validation)?;
+        debug!(?game_pak_path);
+        integrate_specs(
+            &mut state,
+            game_pak_path,
+            Some(&name),
+            &mods,
+            false,
+            false,
+            None,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Create profile `name` from mods of an external list, warning about any which could not be
+/// matched. Returns the mods added to the profile.
+async fn import_profile(
+    state: &mut State,
+    name: &str,
+    entries: &[import::ExternalMod],
+) -> Result<Vec<ModSpecification>> {
+    if state.mod_data.profiles.contains_key(name) {
+        bail!("profile {name:?} already exists, choose a different name with --name");
+    }
+
+    let modio = ModStore::get_provider_factories()
+        .find(|f| f.id == "modio")
+        .context("mod.io provider is not available")?;
+    if !state.store.has_provider(modio.id) {
+        init_provider(state, "https://mod.io".to_string(), modio)?;
+    }
+
+    let matches = import::match_mods(&state.store, entries).await?;
+
+    let interactive = std::io::stdin().is_terminal();
+    let mut mods = vec![];
+    let mut unmatched = vec![];
+    for (entry, m) in entries.iter().zip(matches) {
+        match m {
+            ImportMatch::Matched(m) => mods.push(m.spec),
+            ImportMatch::Ambiguous(candidates) if interactive => {
+                let items = candidates
+                    .iter()
+                    .map(|c| match &c.modio_stats {
+                        Some(stats) => format!("{} <{}> ({stats})", c.name, c.spec.url),
+                        None => format!("{} <{}>", c.name, c.spec.url),
+                    })
+                    .chain(["skip".to_string()])
+                    .collect::<Vec<_>>();
+                let selection =
+                    dialoguer::Select::with_theme(&dialoguer::theme::ColorfulTheme::default())
+                        .with_prompt(format!("Which mod is {:?}?", entry.name))
+                        .items(&items)
+                        .default(0)
+                        .interact()?;
+                match candidates.into_iter().nth(selection) {
+                    Some(c) => mods.push(c.spec),
+                    None => unmatched.push(entry),
+                }
+            }
+            ImportMatch::Ambiguous(candidates) => {
+                warn!("skipping {:?}, it could be any of:", entry.name);
+                for c in candidates {
+                    warn!("  {} <{}>", c.name, c.spec.url);
+                }
+                unmatched.push(entry);
+            }
+            ImportMatch::Unmatched => unmatched.push(entry),
+        }
+    }
+
+    state.mod_data.profiles.insert(
+        name.to_string(),
+        ModProfile {
+            mods: mods
+                .iter()
+                .cloned()
+                .map(|spec| {
+                    ModOrGroup::Individual(ModConfig {
+                        spec,
+                        required: false,
+                        enabled: true,
+                        priority: 0,
+                        display_name: None,
+                        added: Some(unix_time_now()),
+                        frozen_from: None,
+                        priority_overrides: vec![],
+                    })
+                })
+                .collect(),
+            ..Default::default()
+        },
+    );
+    state.mod_data.save()?;
+    info!(
+        "imported {} of {} mods into profile {:?}",
+        entries.len() - unmatched.len(),
+        entries.len(),
+        name
+    );
+
+    if !unmatched.is_empty() {
+        warn!("the following mods could not be matched:");
+        for entry in unmatched {
+            warn!("  {}", entry.name);
+        }
+    }
+
+    Ok(mods)
+}
+
+/// Install `mod_specs`, skipping resolution if nothing changed since the last install and every
+/// mod is already cached. `profile` is only used for the integrate hooks. `export` overrides the
+/// configured path the mods pak is copied to.
+async fn integrate_specs(
+    state: &mut State,
+    game_pak_path: PathBuf,
+    profile: Option<&str>,
+    mod_specs: &[ModSpecification],
+    update: bool,
+    verbose_integrate: bool,
+    export: Option<PathBuf>,
+) -> Result<()> {
+    // only for this install, the flags should not end up in the saved config
+    let persisted_verbose_integrate = state.config.verbose_integrate;
+    state.config.verbose_integrate |= verbose_integrate;
+    let persisted_export_pak_path = state.config.export_pak_path.clone();
+    if export.is_some() {
+        state.config.export_pak_path = export;
+    }
+
+    match Incompatibilities::load(&state.dirs.config_dir) {
+        Ok(incompatibilities) => {
+            for found in incompatibilities.check_specs(&state.store, mod_specs) {
+                warn!(
+                    "installing known incompatible mods: {}",
+                    found.describe(&state.store)
+                );
+            }
+        }
+        Err(e) => warn!("failed to read known incompatible mods: {e}"),
+    }
+
+    match DRGInstallation::from_pak_path(&game_pak_path).map(|i| find_other_paks(&i)) {
+        Ok(Ok(paks)) => {
+            for pak in paks {
+                warn!("other pak in the game's Paks directory: {pak}");
+            }
+        }
+        Ok(Err(e)) => warn!("failed to look for other paks: {e}"),
+        Err(_) => {}
+    }
+
+    let hooks = state.config.integrate_hooks(profile.unwrap_or_default());
+    hooks
+        .run_pre(&game_pak_path)
+        .map_err(|e| anyhow!("{}", e))?;
+
+    let priorities = match profile {
+        Some(profile) => state.mod_priorities(profile, mod_specs)?,
+        None => vec![],
+    };
+    let hash = install_hash(&game_pak_path, mod_specs);
+    let (progress_tx, progress) = cli_fetch_progress();
+
+    let quick = if !update && state.config.last_install_hash.as_ref() == Some(&hash) {
+        quick_integrate(
+            &game_pak_path,
+            state,
+            mod_specs,
+            &priorities,
+            false,
+            Some(progress_tx.clone()),
+        )
+        .await
+        .map_err(|e| anyhow!("{}", e))?
+    } else {
+        None
+    };
+    if quick.is_some() {
+        info!("mods unchanged since last install, skipped resolution");
+        drop(progress_tx);
+    } else {
+        resolve_unordered_and_integrate_with_provider_init(
+            &game_pak_path,
+            state,
+            mod_specs,
+            &priorities,
+            update,
+            false,
+            Some(progress_tx),
+            init_provider,
+        )
+        .await
+        .map_err(|e| anyhow!("{}", e))?;
+    }
+    progress.await?;
+    hooks.run_post(&game_pak_path);
+
+    state.config.verbose_integrate = persisted_verbose_integrate;
+    state.config.export_pak_path = persisted_export_pak_path;
+    state.config.last_install_hash = Some(hash);
+    state.config.game_pak_baseline =
+        PakBaseline::record(&game_pak_path, state.config.hash_game_pak)
+            .inspect_err(|e| warn!("failed to record the game pak for detecting updates: {e}"))
+            .ok();
+    state.config.save()?;
+    state.record_usage(Some(mod_specs));
+    Ok(())
+}
+
+/// Report fetch progress as progress bars when attached to a terminal, or as plain log lines
+/// otherwise. The returned handle completes once the sender has been dropped.
+fn cli_fetch_progress() -> (Sender<FetchProgress>, JoinHandle<()>) {
+    let (tx, mut rx) = mpsc::channel::<FetchProgress>(10);
+    let handle = tokio::spawn(async move {
+        if !std::io::stderr().is_terminal() {
+            while let Some(progress) = rx.recv().await {
+                if let FetchProgress::Complete { resolution } = progress {
+                    info!("fetched {}", resolution.url.0);
+                }
+            }
+            return;
+        }
+
+        let multi = MultiProgress::new();
+        let style = ProgressStyle::with_template(
+            "{bar:30.cyan/blue} {bytes:>10}/{total_bytes:<10} {wide_msg}",
+        )
+        .unwrap();
+        let mut bars = HashMap::new();
+        while let Some(progress) = rx.recv().await {
+            match progress {
+                FetchProgress::Progress {
+                    resolution,
+                    progress,
+                    size,
+                } => {
+                    let bar = bars.entry(resolution.clone()).or_insert_with(|| {
+                        multi.add(
+                            ProgressBar::new(size)
+                                .with_style(style.clone())
+                                .with_message(resolution.url.0),
+                        )
+                    });
+                    bar.set_length(size);
+                    bar.set_position(progress);
+                }
+                FetchProgress::Complete { resolution } => {
+                    if let Some(bar) = bars.get(&resolution) {
+                        bar.finish();
+                    }
+                }
+            }
+        }
+    });
+    (tx, handle)
+}
+
+fn action_export_profile(dirs: Dirs, action: ActionExportProfile) -> Result<()> {
+    let state = State::init(dirs)?;
+    let modpack = Modpack::export_profile(&state.mod_data, &action.profile)?;
+    let output = action
+        .output
+        .unwrap_or_else(|| PathBuf::from(format!("{}.profile.json", action.profile)));
+    modpack
+        .write(&output)
+        .with_context(|| format!("failed to write {}", output.display()))?;
+    info!(
+        "exported profile {:?} to {}",
+        action.profile,
+        output.display()
+    );
+    Ok(())
+}
 
-    let mod_paths = resolve_ordered_with_provider_init(&mut state, &mods, init_provider).await?;
+fn action_import_profile(dirs: Dirs, action: ActionImportProfile) -> Result<()> {
+    let mut state = State::init(dirs)?;
+    let modpack = Modpack::read(&action.file)
+        .with_context(|| format!("failed to read {}", action.file.display()))?;
+    let name = state.import_profile(modpack, action.name)?;
+    info!("imported profile {name:?} from {}", action.file.display());
+    Ok(())
+}
+
+async fn action_bisect(dirs: Dirs, action: ActionBisect, skip_pak_validation: bool) -> Result<()> {
+    let mut state = State::init(dirs)?;
+    let game_pak_path = get_pak_path(&state, &action.fsd_pak, skip_pak_validation)?;
+    debug!(?game_pak_path);
+
+    let profile = action.profile;
+    if !state.mod_data.profiles.contains_key(&profile) {
+        bail!("profile {profile:?} does not exist");
+    }
+    if !std::io::stdin().is_terminal() {
+        bail!("bisecting asks whether the game still crashes and needs an interactive terminal");
+    }
+    let data_dir = state.dirs.data_dir.clone();
+    let mods = state.integration_order(&profile);
+
+    let saved = if action.restart {
+        Bisection::remove(&data_dir)?;
+        None
+    } else {
+        Bisection::read(&data_dir)
+    };
+    let mut bisection = match saved {
+        Some(saved) if saved.profile != profile => {
+            bail!(
+                "a bisection of profile {:?} is unfinished, finish it or use --restart",
+                saved.profile
+            );
+        }
+        Some(saved) if saved.suspects.iter().all(|spec| mods.contains(spec)) => {
+            info!(
+                "continuing bisection of profile {profile:?} after {} steps",
+                saved.steps
+            );
+            saved
+        }
+        Some(_) => {
+            warn!("the enabled mods of profile {profile:?} changed, starting over");
+            Bisection::new(profile.clone(), mods.clone())
+        }
+        None => Bisection::new(profile.clone(), mods.clone()),
+    };
+    if bisection.suspects.is_empty() {
+        bail!("profile {profile:?} has no enabled mods");
+    }
+
+    let theme = dialoguer::theme::ColorfulTheme::default();
+    while bisection.culprit().is_none() {
+        let testing = bisection.testing().to_vec();
+        // keep the integration order of the profile
+        let install = mods
+            .iter()
+            .filter(|spec| testing.contains(spec))
+            .cloned()
+            .collect::<Vec<_>>();
+        info!(
+            "installing {} of {} suspects, about {} steps left",
+            install.len(),
+            bisection.suspects.len(),
+            bisection.remaining_steps()
+        );
+        let infos = install
+            .iter()
+            .filter_map(|spec| state.store.get_mod_info(spec))
+            .collect::<Vec<_>>();
+        for spec in mods.iter().filter(|spec| !install.contains(spec)) {
+            for info in dependents(&infos, spec) {
+                warn!(
+                    "{} depends on {} which is not installed",
+                    info.name, spec.url
+                );
+            }
+        }
+        integrate_specs(
+            &mut state,
+            game_pak_path.clone(),
+            Some(&profile),
+            &install,
+            false,
+            false,
+            None,
+        )
+        .await?;
+
+        let answer = dialoguer::Select::with_theme(&theme)
+            .with_prompt("Start the game. Does it still crash?")
+            .items(&[
+                "Yes, it still crashes",
+                "No, it works now",
+                "Stop for now, continue later",
+            ])
+            .default(0)
+            .interact()?;
+        match answer {
+            0 => bisection.record(true),
+            1 => bisection.record(false),
+            _ => {
+                bisection.write(&data_dir)?;
+                info!("bisection saved, run `mint bisect {profile}` again to continue");
+                return Ok(());
+            }
+        }
+        bisection.write(&data_dir)?;
+    }
+
+    Bisection::remove(&data_dir)?;
+    let culprit = bisection.culprit().unwrap();
+    let name = state
+        .store
+        .get_mod_info(culprit)
+        .map(|info| info.name)
+        .unwrap_or_else(|| culprit.url.clone());
+    info!(
+        "found the mod causing the crash after {} steps: {name} <{}>",
+        bisection.steps, culprit.url
+    );
+    info!(
+        "run `mint profile {profile}` to install the whole profile again, or `mint uninstall --profile {profile} --mods {}` to install it without this mod",
+        culprit.url
+    );
+    Ok(())
+}
+
+async fn action_uninstall(
+    dirs: Dirs,
+    action: ActionUninstall,
+    skip_pak_validation: bool,
+) -> Result<()> {
+    let mut state = State::init(dirs)?;
+    let game_pak_path = get_pak_path(&state, &action.fsd_pak, skip_pak_validation)?;
+    debug!(?game_pak_path);
+
+    let profile = action
+        .profile
+        .unwrap_or_else(|| state.mod_data.active_profile.clone());
+    if !state.mod_data.profiles.contains_key(&profile) {
+        bail!("profile {profile:?} does not exist");
+    }
+    let installed = state
+        .mod_data
+        .enabled_mods_deduped(&profile)
+        .into_iter()
+        .map(|mc| mc.spec)
+        .collect::<Vec<_>>();
+
+    let remove = action
+        .mods
+        .iter()
+        .map(|m| parse_spec(m))
+        .collect::<Vec<_>>();
+    let (remaining, unmatched) = without_mods(&installed, &remove);
+    for spec in &unmatched {
+        warn!("{} is not an enabled mod of profile {profile:?}", spec.url);
+    }
+    if !remove.is_empty() && unmatched.len() == remove.len() {
+        bail!("none of the mods to remove are enabled in profile {profile:?}");
+    }
+
+    if remove.is_empty() || remaining.is_empty() {
+        let modio_mods = installed
+            .iter()
+            .filter_map(|spec| state.store.get_mod_info(spec)?.modio_id)
+            .collect();
+        uninstall(&game_pak_path, modio_mods, !state.config.skip_hook)
+            .map_err(|e| anyhow!("{}", e))?;
+        state.config.last_install_hash = None;
+        state.config.game_pak_baseline = None;
+        state.config.save()?;
+        info!("uninstalled mods");
+        return Ok(());
+    }
+
+    let infos = remaining
+        .iter()
+        .filter_map(|spec| state.store.get_mod_info(spec))
+        .collect::<Vec<_>>();
+    for spec in &remove {
+        for info in dependents(&infos, spec) {
+            warn!("{} depends on removed mod {}", info.name, spec.url);
+        }
+    }
+
+    info!(
+        "installing profile {profile:?} without {} mods",
+        installed.len() - remaining.len()
+    );
+    integrate_specs(
+        &mut state,
+        game_pak_path,
+        Some(&profile),
+        &remaining,
+        false,
+        false,
+        None,
+    )
+    .await
+}
+
+fn action_doctor(dirs: Dirs, action: ActionDoctor, skip_pak_validation: bool) -> Result<()> {
+    let state = State::init(dirs)?;
+    let game_pak_path = get_pak_path(&state, &action.fsd_pak, skip_pak_validation)?;
+    validate_pak(&state, &game_pak_path, skip_pak_validation)
+        .with_context(|| format!("{} is not a DRG pak", game_pak_path.display()))?;
+    println!("game pak: {}", game_pak_path.display());
+
+    let installation = DRGInstallation::from_pak_path(&game_pak_path)?;
+    let mods_installed = installation.mods_pak().exists();
+    if mods_installed {
+        println!("mods pak: {}", installation.mods_pak().display());
+    } else {
+        println!("mods pak: not installed");
+    }
+
+    match find_other_paks(&installation) {
+        Ok(paks) if paks.is_empty() => println!("other paks: none"),
+        Ok(paks) => {
+            for pak in paks {
+                println!("other pak: {pak}");
+            }
+        }
+        Err(e) => println!("other paks: failed to look for them: {e}"),
+    }
+
+    match game_updated(&game_pak_path, state.config.game_pak_baseline.as_ref()) {
+        Ok(true) => {
+            println!("game: updated since mods were installed, install them again to rebuild them")
+        }
+        Ok(false) => {}
+        Err(e) => println!("game: failed to check for updates: {e}"),
+    }
+
+    if action.reinstall_hook {
+        install_hook(&game_pak_path).map_err(|e| anyhow!("{}", e))?;
+        println!("hook DLL: reinstalled");
+    }
+    if state.config.skip_hook {
+        println!("hook DLL: not checked, deployment is skipped in the config");
+        return Ok(());
+    }
+    match check_hook(&game_pak_path).map_err(|e| anyhow!("{}", e))? {
+        None => println!("hook DLL: ok"),
+        Some(HookProblem::Missing) if !mods_installed => println!("hook DLL: not installed"),
+        Some(problem) => bail!(
+            "{problem}, mods may not load. Run `mint doctor --reinstall-hook` or install mods again to redeploy it"
+        ),
+    }
+    Ok(())
+}
+
+fn action_install_hook(
+    dirs: Dirs,
+    action: ActionInstallHook,
+    skip_pak_validation: bool,
+) -> Result<()> {
+    let state = State::init(dirs)?;
+    let game_pak_path = get_pak_path(&state, &action.fsd_pak, skip_pak_validation)?;
+    if state.config.skip_hook {
+        warn!("hook deployment is skipped in the config, installing mods will not update the hook");
+    }
+    match reinstall_hook(&game_pak_path).map_err(|e| anyhow!("{}", e))? {
+        None => println!("hook DLL: reinstalled"),
+        Some(problem) => bail!("{problem} after reinstalling it"),
+    }
+    Ok(())
+}
+
+async fn action_check_links(dirs: Dirs, action: ActionCheckLinks) -> Result<()> {
+    let mut state = State::init(dirs)?;
+    let specs = parse_links(
+        &std::fs::read_to_string(&action.file)
+            .with_context(|| format!("failed to read {}", action.file.display()))?,
+    );
+
+    let reports = check_links_with_provider_init(&mut state, &specs, init_provider).await?;
+    let mut broken = 0;
+    for report in &reports {
+        match &report.result {
+            Ok(name) => println!("{:<14}{} ({name})", "ok", report.spec.url),
+            Err((problem, e)) => {
+                broken += 1;
+                println!("{:<14}{}: {e}", problem.to_string(), report.spec.url);
+            }
+        }
+    }
+
+    if broken > 0 {
+        bail!("{broken} of {} links are broken", reports.len());
+    }
+    println!("all {} links are ok", reports.len());
+    Ok(())
+}
+
+async fn action_conflicts(dirs: Dirs, action: ActionConflicts) -> Result<()> {
+    let mut state = State::init(dirs)?;
+    if !state.mod_data.profiles.contains_key(&action.profile) {
+        bail!("profile {:?} does not exist", action.profile);
+    }
+
+    let mods = state
+        .mod_data
+        .enabled_mods_deduped(&action.profile)
+        .into_iter()
+        .map(|mc| mc.spec)
+        .collect::<Vec<_>>();
+
+    let (progress_tx, progress) = cli_fetch_progress();
+    let mod_paths =
+        resolve_ordered_with_provider_init(&mut state, &mods, Some(progress_tx), init_provider)
+            .await?;
+    progress.await?;
+
+    let pak_key = state.config.pak_key.clone();
+    let all_archive_paks = state.config.all_archive_paks;
+    let conflicts = tokio::task::spawn_blocking(move || {
+        find_conflicts(
+            &mods.into_iter().zip(mod_paths).collect(),
+            pak_key.as_ref(),
+            all_archive_paks,
+        )
+    })
+    .await??;
+
+    if conflicts.is_empty() {
+        println!(
+            "no conflicts between the enabled mods of {:?}",
+            action.profile
+        );
+        return Ok(());
+    }
+
+    // group files by the set of mods modifying them
+    let mut by_mods: BTreeMap<Vec<&str>, Vec<&str>> = BTreeMap::new();
+    for (path, mods) in &conflicts {
+        by_mods
+            .entry(mods.iter().map(|m| m.url.as_str()).collect())
+            .or_default()
+            .push(path);
+    }
+    for (mods, paths) in by_mods {
+        println!("{}:", mods.join(", "));
+        for path in paths {
+            println!("  {path}");
+        }
+    }
+    Ok(())
+}
+
+async fn action_prefetch(dirs: Dirs, action: ActionPrefetch) -> Result<()> {
+    let mut state = State::init(dirs)?;
+    let mods = if let Some(profile) = &action.profile {
+        if !state.mod_data.profiles.contains_key(profile) {
+            bail!("profile {:?} does not exist", profile);
+        }
+        state
+            .mod_data
+            .enabled_mods_deduped(profile)
+            .into_iter()
+            .map(|mc| mc.spec)
+            .collect::<Vec<_>>()
+    } else {
+        action.mods.iter().map(|m| parse_spec(m)).collect()
+    };
+
+    let (progress_tx, progress) = cli_fetch_progress();
+    let mod_paths = prefetch_with_provider_init(
+        &mut state,
+        &mods,
+        action.update,
+        Some(progress_tx),
+        init_provider,
+    )
+    .await?;
+    progress.await?;
+    state.record_usage(None);
+
+    let bytes = mod_paths
+        .iter()
+        .map(|p| std::fs::metadata(p).map(|m| m.len()).unwrap_or(0))
+        .sum::<u64>();
+    println!(
+        "fetched {} mods ({:.1} MiB) into {}",
+        mod_paths.len(),
+        bytes as f64 / (1024. * 1024.),
+        state.store.blob_cache_path().display()
+    );
+    Ok(())
+}
+
+async fn action_versions(dirs: Dirs, action: ActionVersions) -> Result<()> {
+    let mut state = State::init(dirs)?;
+    let spec = parse_spec(&action.url);
+    let versions =
+        list_versions_with_provider_init(&mut state, &spec, action.update, init_provider).await?;
+    if versions.is_empty() {
+        println!("{} has no versions to choose from", spec.url);
+        return Ok(());
+    }
+    for version in &versions {
+        let approval = version
+            .approval
+            .map(|a| format!(" [{a:?}]"))
+            .unwrap_or_default();
+        println!("{}  {}{approval}", version.spec.url, version.name);
+    }
+    Ok(())
+}
+
+/// Returns whether no denied lint had any findings.
+async fn action_lint(dirs: Dirs, action: ActionLint, skip_pak_validation: bool) -> Result<bool> {
+    let mut deny = BTreeSet::new();
+    for name in &action.deny {
+        if name.eq_ignore_ascii_case("all") {
+            deny.extend(LintId::ALL.into_iter().filter(|id| !id.is_advisory()));
+        } else {
+            deny.insert(LintId::from_name(name).with_context(|| format!("unknown lint {name:?}"))?);
+        }
+    }
+
+    let mut state = State::init(dirs)?;
+    // none of the lints run here need the game pak
+    let game_pak_path = get_pak_path(&state, &action.fsd_pak, skip_pak_validation).ok();
+    debug!(?game_pak_path);
+
+    let mods = if let Some(profile) = &action.profile {
+        let mut mods = Vec::new();
+        state.mod_data.for_each_mod(profile, |mc| {
+            mods.push(mc.spec.clone());
+        });
+        mods
+    } else {
+        action.mods.iter().map(|m| parse_spec(m)).collect()
+    };
+    let mods_scanned = mods.len();
+
+    let mod_paths = if action.format == LintFormat::Text {
+        let (progress_tx, progress) = cli_fetch_progress();
+        let mod_paths =
+            resolve_ordered_with_provider_init(&mut state, &mods, Some(progress_tx), init_provider)
+                .await?;
+        progress.await?;
+        mod_paths
+    } else {
+        resolve_ordered_with_provider_init(&mut state, &mods, None, init_provider).await?
+    };
+    let pak_key = state.config.pak_key.clone();
+    let all_archive_paks = action.all_archive_paks || state.config.all_archive_paks;
+    let cache_dir = (!action.no_cache).then(|| state.dirs.cache_dir.clone());
+    let modio_tags = mods
+        .iter()
+        .filter_map(|m| Some((m.clone(), state.store.get_mod_info(m)?.modio_tags?)))
+        .collect();
 
     let report = tokio::task::spawn_blocking(move || {
-        run_lints(
+        let lint_ctxt = LintCtxt::init(
+            mods.into_iter().zip(mod_paths).collect(),
+            game_pak_path,
+            pak_key,
+        )?
+        .all_archive_paks(all_archive_paks)
+        .cache_dir(cache_dir)
+        .modio_tags(modio_tags);
+        run_lints_in(
             &BTreeSet::from([
                 LintId::ARCHIVE_WITH_ONLY_NON_PAK_FILES,
                 LintId::ASSET_REGISTRY_BIN,
@@ -256,12 +2150,66 @@ async fn action_lint(dirs: Dirs, action: ActionLint) -> Result<()> {
                 LintId::ARCHIVE_WITH_MULTIPLE_PAKS,
                 LintId::NON_ASSET_FILES,
                 LintId::SPLIT_ASSET_PAIRS,
+                LintId::OVERLAPPING_MOUNT_POINTS,
+                LintId::MINT_ARTIFACTS,
+                LintId::UNTAGGED_GAMEPLAY,
             ]),
-            mods.into_iter().zip(mod_paths).collect(),
-            Some(game_pak_path),
+            lint_ctxt,
         )
     })
     .await??;
-    println!("{:#?}", report);
-    Ok(())
+
+    let mut summary = LintSummary::new(&report, mods_scanned, &deny);
+    if let Some(baseline_path) = &action.baseline {
+        let baseline = match LintBaseline::read(baseline_path) {
+            Ok(baseline) => baseline,
+            // a baseline which is about to be written does not have to exist yet
+            Err(mint::mod_lints::LintError::IoError { source })
+                if action.write_baseline && source.kind() == std::io::ErrorKind::NotFound =>
+            {
+                LintBaseline::default()
+            }
+            Err(e) => bail!("failed to read baseline {}: {e}", baseline_path.display()),
+        };
+        summary = summary.against_baseline(baseline.diff(&report), &deny);
+        if action.write_baseline {
+            let baseline = LintBaseline::new(&report);
+            baseline.write(baseline_path)?;
+            info!(
+                "wrote {} findings to baseline {}",
+                baseline.findings.len(),
+                baseline_path.display()
+            );
+            // everything found is part of the baseline now
+            summary.denied.clear();
+            summary.passed = true;
+        }
+    }
+    match action.format {
+        LintFormat::Text => {
+            println!("{:#?}", report);
+            println!("{} mods scanned", summary.mods_scanned);
+            for (lint, count) in &summary.findings {
+                let denied = if summary.denied.contains(lint) {
+                    " (denied)"
+                } else {
+                    ""
+                };
+                println!("{lint}: {count}{denied}");
+            }
+            if let Some(diff) = &summary.baseline {
+                println!("{} new findings since the baseline", diff.new.len());
+                for finding in &diff.new {
+                    println!("  + {finding}");
+                }
+                println!("{} findings fixed since the baseline", diff.fixed.len());
+                for finding in &diff.fixed {
+                    println!("  - {finding}");
+                }
+            }
+            println!("{}", if summary.passed { "passed" } else { "failed" });
+        }
+        LintFormat::Json => println!("{}", serde_json::to_string_pretty(&summary)?),
+    }
+    Ok(summary.passed)
 }
@@ -0,0 +1,106 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use fs_err as fs;
+use sha2::{Digest, Sha256};
+
+use crate::integrate::{get_pak_from_data, IntegrationError};
+use crate::{pak_builder, PakKey};
+
+/// Files which differ between two versions of a mod, as paths relative to the game root.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModDiff {
+    pub added: BTreeSet<String>,
+    pub removed: BTreeSet<String>,
+    pub changed: BTreeSet<String>,
+    pub unchanged: usize,
+}
+
+impl ModDiff {
+    pub fn new(old: &BTreeMap<String, [u8; 32]>, new: &BTreeMap<String, [u8; 32]>) -> Self {
+        let mut diff = Self::default();
+        for (path, hash) in old {
+            match new.get(path) {
+                None => {
+                    diff.removed.insert(path.clone());
+                }
+                Some(new_hash) if new_hash != hash => {
+                    diff.changed.insert(path.clone());
+                }
+                Some(_) => diff.unchanged += 1,
+            }
+        }
+        diff.added = new
+            .keys()
+            .filter(|path| !old.contains_key(*path))
+            .cloned()
+            .collect();
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Hash of every file in the pak of a mod, keyed by its lowercase path.
+pub fn pak_file_hashes<P: AsRef<Path>>(
+    path: P,
+    pak_key: Option<&PakKey>,
+) -> Result<BTreeMap<String, [u8; 32]>, IntegrationError> {
+    let mut reader = get_pak_from_data(Box::new(BufReader::new(fs::File::open(path.as_ref())?)))?;
+    let pak = pak_builder(pak_key).reader(&mut reader)?;
+    let mount = PathBuf::from(pak.mount_point());
+    pak.files()
+        .into_iter()
+        .map(|p| {
+            let data = pak.get(&p, &mut reader)?;
+            let path = mount.join(&p);
+            let path = path
+                .strip_prefix("../../../")
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .replace('\\', "/")
+                .to_ascii_lowercase();
+            Ok((path, Sha256::digest(&data).into()))
+        })
+        .collect()
+}
+
+/// Compare the files of two downloaded versions of a mod.
+pub fn diff_paks<P: AsRef<Path>>(
+    old: P,
+    new: P,
+    pak_key: Option<&PakKey>,
+) -> Result<ModDiff, IntegrationError> {
+    Ok(ModDiff::new(
+        &pak_file_hashes(old, pak_key)?,
+        &pak_file_hashes(new, pak_key)?,
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_mod_diff() {
+        let old = [("a", [0; 32]), ("b", [1; 32]), ("c", [2; 32])]
+            .map(|(p, h)| (p.to_string(), h))
+            .into_iter()
+            .collect();
+        let new = [("b", [1; 32]), ("c", [3; 32]), ("d", [4; 32])]
+            .map(|(p, h)| (p.to_string(), h))
+            .into_iter()
+            .collect();
+
+        let diff = ModDiff::new(&old, &new);
+        assert_eq!(diff.removed, BTreeSet::from(["a".to_string()]));
+        assert_eq!(diff.changed, BTreeSet::from(["c".to_string()]));
+        assert_eq!(diff.added, BTreeSet::from(["d".to_string()]));
+        assert_eq!(diff.unchanged, 1);
+        assert!(!diff.is_empty());
+        assert!(ModDiff::new(&old, &old).is_empty());
+    }
+}
@@ -0,0 +1,104 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use fs_err as fs;
+use serde::{Deserialize, Serialize};
+use snafu::prelude::*;
+
+use super::{LintError, LintReport};
+
+/// Bump whenever findings are identified differently so old baselines are rejected instead of
+/// reporting every finding as new.
+pub const LINT_BASELINE_VERSION: u32 = 1;
+
+/// A single finding of a lint, identified by the lint, the mod and what was found, e.g. a file
+/// path. Lints which flag a mod as a whole have no detail.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct LintFinding {
+    pub lint: String,
+    pub url: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub detail: String,
+}
+
+impl std::fmt::Display for LintFinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.lint, self.url)?;
+        if !self.detail.is_empty() {
+            write!(f, ": {}", self.detail)?;
+        }
+        Ok(())
+    }
+}
+
+impl LintReport {
+    /// Every finding of the report, see [`LintFinding`].
+    pub fn findings(&self) -> BTreeSet<LintFinding> {
+        let mut findings = BTreeSet::new();
+        for (spec, lints) in self.by_mod() {
+            for (id, details) in lints {
+                let finding = |detail: String| LintFinding {
+                    lint: id.name().to_string(),
+                    url: spec.url.clone(),
+                    detail,
+                };
+                if details.is_empty() {
+                    findings.insert(finding(String::new()));
+                }
+                findings.extend(details.into_iter().map(finding));
+            }
+        }
+        findings
+    }
+}
+
+/// Findings accepted at some point, so later lint runs only report findings which are new since.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintBaseline {
+    pub version: u32,
+    pub findings: BTreeSet<LintFinding>,
+}
+
+/// Difference between the findings of a lint report and a [`LintBaseline`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct BaselineDiff {
+    /// Findings which are not part of the baseline.
+    pub new: BTreeSet<LintFinding>,
+    /// Findings of the baseline which were not found anymore.
+    pub fixed: BTreeSet<LintFinding>,
+}
+
+impl LintBaseline {
+    pub fn new(report: &LintReport) -> Self {
+        Self {
+            version: LINT_BASELINE_VERSION,
+            findings: report.findings(),
+        }
+    }
+
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<Self, LintError> {
+        let baseline: Self =
+            serde_json::from_slice(&fs::read(path.as_ref())?).context(BaselineSnafu)?;
+        ensure!(
+            baseline.version == LINT_BASELINE_VERSION,
+            UnsupportedBaselineVersionSnafu {
+                version: baseline.version
+            }
+        );
+        Ok(baseline)
+    }
+
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> Result<(), LintError> {
+        let json = serde_json::to_vec_pretty(self).context(BaselineSnafu)?;
+        fs::write(path.as_ref(), json)?;
+        Ok(())
+    }
+
+    pub fn diff(&self, report: &LintReport) -> BaselineDiff {
+        let findings = report.findings();
+        BaselineDiff {
+            new: findings.difference(&self.findings).cloned().collect(),
+            fixed: self.findings.difference(&findings).cloned().collect(),
+        }
+    }
+}
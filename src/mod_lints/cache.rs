@@ -0,0 +1,392 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use fs_err as fs;
+use indexmap::IndexSet;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::{debug, warn};
+
+use crate::providers::ModSpecification;
+
+use super::{run_lints_in, LintCtxt, LintError, LintId, LintReport, SplitAssetPair};
+
+/// Bump whenever a cached lint changes what it reports so stale results are discarded.
+pub const LINT_CACHE_VERSION: u32 = 1;
+/// Name of the lint cache in the cache directory.
+pub const LINT_CACHE_FILE: &str = "lint_cache.json";
+
+/// Lints which only look at a single mod at a time and can therefore be cached per mod. Lints
+/// comparing mods with each other always run, as does the outdated pak version lint which only
+/// reads the pak footer anyway, and the untagged gameplay lint whose results depend on tags which
+/// can change without the mod changing.
+pub const CACHED_LINTS: [LintId; 9] = [
+    LintId::ASSET_REGISTRY_BIN,
+    LintId::SHADER_FILES,
+    LintId::EMPTY_ARCHIVE,
+    LintId::ARCHIVE_WITH_ONLY_NON_PAK_FILES,
+    LintId::ARCHIVE_WITH_MULTIPLE_PAKS,
+    LintId::NON_ASSET_FILES,
+    LintId::SPLIT_ASSET_PAIRS,
+    LintId::UNMODIFIED_GAME_ASSETS,
+    LintId::MINT_ARTIFACTS,
+];
+
+/// Results of the cached lints for a single mod. `None` if the lint has not been run on it yet.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+struct ModLintResults {
+    asset_register_bin: Option<BTreeSet<String>>,
+    shader_files: Option<BTreeSet<String>>,
+    empty_archive: Option<bool>,
+    archive_with_only_non_pak_files: Option<bool>,
+    archive_with_multiple_paks: Option<bool>,
+    non_asset_files: Option<BTreeSet<String>>,
+    split_asset_pairs: Option<BTreeMap<String, SplitAssetPair>>,
+    unmodified_game_assets: Option<BTreeSet<String>>,
+    mint_artifacts: Option<BTreeSet<String>>,
+}
+
+fn take_files<T: Clone + Default>(
+    res: &Option<BTreeMap<ModSpecification, T>>,
+    spec: &ModSpecification,
+) -> Option<T> {
+    res.as_ref()
+        .map(|r| r.get(spec).cloned().unwrap_or_default())
+}
+
+fn take_flag(res: &Option<BTreeSet<ModSpecification>>, spec: &ModSpecification) -> Option<bool> {
+    res.as_ref().map(|r| r.contains(spec))
+}
+
+fn add_files<T: Clone + Default + PartialEq>(
+    res: &mut Option<BTreeMap<ModSpecification, T>>,
+    spec: &ModSpecification,
+    cached: &Option<T>,
+) {
+    if let (Some(res), Some(cached)) = (res, cached)
+        && *cached != T::default()
+    {
+        res.insert(spec.clone(), cached.clone());
+    }
+}
+
+fn add_flag(
+    res: &mut Option<BTreeSet<ModSpecification>>,
+    spec: &ModSpecification,
+    cached: &Option<bool>,
+) {
+    if let (Some(res), Some(true)) = (res, cached) {
+        res.insert(spec.clone());
+    }
+}
+
+impl ModLintResults {
+    fn has(&self, lint: LintId) -> bool {
+        match lint {
+            LintId::ASSET_REGISTRY_BIN => self.asset_register_bin.is_some(),
+            LintId::SHADER_FILES => self.shader_files.is_some(),
+            LintId::EMPTY_ARCHIVE => self.empty_archive.is_some(),
+            LintId::ARCHIVE_WITH_ONLY_NON_PAK_FILES => {
+                self.archive_with_only_non_pak_files.is_some()
+            }
+            LintId::ARCHIVE_WITH_MULTIPLE_PAKS => self.archive_with_multiple_paks.is_some(),
+            LintId::NON_ASSET_FILES => self.non_asset_files.is_some(),
+            LintId::SPLIT_ASSET_PAIRS => self.split_asset_pairs.is_some(),
+            LintId::UNMODIFIED_GAME_ASSETS => self.unmodified_game_assets.is_some(),
+            LintId::MINT_ARTIFACTS => self.mint_artifacts.is_some(),
+            _ => false,
+        }
+    }
+
+    /// Record the results for `spec` of the lints which were run in `report`.
+    fn update(&mut self, report: &LintReport, spec: &ModSpecification) {
+        fn set<T>(field: &mut Option<T>, value: Option<T>) {
+            if value.is_some() {
+                *field = value;
+            }
+        }
+        set(
+            &mut self.asset_register_bin,
+            take_files(&report.asset_register_bin_mods, spec),
+        );
+        set(
+            &mut self.shader_files,
+            take_files(&report.shader_file_mods, spec),
+        );
+        set(
+            &mut self.empty_archive,
+            take_flag(&report.empty_archive_mods, spec),
+        );
+        set(
+            &mut self.archive_with_only_non_pak_files,
+            take_flag(&report.archive_with_only_non_pak_files_mods, spec),
+        );
+        set(
+            &mut self.archive_with_multiple_paks,
+            take_flag(&report.archive_with_multiple_paks_mods, spec),
+        );
+        set(
+            &mut self.non_asset_files,
+            take_files(&report.non_asset_file_mods, spec),
+        );
+        set(
+            &mut self.split_asset_pairs,
+            take_files(&report.split_asset_pairs_mods, spec),
+        );
+        set(
+            &mut self.unmodified_game_assets,
+            take_files(&report.unmodified_game_assets_mods, spec),
+        );
+        set(
+            &mut self.mint_artifacts,
+            take_files(&report.mint_artifact_mods, spec),
+        );
+    }
+
+    /// Add the results for `spec` to the lints which are present in `report`.
+    fn add_to(&self, report: &mut LintReport, spec: &ModSpecification) {
+        add_files(
+            &mut report.asset_register_bin_mods,
+            spec,
+            &self.asset_register_bin,
+        );
+        add_files(&mut report.shader_file_mods, spec, &self.shader_files);
+        add_flag(&mut report.empty_archive_mods, spec, &self.empty_archive);
+        add_flag(
+            &mut report.archive_with_only_non_pak_files_mods,
+            spec,
+            &self.archive_with_only_non_pak_files,
+        );
+        add_flag(
+            &mut report.archive_with_multiple_paks_mods,
+            spec,
+            &self.archive_with_multiple_paks,
+        );
+        add_files(&mut report.non_asset_file_mods, spec, &self.non_asset_files);
+        add_files(
+            &mut report.split_asset_pairs_mods,
+            spec,
+            &self.split_asset_pairs,
+        );
+        add_files(
+            &mut report.unmodified_game_assets_mods,
+            spec,
+            &self.unmodified_game_assets,
+        );
+        add_files(&mut report.mint_artifact_mods, spec, &self.mint_artifacts);
+    }
+}
+
+/// Start an empty result for `lint` so cached findings can be added to it.
+fn mark_run(report: &mut LintReport, lint: LintId) {
+    match lint {
+        LintId::ASSET_REGISTRY_BIN => report.asset_register_bin_mods = Some(Default::default()),
+        LintId::SHADER_FILES => report.shader_file_mods = Some(Default::default()),
+        LintId::EMPTY_ARCHIVE => report.empty_archive_mods = Some(Default::default()),
+        LintId::ARCHIVE_WITH_ONLY_NON_PAK_FILES => {
+            report.archive_with_only_non_pak_files_mods = Some(Default::default())
+        }
+        LintId::ARCHIVE_WITH_MULTIPLE_PAKS => {
+            report.archive_with_multiple_paks_mods = Some(Default::default())
+        }
+        LintId::NON_ASSET_FILES => report.non_asset_file_mods = Some(Default::default()),
+        LintId::SPLIT_ASSET_PAIRS => report.split_asset_pairs_mods = Some(Default::default()),
+        LintId::UNMODIFIED_GAME_ASSETS => {
+            report.unmodified_game_assets_mods = Some(Default::default())
+        }
+        LintId::MINT_ARTIFACTS => report.mint_artifact_mods = Some(Default::default()),
+        _ => {}
+    }
+}
+
+/// Per mod lint results keyed by the hash of the mod's content, so unchanged mods are not
+/// analyzed again on subsequent runs. Only the mods of the last run are kept.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct LintCache {
+    version: u32,
+    mods: HashMap<String, ModLintResults>,
+    #[serde(skip)]
+    path: PathBuf,
+    #[serde(skip)]
+    hits: usize,
+    #[serde(skip)]
+    misses: usize,
+}
+
+impl LintCache {
+    /// Read the cache from `cache_dir`. A missing, unreadable or outdated cache is treated as
+    /// empty.
+    pub fn open<P: AsRef<Path>>(cache_dir: P) -> Self {
+        let path = cache_dir.as_ref().join(LINT_CACHE_FILE);
+        let cache = fs::read(&path)
+            .ok()
+            .and_then(|data| serde_json::from_slice::<Self>(&data).ok())
+            .filter(|cache| cache.version == LINT_CACHE_VERSION);
+        Self {
+            path,
+            ..cache.unwrap_or(Self {
+                version: LINT_CACHE_VERSION,
+                ..Default::default()
+            })
+        }
+    }
+
+    pub fn save(&self) -> Result<(), LintError> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(
+            &self.path,
+            serde_json::to_vec(self).map_err(std::io::Error::from)?,
+        )?;
+        Ok(())
+    }
+
+    /// Number of mods whose results were taken from the cache in the last run.
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// Number of mods which had to be linted in the last run.
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    /// Run `enabled_lints`, only linting mods which are not cached yet with the cacheable lints.
+    pub fn run(
+        &mut self,
+        enabled_lints: &BTreeSet<LintId>,
+        lint_ctxt: LintCtxt,
+    ) -> Result<LintReport, LintError> {
+        let (cached, uncached): (BTreeSet<_>, BTreeSet<_>) = enabled_lints
+            .iter()
+            .copied()
+            .partition(|lint| CACHED_LINTS.contains(lint));
+
+        let context = context_key(&lint_ctxt);
+        let mut keys = vec![];
+        let mut misses = IndexSet::new();
+        for (spec, path) in &lint_ctxt.mods {
+            let key = mod_key(&context, path)?;
+            let hit = self
+                .mods
+                .get(&key)
+                .is_some_and(|entry| cached.iter().all(|lint| entry.has(*lint)));
+            if !hit {
+                misses.insert((spec.clone(), path.clone()));
+            }
+            keys.push((spec.clone(), key, hit));
+        }
+        self.hits = keys.len() - misses.len();
+        self.misses = misses.len();
+        debug!(hits = self.hits, misses = self.misses, "lint cache");
+
+        let mut report = LintReport::default();
+        if !cached.is_empty() {
+            if !misses.is_empty() {
+                let fresh = run_lints_in(
+                    &cached,
+                    LintCtxt {
+                        mods: misses,
+                        fsd_pak_path: lint_ctxt.fsd_pak_path.clone(),
+                        pak_key: lint_ctxt.pak_key.clone(),
+                        all_archive_paks: lint_ctxt.all_archive_paks,
+                        cache_dir: None,
+                        modio_tags: HashMap::new(),
+                    },
+                )?;
+                for (spec, key, _) in keys.iter().filter(|(_, _, hit)| !hit) {
+                    self.mods
+                        .entry(key.clone())
+                        .or_default()
+                        .update(&fresh, spec);
+                }
+            }
+
+            for lint in &cached {
+                mark_run(&mut report, *lint);
+            }
+            for (spec, key, _) in &keys {
+                if let Some(entry) = self.mods.get(key) {
+                    entry.add_to(&mut report, spec);
+                }
+            }
+        }
+
+        if !uncached.is_empty() {
+            let rest = run_lints_in(
+                &uncached,
+                LintCtxt {
+                    cache_dir: None,
+                    ..lint_ctxt
+                },
+            )?;
+            report.conflicting_mods = rest.conflicting_mods;
+            report.outdated_pak_version_mods = rest.outdated_pak_version_mods;
+            report.overlapping_mount_points_mods = rest.overlapping_mount_points_mods;
+            report.untagged_gameplay_mods = rest.untagged_gameplay_mods;
+        }
+
+        // drop mods which were removed or changed since, and results for other options, so the
+        // cache only ever holds the mods of the last run
+        let used = keys
+            .into_iter()
+            .map(|(_, key, _)| key)
+            .collect::<HashSet<_>>();
+        self.mods.retain(|key, _| used.contains(key));
+
+        Ok(report)
+    }
+}
+
+/// Options affecting the results of the cached lints. The game pak is identified by its size and
+/// modification time to avoid hashing it, the pak key by its hash to keep it out of the cache.
+fn context_key(lint_ctxt: &LintCtxt) -> String {
+    let game = lint_ctxt
+        .fsd_pak_path
+        .as_ref()
+        .and_then(|path| fs::metadata(path).ok().map(|m| (path, m)))
+        .map(|(path, metadata)| {
+            let modified = metadata
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map(|d| d.as_secs())
+                .unwrap_or_default();
+            format!("{}:{}:{modified}", path.display(), metadata.len())
+        })
+        .unwrap_or_default();
+    let pak_key = lint_ctxt
+        .pak_key
+        .as_ref()
+        .map(|key| hex::encode(Sha256::digest(key.to_string())))
+        .unwrap_or_default();
+    format!(
+        "all_archive_paks={};pak_key={pak_key};game={game}",
+        lint_ctxt.all_archive_paks
+    )
+}
+
+fn mod_key(context: &str, path: &Path) -> Result<String, LintError> {
+    let mut hasher = Sha256::new();
+    hasher.update(context.as_bytes());
+    std::io::copy(&mut BufReader::new(fs::File::open(path)?), &mut hasher)?;
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Run the lints through the cache in `cache_dir`, saving it afterwards. Failing to save only
+/// costs the next run some time so it is not an error.
+pub(crate) fn run_cached(
+    cache_dir: &Path,
+    enabled_lints: &BTreeSet<LintId>,
+    lint_ctxt: LintCtxt,
+) -> Result<LintReport, LintError> {
+    let mut cache = LintCache::open(cache_dir);
+    let report = cache.run(enabled_lints, lint_ctxt)?;
+    if let Err(e) = cache.save() {
+        warn!("failed to save lint cache: {e}");
+    }
+    Ok(report)
+}
@@ -1,10 +1,12 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
 
 use indexmap::IndexSet;
 
 use crate::providers::ModSpecification;
+use crate::{pak_builder, PakKey};
 
-use super::{Lint, LintCtxt, LintError};
+use super::{open_mod_paks, Lint, LintCtxt, LintError};
 
 #[derive(Default)]
 pub struct ConflictingModsLint;
@@ -15,31 +17,84 @@ impl Lint for ConflictingModsLint {
     type Output = BTreeMap<String, IndexSet<ModSpecification>>;
 
     fn check_mods(&mut self, lcx: &LintCtxt) -> Result<Self::Output, LintError> {
-        let mut per_path_modifiers = BTreeMap::new();
-
-        lcx.for_each_mod_file(|mod_spec, _, _, _, normalized_path| {
-            per_path_modifiers
-                .entry(normalized_path)
-                .and_modify(|modifiers: &mut IndexSet<ModSpecification>| {
-                    modifiers.insert(mod_spec.clone());
-                })
-                .or_insert_with(|| [mod_spec.clone()].into());
-            Ok(())
-        })?;
-
-        let conflicting_mods = per_path_modifiers
-            .into_iter()
-            .filter(|(p, _)| {
-                for whitelisted_path in CONFLICTING_MODS_LINT_WHITELIST {
-                    if p.starts_with(whitelisted_path) {
-                        return false;
-                    }
+        find_conflicts(&lcx.mods, lcx.pak_key.as_ref(), lcx.all_archive_paks)
+    }
+}
+
+/// Files modified by more than one mod, keyed by normalized path.
+///
+/// Only the pak indexes are read, so this is cheap enough to run on every profile change. Paks of
+/// the same archive overriding each other's files are not conflicts.
+pub fn find_conflicts(
+    mods: &IndexSet<(ModSpecification, PathBuf)>,
+    pak_key: Option<&PakKey>,
+    all_archive_paks: bool,
+) -> Result<BTreeMap<String, IndexSet<ModSpecification>>, LintError> {
+    let conflicting_mods = collect_all_assets(mods, pak_key, all_archive_paks)?
+        .into_iter()
+        .filter(|(p, _)| {
+            for whitelisted_path in CONFLICTING_MODS_LINT_WHITELIST {
+                if p.starts_with(whitelisted_path) {
+                    return false;
                 }
-                true
-            })
-            .filter(|(_, modifiers)| modifiers.len() > 1)
-            .collect::<BTreeMap<String, IndexSet<ModSpecification>>>();
+            }
+            true
+        })
+        .filter(|(_, modifiers)| modifiers.len() > 1)
+        .collect::<BTreeMap<String, IndexSet<ModSpecification>>>();
+
+    Ok(conflicting_mods)
+}
+
+/// Every file of `mods`, keyed by normalized path, with the mods providing it in the order of
+/// `mods`. Integrating the mods in that order takes each file from the first of them.
+pub fn collect_all_assets(
+    mods: &IndexSet<(ModSpecification, PathBuf)>,
+    pak_key: Option<&PakKey>,
+    all_archive_paks: bool,
+) -> Result<BTreeMap<String, IndexSet<ModSpecification>>, LintError> {
+    let mut per_path_modifiers = BTreeMap::new();
+
+    for (mod_spec, mod_pak_path) in mods {
+        for mut reader in open_mod_paks(mod_pak_path, all_archive_paks)? {
+            let pak_reader = pak_builder(pak_key).reader(&mut reader)?;
+            let mount = PathBuf::from(pak_reader.mount_point());
+            for p in pak_reader.files() {
+                let path = mount.join(&p);
+                let normalized_path = path
+                    .strip_prefix("../../../")?
+                    .to_string_lossy()
+                    .replace('\\', "/")
+                    .to_ascii_lowercase();
+                per_path_modifiers
+                    .entry(normalized_path)
+                    .and_modify(|modifiers: &mut IndexSet<ModSpecification>| {
+                        modifiers.insert(mod_spec.clone());
+                    })
+                    .or_insert_with(|| [mod_spec.clone()].into());
+            }
+        }
+    }
+
+    Ok(per_path_modifiers)
+}
 
-        Ok(conflicting_mods)
+/// Files shared by every pair of mods which conflict, aggregated from the result of
+/// [`find_conflicts`]. The smaller spec of a pair comes first.
+pub fn conflict_pairs<'a>(
+    conflicts: impl IntoIterator<Item = (&'a String, &'a IndexSet<ModSpecification>)>,
+) -> BTreeMap<(ModSpecification, ModSpecification), BTreeSet<String>> {
+    let mut pairs: BTreeMap<_, BTreeSet<String>> = BTreeMap::new();
+    for (path, mods) in conflicts {
+        for (i, a) in mods.iter().enumerate() {
+            for b in mods.iter().skip(i + 1) {
+                let pair = if a < b { (a, b) } else { (b, a) };
+                pairs
+                    .entry((pair.0.clone(), pair.1.clone()))
+                    .or_default()
+                    .insert(path.clone());
+            }
+        }
     }
+    pairs
 }
@@ -0,0 +1,68 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::BufReader;
+use std::path::PathBuf;
+
+use fs_err as fs;
+
+use crate::pak_builder;
+use crate::providers::ModSpecification;
+
+use super::{open_mod_paks, Lint, LintCtxt, LintError};
+
+/// Files mint writes next to the game: the hook DLL under the name used by each installation type,
+/// see [`DRGInstallationType::hook_dll_name`](mint_lib::DRGInstallationType::hook_dll_name), the
+/// hook's log and the mods pak. Mods shipping them were usually packaged from a game directory
+/// mint had installed to.
+const ARTIFACT_NAMES: [&str; 4] = ["x3daudio1_7.dll", "d3d9.dll", "mint_hook.log", "mods_p.pak"];
+
+fn is_artifact(path: &str) -> bool {
+    let name = path.rsplit(['/', '\\']).next().unwrap_or(path);
+    ARTIFACT_NAMES
+        .iter()
+        .any(|artifact| name.eq_ignore_ascii_case(artifact))
+}
+
+#[derive(Default)]
+pub struct MintArtifactsLint;
+
+impl Lint for MintArtifactsLint {
+    type Output = BTreeMap<ModSpecification, BTreeSet<String>>;
+
+    fn check_mods(&mut self, lcx: &LintCtxt) -> Result<Self::Output, LintError> {
+        let mut mint_artifact_mods = BTreeMap::new();
+
+        for (mod_spec, mod_path) in &lcx.mods {
+            let mut artifacts = BTreeSet::new();
+
+            // archive entries which are not part of a pak, e.g. the DLL next to the mod's pak
+            let mut reader = BufReader::new(fs::File::open(mod_path)?);
+            if let Ok(archive) = zip::ZipArchive::new(&mut reader) {
+                artifacts.extend(
+                    archive
+                        .file_names()
+                        .filter(|name| is_artifact(name))
+                        .map(|name| name.replace('\\', "/").to_ascii_lowercase()),
+                );
+            }
+
+            for mut pak_read_seek in open_mod_paks(mod_path, lcx.all_archive_paks)? {
+                let pak_reader = pak_builder(lcx.pak_key.as_ref()).reader(&mut pak_read_seek)?;
+                let mount = PathBuf::from(pak_reader.mount_point());
+                for p in pak_reader.files() {
+                    let path = mount.join(&p);
+                    let path = path.strip_prefix("../../../")?;
+                    let normalized_path = path.to_string_lossy().replace('\\', "/");
+                    if is_artifact(&normalized_path) {
+                        artifacts.insert(normalized_path.to_ascii_lowercase());
+                    }
+                }
+            }
+
+            if !artifacts.is_empty() {
+                mint_artifact_mods.insert(mod_spec.clone(), artifacts);
+            }
+        }
+
+        Ok(mint_artifact_mods)
+    }
+}
@@ -1,36 +1,52 @@
 mod archive_multiple_paks;
 mod archive_only_non_pak_files;
 mod asset_register_bin;
+mod baseline;
+mod cache;
 mod conflicting_mods;
 mod empty_archive;
+mod mint_artifacts;
 mod non_asset_files;
 mod outdated_pak_version;
+mod overlapping_mount_points;
 mod shader_files;
 mod split_asset_pairs;
 mod unmodified_game_assets;
+mod untagged_gameplay;
 
-use std::collections::{BTreeMap, BTreeSet};
-use std::io::{BufReader, Cursor, Read};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::io::{BufReader, Cursor, Read, Seek};
 use std::path::{Path, PathBuf};
 
 use fs_err as fs;
 use indexmap::IndexSet;
+use mint_lib::mod_info::ModioTags;
 use repak::PakReader;
+use serde::Serialize;
 use snafu::prelude::*;
 use tracing::trace;
 
 use self::archive_multiple_paks::ArchiveMultiplePaksLint;
 use self::archive_only_non_pak_files::ArchiveOnlyNonPakFilesLint;
 use self::asset_register_bin::AssetRegisterBinLint;
+pub use self::baseline::{BaselineDiff, LintBaseline, LintFinding, LINT_BASELINE_VERSION};
+pub use self::cache::{LintCache, CACHED_LINTS, LINT_CACHE_FILE, LINT_CACHE_VERSION};
 use self::empty_archive::EmptyArchiveLint;
+use self::mint_artifacts::MintArtifactsLint;
 use self::non_asset_files::NonAssetFilesLint;
 use self::outdated_pak_version::OutdatedPakVersionLint;
+pub use self::overlapping_mount_points::MountPointOverlap;
+use self::overlapping_mount_points::OverlappingMountPointsLint;
 use self::shader_files::ShaderFilesLint;
-pub use self::split_asset_pairs::SplitAssetPair;
 use self::split_asset_pairs::SplitAssetPairsLint;
+pub use self::split_asset_pairs::{find_split_asset_pairs, SplitAssetPair};
 use self::unmodified_game_assets::UnmodifiedGameAssetsLint;
+use self::untagged_gameplay::UntaggedGameplayLint;
+use crate::integrate::pak_load_order_key;
 use crate::mod_lints::conflicting_mods::ConflictingModsLint;
+pub use crate::mod_lints::conflicting_mods::{collect_all_assets, conflict_pairs, find_conflicts};
 use crate::providers::{ModSpecification, ReadSeek};
+use crate::{pak_builder, PakKey};
 
 #[derive(Debug, Snafu)]
 pub enum LintError {
@@ -48,20 +64,59 @@ pub enum LintError {
     OnlyNonPakFiles,
     #[snafu(display("some lints require specifying a valid game pak path"))]
     InvalidGamePath,
+    #[snafu(display("failed to read or write lint baseline: {source}"))]
+    Baseline { source: serde_json::Error },
+    #[snafu(display("unsupported lint baseline version {version}"))]
+    UnsupportedBaselineVersion { version: u32 },
 }
 
 pub struct LintCtxt {
     pub(crate) mods: IndexSet<(ModSpecification, PathBuf)>,
     pub(crate) fsd_pak_path: Option<PathBuf>,
+    pub(crate) pak_key: Option<PakKey>,
+    /// Check every pak of archives with multiple paks instead of only the first.
+    pub(crate) all_archive_paks: bool,
+    /// Cache per mod results in this directory, see [`LintCache`].
+    pub(crate) cache_dir: Option<PathBuf>,
+    /// mod.io tags of the mods which have them.
+    pub(crate) modio_tags: HashMap<ModSpecification, ModioTags>,
 }
 
 impl LintCtxt {
     pub fn init(
         mods: IndexSet<(ModSpecification, PathBuf)>,
         fsd_pak_path: Option<PathBuf>,
+        pak_key: Option<PakKey>,
     ) -> Result<Self, LintError> {
         trace!("LintCtxt::init");
-        Ok(Self { mods, fsd_pak_path })
+        Ok(Self {
+            mods,
+            fsd_pak_path,
+            pak_key,
+            all_archive_paks: false,
+            cache_dir: None,
+            modio_tags: HashMap::new(),
+        })
+    }
+
+    /// Check every pak of archives with multiple paks in load order, see
+    /// [`pak_load_order_key`].
+    pub fn all_archive_paks(mut self, all_archive_paks: bool) -> Self {
+        self.all_archive_paks = all_archive_paks;
+        self
+    }
+
+    /// Skip mods whose content has been linted before by caching results in `cache_dir`.
+    pub fn cache_dir(mut self, cache_dir: Option<PathBuf>) -> Self {
+        self.cache_dir = cache_dir;
+        self
+    }
+
+    /// mod.io tags of the mods, for lints which compare the content of mods with what their tags
+    /// declare. Mods without tags are treated as untagged.
+    pub fn modio_tags(mut self, modio_tags: HashMap<ModSpecification, ModioTags>) -> Self {
+        self.modio_tags = modio_tags;
+        self
     }
 
     pub fn for_each_mod<F, EmptyArchiveHandler, OnlyNonPakFilesHandler, MultiplePakFilesHandler>(
@@ -100,8 +155,8 @@ impl LintCtxt {
 
             let mut individual_pak_readers = bufs
                 .into_iter()
-                .filter_map(|(_, pak_or_non_pak)| match pak_or_non_pak {
-                    PakOrNotPak::Pak(individual_pak_reader) => Some(individual_pak_reader),
+                .filter_map(|(path, pak_or_non_pak)| match pak_or_non_pak {
+                    PakOrNotPak::Pak(individual_pak_reader) => Some((path, individual_pak_reader)),
                     PakOrNotPak::NotPak => None,
                 })
                 .collect::<Vec<_>>();
@@ -112,9 +167,15 @@ impl LintCtxt {
                 }
             }
 
-            let mut first_pak_read_seek = individual_pak_readers.remove(0);
-            let pak_reader = repak::PakBuilder::new().reader(&mut first_pak_read_seek)?;
-            f(mod_spec.clone(), &mut first_pak_read_seek, &pak_reader)?
+            if self.all_archive_paks {
+                individual_pak_readers.sort_by_cached_key(|(path, _)| pak_load_order_key(path));
+            } else {
+                individual_pak_readers.truncate(1);
+            }
+            for (_, mut pak_read_seek) in individual_pak_readers {
+                let pak_reader = pak_builder(self.pak_key.as_ref()).reader(&mut pak_read_seek)?;
+                f(mod_spec.clone(), &mut pak_read_seek, &pak_reader)?
+            }
         }
 
         Ok(())
@@ -156,6 +217,42 @@ impl LintCtxt {
     }
 }
 
+/// Readers for the paks of a mod without extracting anything else. For archives this is the first
+/// pak inside, or with `all_archive_paks` every pak in load order. Empty if there is none.
+pub(crate) fn open_mod_paks(
+    path: &Path,
+    all_archive_paks: bool,
+) -> Result<Vec<Box<dyn ReadSeek>>, LintError> {
+    let mut data: Box<dyn ReadSeek> = Box::new(BufReader::new(fs::File::open(path)?));
+    if let Ok(mut archive) = zip::ZipArchive::new(&mut data) {
+        let mut paks = vec![];
+        for i in 0..archive.len() {
+            let mut file = archive
+                .by_index(i)
+                .map_err(|_| LintError::ZipArchiveError)?;
+            if let Some(p) = file.enclosed_name()
+                && file.is_file()
+                && p.extension().is_some_and(|e| e == "pak")
+            {
+                let mut buf = vec![];
+                file.read_to_end(&mut buf)?;
+                paks.push((
+                    pak_load_order_key(&p),
+                    Box::new(Cursor::new(buf)) as Box<dyn ReadSeek>,
+                ));
+                if !all_archive_paks {
+                    break;
+                }
+            }
+        }
+        paks.sort_by(|(a, _), (b, _)| a.cmp(b));
+        Ok(paks.into_iter().map(|(_, pak)| pak).collect())
+    } else {
+        data.rewind()?;
+        Ok(vec![data])
+    }
+}
+
 pub(crate) enum PakOrNotPak {
     Pak(Box<dyn ReadSeek>),
     NotPak,
@@ -253,6 +350,47 @@ impl LintId {
     pub const UNMODIFIED_GAME_ASSETS: Self = LintId {
         name: "unmodified_game_assets",
     };
+    pub const OVERLAPPING_MOUNT_POINTS: Self = LintId {
+        name: "overlapping_mount_points",
+    };
+    pub const MINT_ARTIFACTS: Self = LintId {
+        name: "mint_artifacts",
+    };
+    pub const UNTAGGED_GAMEPLAY: Self = LintId {
+        name: "untagged_gameplay",
+    };
+
+    pub const ALL: [Self; 13] = [
+        Self::CONFLICTING,
+        Self::ASSET_REGISTRY_BIN,
+        Self::SHADER_FILES,
+        Self::OUTDATED_PAK_VERSION,
+        Self::EMPTY_ARCHIVE,
+        Self::ARCHIVE_WITH_ONLY_NON_PAK_FILES,
+        Self::ARCHIVE_WITH_MULTIPLE_PAKS,
+        Self::NON_ASSET_FILES,
+        Self::SPLIT_ASSET_PAIRS,
+        Self::UNMODIFIED_GAME_ASSETS,
+        Self::OVERLAPPING_MOUNT_POINTS,
+        Self::MINT_ARTIFACTS,
+        Self::UNTAGGED_GAMEPLAY,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Whether the lint is only a hint to double check something rather than a likely problem,
+    /// e.g. because it is based on heuristics.
+    pub fn is_advisory(&self) -> bool {
+        *self == Self::UNTAGGED_GAMEPLAY
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|id| id.name.eq_ignore_ascii_case(name))
+    }
 }
 
 #[derive(Default, Debug)]
@@ -268,14 +406,252 @@ pub struct LintReport {
     pub split_asset_pairs_mods:
         Option<BTreeMap<ModSpecification, BTreeMap<String, SplitAssetPair>>>,
     pub unmodified_game_assets_mods: Option<BTreeMap<ModSpecification, BTreeSet<String>>>,
+    /// Keyed by `(broad, narrow)` mod pairs.
+    pub overlapping_mount_points_mods:
+        Option<BTreeMap<(ModSpecification, ModSpecification), MountPointOverlap>>,
+    /// Files of mint's hook and its output shipped by mods, see [`LintId::MINT_ARTIFACTS`].
+    pub mint_artifact_mods: Option<BTreeMap<ModSpecification, BTreeSet<String>>>,
+    /// Gameplay assets of mods which are neither required by all players nor verified, see
+    /// [`LintId::UNTAGGED_GAMEPLAY`]. Advisory only, as the assets are recognized by name.
+    pub untagged_gameplay_mods: Option<BTreeMap<ModSpecification, BTreeSet<String>>>,
+}
+
+impl LintReport {
+    /// Number of findings of every lint which was run.
+    pub fn finding_counts(&self) -> BTreeMap<LintId, usize> {
+        fn count<I: IntoIterator>(
+            counts: &mut BTreeMap<LintId, usize>,
+            id: LintId,
+            res: Option<I>,
+        ) {
+            if let Some(res) = res {
+                counts.insert(id, res.into_iter().count());
+            }
+        }
+        let mut counts = BTreeMap::new();
+        count(
+            &mut counts,
+            LintId::CONFLICTING,
+            self.conflicting_mods.as_ref(),
+        );
+        count(
+            &mut counts,
+            LintId::ASSET_REGISTRY_BIN,
+            self.asset_register_bin_mods.as_ref(),
+        );
+        count(
+            &mut counts,
+            LintId::SHADER_FILES,
+            self.shader_file_mods.as_ref(),
+        );
+        count(
+            &mut counts,
+            LintId::OUTDATED_PAK_VERSION,
+            self.outdated_pak_version_mods.as_ref(),
+        );
+        count(
+            &mut counts,
+            LintId::EMPTY_ARCHIVE,
+            self.empty_archive_mods.as_ref(),
+        );
+        count(
+            &mut counts,
+            LintId::ARCHIVE_WITH_ONLY_NON_PAK_FILES,
+            self.archive_with_only_non_pak_files_mods.as_ref(),
+        );
+        count(
+            &mut counts,
+            LintId::ARCHIVE_WITH_MULTIPLE_PAKS,
+            self.archive_with_multiple_paks_mods.as_ref(),
+        );
+        count(
+            &mut counts,
+            LintId::NON_ASSET_FILES,
+            self.non_asset_file_mods.as_ref(),
+        );
+        count(
+            &mut counts,
+            LintId::SPLIT_ASSET_PAIRS,
+            self.split_asset_pairs_mods.as_ref(),
+        );
+        count(
+            &mut counts,
+            LintId::UNMODIFIED_GAME_ASSETS,
+            self.unmodified_game_assets_mods.as_ref(),
+        );
+        count(
+            &mut counts,
+            LintId::OVERLAPPING_MOUNT_POINTS,
+            self.overlapping_mount_points_mods.as_ref(),
+        );
+        count(
+            &mut counts,
+            LintId::MINT_ARTIFACTS,
+            self.mint_artifact_mods.as_ref(),
+        );
+        count(
+            &mut counts,
+            LintId::UNTAGGED_GAMEPLAY,
+            self.untagged_gameplay_mods.as_ref(),
+        );
+        counts
+    }
+
+    /// The findings of every lint which was run, grouped by the mods they concern instead of by
+    /// lint. Each finding has a list of details such as the affected files, which is empty for
+    /// lints concerning the mod as a whole. Findings involving several mods, e.g. conflicts, are
+    /// listed under each of them.
+    pub fn by_mod(&self) -> BTreeMap<ModSpecification, BTreeMap<LintId, Vec<String>>> {
+        let mut by_mod: BTreeMap<ModSpecification, BTreeMap<LintId, Vec<String>>> = BTreeMap::new();
+        let mut add = |spec: &ModSpecification, id: LintId, details: Vec<String>| {
+            by_mod
+                .entry(spec.clone())
+                .or_default()
+                .entry(id)
+                .or_default()
+                .extend(details);
+        };
+        let paths = [
+            (LintId::ASSET_REGISTRY_BIN, &self.asset_register_bin_mods),
+            (LintId::SHADER_FILES, &self.shader_file_mods),
+            (LintId::NON_ASSET_FILES, &self.non_asset_file_mods),
+            (
+                LintId::UNMODIFIED_GAME_ASSETS,
+                &self.unmodified_game_assets_mods,
+            ),
+            (LintId::MINT_ARTIFACTS, &self.mint_artifact_mods),
+            (LintId::UNTAGGED_GAMEPLAY, &self.untagged_gameplay_mods),
+        ];
+        for (id, mods) in paths {
+            for (spec, paths) in mods.iter().flatten() {
+                add(spec, id, paths.iter().cloned().collect());
+            }
+        }
+        let archives = [
+            (LintId::EMPTY_ARCHIVE, &self.empty_archive_mods),
+            (
+                LintId::ARCHIVE_WITH_ONLY_NON_PAK_FILES,
+                &self.archive_with_only_non_pak_files_mods,
+            ),
+            (
+                LintId::ARCHIVE_WITH_MULTIPLE_PAKS,
+                &self.archive_with_multiple_paks_mods,
+            ),
+        ];
+        for (id, mods) in archives {
+            for spec in mods.iter().flatten() {
+                add(spec, id, vec![]);
+            }
+        }
+        for (path, mods) in self.conflicting_mods.iter().flatten() {
+            for spec in mods {
+                add(spec, LintId::CONFLICTING, vec![path.clone()]);
+            }
+        }
+        for (spec, version) in self.outdated_pak_version_mods.iter().flatten() {
+            add(
+                spec,
+                LintId::OUTDATED_PAK_VERSION,
+                vec![format!("pak version {version}")],
+            );
+        }
+        for (spec, files) in self.split_asset_pairs_mods.iter().flatten() {
+            let details = files
+                .iter()
+                .map(|(file, kind)| match kind {
+                    SplitAssetPair::MissingUasset => {
+                        format!("`{file}` missing matching .uasset file")
+                    }
+                    SplitAssetPair::MissingUexp => format!("`{file}` missing matching .uexp file"),
+                })
+                .collect();
+            add(spec, LintId::SPLIT_ASSET_PAIRS, details);
+        }
+        for ((broad, narrow), overlap) in self.overlapping_mount_points_mods.iter().flatten() {
+            add(
+                broad,
+                LintId::OVERLAPPING_MOUNT_POINTS,
+                vec![format!(
+                    "mounted at `{}`, overlaps {} (mounted at `{}`)",
+                    overlap.broad_mount_point, narrow.url, overlap.narrow_mount_point
+                )],
+            );
+            add(
+                narrow,
+                LintId::OVERLAPPING_MOUNT_POINTS,
+                vec![format!(
+                    "mounted at `{}`, overlapped by {} (mounted at `{}`)",
+                    overlap.narrow_mount_point, broad.url, overlap.broad_mount_point
+                )],
+            );
+        }
+        by_mod
+    }
+}
+
+/// Condensed outcome of a lint run, meant for CI.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LintSummary {
+    pub mods_scanned: usize,
+    /// Number of findings keyed by lint name.
+    pub findings: BTreeMap<&'static str, usize>,
+    /// Lints which had findings and were denied.
+    pub denied: BTreeSet<&'static str>,
+    pub passed: bool,
+    /// New and fixed findings if the run was compared against a baseline.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub baseline: Option<BaselineDiff>,
+}
+
+impl LintSummary {
+    pub fn new(report: &LintReport, mods_scanned: usize, deny: &BTreeSet<LintId>) -> Self {
+        let counts = report.finding_counts();
+        let denied: BTreeSet<_> = counts
+            .iter()
+            .filter(|(id, n)| **n > 0 && deny.contains(id))
+            .map(|(id, _)| id.name())
+            .collect();
+        Self {
+            mods_scanned,
+            findings: counts.into_iter().map(|(id, n)| (id.name(), n)).collect(),
+            passed: denied.is_empty(),
+            denied,
+            baseline: None,
+        }
+    }
+
+    /// Only deny lints with findings which are not part of the baseline `diff` was made against.
+    pub fn against_baseline(mut self, diff: BaselineDiff, deny: &BTreeSet<LintId>) -> Self {
+        self.denied = diff
+            .new
+            .iter()
+            .filter_map(|finding| LintId::from_name(&finding.lint))
+            .filter(|id| deny.contains(id))
+            .map(|id| id.name())
+            .collect();
+        self.passed = self.denied.is_empty();
+        self.baseline = Some(diff);
+        self
+    }
 }
 
 pub fn run_lints(
     enabled_lints: &BTreeSet<LintId>,
     mods: IndexSet<(ModSpecification, PathBuf)>,
     fsd_pak_path: Option<PathBuf>,
+    pak_key: Option<PakKey>,
 ) -> Result<LintReport, LintError> {
-    let lint_ctxt = LintCtxt::init(mods, fsd_pak_path)?;
+    run_lints_in(enabled_lints, LintCtxt::init(mods, fsd_pak_path, pak_key)?)
+}
+
+pub fn run_lints_in(
+    enabled_lints: &BTreeSet<LintId>,
+    lint_ctxt: LintCtxt,
+) -> Result<LintReport, LintError> {
+    if let Some(cache_dir) = lint_ctxt.cache_dir.clone() {
+        return cache::run_cached(&cache_dir, enabled_lints, lint_ctxt);
+    }
+
     let mut lint_report = LintReport::default();
 
     for lint_id in enabled_lints {
@@ -320,6 +696,18 @@ pub fn run_lints(
                 let res = UnmodifiedGameAssetsLint.check_mods(&lint_ctxt)?;
                 lint_report.unmodified_game_assets_mods = Some(res);
             }
+            LintId::OVERLAPPING_MOUNT_POINTS => {
+                let res = OverlappingMountPointsLint.check_mods(&lint_ctxt)?;
+                lint_report.overlapping_mount_points_mods = Some(res);
+            }
+            LintId::MINT_ARTIFACTS => {
+                let res = MintArtifactsLint.check_mods(&lint_ctxt)?;
+                lint_report.mint_artifact_mods = Some(res);
+            }
+            LintId::UNTAGGED_GAMEPLAY => {
+                let res = UntaggedGameplayLint.check_mods(&lint_ctxt)?;
+                lint_report.untagged_gameplay_mods = Some(res);
+            }
             _ => unimplemented!(),
         }
     }
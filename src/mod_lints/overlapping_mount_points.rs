@@ -0,0 +1,83 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use crate::providers::ModSpecification;
+
+use super::{Lint, LintCtxt, LintError};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MountPointOverlap {
+    pub broad_mount_point: String,
+    pub narrow_mount_point: String,
+    /// Files of the broadly mounted mod which fall inside the narrower mount point.
+    pub shadowed_files: BTreeSet<String>,
+}
+
+#[derive(Default)]
+pub struct OverlappingMountPointsLint;
+
+fn normalize(path: &Path) -> PathBuf {
+    PathBuf::from(
+        path.to_string_lossy()
+            .replace('\\', "/")
+            .to_ascii_lowercase(),
+    )
+}
+
+impl Lint for OverlappingMountPointsLint {
+    type Output = BTreeMap<(ModSpecification, ModSpecification), MountPointOverlap>;
+
+    fn check_mods(&mut self, lcx: &LintCtxt) -> Result<Self::Output, LintError> {
+        let mut mounts = vec![];
+
+        lcx.for_each_mod(
+            |mod_spec, _, pak_reader| {
+                let mount_point = pak_reader.mount_point().to_string();
+                let mount = PathBuf::from(&mount_point);
+                let normalized_mount = normalize(mount.strip_prefix("../../../")?);
+                let files = pak_reader
+                    .files()
+                    .into_iter()
+                    .map(|p| normalized_mount.join(normalize(Path::new(&p))))
+                    .collect::<Vec<_>>();
+                mounts.push((mod_spec, mount_point, normalized_mount, files));
+                Ok(())
+            },
+            None::<fn(ModSpecification)>,
+            None::<fn(ModSpecification)>,
+            None::<fn(ModSpecification)>,
+        )?;
+
+        let mut overlapping_mount_points = BTreeMap::new();
+
+        for (broad_spec, broad_mount_point, broad_mount, broad_files) in &mounts {
+            for (narrow_spec, narrow_mount_point, narrow_mount, _) in &mounts {
+                if broad_spec == narrow_spec
+                    || broad_mount == narrow_mount
+                    || !narrow_mount.starts_with(broad_mount)
+                {
+                    continue;
+                }
+
+                let shadowed_files = broad_files
+                    .iter()
+                    .filter(|f| f.starts_with(narrow_mount))
+                    .map(|f| f.to_string_lossy().to_string())
+                    .collect::<BTreeSet<_>>();
+
+                if !shadowed_files.is_empty() {
+                    overlapping_mount_points.insert(
+                        (broad_spec.clone(), narrow_spec.clone()),
+                        MountPointOverlap {
+                            broad_mount_point: broad_mount_point.clone(),
+                            narrow_mount_point: narrow_mount_point.clone(),
+                            shadowed_files,
+                        },
+                    );
+                }
+            }
+        }
+
+        Ok(overlapping_mount_points)
+    }
+}
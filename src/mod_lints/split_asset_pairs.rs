@@ -1,5 +1,6 @@
 use std::collections::{BTreeMap, BTreeSet};
 
+use serde::{Deserialize, Serialize};
 use tracing::trace;
 
 use crate::providers::ModSpecification;
@@ -9,87 +10,64 @@ use super::{Lint, LintCtxt, LintError};
 #[derive(Default)]
 pub struct SplitAssetPairsLint;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum SplitAssetPair {
     MissingUexp,
     MissingUasset,
 }
 
+/// Find the halves of `.uasset`/`.uexp` pairs among `paths` whose other half is missing. Paths are
+/// compared case-insensitively and returned as given.
+pub fn find_split_asset_pairs<'a>(
+    paths: impl IntoIterator<Item = &'a str>,
+) -> BTreeMap<String, SplitAssetPair> {
+    // lowercase path without extension => (uasset, uexp)
+    let mut pairs: BTreeMap<String, (Option<&str>, Option<&str>)> = BTreeMap::new();
+    for path in paths {
+        let Some((stem, ext)) = path.rsplit_once('.') else {
+            continue;
+        };
+        let pair = pairs.entry(stem.to_ascii_lowercase()).or_default();
+        match ext.to_ascii_lowercase().as_str() {
+            "uasset" => pair.0 = Some(path),
+            "uexp" => pair.1 = Some(path),
+            _ => {}
+        }
+    }
+
+    pairs
+        .into_values()
+        .filter_map(|pair| match pair {
+            (Some(uasset), None) => Some((uasset.to_string(), SplitAssetPair::MissingUexp)),
+            (None, Some(uexp)) => Some((uexp.to_string(), SplitAssetPair::MissingUasset)),
+            _ => None,
+        })
+        .collect()
+}
+
 impl Lint for SplitAssetPairsLint {
     type Output = BTreeMap<ModSpecification, BTreeMap<String, SplitAssetPair>>;
 
     fn check_mods(&mut self, lcx: &LintCtxt) -> Result<Self::Output, LintError> {
-        let mut per_mod_path_without_final_ext_to_exts_map = BTreeMap::new();
+        let mut per_mod_paths = BTreeMap::new();
 
         lcx.for_each_mod_file(|mod_spec, _, _, _, normalized_path| {
-            let mut iter = normalized_path.rsplit('.').take(2);
-            let Some(final_ext) = iter.next() else {
-                return Ok(());
-            };
-            let Some(path_without_final_ext) = iter.next() else {
-                return Ok(());
-            };
-
-            per_mod_path_without_final_ext_to_exts_map
+            per_mod_paths
                 .entry(mod_spec)
-                .and_modify(|map: &mut BTreeMap<String, BTreeSet<String>>| {
-                    map.entry(path_without_final_ext.to_string())
-                        .and_modify(|exts: &mut BTreeSet<String>| {
-                            exts.insert(final_ext.to_string());
-                        })
-                        .or_insert_with(|| [final_ext.to_string()].into());
-                })
-                .or_insert_with(|| {
-                    [(
-                        path_without_final_ext.to_string(),
-                        [final_ext.to_string()].into(),
-                    )]
-                    .into()
-                });
-
+                .or_insert_with(BTreeSet::new)
+                .insert(normalized_path);
             Ok(())
         })?;
 
-        let mut split_asset_pairs_mods = BTreeMap::new();
-
-        for (mod_spec, map) in per_mod_path_without_final_ext_to_exts_map {
-            for (path_without_final_ext, final_exts) in map {
-                split_asset_pairs_mods
-                    .entry(mod_spec.clone())
-                    .and_modify(|map: &mut BTreeMap<String, SplitAssetPair>| {
-                        match (final_exts.contains("uexp"), final_exts.contains("uasset")) {
-                            (true, false) => {
-                                map.insert(
-                                    format!("{path_without_final_ext}.uexp"),
-                                    SplitAssetPair::MissingUasset,
-                                );
-                            }
-                            (false, true) => {
-                                map.insert(
-                                    format!("{path_without_final_ext}.uasset"),
-                                    SplitAssetPair::MissingUexp,
-                                );
-                            }
-                            _ => {}
-                        }
-                    })
-                    .or_insert_with(|| {
-                        match (final_exts.contains("uexp"), final_exts.contains("uasset")) {
-                            (true, false) => [(
-                                format!("{path_without_final_ext}.uexp"),
-                                SplitAssetPair::MissingUasset,
-                            )]
-                            .into(),
-                            (false, true) => [(
-                                format!("{path_without_final_ext}.uasset"),
-                                SplitAssetPair::MissingUexp,
-                            )]
-                            .into(),
-                            _ => BTreeMap::default(),
-                        }
-                    });
-            }
-        }
+        let mut split_asset_pairs_mods = per_mod_paths
+            .into_iter()
+            .map(|(mod_spec, paths)| {
+                (
+                    mod_spec,
+                    find_split_asset_pairs(paths.iter().map(String::as_str)),
+                )
+            })
+            .collect::<BTreeMap<_, _>>();
 
         split_asset_pairs_mods.retain(|_, map| !map.is_empty());
 
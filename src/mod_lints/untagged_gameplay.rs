@@ -0,0 +1,65 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use mint_lib::mod_info::{ApprovalStatus, ModioTags, RequiredStatus};
+
+use crate::providers::ModSpecification;
+
+use super::{Lint, LintCtxt, LintError};
+
+/// Directories of the game containing enemies, missions, weapons and tools.
+const GAMEPLAY_DIRS: [&str; 3] = [
+    "fsd/content/enemies/",
+    "fsd/content/gameelements/",
+    "fsd/content/weaponsntools/",
+];
+
+/// Name prefixes of blueprints, data assets, enemy descriptors and upgrades, as opposed to meshes,
+/// textures and sounds which next to them only change how the game looks or sounds.
+const GAMEPLAY_PREFIXES: [&str; 4] = ["bp_", "da_", "ed_", "upg_"];
+
+/// Whether the normalized `path` looks like it changes gameplay. This is only a guess from the
+/// game's naming conventions: gameplay logic elsewhere is missed, and a blueprint which was only
+/// modified to swap a model is reported too.
+fn is_gameplay_asset(path: &str) -> bool {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    path.ends_with(".uasset")
+        && GAMEPLAY_DIRS.iter().any(|dir| path.starts_with(dir))
+        && GAMEPLAY_PREFIXES
+            .iter()
+            .any(|prefix| name.starts_with(prefix))
+}
+
+/// Whether the tags say the mod has to be installed by every player, or mod.io verified that it
+/// does not affect other players. Mods without tags, e.g. mods not hosted on mod.io, declare
+/// neither.
+fn declares_multiplayer_status(tags: Option<&ModioTags>) -> bool {
+    tags.is_some_and(|tags| {
+        tags.required_status == RequiredStatus::RequiredByAll
+            || tags.approval_status == ApprovalStatus::Verified
+    })
+}
+
+#[derive(Default)]
+pub struct UntaggedGameplayLint;
+
+impl Lint for UntaggedGameplayLint {
+    type Output = BTreeMap<ModSpecification, BTreeSet<String>>;
+
+    fn check_mods(&mut self, lcx: &LintCtxt) -> Result<Self::Output, LintError> {
+        let mut untagged_gameplay_mods = BTreeMap::new();
+
+        lcx.for_each_mod_file(|mod_spec, _, _, _, normalized_path| {
+            if is_gameplay_asset(&normalized_path)
+                && !declares_multiplayer_status(lcx.modio_tags.get(&mod_spec))
+            {
+                untagged_gameplay_mods
+                    .entry(mod_spec)
+                    .or_insert_with(BTreeSet::new)
+                    .insert(normalized_path);
+            }
+            Ok(())
+        })?;
+
+        Ok(untagged_gameplay_mods)
+    }
+}
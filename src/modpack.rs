@@ -0,0 +1,447 @@
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Component, Path, PathBuf};
+
+use fs_err as fs;
+use serde::{Deserialize, Serialize};
+use snafu::prelude::*;
+
+use crate::providers::{ModInfo, ModSpecification};
+use crate::state::{
+    unix_time_now, ModConfig, ModData_v0_1_0 as ModData, ModOrGroup, PriorityOverride,
+};
+
+const MODPACK_FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Snafu)]
+pub enum ModpackError {
+    #[snafu(transparent)]
+    IoError { source: std::io::Error },
+    #[snafu(display("failed to parse modpack: {source}"))]
+    Parse { source: serde_json::Error },
+    #[snafu(display("failed to parse modpack: {source}"))]
+    ParseToml { source: toml::de::Error },
+    #[snafu(display("unsupported modpack version {version}"))]
+    UnsupportedVersion { version: u32 },
+    #[snafu(display("profile {profile:?} does not exist"))]
+    ProfileNotFound { profile: String },
+}
+
+/// Portable description of a profile which another mint can import and install.
+///
+/// Modpacks are written as JSON, or as TOML for hand editing if the file name ends in `.toml`.
+///
+/// A modpack only references mods by URL, so whoever installs it downloads whatever those URLs
+/// point to at that time unless the versions were pinned. Local mods may be given relative to the
+/// modpack file so it can be shared together with the paks it needs.
+///
+/// Modpacks made with [`Modpack::export_profile`] also hold the disabled mods and groups of the
+/// profile so it can be imported as it is with [`ModData::import_profile`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Modpack {
+    pub version: u32,
+    pub name: String,
+    /// Description of the profile the modpack was created from.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    /// Groups which are disabled as a whole, see [`ModpackMod::group`].
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub disabled_groups: BTreeSet<String>,
+    pub mods: Vec<ModpackMod>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModpackMod {
+    pub spec: ModSpecification,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub priority_overrides: Vec<PriorityOverride>,
+    /// Disabled mods are kept to import the profile as it is but are not installed.
+    #[serde(default = "default_true", skip_serializing_if = "is_true")]
+    pub enabled: bool,
+    /// Group of the profile the mod is in. The mods of a group follow each other.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn is_true(value: &bool) -> bool {
+    *value
+}
+
+impl From<&ModConfig> for ModpackMod {
+    fn from(mc: &ModConfig) -> Self {
+        Self {
+            spec: mc.spec.clone(),
+            required: mc.required,
+            priority: mc.priority,
+            priority_overrides: mc.priority_overrides.clone(),
+            enabled: mc.enabled,
+            group: None,
+        }
+    }
+}
+
+impl ModpackMod {
+    pub fn to_mod_config(&self) -> ModConfig {
+        ModConfig {
+            spec: self.spec.clone(),
+            required: self.required,
+            enabled: self.enabled,
+            priority: self.priority,
+            display_name: None,
+            added: Some(unix_time_now()),
+            frozen_from: None,
+            priority_overrides: self.priority_overrides.clone(),
+        }
+    }
+}
+
+impl Modpack {
+    /// Collect the enabled mods of `profile`, including those of enabled groups.
+    pub fn from_profile(mod_data: &ModData, profile: &str) -> Result<Self, ModpackError> {
+        ensure!(
+            mod_data.profiles.contains_key(profile),
+            ProfileNotFoundSnafu { profile }
+        );
+
+        let mut mods = vec![];
+        mod_data.for_each_enabled_mod(profile, |mc| {
+            mods.push(ModpackMod::from(mc));
+        });
+
+        Ok(Self {
+            version: MODPACK_FORMAT_VERSION,
+            name: profile.to_string(),
+            description: mod_data.profiles[profile].description.clone(),
+            disabled_groups: BTreeSet::new(),
+            mods,
+        })
+    }
+
+    /// All of `profile` to share it as a profile rather than to install it. Unlike
+    /// [`Self::from_profile`] disabled mods are kept and the mods of groups are marked with their
+    /// group.
+    pub fn export_profile(mod_data: &ModData, profile: &str) -> Result<Self, ModpackError> {
+        let entries = &mod_data
+            .profiles
+            .get(profile)
+            .context(ProfileNotFoundSnafu { profile })?
+            .mods;
+
+        let mut mods = vec![];
+        let mut disabled_groups = BTreeSet::new();
+        for entry in entries {
+            match entry {
+                ModOrGroup::Individual(mc) => mods.push(ModpackMod::from(mc)),
+                ModOrGroup::Group {
+                    group_name,
+                    enabled,
+                } => {
+                    if !enabled {
+                        disabled_groups.insert(group_name.clone());
+                    }
+                    let members = mod_data.groups.get(group_name).map(|g| g.mods.iter());
+                    mods.extend(members.into_iter().flatten().map(|mc| ModpackMod {
+                        group: Some(group_name.clone()),
+                        ..ModpackMod::from(mc)
+                    }));
+                }
+            }
+        }
+
+        Ok(Self {
+            version: MODPACK_FORMAT_VERSION,
+            name: profile.to_string(),
+            description: mod_data.profiles[profile].description.clone(),
+            disabled_groups,
+            mods,
+        })
+    }
+
+    /// The mods to install, see [`Self::is_installed`].
+    pub fn installed(&self) -> impl Iterator<Item = &ModpackMod> {
+        self.mods.iter().filter(|m| self.is_installed(m))
+    }
+
+    /// Whether `m` is installed, i.e. neither disabled itself nor in a disabled group.
+    pub fn is_installed(&self, m: &ModpackMod) -> bool {
+        m.enabled
+            && !m
+                .group
+                .as_ref()
+                .is_some_and(|group| self.disabled_groups.contains(group))
+    }
+
+    /// Replace every mod with the exact version it currently resolves to so the modpack
+    /// reproduces this install even after the mods are updated upstream.
+    pub fn pin(&mut self, resolved: &HashMap<ModSpecification, ModInfo>) {
+        for m in &mut self.mods {
+            if let Some(info) = resolved.get(&m.spec) {
+                m.spec = ModSpecification::new(info.resolution.url.0.clone());
+            }
+        }
+    }
+
+    pub fn specs(&self) -> Vec<ModSpecification> {
+        self.installed().map(|m| m.spec.clone()).collect()
+    }
+
+    /// The mods to install as mods of a profile, see [`Self::installed`].
+    pub fn to_mod_configs(&self) -> Vec<ModConfig> {
+        self.installed().map(ModpackMod::to_mod_config).collect()
+    }
+
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<Self, ModpackError> {
+        let content = fs::read_to_string(path.as_ref())?;
+        let mut modpack = if is_toml(path.as_ref()) {
+            Self::from_toml(&content)?
+        } else {
+            Self::from_json(&content)?
+        };
+        if let Some(base) = std::path::absolute(path.as_ref())?.parent() {
+            modpack.resolve_relative_paths(base);
+        }
+        Ok(modpack)
+    }
+
+    /// Make relative paths of local mods absolute by resolving them against `base` instead of
+    /// the working directory. URLs are left as they are.
+    pub fn resolve_relative_paths(&mut self, base: &Path) {
+        for m in &mut self.mods {
+            let url = &m.spec.url;
+            // keep any transforms, see `ModSpecification::split_transforms`
+            let (path, transforms) = url.split_at(url.find('|').unwrap_or(url.len()));
+            if path.contains("://") || Path::new(path).is_absolute() {
+                continue;
+            }
+            let path = Path::new(path)
+                .components()
+                .filter(|c| *c != Component::CurDir)
+                .collect::<PathBuf>();
+            m.spec =
+                ModSpecification::new(format!("{}{transforms}", base.join(path).to_string_lossy()));
+        }
+    }
+
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> Result<(), ModpackError> {
+        let content = if is_toml(path.as_ref()) {
+            self.to_toml()
+        } else {
+            self.to_json()
+        };
+        fs::write(path, content)?;
+        Ok(())
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, ModpackError> {
+        let modpack: Self = serde_json::from_str(json).context(ParseSnafu)?;
+        modpack.check_version()
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    pub fn from_toml(content: &str) -> Result<Self, ModpackError> {
+        let modpack: Self = toml::from_str(content).context(ParseTomlSnafu)?;
+        modpack.check_version()
+    }
+
+    pub fn to_toml(&self) -> String {
+        toml::to_string_pretty(self).unwrap()
+    }
+
+    fn check_version(self) -> Result<Self, ModpackError> {
+        ensure!(
+            self.version <= MODPACK_FORMAT_VERSION,
+            UnsupportedVersionSnafu {
+                version: self.version
+            }
+        );
+        Ok(self)
+    }
+}
+
+fn is_toml(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("toml"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::state::mod_data_tests::{mod_config, mod_info};
+    use crate::state::{ModGroup, ModOrGroup, ModProfile_v0_1_0 as ModProfile};
+
+    fn modpack_mod(url: &str) -> ModpackMod {
+        ModpackMod::from(&mod_config(url))
+    }
+
+    #[test]
+    fn test_modpack_roundtrip() {
+        let mod_data = ModData {
+            active_profile: "default".to_string(),
+            profiles: [(
+                "default".to_string(),
+                ModProfile {
+                    mods: vec![
+                        ModOrGroup::Individual(mod_config("a")),
+                        ModOrGroup::Individual(ModConfig {
+                            enabled: false,
+                            ..mod_config("b")
+                        }),
+                        ModOrGroup::Group {
+                            group_name: "g".to_string(),
+                            enabled: true,
+                        },
+                    ],
+                    description: "hardcore co-op build".to_string(),
+                    ..Default::default()
+                },
+            )]
+            .into(),
+            groups: [(
+                "g".to_string(),
+                ModGroup {
+                    mods: vec![mod_config("c")],
+                },
+            )]
+            .into(),
+            profile_order: vec![],
+        };
+
+        assert!(Modpack::from_profile(&mod_data, "missing").is_err());
+        assert!(Modpack::export_profile(&mod_data, "missing").is_err());
+
+        let exported = Modpack::export_profile(&mod_data, "default").unwrap();
+        assert_eq!(
+            exported
+                .mods
+                .iter()
+                .map(|m| (m.spec.url.as_str(), m.enabled, m.group.as_deref()))
+                .collect::<Vec<_>>(),
+            [
+                ("a", true, None),
+                ("b", false, None),
+                ("c", true, Some("g"))
+            ]
+        );
+        assert_eq!(
+            exported.specs(),
+            Modpack::from_profile(&mod_data, "default").unwrap().specs()
+        );
+
+        let mut modpack = Modpack::from_profile(&mod_data, "default").unwrap();
+        assert_eq!(modpack.description, "hardcore co-op build");
+        assert_eq!(
+            modpack.specs(),
+            vec![
+                ModSpecification::new("a".to_string()),
+                ModSpecification::new("c".to_string())
+            ]
+        );
+
+        let resolved = [(ModSpecification::new("a".to_string()), mod_info("a", "a#1"))].into();
+        modpack.pin(&resolved);
+        assert_eq!(modpack.mods[0].spec.url, "a#1");
+        assert_eq!(modpack.mods[1].spec.url, "c");
+
+        assert_eq!(Modpack::from_json(&modpack.to_json()).unwrap(), modpack);
+
+        let mut future = modpack.clone();
+        future.version = MODPACK_FORMAT_VERSION + 1;
+        assert!(Modpack::from_json(&future.to_json()).is_err());
+    }
+
+    #[test]
+    fn test_modpack_toml() {
+        let modpack = Modpack {
+            version: MODPACK_FORMAT_VERSION,
+            name: "default".to_string(),
+            description: "hardcore co-op build".to_string(),
+            disabled_groups: ["extras".to_string()].into(),
+            mods: vec![
+                ModpackMod {
+                    required: true,
+                    ..modpack_mod("https://mod.io/g/drg/m/a#1/2")
+                },
+                ModpackMod {
+                    priority: -1,
+                    priority_overrides: vec![PriorityOverride::new("FSD/Content/UI", 2).unwrap()],
+                    enabled: false,
+                    group: Some("extras".to_string()),
+                    ..modpack_mod("https://example.com/b.zip")
+                },
+            ],
+        };
+        assert_eq!(modpack.specs(), [modpack.mods[0].spec.clone()]);
+        assert_eq!(Modpack::from_toml(&modpack.to_toml()).unwrap(), modpack);
+
+        // hand written, relying on the defaults
+        let written = Modpack::from_toml(
+            r#"
+            version = 1
+            name = "hand written"
+
+            [[mods]]
+            spec = { url = "https://example.com/b.zip" }
+            "#,
+        )
+        .unwrap();
+        assert!(written.description.is_empty());
+        assert_eq!(written.mods[0].priority, 0);
+        assert!(!written.mods[0].required);
+        assert!(written.mods[0].enabled);
+
+        let mut future = modpack.clone();
+        future.version = MODPACK_FORMAT_VERSION + 1;
+        assert!(Modpack::from_toml(&future.to_toml()).is_err());
+        assert!(Modpack::from_toml("version = ").is_err());
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("default.mint.toml");
+        modpack.write(&path).unwrap();
+        assert!(fs::read_to_string(&path).unwrap().contains("[[mods]]"));
+        assert_eq!(Modpack::read(&path).unwrap(), modpack);
+    }
+
+    #[test]
+    fn test_modpack_relative_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let absolute = dir.path().join("elsewhere.pak");
+        fs::create_dir(dir.path().join("mods")).unwrap();
+        fs::write(dir.path().join("mods/foo.pak"), b"").unwrap();
+
+        let modpack = Modpack {
+            version: MODPACK_FORMAT_VERSION,
+            name: "local".to_string(),
+            description: String::new(),
+            disabled_groups: BTreeSet::new(),
+            mods: vec![
+                modpack_mod("./mods/foo.pak"),
+                modpack_mod("mods/foo.pak|repack"),
+                modpack_mod(&absolute.to_string_lossy()),
+                modpack_mod("https://mod.io/g/drg/m/a#1/2"),
+            ],
+        };
+        let path = dir.path().join("local.mint.json");
+        modpack.write(&path).unwrap();
+
+        let read = Modpack::read(&path).unwrap();
+        let foo = dir.path().join("mods").join("foo.pak");
+        assert!(Path::new(&read.mods[0].spec.url).exists());
+        assert_eq!(read.mods[0].spec.url, foo.to_string_lossy());
+        assert_eq!(
+            read.mods[1].spec.url,
+            format!("{}|repack", foo.to_string_lossy())
+        );
+        assert_eq!(read.mods[2].spec.url, absolute.to_string_lossy());
+        assert_eq!(read.mods[3].spec.url, "https://mod.io/g/drg/m/a#1/2");
+    }
+}
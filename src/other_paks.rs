@@ -0,0 +1,127 @@
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+
+use fs_err as fs;
+use mint_lib::DRGInstallation;
+
+/// Name of the pak mint installs mods to, see [`DRGInstallation::mods_pak`].
+const MODS_PAK: &str = "mods_p.pak";
+
+/// A patch pak in the game's Paks directory which mint did not write, e.g. one installed by
+/// another mod manager or by hand. The game loads it together with mint's `mods_P.pak`, so files
+/// contained in both come from whichever the game gives a higher priority.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OtherPak {
+    /// Path relative to the Paks directory.
+    pub path: PathBuf,
+    /// Number before the `_P` of the name, e.g. 2 for `Other_2_P.pak`.
+    pub suffix: Option<u32>,
+}
+
+impl OtherPak {
+    fn new(path: PathBuf) -> Option<Self> {
+        let name = path.file_name()?.to_string_lossy().to_ascii_lowercase();
+        let stem = name.strip_suffix("_p.pak")?;
+        let suffix = stem
+            .rsplit_once('_')
+            .and_then(|(_, number)| number.parse().ok());
+        Some(Self { path, suffix })
+    }
+
+    /// Whether the game gives the pak a higher priority than `mods_P.pak`, so its files override
+    /// the installed mods. The game prioritizes patch paks by the number before `_P` starting at
+    /// 1, while paks without a number, or with 0, share the lowest patch priority with
+    /// `mods_P.pak`.
+    pub fn overrides_mods(&self) -> bool {
+        self.suffix.is_some_and(|suffix| suffix >= 1)
+    }
+}
+
+impl Display for OtherPak {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let path = self.path.display();
+        match self.suffix {
+            Some(suffix) if self.overrides_mods() => write!(
+                f,
+                "{path} (suffix {suffix}) has a higher priority than mods_P.pak, its files override the installed mods"
+            ),
+            _ => write!(
+                f,
+                "{path} has the same priority as mods_P.pak, which of them wins for files they both contain is undefined. Add a number before `_P`, e.g. `_1_P.pak`, to let it override the installed mods"
+            ),
+        }
+    }
+}
+
+/// Find patch paks in the Paks directory of `installation`, including its subdirectories, which
+/// were not written by mint. Paks are sorted by path.
+pub fn find_other_paks(installation: &DRGInstallation) -> std::io::Result<Vec<OtherPak>> {
+    let paks_path = installation.paks_path();
+    let mut paks = vec![];
+    find_in(&paks_path, &paks_path, &mut paks)?;
+    paks.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(paks)
+}
+
+fn find_in(root: &Path, dir: &Path, paks: &mut Vec<OtherPak>) -> std::io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if entry.file_type()?.is_dir() {
+            find_in(root, &path, paks)?;
+            continue;
+        }
+        let relative = path.strip_prefix(root).unwrap_or(&path).to_path_buf();
+        if relative.to_string_lossy().to_ascii_lowercase() == MODS_PAK {
+            continue;
+        }
+        paks.extend(OtherPak::new(relative));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_find_other_paks() {
+        let dir = tempfile::tempdir().unwrap();
+        let paks = dir.path().join("FSD/Content/Paks");
+        fs::create_dir_all(paks.join("LogicMods")).unwrap();
+        for name in [
+            "FSD-WindowsNoEditor.pak",
+            "mods_P.pak",
+            "Other_P.pak",
+            "zz_Loader_2_P.pak",
+            "LogicMods/Logic_0_P.pak",
+            "notes_P.txt",
+        ] {
+            fs::write(paks.join(name), b"").unwrap();
+        }
+
+        let installation =
+            DRGInstallation::from_pak_path(paks.join("FSD-WindowsNoEditor.pak")).unwrap();
+        let found = find_other_paks(&installation).unwrap();
+        assert_eq!(
+            found,
+            [
+                OtherPak {
+                    path: Path::new("LogicMods").join("Logic_0_P.pak"),
+                    suffix: Some(0),
+                },
+                OtherPak {
+                    path: "Other_P.pak".into(),
+                    suffix: None,
+                },
+                OtherPak {
+                    path: "zz_Loader_2_P.pak".into(),
+                    suffix: Some(2),
+                },
+            ]
+        );
+        assert!(!found[0].overrides_mods());
+        assert!(!found[1].overrides_mods());
+        assert!(found[2].overrides_mods());
+    }
+}
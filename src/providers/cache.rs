@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 use std::ops::{Deref, DerefMut};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, RwLock};
 
 use fs_err as fs;
@@ -35,13 +36,13 @@ impl Cache {
             .is_none()
     }
 
-    pub(super) fn get<T: ModProviderCache + 'static>(&self, id: &str) -> Option<&T> {
+    pub fn get<T: ModProviderCache + 'static>(&self, id: &str) -> Option<&T> {
         self.cache
             .get(id)
             .and_then(|c| c.as_any().downcast_ref::<T>())
     }
 
-    pub(super) fn get_mut<T: ModProviderCache + 'static>(&mut self, id: &str) -> &mut T {
+    pub fn get_mut<T: ModProviderCache + 'static>(&mut self, id: &str) -> &mut T {
         if self.has::<T>(id) {
             self.cache.insert(id.to_owned(), Box::new(T::new()));
         }
@@ -196,6 +197,8 @@ pub struct BlobCacheError {
 #[derive(Debug, Clone)]
 pub struct BlobCache {
     path: PathBuf,
+    /// Bytes written through this handle and its clones, see [`Self::counting`].
+    written: Arc<AtomicU64>,
 }
 
 impl BlobCache {
@@ -203,10 +206,25 @@ impl BlobCache {
         fs::create_dir(&path).ok();
         Self {
             path: path.as_ref().to_path_buf(),
+            written: Default::default(),
         }
     }
 
-    pub(super) fn write(&self, blob: &[u8]) -> Result<BlobRef, BlobCacheError> {
+    /// A handle to the same cache which counts the bytes written through it separately, to tell
+    /// whether a fetch had to write anything.
+    pub(super) fn counting(&self) -> Self {
+        Self {
+            path: self.path.clone(),
+            written: Default::default(),
+        }
+    }
+
+    /// Bytes written through this handle, see [`Self::counting`].
+    pub(super) fn written(&self) -> u64 {
+        self.written.load(Ordering::Relaxed)
+    }
+
+    pub fn write(&self, blob: &[u8]) -> Result<BlobRef, BlobCacheError> {
         use sha2::{Digest, Sha256};
 
         let mut hasher = Sha256::new();
@@ -216,12 +234,31 @@ impl BlobCache {
         let tmp = self.path.join(format!(".{hash}"));
         fs::write(&tmp, blob).context(BlobCacheSnafu { kind: "write" })?;
         fs::rename(tmp, self.path.join(&hash)).context(BlobCacheSnafu { kind: "rename" })?;
+        self.written.fetch_add(blob.len() as u64, Ordering::Relaxed);
 
         Ok(BlobRef(hash))
     }
 
-    pub(super) fn get_path(&self, blob: &BlobRef) -> Option<PathBuf> {
+    pub fn get_path(&self, blob: &BlobRef) -> Option<PathBuf> {
         let path = self.path.join(&blob.0);
         path.exists().then_some(path)
     }
+
+    pub(super) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Remove every blob. Providers fetch missing blobs again on demand, so this is always safe.
+    pub(super) fn clear(&self) -> Result<(), BlobCacheError> {
+        for entry in fs::read_dir(&self.path).context(BlobCacheSnafu { kind: "read" })? {
+            let path = entry.context(BlobCacheSnafu { kind: "read" })?.path();
+            if path.is_dir() {
+                fs::remove_dir_all(path)
+            } else {
+                fs::remove_file(path)
+            }
+            .context(BlobCacheSnafu { kind: "remove" })?;
+        }
+        Ok(())
+    }
 }
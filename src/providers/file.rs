@@ -5,8 +5,8 @@ use std::sync::Arc;
 use tokio::sync::mpsc::Sender;
 
 use super::{
-    BlobCache, FetchProgress, ModInfo, ModProvider, ModResolution, ModResponse, ModSpecification,
-    ProviderCache, ProviderError,
+    BlobCache, FetchProgress, ModInfo, ModProvider, ModResolution, ModResponse, ModSearchResult,
+    ModSpecification, PathFilter, ProviderCache, ProviderError,
 };
 
 inventory::submit! {
@@ -19,17 +19,23 @@ inventory::submit! {
 }
 
 #[derive(Debug)]
-pub struct FileProvider {}
+pub struct FileProvider {
+    filter: PathFilter,
+}
 
 impl FileProvider {
     pub fn new_provider(
-        _parameters: &HashMap<String, String>,
+        parameters: &HashMap<String, String>,
     ) -> Result<Arc<dyn ModProvider>, ProviderError> {
-        Ok(Arc::new(Self::new()))
+        Ok(Arc::new(Self {
+            filter: PathFilter::from_parameters(parameters),
+        }))
     }
 
     pub fn new() -> Self {
-        Self {}
+        Self {
+            filter: PathFilter::default(),
+        }
     }
 }
 
@@ -63,6 +69,8 @@ impl ModProvider for FileProvider {
             suggested_dependencies: vec![],
             modio_tags: None,
             modio_id: None,
+            modio_stats: None,
+            description: None,
         }))
     }
 
@@ -71,9 +79,22 @@ impl ModProvider for FileProvider {
         res: &ModResolution,
         _update: bool,
         _cache: ProviderCache,
-        _blob_cache: &BlobCache,
+        blob_cache: &BlobCache,
         tx: Option<Sender<FetchProgress>>,
     ) -> Result<PathBuf, ProviderError> {
+        let path = if self.filter.is_empty() {
+            PathBuf::from(&res.url.0)
+        } else {
+            // the local file may change at any time so it is filtered again on every fetch
+            let data = self.filter.apply_file(&res.url.0).map_err(|e| {
+                ProviderError::PathFilterFailed {
+                    source: e.into(),
+                    url: res.url.0.to_string(),
+                }
+            })?;
+            let blob = blob_cache.write(&data)?;
+            blob_cache.get_path(&blob).unwrap()
+        };
         if let Some(tx) = tx {
             tx.send(FetchProgress::Complete {
                 resolution: res.clone(),
@@ -81,7 +102,7 @@ impl ModProvider for FileProvider {
             .await
             .unwrap();
         }
-        Ok(PathBuf::from(&res.url.0))
+        Ok(path)
     }
 
     async fn update_cache(&self, _cache: ProviderCache) -> Result<(), ProviderError> {
@@ -92,6 +113,10 @@ impl ModProvider for FileProvider {
         Ok(())
     }
 
+    async fn search_mods(&self, _query: &str) -> Result<Vec<ModSearchResult>, ProviderError> {
+        Ok(vec![])
+    }
+
     fn get_mod_info(&self, spec: &ModSpecification, _cache: ProviderCache) -> Option<ModInfo> {
         let path = Path::new(&spec.url);
         let name = path
@@ -113,6 +138,8 @@ impl ModProvider for FileProvider {
             suggested_dependencies: vec![],
             modio_tags: None,
             modio_id: None,
+            modio_stats: None,
+            description: None,
         })
     }
 
@@ -123,4 +150,8 @@ impl ModProvider for FileProvider {
     fn get_version_name(&self, _spec: &ModSpecification, _cache: ProviderCache) -> Option<String> {
         Some("latest".to_string())
     }
+
+    fn get_version(&self, _spec: &ModSpecification, _cache: ProviderCache) -> Option<String> {
+        None
+    }
 }
@@ -1,7 +1,7 @@
 use std::sync::OnceLock;
 
 use serde::{Deserialize, Serialize};
-use tracing::info;
+use tracing::{info, warn};
 
 use crate::providers::*;
 
@@ -9,12 +9,7 @@ inventory::submit! {
     super::ProviderFactory {
         id: "http",
         new: HttpProvider::new_provider,
-        can_provide: |url| -> bool {
-            re_mod()
-                .captures(url)
-                .and_then(|c| c.name("hostname"))
-                .is_some_and(|h| !["mod.io", "drg.mod.io", "drg.old.mod.io"].contains(&h.as_str()))
-        },
+        can_provide: |url| is_http_mod_url(url) && !super::http_index::is_index_url(url),
         parameters: &[],
     }
 }
@@ -42,18 +37,23 @@ impl ModProviderCache for HttpProviderCache {
 #[derive(Debug)]
 pub struct HttpProvider {
     client: reqwest::Client,
+    filter: PathFilter,
 }
 
 impl HttpProvider {
     pub fn new_provider(
-        _parameters: &HashMap<String, String>,
+        parameters: &HashMap<String, String>,
     ) -> Result<Arc<dyn ModProvider>, ProviderError> {
-        Ok(Arc::new(Self::new()))
+        Ok(Arc::new(Self {
+            client: reqwest::Client::new(),
+            filter: PathFilter::from_parameters(parameters),
+        }))
     }
 
     pub fn new() -> Self {
         Self {
             client: reqwest::Client::new(),
+            filter: PathFilter::default(),
         }
     }
 }
@@ -63,8 +63,37 @@ fn re_mod() -> &'static regex::Regex {
     RE_MOD.get_or_init(|| regex::Regex::new(r"^https?://(?P<hostname>[^/]+)(/|$)").unwrap())
 }
 
+/// Whether `url` is an HTTP URL on a host other than mod.io.
+pub(super) fn is_http_mod_url(url: &str) -> bool {
+    re_mod()
+        .captures(url)
+        .and_then(|c| c.name("hostname"))
+        .is_some_and(|h| {
+            !["mod.io", "www.mod.io", "drg.mod.io", "drg.old.mod.io"].contains(&h.as_str())
+        })
+}
+
 const HTTP_PROVIDER_ID: &str = "http";
 
+const EXPECTED_CONTENT_TYPES: [&str; 2] = ["application/zip", "application/octet-stream"];
+
+/// Check whether `data` looks like a zip or pak regardless of what content type it was served
+/// with. Zips start with a local file header (or end of central directory record if empty) while
+/// paks end with a footer containing the pak magic.
+fn is_mod_archive(data: &[u8]) -> bool {
+    const ZIP_MAGIC: [&[u8]; 2] = [b"PK\x03\x04", b"PK\x05\x06"];
+    const PAK_MAGIC: [u8; 4] = 0x5A6F12E1u32.to_le_bytes();
+    // largest pak footer across all versions is well under this
+    const PAK_FOOTER_SEARCH_LEN: usize = 256;
+
+    if ZIP_MAGIC.iter().any(|magic| data.starts_with(magic)) {
+        return true;
+    }
+    data[data.len().saturating_sub(PAK_FOOTER_SEARCH_LEN)..]
+        .windows(PAK_MAGIC.len())
+        .any(|w| w == PAK_MAGIC)
+}
+
 #[async_trait::async_trait]
 impl ModProvider for HttpProvider {
     async fn resolve_mod(
@@ -95,6 +124,8 @@ impl ModProvider for HttpProvider {
             suggested_dependencies: vec![],
             modio_tags: None,
             modio_id: None,
+            modio_stats: None,
+            description: None,
         }))
     }
 
@@ -107,6 +138,7 @@ impl ModProvider for HttpProvider {
         tx: Option<Sender<FetchProgress>>,
     ) -> Result<PathBuf, ProviderError> {
         let url = &res.url;
+        let cache_key = self.filter.cache_key(&url.0);
         Ok(
             if let Some(path) = if update {
                 None
@@ -115,7 +147,7 @@ impl ModProvider for HttpProvider {
                     .read()
                     .unwrap()
                     .get::<HttpProviderCache>(HTTP_PROVIDER_ID)
-                    .and_then(|c| c.url_blobs.get(&url.0))
+                    .and_then(|c| c.url_blobs.get(&cache_key))
                     .and_then(|r| blob_cache.get_path(r))
             } {
                 if let Some(tx) = tx {
@@ -127,71 +159,88 @@ impl ModProvider for HttpProvider {
                 }
                 path
             } else {
-                info!("downloading mod {url:?}...");
-                let response = self
-                    .client
-                    .get(&url.0)
-                    .send()
-                    .await
-                    .context(RequestFailedSnafu {
-                        url: url.0.to_string(),
-                    })?
-                    .error_for_status()
-                    .context(ResponseSnafu {
-                        url: url.0.to_string(),
-                    })?;
-                let size = response.content_length(); // TODO will be incorrect if compressed
-                if let Some(mime) = response
-                    .headers()
-                    .get(reqwest::header::HeaderName::from_static("content-type"))
-                {
-                    let content_type = mime.to_str().context(InvalidMimeSnafu {
-                        url: url.0.to_string(),
-                    })?;
+                let mut attempt = 1;
+                let (data, unexpected_content_type) = loop {
+                    info!("downloading mod {url:?}...");
+                    let response = self
+                        .client
+                        .get(&url.0)
+                        .send()
+                        .await
+                        .context(RequestFailedSnafu {
+                            url: url.0.to_string(),
+                        })?
+                        .error_for_status()
+                        .context(ResponseSnafu {
+                            url: url.0.to_string(),
+                        })?;
+                    // reqwest is built without decompression so this is the size of the body
+                    let size = response.content_length();
+                    // some hosts serve mods with a generic content type so rather than failing
+                    // outright, check the downloaded data before rejecting it
+                    let unexpected_content_type = match response
+                        .headers()
+                        .get(reqwest::header::HeaderName::from_static("content-type"))
+                    {
+                        Some(mime) => {
+                            let content_type = mime.to_str().context(InvalidMimeSnafu {
+                                url: url.0.to_string(),
+                            })?;
+                            (!EXPECTED_CONTENT_TYPES.contains(&content_type))
+                                .then(|| content_type.to_string())
+                        }
+                        None => None,
+                    };
+
+                    let result =
+                        read_download(response.bytes_stream(), res, size, tx.as_ref(), |source| {
+                            ProviderError::FetchError {
+                                source,
+                                url: url.0.to_string(),
+                            }
+                        })
+                        .await;
+                    match result {
+                        Err(e @ ProviderError::DownloadSizeMismatch { .. })
+                            if attempt < DOWNLOAD_ATTEMPTS =>
+                        {
+                            warn!("{e}, downloading again");
+                            attempt += 1;
+                        }
+                        result => break (result?, unexpected_content_type),
+                    }
+                };
+
+                if let Some(found_content_type) = unexpected_content_type {
                     ensure!(
-                        ["application/zip", "application/octet-stream"].contains(&content_type),
+                        is_mod_archive(&data),
                         UnexpectedContentTypeSnafu {
-                            found_content_type: content_type.to_string(),
+                            found_content_type,
                             url: url.0.to_string(),
                         }
                     );
+                    info!("{url:?} served unexpected content type {found_content_type:?} but looks like a mod archive");
                 }
 
-                use futures::stream::TryStreamExt;
-                use tokio::io::AsyncWriteExt;
-
-                let mut cursor = std::io::Cursor::new(vec![]);
-                let mut stream = response.bytes_stream();
-                while let Some(bytes) = stream.try_next().await.with_context(|_| FetchSnafu {
-                    url: url.0.to_string(),
-                })? {
-                    cursor
-                        .write_all(&bytes)
-                        .await
-                        .with_context(|_| BufferIoSnafu {
+                let data = if self.filter.is_empty() {
+                    data
+                } else {
+                    self.filter
+                        .apply(data)
+                        .map_err(|e| ProviderError::PathFilterFailed {
+                            source: e.into(),
                             url: url.0.to_string(),
-                        })?;
-                    if let Some(size) = size {
-                        if let Some(tx) = &tx {
-                            tx.send(FetchProgress::Progress {
-                                resolution: res.clone(),
-                                progress: cursor.get_ref().len() as u64,
-                                size,
-                            })
-                            .await
-                            .unwrap();
-                        }
-                    }
-                }
+                        })?
+                };
 
-                let blob = blob_cache.write(&cursor.into_inner())?;
+                let blob = blob_cache.write(&data)?;
                 let path = blob_cache.get_path(&blob).unwrap();
                 cache
                     .write()
                     .unwrap()
                     .get_mut::<HttpProviderCache>(HTTP_PROVIDER_ID)
                     .url_blobs
-                    .insert(url.0.to_owned(), blob);
+                    .insert(cache_key, blob);
 
                 if let Some(tx) = tx {
                     tx.send(FetchProgress::Complete {
@@ -213,6 +262,10 @@ impl ModProvider for HttpProvider {
         Ok(())
     }
 
+    async fn search_mods(&self, _query: &str) -> Result<Vec<ModSearchResult>, ProviderError> {
+        Ok(vec![])
+    }
+
     fn get_mod_info(&self, spec: &ModSpecification, _cache: ProviderCache) -> Option<ModInfo> {
         let url = url::Url::parse(&spec.url).ok()?;
         let name = url
@@ -230,6 +283,8 @@ impl ModProvider for HttpProvider {
             suggested_dependencies: vec![],
             modio_tags: None,
             modio_id: None,
+            modio_stats: None,
+            description: None,
         })
     }
 
@@ -240,4 +295,38 @@ impl ModProvider for HttpProvider {
     fn get_version_name(&self, _spec: &ModSpecification, _cache: ProviderCache) -> Option<String> {
         Some("latest".to_string())
     }
+
+    fn get_version(&self, _spec: &ModSpecification, _cache: ProviderCache) -> Option<String> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::is_mod_archive;
+
+    #[test]
+    fn test_sniff_zip() {
+        assert!(is_mod_archive(include_bytes!(
+            "../../test_assets/lints/multiple_paks.zip"
+        )));
+        assert!(is_mod_archive(include_bytes!(
+            "../../test_assets/lints/empty_archive.zip"
+        )));
+    }
+
+    #[test]
+    fn test_sniff_pak() {
+        assert!(is_mod_archive(include_bytes!(
+            "../../test_assets/lints/A.pak"
+        )));
+    }
+
+    #[test]
+    fn test_sniff_rejects_html() {
+        assert!(!is_mod_archive(
+            b"<!DOCTYPE html><html><body>404 Not Found</body></html>"
+        ));
+        assert!(!is_mod_archive(b""));
+    }
 }
@@ -0,0 +1,305 @@
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::providers::http::{is_http_mod_url, HttpProvider};
+use crate::providers::*;
+
+inventory::submit! {
+    super::ProviderFactory {
+        id: HTTP_INDEX_PROVIDER_ID,
+        new: HttpIndexProvider::new_provider,
+        can_provide: is_index_url,
+        parameters: &[],
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct HttpIndexProviderCache {
+    /// Names of the mods listed in a directory, keyed by directory URL.
+    listings: HashMap<String, Vec<String>>,
+}
+
+#[typetag::serde]
+impl ModProviderCache for HttpIndexProviderCache {
+    fn new() -> Self {
+        Default::default()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+/// Directory listings served by web servers (e.g. Apache or nginx autoindex pages), treated like
+/// a mod.io mod whose versions are the listed `.pak` and `.zip` files. The directory URL follows
+/// the last file by name and a single file is pinned with its name as the URL fragment, e.g.
+/// `https://example.com/mods/#example_v2.pak`. The files themselves are downloaded and cached by
+/// the HTTP provider.
+#[derive(Debug)]
+pub struct HttpIndexProvider {
+    client: reqwest::Client,
+    http: HttpProvider,
+}
+
+impl HttpIndexProvider {
+    pub fn new_provider(
+        _parameters: &HashMap<String, String>,
+    ) -> Result<Arc<dyn ModProvider>, ProviderError> {
+        Ok(Arc::new(Self {
+            client: reqwest::Client::new(),
+            http: HttpProvider::new(),
+        }))
+    }
+
+    async fn listing(
+        &self,
+        dir: &url::Url,
+        update: bool,
+        cache: &ProviderCache,
+    ) -> Result<Vec<String>, ProviderError> {
+        if !update
+            && let Some(names) = cache
+                .read()
+                .unwrap()
+                .get::<HttpIndexProviderCache>(HTTP_INDEX_PROVIDER_ID)
+                .and_then(|c| c.listings.get(dir.as_str()))
+        {
+            return Ok(names.clone());
+        }
+
+        info!("fetching directory listing {dir}");
+        let url = dir.to_string();
+        let html = self
+            .client
+            .get(dir.clone())
+            .send()
+            .await
+            .context(RequestFailedSnafu { url: url.clone() })?
+            .error_for_status()
+            .context(ResponseSnafu { url: url.clone() })?
+            .text()
+            .await
+            .context(FetchSnafu { url: url.clone() })?;
+        let names = parse_listing(dir, &html);
+        ensure!(!names.is_empty(), EmptyIndexSnafu { url });
+
+        cache
+            .write()
+            .unwrap()
+            .get_mut::<HttpIndexProviderCache>(HTTP_INDEX_PROVIDER_ID)
+            .listings
+            .insert(dir.to_string(), names.clone());
+        Ok(names)
+    }
+}
+
+const HTTP_INDEX_PROVIDER_ID: &str = "http-index";
+
+/// Whether `url` points to a directory of a web server rather than to a single file.
+pub fn is_index_url(url: &str) -> bool {
+    is_http_mod_url(url) && split_url(url).is_some_and(|(dir, _)| dir.path().ends_with('/'))
+}
+
+/// Split a spec URL into the directory URL and the name of the pinned file, if any.
+fn split_url(url: &str) -> Option<(url::Url, Option<String>)> {
+    let mut dir = url::Url::parse(url).ok()?;
+    let file = dir.fragment().filter(|f| !f.is_empty()).map(str::to_string);
+    dir.set_fragment(None);
+    Some((dir, file))
+}
+
+fn file_spec(dir: &url::Url, name: &str) -> ModSpecification {
+    ModSpecification::new(format!("{dir}#{name}"))
+}
+
+fn mod_info(dir: &url::Url, name: &str, names: &[String]) -> Option<ModInfo> {
+    Some(ModInfo {
+        provider: HTTP_INDEX_PROVIDER_ID,
+        name: name.to_string(),
+        spec: ModSpecification::new(dir.to_string()),
+        versions: names.iter().map(|n| file_spec(dir, n)).collect(),
+        resolution: ModResolution::resolvable(dir.join(name).ok()?.to_string().into()),
+        suggested_require: false,
+        suggested_dependencies: vec![],
+        modio_tags: None,
+        modio_id: None,
+        modio_stats: None,
+        description: None,
+    })
+}
+
+static RE_HREF: OnceLock<regex::Regex> = OnceLock::new();
+fn re_href() -> &'static regex::Regex {
+    RE_HREF.get_or_init(|| {
+        regex::Regex::new(r#"(?i)<a\s[^>]*?href\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap()
+    })
+}
+
+/// Names of the mods linked from the listing of `dir`, sorted. Links may be relative or absolute
+/// but only files directly inside `dir` are kept, which skips links to parent and
+/// subdirectories, column sorting links and anything that is not a `.pak` or `.zip`.
+fn parse_listing(dir: &url::Url, html: &str) -> Vec<String> {
+    let mut names = re_href()
+        .captures_iter(html)
+        .filter_map(|c| c.get(1).or_else(|| c.get(2)))
+        .filter_map(|href| dir.join(&href.as_str().replace("&amp;", "&")).ok())
+        .filter(|url| url.origin() == dir.origin())
+        .filter_map(|url| {
+            let name = url.path().strip_prefix(dir.path())?;
+            let lower = name.to_lowercase();
+            (!name.contains('/') && (lower.ends_with(".pak") || lower.ends_with(".zip")))
+                .then(|| name.to_string())
+        })
+        .collect::<Vec<_>>();
+    names.sort();
+    names.dedup();
+    names
+}
+
+#[async_trait::async_trait]
+impl ModProvider for HttpIndexProvider {
+    async fn resolve_mod(
+        &self,
+        spec: &ModSpecification,
+        update: bool,
+        cache: ProviderCache,
+    ) -> Result<ModResponse, ProviderError> {
+        let (dir, file) = split_url(&spec.url).with_context(|| InvalidUrlSnafu {
+            url: spec.url.to_string(),
+        })?;
+        let names = self.listing(&dir, update, &cache).await?;
+        match file {
+            // the listing is not empty
+            None => Ok(ModResponse::Redirect(file_spec(
+                &dir,
+                names.last().unwrap(),
+            ))),
+            Some(name) => Ok(ModResponse::Resolve(
+                mod_info(&dir, &name, &names).with_context(|| InvalidUrlSnafu {
+                    url: spec.url.to_string(),
+                })?,
+            )),
+        }
+    }
+
+    async fn fetch_mod(
+        &self,
+        res: &ModResolution,
+        update: bool,
+        cache: ProviderCache,
+        blob_cache: &BlobCache,
+        tx: Option<Sender<FetchProgress>>,
+    ) -> Result<PathBuf, ProviderError> {
+        // resolutions point to the files themselves, which are plain HTTP downloads
+        self.http
+            .fetch_mod(res, update, cache, blob_cache, tx)
+            .await
+    }
+
+    async fn update_cache(&self, _cache: ProviderCache) -> Result<(), ProviderError> {
+        Ok(())
+    }
+
+    async fn check(&self) -> Result<(), ProviderError> {
+        Ok(())
+    }
+
+    async fn search_mods(&self, _query: &str) -> Result<Vec<ModSearchResult>, ProviderError> {
+        Ok(vec![])
+    }
+
+    fn get_mod_info(&self, spec: &ModSpecification, cache: ProviderCache) -> Option<ModInfo> {
+        let (dir, file) = split_url(&spec.url)?;
+        let cache = cache.read().unwrap();
+        let names = cache
+            .get::<HttpIndexProviderCache>(HTTP_INDEX_PROVIDER_ID)?
+            .listings
+            .get(dir.as_str())?;
+        let name = file.as_ref().or(names.last())?;
+        mod_info(&dir, name, names)
+    }
+
+    fn is_pinned(&self, spec: &ModSpecification, _cache: ProviderCache) -> bool {
+        split_url(&spec.url).is_some_and(|(_, file)| file.is_some())
+    }
+
+    fn get_version_name(&self, spec: &ModSpecification, cache: ProviderCache) -> Option<String> {
+        self.get_mod_info(spec, cache).map(|info| info.name)
+    }
+
+    fn get_version(&self, _spec: &ModSpecification, _cache: ProviderCache) -> Option<String> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_is_index_url() {
+        assert!(is_index_url("https://example.com/mods/"));
+        assert!(is_index_url("http://example.com/mods/#a.pak"));
+        assert!(is_index_url("https://example.com/"));
+        assert!(!is_index_url("https://example.com/mods/a.pak"));
+        assert!(!is_index_url("https://example.com/mods"));
+        assert!(!is_index_url("https://mod.io/g/drg/m/example/"));
+        assert!(!is_index_url("/home/user/mods/"));
+    }
+
+    #[test]
+    fn test_parse_apache_listing() {
+        let dir = url::Url::parse("https://example.com/drg/mods/").unwrap();
+        let html = r#"
+            <h1>Index of /drg/mods</h1>
+            <table>
+            <tr><th><a href="?C=N;O=D">Name</a></th><th><a href="?C=M;O=A">Last modified</a></th></tr>
+            <tr><td><a href="/drg/">Parent Directory</a></td></tr>
+            <tr><td><a href="old/">old/</a></td></tr>
+            <tr><td><a href="Better%20HUD.pak">Better HUD.pak</a></td></tr>
+            <tr><td><a href="readme.txt">readme.txt</a></td></tr>
+            <tr><td><A HREF='Sandbox.ZIP'>Sandbox.ZIP</A></td></tr>
+            <tr><td><a class="file" href="/drg/mods/Another.pak">Another.pak</a></td></tr>
+            <tr><td><a href="https://example.com/drg/mods/Another.pak">Another.pak</a></td></tr>
+            <tr><td><a href="https://mirror.example.com/drg/mods/Mirror.pak">Mirror.pak</a></td></tr>
+            <tr><td><a href="old/Nested.pak">Nested.pak</a></td></tr>
+            </table>"#;
+        assert_eq!(
+            parse_listing(&dir, html),
+            ["Another.pak", "Better%20HUD.pak", "Sandbox.ZIP"]
+        );
+    }
+
+    #[test]
+    fn test_parse_nginx_listing() {
+        let dir = url::Url::parse("http://example.com/mods/").unwrap();
+        let html = "<html><head><title>Index of /mods/</title></head><body>\n\
+            <h1>Index of /mods/</h1><hr><pre><a href=\"../\">../</a>\n\
+            <a href=\"a_v1.pak\">a_v1.pak</a>                16-Oct-2026 10:00     1234\n\
+            <a href=\"a_v2.pak\">a_v2.pak</a>                16-Oct-2026 11:00     1234\n\
+            </pre><hr></body></html>";
+        let names = parse_listing(&dir, html);
+        assert_eq!(names, ["a_v1.pak", "a_v2.pak"]);
+
+        let info = mod_info(&dir, "a_v1.pak", &names).unwrap();
+        assert_eq!(info.spec.url, "http://example.com/mods/");
+        assert_eq!(info.resolution.url.0, "http://example.com/mods/a_v1.pak");
+        assert_eq!(
+            info.versions,
+            [
+                ModSpecification::new("http://example.com/mods/#a_v1.pak".to_string()),
+                ModSpecification::new("http://example.com/mods/#a_v2.pak".to_string()),
+            ]
+        );
+        assert_eq!(
+            split_url("http://example.com/mods/#a_v2.pak"),
+            Some((dir, Some("a_v2.pak".to_string())))
+        );
+    }
+}
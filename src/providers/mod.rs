@@ -1,9 +1,12 @@
 pub mod file;
 pub mod http;
+pub mod http_index;
 pub mod modio;
 #[macro_use]
 pub mod cache;
 pub mod mod_store;
+pub mod path_filter;
+pub mod transform;
 
 use snafu::prelude::*;
 use tokio::sync::mpsc::Sender;
@@ -15,7 +18,10 @@ use std::sync::{Arc, RwLock};
 
 pub use cache::*;
 pub use mint_lib::mod_info::*;
+pub use mint_lib::version_range::{self, VersionRange};
 pub use mod_store::*;
+pub use path_filter::PathFilter;
+pub use transform::Transform;
 
 use self::modio::DrgModioError;
 
@@ -45,14 +51,26 @@ impl FetchProgress {
     }
 }
 
+/// A source of mods, such as mod.io or plain HTTP downloads. Providers are created by a
+/// [`ProviderFactory`], either built in or registered with [`ModStore::register_provider`], and
+/// are chosen by the factory's `can_provide` for each mod URL.
+///
+/// Metadata which should survive restarts belongs in the shared [`ProviderCache`] under the
+/// provider's ID, in a [`ModProviderCache`] implementation. Downloaded files belong in the
+/// [`BlobCache`].
 #[async_trait::async_trait]
 pub trait ModProvider: Send + Sync {
+    /// Resolve `spec` to a mod, or redirect to another spec to resolve instead, e.g. the latest
+    /// version of an unpinned mod. Cached metadata may be used unless `update` is set.
     async fn resolve_mod(
         &self,
         spec: &ModSpecification,
         update: bool,
         cache: ProviderCache,
     ) -> Result<ModResponse, ProviderError>;
+    /// Fetch the file of a resolution previously returned by [`Self::resolve_mod`] and return the
+    /// path of the `.pak` or `.zip`. Progress is reported to `tx` if given, ending with
+    /// [`FetchProgress::Complete`]. Already fetched files may be reused unless `update` is set.
     async fn fetch_mod(
         &self,
         url: &ModResolution,
@@ -61,12 +79,94 @@ pub trait ModProvider: Send + Sync {
         blob_cache: &BlobCache,
         tx: Option<Sender<FetchProgress>>,
     ) -> Result<PathBuf, ProviderError>;
+    /// Refresh the cached metadata of every mod in `cache`.
     async fn update_cache(&self, cache: ProviderCache) -> Result<(), ProviderError>;
+    /// Fetch the metadata of `spec` again, e.g. its name, tags and approval, without changing the
+    /// version it resolves to, so no mod has to be downloaded again afterwards. Mods which were
+    /// never resolved are left alone. By default nothing is fetched, which suits providers whose
+    /// metadata comes from the URL itself.
+    async fn refresh_metadata(
+        &self,
+        _spec: &ModSpecification,
+        _cache: ProviderCache,
+    ) -> Result<(), ProviderError> {
+        Ok(())
+    }
     /// Check if provider is configured correctly
     async fn check(&self) -> Result<(), ProviderError>;
+    /// Look up mods by display name.
+    async fn search_mods(&self, query: &str) -> Result<Vec<ModSearchResult>, ProviderError>;
+    /// Info of an already resolved mod, from the cache only. This is called while drawing the GUI
+    /// so it must not block on the network.
     fn get_mod_info(&self, spec: &ModSpecification, cache: ProviderCache) -> Option<ModInfo>;
+    /// Whether `spec` refers to one specific version rather than following the latest one.
     fn is_pinned(&self, spec: &ModSpecification, cache: ProviderCache) -> bool;
+    /// Name of the version `spec` refers to, shown next to the mod.
     fn get_version_name(&self, spec: &ModSpecification, cache: ProviderCache) -> Option<String>;
+    /// Author-provided version of a pinned spec, used to match version ranges.
+    fn get_version(&self, spec: &ModSpecification, cache: ProviderCache) -> Option<String>;
+    /// Every version of the mod `spec` refers to, oldest first. The mod is resolved first, so its
+    /// metadata is cached the same way as by [`Self::resolve_mod`]. By default the versions of
+    /// the resolved [`ModInfo`] are listed.
+    async fn list_versions(
+        &self,
+        spec: &ModSpecification,
+        update: bool,
+        cache: ProviderCache,
+    ) -> Result<Vec<ModVersion>, ProviderError> {
+        let info = resolve_in_provider(self, spec, update, cache.clone()).await?;
+        let approval = info.modio_tags.map(|t| t.approval_status);
+        Ok(info
+            .versions
+            .into_iter()
+            .map(|spec| ModVersion {
+                name: self
+                    .get_version_name(&spec, cache.clone())
+                    .unwrap_or_else(|| spec.url.clone()),
+                version: self.get_version(&spec, cache.clone()),
+                approval,
+                spec,
+            })
+            .collect())
+    }
+}
+
+/// Resolve `spec` with `provider` alone, following redirects until it resolves.
+async fn resolve_in_provider<P: ModProvider + ?Sized>(
+    provider: &P,
+    spec: &ModSpecification,
+    update: bool,
+    cache: ProviderCache,
+) -> Result<ModInfo, ProviderError> {
+    let mut spec = spec.clone();
+    loop {
+        match provider.resolve_mod(&spec, update, cache.clone()).await? {
+            ModResponse::Resolve(info) => return Ok(info),
+            ModResponse::Redirect(redirected) => spec = redirected,
+        }
+    }
+}
+
+/// A version of a mod, as listed by [`ModProvider::list_versions`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModVersion {
+    /// Spec pinned to this version.
+    pub spec: ModSpecification,
+    /// Name shown for the version, see [`ModProvider::get_version_name`].
+    pub name: String,
+    /// Author-provided version, see [`ModProvider::get_version`].
+    pub version: Option<String>,
+    /// Approval of the mod as a whole, mod.io does not approve versions individually. Only
+    /// available for mods from mod.io.
+    pub approval: Option<ApprovalStatus>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModSearchResult {
+    pub name: String,
+    pub spec: ModSpecification,
+    /// Only available for mods from mod.io.
+    pub modio_stats: Option<ModioStats>,
 }
 
 #[derive(Debug, Snafu)]
@@ -88,6 +188,8 @@ pub enum ProviderError {
     BlobCacheError { source: BlobCacheError },
     #[snafu(display("could not find mod provider for {url}"))]
     ProviderNotFound { url: String },
+    #[snafu(display("a mod provider with ID \"{id}\" is already registered"))]
+    DuplicateProvider { id: &'static str },
     NoProvider {
         url: String,
         factory: &'static ProviderFactory,
@@ -110,16 +212,101 @@ pub enum ProviderError {
     },
     #[snafu(display("error while fetching mod <{url}>"))]
     FetchError { source: reqwest::Error, url: String },
+    #[snafu(display("no .pak or .zip files are listed at <{url}>"))]
+    EmptyIndex { url: String },
+    #[snafu(display(
+        "download of <{url}> is incomplete or corrupt: expected {expected} bytes but got {actual}"
+    ))]
+    DownloadSizeMismatch {
+        url: String,
+        expected: u64,
+        actual: u64,
+    },
     #[snafu(display("error processing <{url}> while writing to local buffer"))]
     BufferIoError { source: std::io::Error, url: String },
+    #[snafu(display("<{url}> is not a valid mod.io mod link: {reason}"))]
+    MalformedModioUrl { url: String, reason: &'static str },
     #[snafu(display("preview mod links cannot be added directly, please subscribe to the mod on mod.io and and then use the non-preview link"))]
     PreviewLink { url: String },
     #[snafu(display("mod <{url}> does not have an associated modfile"))]
     NoAssociatedModfile { url: String },
-    #[snafu(display("multiple mods returned for name \"{name_id}\""))]
+    #[snafu(display(
+        "multiple mod.io mods are named \"{name_id}\", use a link including the mod ID instead"
+    ))]
     AmbiguousModNameId { name_id: String },
-    #[snafu(display("no mods returned for name \"{name_id}\""))]
+    #[snafu(display("no mod.io mod is named \"{name_id}\""))]
     NoModsForNameId { name_id: String },
+    #[snafu(display("failed to filter paths of <{url}>: {source}"))]
+    PathFilterFailed {
+        source: Box<dyn std::error::Error + Send + Sync>,
+        url: String,
+    },
+    #[snafu(display("unknown transform \"{transform}\" for <{url}>"))]
+    UnknownTransform { url: String, transform: String },
+    #[snafu(display("failed to apply transform \"{transform}\" to <{url}>: {source}"))]
+    TransformFailed {
+        source: Box<dyn std::error::Error + Send + Sync>,
+        url: String,
+        transform: String,
+    },
+    #[snafu(display("<{url}> is not usable: {reason}"))]
+    UnusableMod { url: String, reason: String },
+    #[snafu(display("invalid version range \"{range}\" for <{url}>"))]
+    InvalidVersionRange { url: String, range: String },
+    #[snafu(display("no available version of <{url}> satisfies \"{range}\""))]
+    UnsatisfiableVersionRange { url: String, range: String },
+}
+
+/// Parse user input into a [`ModSpecification`], expanding shorthand such as bare mod.io
+/// name-ids into full URLs.
+pub fn parse_spec(input: &str) -> ModSpecification {
+    let input = input.trim();
+    ModSpecification::new(modio::normalize_url(input).unwrap_or_else(|| input.to_string()))
+}
+
+/// Number of times a mod is downloaded before giving up on it not having the expected size.
+pub const DOWNLOAD_ATTEMPTS: usize = 2;
+
+/// Read a download into memory, reporting progress if its size is known. A download of the wrong
+/// size is rejected since truncated files otherwise only show up later as broken mods.
+pub(crate) async fn read_download<S, B, E>(
+    stream: S,
+    res: &ModResolution,
+    size: Option<u64>,
+    tx: Option<&Sender<FetchProgress>>,
+    map_err: impl Fn(E) -> ProviderError,
+) -> Result<Vec<u8>, ProviderError>
+where
+    S: futures::Stream<Item = Result<B, E>>,
+    B: AsRef<[u8]>,
+{
+    use futures::stream::TryStreamExt;
+
+    let mut data = vec![];
+    let mut stream = std::pin::pin!(stream);
+    while let Some(bytes) = stream.try_next().await.map_err(&map_err)? {
+        data.extend_from_slice(bytes.as_ref());
+        if let (Some(size), Some(tx)) = (size, tx) {
+            tx.send(FetchProgress::Progress {
+                resolution: res.clone(),
+                progress: data.len() as u64,
+                size,
+            })
+            .await
+            .unwrap();
+        }
+    }
+    if let Some(expected) = size {
+        ensure!(
+            data.len() as u64 == expected,
+            DownloadSizeMismatchSnafu {
+                url: res.url.0.clone(),
+                expected,
+                actual: data.len() as u64,
+            }
+        );
+    }
+    Ok(data)
 }
 
 impl ProviderError {
@@ -142,6 +329,27 @@ pub struct ProviderFactory {
     pub parameters: &'static [ProviderParameter<'static>],
 }
 
+impl ProviderFactory {
+    /// Describe a provider for [`ModStore::register_provider`]. `new` creates the provider from
+    /// the values of `parameters`, which are configured by the user and keyed by parameter ID.
+    /// `can_provide` decides whether a mod URL belongs to the provider and must be cheap since it
+    /// is called for every lookup.
+    #[allow(clippy::type_complexity)]
+    pub const fn new(
+        id: &'static str,
+        new: fn(&HashMap<String, String>) -> Result<Arc<dyn ModProvider>, ProviderError>,
+        can_provide: fn(&str) -> bool,
+        parameters: &'static [ProviderParameter<'static>],
+    ) -> Self {
+        Self {
+            id,
+            new,
+            can_provide,
+            parameters,
+        }
+    }
+}
+
 impl std::fmt::Debug for ProviderFactory {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ProviderFactory")
@@ -160,3 +368,59 @@ pub struct ProviderParameter<'a> {
 }
 
 inventory::collect!(ProviderFactory);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn chunks(chunks: &[&'static [u8]]) -> impl futures::Stream<Item = Result<&'static [u8], ()>> {
+        futures::stream::iter(chunks.iter().map(|c| Ok(*c)).collect::<Vec<_>>())
+    }
+
+    #[tokio::test]
+    async fn test_read_download() {
+        let res = ModResolution::resolvable("https://example.com/mod.pak".to_string().into());
+        let (tx, mut rx) = tokio::sync::mpsc::channel(10);
+        let data = read_download(
+            chunks(&[b"abc", b"de"]),
+            &res,
+            Some(5),
+            Some(&tx),
+            |_| unreachable!(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(data, b"abcde");
+        assert!(matches!(
+            rx.recv().await,
+            Some(FetchProgress::Progress {
+                progress: 3,
+                size: 5,
+                ..
+            })
+        ));
+
+        // the size is only checked when known
+        assert!(
+            read_download(chunks(&[b"abc"]), &res, None, None, |_| unreachable!())
+                .await
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_download_short_read() {
+        let res = ModResolution::resolvable("https://example.com/mod.pak".to_string().into());
+        let err = read_download(chunks(&[b"abc"]), &res, Some(5), None, |_| unreachable!())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ProviderError::DownloadSizeMismatch {
+                expected: 5,
+                actual: 3,
+                ..
+            }
+        ));
+    }
+}
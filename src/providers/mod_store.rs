@@ -1,16 +1,39 @@
 use std::collections::HashSet;
+use std::future::Future;
 use std::path::Path;
+use std::sync::Mutex;
 
 use snafu::prelude::*;
 use tracing::*;
 
+use crate::integrate::get_pak_from_data;
 use crate::providers::*;
 use crate::state::config::ConfigWrapper;
+use crate::stats::FetchStats;
+use crate::{pak_builder, PakKey};
+
+/// Saves the provider cache when dropped, so the blobs fetched so far are remembered even if mint
+/// is killed after a cancelled install.
+struct CacheCheckpoint<'a>(&'a ProviderCache);
+
+impl Drop for CacheCheckpoint<'_> {
+    fn drop(&mut self) {
+        if let Ok(cache) = self.0.read()
+            && let Err(e) = cache.save()
+        {
+            warn!("failed to save provider cache: {e}");
+        }
+    }
+}
 
 pub struct ModStore {
     providers: Providers,
+    /// Factories registered at runtime, see [`ModStore::register_provider`].
+    registered: RwLock<Vec<&'static ProviderFactory>>,
     cache: ProviderCache,
     blob_cache: BlobCache,
+    /// Fetches since [`Self::take_fetch_stats`] was last called.
+    fetch_stats: Mutex<FetchStats>,
 }
 
 impl ModStore {
@@ -18,6 +41,40 @@ impl ModStore {
         cache_path: P,
         parameters: &HashMap<String, HashMap<String, String>>,
     ) -> Result<Self, ProviderError> {
+        let cache_metadata_path = cache_path.as_ref().join("cache.json");
+
+        let cache = read_cache_metadata_or_default(&cache_metadata_path)?;
+        let cache = ConfigWrapper::new(&cache_metadata_path, cache);
+        cache.save().unwrap();
+
+        Ok(Self {
+            providers: RwLock::new(Self::init_providers(parameters)?),
+            registered: Default::default(),
+            cache: Arc::new(RwLock::new(cache)),
+            blob_cache: BlobCache::new(cache_path.as_ref().join("blobs")),
+            fetch_stats: Default::default(),
+        })
+    }
+
+    /// Like [`ModStore::new`] but starting with empty provider metadata which is only kept in
+    /// memory, so `cache.json` is neither read nor written. Fetched mods still go to the blob
+    /// cache.
+    pub fn new_in_memory<P: AsRef<Path>>(
+        cache_path: P,
+        parameters: &HashMap<String, HashMap<String, String>>,
+    ) -> Result<Self, ProviderError> {
+        Ok(Self {
+            providers: RwLock::new(Self::init_providers(parameters)?),
+            registered: Default::default(),
+            cache: Arc::new(RwLock::new(ConfigWrapper::memory(Default::default()))),
+            blob_cache: BlobCache::new(cache_path.as_ref().join("blobs")),
+            fetch_stats: Default::default(),
+        })
+    }
+
+    fn init_providers(
+        parameters: &HashMap<String, HashMap<String, String>>,
+    ) -> Result<HashMap<&'static str, Arc<dyn ModProvider>>, ProviderError> {
         let mut providers = HashMap::new();
         for prov in Self::get_provider_factories() {
             let params = parameters.get(prov.id).cloned().unwrap_or_default();
@@ -31,24 +88,35 @@ impl ModStore {
                 providers.insert(prov.id, provider);
             }
         }
-
-        let cache_metadata_path = cache_path.as_ref().join("cache.json");
-
-        let cache = read_cache_metadata_or_default(&cache_metadata_path)?;
-        let cache = ConfigWrapper::new(&cache_metadata_path, cache);
-        cache.save().unwrap();
-
-        Ok(Self {
-            providers: RwLock::new(providers),
-            cache: Arc::new(RwLock::new(cache)),
-            blob_cache: BlobCache::new(cache_path.as_ref().join("blobs")),
-        })
+        Ok(providers)
     }
 
     pub fn get_provider_factories() -> impl Iterator<Item = &'static ProviderFactory> {
         inventory::iter::<ProviderFactory>()
     }
 
+    /// Register a provider which is not built into mint, e.g. by an application embedding it.
+    /// Registered providers take precedence over built-in ones for the URLs they can provide.
+    /// Providers without parameters are created right away, others are added with
+    /// [`Self::add_provider`] once their parameters are known, like built-in ones.
+    pub fn register_provider(
+        &self,
+        factory: &'static ProviderFactory,
+    ) -> Result<(), ProviderError> {
+        let mut registered = self.registered.write().unwrap();
+        ensure!(
+            !Self::get_provider_factories()
+                .chain(registered.iter().copied())
+                .any(|f| f.id == factory.id),
+            DuplicateProviderSnafu { id: factory.id }
+        );
+        if factory.parameters.is_empty() {
+            self.add_provider(factory, &HashMap::new())?;
+        }
+        registered.push(factory);
+        Ok(())
+    }
+
     pub fn add_provider(
         &self,
         provider_factory: &ProviderFactory,
@@ -62,6 +130,15 @@ impl ModStore {
         Ok(())
     }
 
+    /// Create a provider with `parameters` and [check](ModProvider::check) it without adding it,
+    /// e.g. to test credentials before saving them.
+    pub async fn check_provider(
+        provider_factory: &ProviderFactory,
+        parameters: &HashMap<String, String>,
+    ) -> Result<(), ProviderError> {
+        (provider_factory.new)(parameters)?.check().await
+    }
+
     pub async fn add_provider_checked(
         &self,
         provider_factory: &ProviderFactory,
@@ -77,7 +154,13 @@ impl ModStore {
     }
 
     pub fn get_provider(&self, url: &str) -> Result<Arc<dyn ModProvider>, ProviderError> {
-        let factory = Self::get_provider_factories()
+        let factory = self
+            .registered
+            .read()
+            .unwrap()
+            .iter()
+            .copied()
+            .chain(Self::get_provider_factories())
             .find(|f| (f.can_provide)(url))
             .context(ProviderNotFoundSnafu {
                 url: url.to_string(),
@@ -133,37 +216,117 @@ impl ModStore {
         Ok(mods_map)
     }
 
+    /// Resolve `specs` as a batch, falling back to resolving them one by one if that fails so a
+    /// single broken mod does not prevent adding the rest.
+    pub async fn resolve_mods_lenient(
+        &self,
+        specs: &[ModSpecification],
+        update: bool,
+    ) -> (
+        HashMap<ModSpecification, ModInfo>,
+        Vec<ModSpecification>,
+        Option<ProviderError>,
+    ) {
+        match self.resolve_mods(specs, update).await {
+            Ok(resolved) => return (resolved, vec![], None),
+            // a missing provider has to be set up first, retrying individually would not help
+            Err(e) if specs.len() == 1 || matches!(e, ProviderError::NoProvider { .. }) => {
+                return (HashMap::new(), specs.to_vec(), Some(e));
+            }
+            Err(_) => {}
+        }
+
+        let mut resolved = HashMap::new();
+        let mut failed = vec![];
+        let mut error = None;
+        for spec in specs {
+            match self.resolve_mods(std::slice::from_ref(spec), update).await {
+                Ok(mods) => resolved.extend(mods),
+                Err(e) => {
+                    failed.push(spec.clone());
+                    error.get_or_insert(e);
+                }
+            }
+        }
+        (resolved, failed, error)
+    }
+
     pub async fn resolve_mod(
         &self,
         original_spec: ModSpecification,
         update: bool,
     ) -> Result<(ModSpecification, ModInfo), ProviderError> {
-        let mut spec = original_spec.clone();
+        let spec = match original_spec.split_version_range() {
+            (spec, Some(range)) => self.resolve_version_range(spec, range, update).await?,
+            (spec, None) => spec,
+        };
+        Ok((original_spec, self.resolve_spec(spec, update).await?))
+    }
+
+    async fn resolve_spec(
+        &self,
+        mut spec: ModSpecification,
+        update: bool,
+    ) -> Result<ModInfo, ProviderError> {
         loop {
             match self
                 .get_provider(&spec.url)?
                 .resolve_mod(&spec, update, self.cache.clone())
                 .await?
             {
-                ModResponse::Resolve(m) => {
-                    return Ok((original_spec, m));
-                }
+                ModResponse::Resolve(m) => return Ok(m),
                 ModResponse::Redirect(redirected_spec) => spec = redirected_spec,
             };
         }
     }
 
+    /// Pick the newest available version of `spec` which satisfies `range`.
+    async fn resolve_version_range(
+        &self,
+        spec: ModSpecification,
+        range: &str,
+        update: bool,
+    ) -> Result<ModSpecification, ProviderError> {
+        let range =
+            range
+                .parse::<VersionRange>()
+                .ok()
+                .with_context(|| InvalidVersionRangeSnafu {
+                    url: spec.url.clone(),
+                    range,
+                })?;
+        let info = self.resolve_spec(spec.clone(), update).await?;
+        let provider = self.get_provider(&spec.url)?;
+        let versions = info
+            .versions
+            .iter()
+            .filter_map(|v| Some((v, provider.get_version(v, self.cache.clone())?)))
+            .collect::<Vec<_>>();
+        range
+            .select(versions.iter().map(|(v, version)| (*v, version.as_str())))
+            .cloned()
+            .with_context(|| UnsatisfiableVersionRangeSnafu {
+                url: spec.url.clone(),
+                range: range.to_string(),
+            })
+    }
+
+    /// Fetch `mods`, pairs of a requested specification and its resolution, in any order, see
+    /// [`Self::fetch_mod`]. Mods which were already fetched are taken from the blob cache unless
+    /// `update` is set. The provider cache is saved when fetching stops, even if it is cancelled
+    /// by dropping the future, so finished downloads are kept for the next attempt.
     pub async fn fetch_mods(
         &self,
-        mods: &[&ModResolution],
+        mods: &[(&ModSpecification, &ModResolution)],
         update: bool,
         tx: Option<Sender<FetchProgress>>,
     ) -> Result<Vec<PathBuf>, ProviderError> {
         use futures::stream::{self, StreamExt, TryStreamExt};
 
+        let _checkpoint = CacheCheckpoint(&self.cache);
         stream::iter(
             mods.iter()
-                .map(|res| self.fetch_mod(res, update, tx.clone())),
+                .map(|(spec, res)| self.fetch_mod(spec, res, update, tx.clone())),
         )
         .boxed() // without this the future becomes !Send https://github.com/rust-lang/rust/issues/104382
         .buffer_unordered(5)
@@ -173,15 +336,16 @@ impl ModStore {
 
     pub async fn fetch_mods_ordered(
         &self,
-        mods: &[&ModResolution],
+        mods: &[(&ModSpecification, &ModResolution)],
         update: bool,
         tx: Option<Sender<FetchProgress>>,
     ) -> Result<Vec<PathBuf>, ProviderError> {
         use futures::stream::{self, StreamExt, TryStreamExt};
 
+        let _checkpoint = CacheCheckpoint(&self.cache);
         stream::iter(
             mods.iter()
-                .map(|res| self.fetch_mod(res, update, tx.clone())),
+                .map(|(spec, res)| self.fetch_mod(spec, res, update, tx.clone())),
         )
         .boxed() // without this the future becomes !Send https://github.com/rust-lang/rust/issues/104382
         .buffered(5)
@@ -189,21 +353,181 @@ impl ModStore {
         .await
     }
 
+    /// Fetch mods like [`Self::fetch_mods_ordered`], but if the resolved version of a mod cannot be
+    /// fetched or its pak is unusable, fall back to the newest older version which works. Pinned
+    /// mods are never downgraded and mods with a version range only within the range.
+    ///
+    /// `mods` pairs the specification a mod was requested with to its resolved info. Returned
+    /// infos point at the version which was actually fetched.
+    pub async fn fetch_mods_with_fallback(
+        &self,
+        mods: &[(&ModSpecification, &ModInfo)],
+        update: bool,
+        tx: Option<Sender<FetchProgress>>,
+        pak_key: Option<&PakKey>,
+    ) -> Result<Vec<(ModInfo, PathBuf)>, ProviderError> {
+        use futures::stream::{self, StreamExt, TryStreamExt};
+
+        let _checkpoint = CacheCheckpoint(&self.cache);
+        stream::iter(mods.iter().map(|(spec, info)| {
+            self.fetch_mod_with_fallback(spec, info, update, tx.clone(), pak_key)
+        }))
+        .boxed() // without this the future becomes !Send https://github.com/rust-lang/rust/issues/104382
+        .buffered(5)
+        .try_collect::<Vec<_>>()
+        .await
+    }
+
+    async fn fetch_mod_with_fallback(
+        &self,
+        spec: &ModSpecification,
+        info: &ModInfo,
+        update: bool,
+        tx: Option<Sender<FetchProgress>>,
+        pak_key: Option<&PakKey>,
+    ) -> Result<(ModInfo, PathBuf), ProviderError> {
+        let (unversioned, range) = spec.split_version_range();
+        let older = if range.is_none() && self.is_pinned(&unversioned) {
+            vec![]
+        } else {
+            let range = range.and_then(|r| r.parse::<VersionRange>().ok());
+            let provider = self.get_provider(&info.spec.url)?;
+            let versions = info
+                .versions
+                .iter()
+                .map(|v| (v.clone(), provider.get_version(v, self.cache.clone())))
+                .collect::<Vec<_>>();
+            fallback_versions(&info.spec, range.as_ref(), &versions)
+                .into_iter()
+                .cloned()
+                .collect()
+        };
+
+        let candidates = std::iter::once(None).chain(older.into_iter().map(Some));
+        first_success(candidates, |version| {
+            let tx = tx.clone();
+            async move {
+                let fallback = match &version {
+                    Some(version) => {
+                        let fallback = self.resolve_spec(version.clone(), update).await?;
+                        if fallback.resolution == info.resolution {
+                            return UnusableModSnafu {
+                                url: version.url.clone(),
+                                reason: "same as the resolved version",
+                            }
+                            .fail();
+                        }
+                        Some(fallback)
+                    }
+                    None => None,
+                };
+                let res = fallback
+                    .as_ref()
+                    .map_or(&info.resolution, |f| &f.resolution);
+                let path = self.fetch_mod(spec, res, update, tx).await?;
+                if let Err(reason) = check_pak(&path, pak_key) {
+                    return UnusableModSnafu {
+                        url: res.url.0.clone(),
+                        reason,
+                    }
+                    .fail();
+                }
+                let mut info = info.clone();
+                if let Some(fallback) = fallback {
+                    warn!(
+                        "newest version of {} is not usable, downgraded to {}",
+                        info.name, fallback.resolution.url.0
+                    );
+                    info.resolution = fallback.resolution;
+                }
+                Ok::<_, ProviderError>((info, path))
+            }
+        })
+        .await
+        .expect("the resolved version is always a candidate")
+    }
+
+    /// Fetch `res`, the resolution of `spec`, and apply the transforms `spec` declares to it.
     pub async fn fetch_mod(
         &self,
+        spec: &ModSpecification,
         res: &ModResolution,
         update: bool,
         tx: Option<Sender<FetchProgress>>,
     ) -> Result<PathBuf, ProviderError> {
-        self.get_provider(&res.url.0)?
-            .fetch_mod(
-                res,
-                update,
-                self.cache.clone(),
-                &self.blob_cache.clone(),
-                tx,
-            )
-            .await
+        let blob_cache = self.blob_cache.counting();
+        let path = self
+            .get_provider(&res.url.0)?
+            .fetch_mod(res, update, self.cache.clone(), &blob_cache, tx)
+            .await?;
+        let mut stats = self.fetch_stats.lock().unwrap();
+        match blob_cache.written() {
+            0 => stats.cache_hits += 1,
+            bytes => {
+                stats.downloads += 1;
+                stats.bytes += bytes;
+            }
+        }
+        drop(stats);
+        self.apply_transforms(spec, path)
+    }
+
+    /// Mods fetched since the last call, for [`crate::stats::UsageStats`]. A fetch which wrote
+    /// nothing to the blob cache counts as a cache hit.
+    pub fn take_fetch_stats(&self) -> FetchStats {
+        std::mem::take(&mut self.fetch_stats.lock().unwrap())
+    }
+
+    /// Apply the transforms `spec` declares to the fetched mod at `path`. The result is written to
+    /// the blob cache. Transforms are cheap compared to downloads so they run on every fetch.
+    fn apply_transforms(
+        &self,
+        spec: &ModSpecification,
+        path: PathBuf,
+    ) -> Result<PathBuf, ProviderError> {
+        let (unversioned, transforms) = spec.split_transforms();
+        if transforms.is_empty() {
+            return Ok(path);
+        }
+        let transforms = transforms
+            .into_iter()
+            .map(|id| {
+                Transform::get(id).with_context(|| UnknownTransformSnafu {
+                    url: unversioned.url.clone(),
+                    transform: id,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut data = fs_err::read(&path).map_err(|e| ProviderError::TransformFailed {
+            source: e.into(),
+            url: unversioned.url.clone(),
+            transform: transforms[0].id.to_string(),
+        })?;
+        for transform in transforms {
+            debug!("applying transform {} to {}", transform.id, unversioned.url);
+            data = (transform.apply)(data).map_err(|e| ProviderError::TransformFailed {
+                source: e.into(),
+                url: unversioned.url.clone(),
+                transform: transform.id.to_string(),
+            })?;
+        }
+        let blob = self.blob_cache.write(&data)?;
+        Ok(self.blob_cache.get_path(&blob).unwrap())
+    }
+
+    pub fn has_provider(&self, id: &str) -> bool {
+        self.providers.read().unwrap().contains_key(id)
+    }
+
+    /// Search all initialized providers for mods named like `query`.
+    pub async fn search_mods(&self, query: &str) -> Result<Vec<ModSearchResult>, ProviderError> {
+        let providers = self.providers.read().unwrap().clone();
+        let mut results = vec![];
+        for provider in providers.values() {
+            results.extend(provider.search_mods(query).await?);
+        }
+        Ok(results)
     }
 
     pub async fn update_cache(&self) -> Result<(), ProviderError> {
@@ -215,21 +539,200 @@ impl ModStore {
         Ok(())
     }
 
+    /// Refresh the metadata of `mods` (names, tags, approval) and resolve them with it, see
+    /// [`ModProvider::refresh_metadata`]. The mods keep resolving to the versions they did before
+    /// so the mods already in the blob cache stay in use.
+    pub async fn refresh_metadata(
+        &self,
+        mods: &[ModSpecification],
+    ) -> Result<HashMap<ModSpecification, ModInfo>, ProviderError> {
+        use futures::stream::{self, StreamExt, TryStreamExt};
+
+        let _checkpoint = CacheCheckpoint(&self.cache);
+        stream::iter(mods.iter().map(|spec| async move {
+            let (spec, _) = spec.split_version_range();
+            self.get_provider(&spec.url)?
+                .refresh_metadata(&spec, self.cache.clone())
+                .await
+        }))
+        .boxed() // without this the future becomes !Send https://github.com/rust-lang/rust/issues/104382
+        .buffer_unordered(5)
+        .try_collect::<Vec<_>>()
+        .await?;
+        self.resolve_mods(mods, false).await
+    }
+
+    /// Every version of the mod `spec` refers to, oldest first, see [`ModProvider::list_versions`].
+    /// A version range of `spec` is ignored. The metadata fetched on the way is saved to the cache.
+    pub async fn list_versions(
+        &self,
+        spec: &ModSpecification,
+        update: bool,
+    ) -> Result<Vec<ModVersion>, ProviderError> {
+        let (spec, _) = spec.split_version_range();
+        let _checkpoint = CacheCheckpoint(&self.cache);
+        self.get_provider(&spec.url)?
+            .list_versions(&spec, update, self.cache.clone())
+            .await
+    }
+
+    pub fn blob_cache_path(&self) -> &Path {
+        self.blob_cache.path()
+    }
+
+    /// Delete all downloaded mods. They are fetched again the next time they are needed.
+    pub fn clear_blob_cache(&self) -> Result<(), ProviderError> {
+        info!("clearing blob cache");
+        Ok(self.blob_cache.clear()?)
+    }
+
     pub fn get_mod_info(&self, spec: &ModSpecification) -> Option<ModInfo> {
+        let (spec, _) = spec.split_version_range();
         self.get_provider(&spec.url)
             .ok()?
-            .get_mod_info(spec, self.cache.clone())
+            .get_mod_info(&spec, self.cache.clone())
     }
 
     pub fn is_pinned(&self, spec: &ModSpecification) -> bool {
-        self.get_provider(&spec.url)
-            .unwrap()
-            .is_pinned(spec, self.cache.clone())
+        match spec.split_version_range() {
+            (_, Some(_)) => false,
+            (spec, None) => self
+                .get_provider(&spec.url)
+                .unwrap()
+                .is_pinned(&spec, self.cache.clone()),
+        }
     }
 
     pub fn get_version_name(&self, spec: &ModSpecification) -> Option<String> {
-        self.get_provider(&spec.url)
-            .unwrap()
-            .get_version_name(spec, self.cache.clone())
+        match spec.split_version_range() {
+            (_, Some(range)) => Some(range.to_string()),
+            (spec, None) => self
+                .get_provider(&spec.url)
+                .unwrap()
+                .get_version_name(&spec, self.cache.clone()),
+        }
+    }
+}
+
+/// Older versions of a mod to try when `current` is unusable, newest first. `versions` lists every
+/// version oldest first along with its version name.
+fn fallback_versions<'a>(
+    current: &ModSpecification,
+    range: Option<&VersionRange>,
+    versions: &'a [(ModSpecification, Option<String>)],
+) -> Vec<&'a ModSpecification> {
+    let older = match versions.iter().position(|(v, _)| v == current) {
+        Some(i) => &versions[..i],
+        None => versions,
+    };
+    older
+        .iter()
+        .rev()
+        .filter(|(_, name)| match (range, name) {
+            (Some(range), Some(name)) => range.matches(name),
+            (Some(_), None) => false,
+            (None, _) => true,
+        })
+        .map(|(v, _)| v)
+        .collect()
+}
+
+/// Try `candidates` in order until `attempt` succeeds. If every candidate fails the error of the
+/// first one is returned since that is the one which was asked for. Returns `None` if there are
+/// no candidates.
+async fn first_success<T, R, E, F, Fut>(
+    candidates: impl IntoIterator<Item = T>,
+    mut attempt: F,
+) -> Option<Result<R, E>>
+where
+    F: FnMut(T) -> Fut,
+    Fut: Future<Output = Result<R, E>>,
+{
+    let mut first_err = None;
+    for candidate in candidates {
+        match attempt(candidate).await {
+            Ok(r) => return Some(Ok(r)),
+            Err(e) => {
+                debug!("fallback candidate failed");
+                first_err.get_or_insert(e);
+            }
+        }
+    }
+    first_err.map(Err)
+}
+
+/// Check that a fetched mod contains a pak which can be read. An outdated pak version is only a
+/// lint warning and installs without falling back, so it is accepted here as well.
+fn check_pak(path: &Path, pak_key: Option<&PakKey>) -> Result<(), String> {
+    let file = fs_err::File::open(path).map_err(|e| e.to_string())?;
+    let mut reader =
+        get_pak_from_data(Box::new(std::io::BufReader::new(file))).map_err(|e| e.to_string())?;
+    pak_builder(pak_key)
+        .reader(&mut reader)
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn spec(url: &str) -> ModSpecification {
+        ModSpecification::new(url.to_string())
+    }
+
+    #[test]
+    fn test_fallback_versions() {
+        let versions = [("1", "1.0"), ("2", "1.1"), ("3", "2.0"), ("4", "2.1")]
+            .map(|(v, name)| (spec(v), Some(name.to_string())));
+
+        assert_eq!(
+            fallback_versions(&spec("4"), None, &versions),
+            vec![&spec("3"), &spec("2"), &spec("1")]
+        );
+        assert_eq!(
+            fallback_versions(&spec("3"), None, &versions),
+            vec![&spec("2"), &spec("1")]
+        );
+        let range = ">=1.1".parse::<VersionRange>().unwrap();
+        assert_eq!(
+            fallback_versions(&spec("4"), Some(&range), &versions),
+            vec![&spec("3"), &spec("2")]
+        );
+        assert!(fallback_versions(&spec("1"), None, &versions).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_first_success() {
+        // newest version fails, the next older one is used
+        let mut tried = vec![];
+        let result = first_success(["4", "3", "2"], |v| {
+            tried.push(v);
+            async move {
+                match v {
+                    "4" => Err(format!("{v} failed")),
+                    _ => Ok(v),
+                }
+            }
+        })
+        .await;
+        assert_eq!(result, Some(Ok("3")));
+        assert_eq!(tried, ["4", "3"]);
+
+        // nothing works, report why the requested version failed
+        let result =
+            first_success(["4", "3"], |v| async move { Err::<(), _>(format!("{v}")) }).await;
+        assert_eq!(result, Some(Err("4".to_string())));
+
+        let result = first_success([], |v: &str| async move { Ok::<_, ()>(v) }).await;
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_check_pak() {
+        let assets = Path::new(env!("CARGO_MANIFEST_DIR")).join("test_assets/lints");
+        assert!(check_pak(&assets.join("A.pak"), None).is_ok());
+        assert!(check_pak(&assets.join("outdated_pak_version.pak"), None).is_ok());
+        assert!(check_pak(&assets.join("only_non_pak_files.zip"), None).is_err());
     }
 }
@@ -20,6 +20,78 @@ fn re_mod() -> &'static regex::Regex {
     RE_MOD.get_or_init(|| regex::Regex::new("^https://mod.io/g/drg/m/(?P<name_id>[^/#]+)(:?#(?P<mod_id>\\d+)(:?/(?P<modfile_id>\\d+))?)?$").unwrap())
 }
 
+static RE_MOD_LINK: OnceLock<regex::Regex> = OnceLock::new();
+/// Looser variant of [`re_mod`] matching links the way they are copied from a browser.
+fn re_mod_link() -> &'static regex::Regex {
+    RE_MOD_LINK.get_or_init(|| regex::Regex::new("^(?:https?://)?(?:www\\.)?mod\\.io/g/drg/m/(?P<name_id>[^/#?]+)/?(?:\\?[^#]*)?(?P<ids>#\\d+(?:/\\d+)?)?$").unwrap())
+}
+
+static RE_MODIO_LINK: OnceLock<regex::Regex> = OnceLock::new();
+/// Any link to mod.io, split into path and fragment for [`validate_url`].
+fn re_modio_link() -> &'static regex::Regex {
+    RE_MODIO_LINK.get_or_init(|| regex::Regex::new("^(?i:(?:https?://)?(?:www\\.)?mod\\.io)(?P<path>/[^?#]*)?(?:\\?[^#]*)?(?P<fragment>#.*)?$").unwrap())
+}
+
+static RE_NAME_ID: OnceLock<regex::Regex> = OnceLock::new();
+fn re_name_id() -> &'static regex::Regex {
+    RE_NAME_ID.get_or_init(|| regex::Regex::new("^[A-Za-z0-9][A-Za-z0-9_-]*$").unwrap())
+}
+
+/// Turn a mod.io link in any of the forms it is commonly copied in, or a bare name-id such as
+/// `better-scout`, into the canonical `https://mod.io/g/drg/m/<name-id>` form. Returns `None`
+/// for anything else, including links which are already canonical.
+pub fn normalize_url(input: &str) -> Option<String> {
+    // preview links are rejected with a dedicated error on resolution
+    if re_mod().is_match(input) || input.contains("?preview=") {
+        None
+    } else if let Some(captures) = re_mod_link().captures(input) {
+        Some(format!(
+            "https://mod.io/g/drg/m/{}{}",
+            captures["name_id"].to_ascii_lowercase(),
+            captures.name("ids").map_or("", |ids| ids.as_str())
+        ))
+    } else if re_name_id().is_match(input) && !std::path::Path::new(input).exists() {
+        Some(format!(
+            "https://mod.io/g/drg/m/{}",
+            input.to_ascii_lowercase()
+        ))
+    } else {
+        None
+    }
+}
+
+/// Check the shape of a link to mod.io before it is resolved, so typos are reported right away
+/// instead of as a failed request. Anything not pointing at mod.io is left to other providers.
+pub fn validate_url(url: &str) -> Result<(), ProviderError> {
+    // version ranges and transforms are checked on their own when resolving
+    let (spec, _) = ModSpecification::new(url.to_string()).split_version_range();
+    let Some(captures) = re_modio_link().captures(&spec.url) else {
+        return Ok(());
+    };
+    ensure!(!spec.url.contains("?preview="), PreviewLinkSnafu { url });
+    if re_mod().is_match(&spec.url) || normalize_url(&spec.url).is_some() {
+        return Ok(());
+    }
+
+    let segments = captures
+        .name("path")
+        .map_or("", |path| path.as_str())
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>();
+    let reason = match segments.as_slice() {
+        ["g", game, ..] if !game.eq_ignore_ascii_case("drg") => {
+            "only Deep Rock Galactic mods (mod.io/g/drg/m/...) are supported"
+        }
+        ["g", _, "m", _] if captures.name("fragment").is_some() => {
+            "the mod and file IDs after `#` have to be numbers, e.g. #1234/5678"
+        }
+        ["g", _, "m", _, _, ..] => "expected nothing after the mod name, e.g. mod.io/g/drg/m/<mod>",
+        _ => "expected a link to a mod like mod.io/g/drg/m/<mod>",
+    };
+    MalformedModioUrlSnafu { url, reason }.fail()
+}
+
 const MODIO_DRG_ID: u32 = 2475;
 const MODIO_PROVIDER_ID: &str = "modio";
 
@@ -27,7 +99,8 @@ inventory::submit! {
     super::ProviderFactory {
         id: MODIO_PROVIDER_ID,
         new: ModioProvider::<modio::Modio>::new_provider,
-        can_provide: |url| re_mod().is_match(url),
+        // malformed mod.io links are claimed too so resolving them explains what is wrong
+        can_provide: |url| re_modio_link().is_match(url),
         parameters: &[
             super::ProviderParameter {
                 id: "oauth",
@@ -103,6 +176,8 @@ pub struct ModioMod {
     latest_modfile: Option<u32>,
     modfiles: Vec<ModioFile>,
     tags: HashSet<String>,
+    description: Option<String>,
+    stats: Option<ModioStats>,
 }
 
 impl ModioMod {
@@ -113,6 +188,11 @@ impl ModioMod {
             latest_modfile: mod_.modfile.map(|f| f.id),
             modfiles: files.into_iter().map(ModioFile::new).collect(),
             tags: mod_.tags.into_iter().map(|t| t.name).collect(),
+            description: mod_
+                .description_plaintext
+                .filter(|d| !d.trim().is_empty())
+                .or_else(|| (!mod_.summary.trim().is_empty()).then_some(mod_.summary)),
+            stats: Some((&mod_.stats).into()),
         }
     }
 }
@@ -128,6 +208,36 @@ impl From<modio::mods::Mod> for ModioModResponse {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct ModioSearchResult {
+    id: u32,
+    name_id: String,
+    name: String,
+    stats: ModioStats,
+}
+
+impl From<&modio::mods::Statistics> for ModioStats {
+    fn from(value: &modio::mods::Statistics) -> Self {
+        Self {
+            downloads: value.downloads_total,
+            subscribers: value.subscribers_total,
+            ratings_positive: value.ratings.positive,
+            ratings_negative: value.ratings.negative,
+        }
+    }
+}
+
+impl From<modio::mods::Mod> for ModioSearchResult {
+    fn from(value: modio::mods::Mod) -> Self {
+        Self {
+            id: value.id,
+            stats: (&value.stats).into(),
+            name_id: value.name_id,
+            name: value.name,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ModioFile {
     id: u32,
@@ -260,6 +370,7 @@ pub trait DrgModio: Sync + Send {
         &self,
         filter_ids: Vec<u32>,
     ) -> Result<Vec<modio::mods::Mod>, DrgModioError>;
+    async fn search_mods(&self, query: &str) -> Result<Vec<ModioSearchResult>, DrgModioError>;
     async fn fetch_mod_updates_since(
         &self,
         mod_ids: Vec<u32>,
@@ -426,6 +537,23 @@ impl DrgModio for modio::Modio {
             .context(GenericModioSnafu)?)
     }
 
+    async fn search_mods(&self, query: &str) -> Result<Vec<ModioSearchResult>, DrgModioError> {
+        use modio::filter::{Eq, In};
+        use modio::mods::filters::{Fulltext, Visible};
+
+        let filter = Fulltext::eq(query).and(Visible::_in(vec![0, 1]));
+        Ok(self
+            .game(MODIO_DRG_ID)
+            .mods()
+            .search(filter)
+            .first_page()
+            .await
+            .context(GenericModioSnafu)?
+            .into_iter()
+            .map(|m| m.into())
+            .collect())
+    }
+
     async fn fetch_mod_updates_since(
         &self,
         mod_ids: Vec<u32>,
@@ -473,12 +601,7 @@ impl<M: DrgModio + Send + Sync> ModProvider for ModioProvider<M> {
         update: bool,
         cache: ProviderCache,
     ) -> Result<ModResponse, ProviderError> {
-        ensure!(
-            !spec.url.contains("?preview="),
-            PreviewLinkSnafu {
-                url: spec.url.to_string()
-            }
-        );
+        validate_url(&spec.url)?;
 
         fn read_cache<F, R>(cache: &ProviderCache, update: bool, f: F) -> Option<R>
         where
@@ -608,6 +731,8 @@ impl<M: DrgModio + Send + Sync> ModProvider for ModioProvider<M> {
                 suggested_dependencies: deps,
                 modio_tags: Some(process_modio_tags(&mod_.tags)),
                 modio_id: Some(mod_id),
+                modio_stats: mod_.stats,
+                description: mod_.description,
             }))
         } else if let Some(mod_id) = captures.name("mod_id") {
             // only mod ID specified, use latest version (either cached local or remote depending)
@@ -741,42 +866,38 @@ impl<M: DrgModio + Send + Sync> ModProvider for ModioProvider<M> {
                     }
                     path
                 } else {
-                    let file = self
-                        .modio
-                        .fetch_file(res.url.0.clone(), mod_id, modfile_id)
-                        .await?;
-
-                    let size = file.filesize;
-                    let download: modio::download::DownloadAction = file.into();
-
-                    info!("downloading mod {url:?}...");
-
-                    use futures::stream::TryStreamExt;
-                    use tokio::io::AsyncWriteExt;
+                    let mut attempt = 1;
+                    let data = loop {
+                        let file = self
+                            .modio
+                            .fetch_file(res.url.0.clone(), mod_id, modfile_id)
+                            .await?;
 
-                    let mut cursor = std::io::Cursor::new(vec![]);
-                    let mut stream = Box::pin(self.modio.download(download).stream());
-                    while let Some(bytes) = stream
-                        .try_next()
-                        .await
-                        .with_context(|_| ModCtxtModioSnafu { mod_id })?
-                    {
-                        cursor
-                            .write_all(&bytes)
-                            .await
-                            .with_context(|_| ModCtxtIoSnafu { mod_id })?;
-                        if let Some(tx) = &tx {
-                            tx.send(FetchProgress::Progress {
-                                resolution: res.clone(),
-                                progress: cursor.get_ref().len() as u64,
-                                size,
-                            })
-                            .await
-                            .unwrap();
+                        let size = file.filesize;
+                        let download: modio::download::DownloadAction = file.into();
+
+                        info!("downloading mod {url:?}...");
+
+                        let result = read_download(
+                            self.modio.download(download).stream(),
+                            res,
+                            Some(size),
+                            tx.as_ref(),
+                            |source| ProviderError::ModCtxtModioError { source, mod_id },
+                        )
+                        .await;
+                        match result {
+                            Err(e @ ProviderError::DownloadSizeMismatch { .. })
+                                if attempt < DOWNLOAD_ATTEMPTS =>
+                            {
+                                warn!("{e}, downloading again");
+                                attempt += 1;
+                            }
+                            result => break result?,
                         }
-                    }
+                    };
 
-                    let blob = blob_cache.write(&cursor.into_inner())?;
+                    let blob = blob_cache.write(&data)?;
                     let path = blob_cache.get_path(&blob).unwrap();
 
                     cache
@@ -896,10 +1017,64 @@ impl<M: DrgModio + Send + Sync> ModProvider for ModioProvider<M> {
         Ok(())
     }
 
+    async fn refresh_metadata(
+        &self,
+        spec: &ModSpecification,
+        cache: ProviderCache,
+    ) -> Result<(), ProviderError> {
+        let captures = re_mod().captures(&spec.url).context(InvalidUrlSnafu {
+            url: spec.url.to_string(),
+        })?;
+        let cached_id = match captures.name("mod_id") {
+            Some(mod_id) => mod_id.as_str().parse::<u32>().ok(),
+            None => cache
+                .read()
+                .unwrap()
+                .get::<ModioCache>(MODIO_PROVIDER_ID)
+                .and_then(|c| c.mod_id_map.get(&captures["name_id"]).copied()),
+        };
+        let Some((mod_id, latest_modfile)) = cached_id.and_then(|id| {
+            let cache = cache.read().unwrap();
+            let mod_ = cache.get::<ModioCache>(MODIO_PROVIDER_ID)?.mods.get(&id)?;
+            Some((id, mod_.latest_modfile))
+        }) else {
+            // resolving fetches it anyway
+            return Ok(());
+        };
+
+        let mod_ = self.modio.fetch_mod(spec.url.clone(), mod_id).await?;
+        let mut lock = cache.write().unwrap();
+        let c = lock.get_mut::<ModioCache>(MODIO_PROVIDER_ID);
+        c.mod_id_map.insert(mod_.name_id.clone(), mod_id);
+        // unpinned mods resolve to the latest modfile, which is left for updating mods to change
+        c.mods.insert(
+            mod_id,
+            ModioMod {
+                latest_modfile,
+                ..mod_
+            },
+        );
+        Ok(())
+    }
+
     async fn check(&self) -> Result<(), ProviderError> {
         self.modio.check().await.map_err(Into::into)
     }
 
+    async fn search_mods(&self, query: &str) -> Result<Vec<ModSearchResult>, ProviderError> {
+        Ok(self
+            .modio
+            .search_mods(query)
+            .await?
+            .into_iter()
+            .map(|m| ModSearchResult {
+                spec: format_spec(&m.name_id, m.id, None),
+                name: m.name,
+                modio_stats: Some(m.stats),
+            })
+            .collect())
+    }
+
     fn get_mod_info(&self, spec: &ModSpecification, cache: ProviderCache) -> Option<ModInfo> {
         let url = &spec.url;
         let captures = re_mod().captures(url)?;
@@ -921,16 +1096,21 @@ impl<M: DrgModio + Send + Sync> ModProvider for ModioProvider<M> {
             mod_.modfiles.last().map(|f| f.id)
         }?;
 
+        // missing dependency metadata should not hide the mod itself, only report the
+        // dependencies that are actually known
         let deps = prov
             .dependencies
-            .get(&mod_id)?
-            .iter()
-            .map(|id| {
-                prov.mods
-                    .get(id)
-                    .map(|m| format_spec(&m.name_id, *id, None))
+            .get(&mod_id)
+            .map(|deps| {
+                deps.iter()
+                    .filter_map(|id| {
+                        prov.mods
+                            .get(id)
+                            .map(|m| format_spec(&m.name_id, *id, None))
+                    })
+                    .collect::<Vec<_>>()
             })
-            .collect::<Option<Vec<_>>>()?;
+            .unwrap_or_default();
 
         Some(ModInfo {
             provider: MODIO_PROVIDER_ID,
@@ -950,6 +1130,8 @@ impl<M: DrgModio + Send + Sync> ModProvider for ModioProvider<M> {
             suggested_dependencies: deps,
             modio_tags: Some(process_modio_tags(&mod_.tags)),
             modio_id: Some(mod_id),
+            modio_stats: mod_.stats,
+            description: mod_.description.clone(),
         })
     }
 
@@ -998,6 +1180,54 @@ impl<M: DrgModio + Send + Sync> ModProvider for ModioProvider<M> {
             None
         }
     }
+
+    fn get_version(&self, spec: &ModSpecification, cache: ProviderCache) -> Option<String> {
+        let captures = re_mod().captures(&spec.url)?;
+        let mod_id = captures.name("mod_id")?.as_str().parse::<u32>().ok()?;
+        let modfile_id = captures.name("modfile_id")?.as_str().parse::<u32>().ok()?;
+
+        let cache = cache.read().unwrap();
+        let prov = cache.get::<ModioCache>(MODIO_PROVIDER_ID)?;
+        prov.mods
+            .get(&mod_id)?
+            .modfiles
+            .iter()
+            .find(|f| f.id == modfile_id)?
+            .version
+            .clone()
+    }
+
+    async fn list_versions(
+        &self,
+        spec: &ModSpecification,
+        update: bool,
+        cache: ProviderCache,
+    ) -> Result<Vec<ModVersion>, ProviderError> {
+        let info = super::resolve_in_provider(self, spec, update, cache.clone()).await?;
+        let mod_id = info.modio_id.expect("mods from mod.io have an ID");
+        let approval = info.modio_tags.map(|t| t.approval_status);
+
+        let cache = cache.read().unwrap();
+        let Some(mod_) = cache
+            .get::<ModioCache>(MODIO_PROVIDER_ID)
+            .and_then(|c| c.mods.get(&mod_id))
+        else {
+            return Ok(vec![]);
+        };
+        Ok(mod_
+            .modfiles
+            .iter()
+            .map(|file| ModVersion {
+                spec: format_spec(&mod_.name_id, mod_id, Some(file.id)),
+                name: match &file.version {
+                    Some(version) => format!("{} - {}", file.id, version),
+                    None => file.id.to_string(),
+                },
+                version: file.version.clone(),
+                approval,
+            })
+            .collect())
+    }
 }
 
 fn process_modio_tags(set: &HashSet<String>) -> ModioTags {
@@ -1040,12 +1270,290 @@ fn process_modio_tags(set: &HashSet<String>) -> ModioTags {
 #[cfg(test)]
 mod test {
     use super::{
-        Arc, DrgModioError, HashMap, HashSet, MockDrgModio, ModProvider, ModResponse,
-        ModSpecification, ModioCache, ModioFile, ModioMod, ModioModResponse, ModioProvider,
-        OnceLock, RwLock, VersionAnnotatedCache, MODIO_PROVIDER_ID,
+        normalize_url, validate_url, Arc, DrgModioError, HashMap, HashSet, MockDrgModio,
+        ModProvider, ModResponse, ModSpecification, ModioCache, ModioFile, ModioMod,
+        ModioModResponse, ModioProvider, ModioStats, OnceLock, ProviderCache, ProviderError,
+        RwLock, VersionAnnotatedCache, MODIO_PROVIDER_ID,
     };
+    use crate::providers::{parse_spec, resolve_in_provider, ApprovalStatus};
     use crate::state::config::ConfigWrapper;
 
+    fn empty_cache() -> ProviderCache {
+        Arc::new(RwLock::new(ConfigWrapper::<VersionAnnotatedCache>::memory(
+            VersionAnnotatedCache::default(),
+        )))
+    }
+
+    fn test_mod() -> ModioMod {
+        ModioMod {
+            name_id: "test-mod".to_string(),
+            name: "Test Mod".to_string(),
+            latest_modfile: Some(5),
+            modfiles: vec![ModioFile {
+                id: 5,
+                date_added: 12345,
+                version: None,
+                changelog: None,
+            }],
+            tags: HashSet::new(),
+            description: None,
+            stats: None,
+        }
+    }
+
+    #[test]
+    fn test_normalize_url() {
+        let canonical = Some("https://mod.io/g/drg/m/test-mod".to_string());
+        assert_eq!(normalize_url("test-mod"), canonical);
+        assert_eq!(normalize_url("Test-Mod"), canonical);
+        assert_eq!(normalize_url("mod.io/g/drg/m/test-mod"), canonical);
+        assert_eq!(
+            normalize_url("https://www.mod.io/g/drg/m/test-mod/"),
+            canonical
+        );
+        assert_eq!(
+            normalize_url("http://mod.io/g/drg/m/test-mod?tab=files"),
+            canonical
+        );
+        assert_eq!(
+            normalize_url("mod.io/g/drg/m/test-mod#3/5"),
+            Some("https://mod.io/g/drg/m/test-mod#3/5".to_string())
+        );
+        assert_eq!(normalize_url("https://mod.io/g/drg/m/test-mod#3"), None);
+        assert_eq!(
+            normalize_url("https://mod.io/g/drg/m/test-mod?preview=abc"),
+            None
+        );
+        assert_eq!(normalize_url("https://example.com/mod.pak"), None);
+    }
+
+    #[test]
+    fn test_validate_url() {
+        for valid in [
+            "https://mod.io/g/drg/m/test-mod",
+            "https://mod.io/g/drg/m/test-mod#3",
+            "https://mod.io/g/drg/m/test-mod#3/5",
+            "http://mod.io/g/drg/m/test-mod?tab=files",
+            "https://mod.io/g/drg/m/test-mod#3@>=1.2, <2",
+            "https://mod.io/g/drg/m/test-mod#3/5|extract-pak",
+            // not mod.io, up to other providers
+            "https://example.com/mod.io/g/drg/m/test-mod#abc",
+            "https://example.com/mod.pak",
+            "test-mod",
+        ] {
+            assert!(validate_url(valid).is_ok(), "{valid}");
+        }
+        assert!(matches!(
+            validate_url("https://mod.io/g/drg/m/test-mod?preview=abc"),
+            Err(ProviderError::PreviewLink { .. })
+        ));
+
+        let reason = |url| match validate_url(url) {
+            Err(ProviderError::MalformedModioUrl { reason, .. }) => reason,
+            other => panic!("unexpected result for {url}: {other:?}"),
+        };
+        assert!(reason("https://mod.io/g/drg/m/test-mod#abc").contains("numbers"));
+        assert!(reason("https://mod.io/g/drg/m/test-mod#3/").contains("numbers"));
+        assert!(reason("https://mod.io/g/satisfactory/m/test-mod").contains("Deep Rock"));
+        assert!(reason("https://mod.io/g/drg/m/test-mod/files").contains("nothing after"));
+        assert!(reason("https://mod.io/g/drg").contains("link to a mod"));
+        assert!(reason("https://mod.io").contains("link to a mod"));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_bare_name_id() {
+        let mut mock = MockDrgModio::new();
+        mock.expect_fetch_mods_by_name()
+            .times(1)
+            .returning(|_| Ok(vec![ModioModResponse { id: 3 }]));
+        mock.expect_fetch_mod()
+            .times(1)
+            .withf(|_, id| *id == 3)
+            .returning(|_, _| Ok(test_mod()));
+
+        let modio_provider = ModioProvider::new(mock);
+        let resolved = modio_provider
+            .resolve_mod(&parse_spec("test-mod"), false, empty_cache())
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            resolved,
+            ModResponse::Redirect(spec) if spec.url == "https://mod.io/g/drg/m/test-mod#3/5"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_malformed_url() {
+        let mut mock = MockDrgModio::new();
+        mock.expect_fetch_mods_by_name().times(0);
+        mock.expect_fetch_mod().times(0);
+
+        let modio_provider = ModioProvider::new(mock);
+        let result = modio_provider
+            .resolve_mod(
+                &ModSpecification::new("https://mod.io/g/drg/m/test-mod#abc".to_string()),
+                false,
+                empty_cache(),
+            )
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ProviderError::MalformedModioUrl { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_ambiguous_name_id() {
+        let mut mock = MockDrgModio::new();
+        mock.expect_fetch_mods_by_name()
+            .times(1)
+            .returning(|_| Ok(vec![ModioModResponse { id: 3 }, ModioModResponse { id: 4 }]));
+        mock.expect_fetch_mod().times(0);
+
+        let modio_provider = ModioProvider::new(mock);
+        let result = modio_provider
+            .resolve_mod(&parse_spec("test-mod"), false, empty_cache())
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ProviderError::AmbiguousModNameId { name_id }) if name_id == "test-mod"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_unknown_name_id() {
+        let mut mock = MockDrgModio::new();
+        mock.expect_fetch_mods_by_name()
+            .times(1)
+            .returning(|_| Ok(vec![]));
+
+        let modio_provider = ModioProvider::new(mock);
+        let result = modio_provider
+            .resolve_mod(&parse_spec("test-mod"), false, empty_cache())
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ProviderError::NoModsForNameId { name_id }) if name_id == "test-mod"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_list_versions() {
+        let mut mock = MockDrgModio::new();
+        mock.expect_fetch_mods_by_name()
+            .times(1)
+            .returning(|_| Ok(vec![ModioModResponse { id: 3 }]));
+        mock.expect_fetch_mod().times(1).returning(|_, _| {
+            Ok(ModioMod {
+                modfiles: vec![
+                    ModioFile {
+                        id: 4,
+                        date_added: 12000,
+                        version: Some("1.0".to_string()),
+                        changelog: None,
+                    },
+                    ModioFile {
+                        id: 5,
+                        date_added: 12345,
+                        version: None,
+                        changelog: None,
+                    },
+                ],
+                tags: HashSet::from(["Verified".to_string()]),
+                ..test_mod()
+            })
+        });
+        mock.expect_fetch_dependencies()
+            .times(1)
+            .returning(|_, _| Ok(vec![]));
+
+        let modio_provider = ModioProvider::new(mock);
+        let cache = empty_cache();
+        let versions = modio_provider
+            .list_versions(&parse_spec("test-mod"), false, cache.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            versions
+                .iter()
+                .map(|v| (v.spec.url.as_str(), v.name.as_str()))
+                .collect::<Vec<_>>(),
+            [
+                ("https://mod.io/g/drg/m/test-mod#3/4", "4 - 1.0"),
+                ("https://mod.io/g/drg/m/test-mod#3/5", "5"),
+            ]
+        );
+        assert_eq!(versions[0].version.as_deref(), Some("1.0"));
+        assert_eq!(versions[0].approval, Some(ApprovalStatus::Verified));
+
+        // listed from the cache the second time
+        let cached = modio_provider
+            .list_versions(&parse_spec("test-mod"), false, cache)
+            .await
+            .unwrap();
+        assert_eq!(cached, versions);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_metadata() {
+        let mut mock = MockDrgModio::new();
+        mock.expect_fetch_mods_by_name()
+            .times(1)
+            .returning(|_| Ok(vec![ModioModResponse { id: 3 }]));
+        mock.expect_fetch_mod()
+            .times(1)
+            .returning(|_, _| Ok(test_mod()));
+        mock.expect_fetch_dependencies()
+            .times(1)
+            .returning(|_, _| Ok(vec![]));
+
+        let mut modio_provider = ModioProvider::new(mock);
+        let cache = empty_cache();
+        let spec = parse_spec("test-mod");
+        let info = resolve_in_provider(&modio_provider, &spec, false, cache.clone())
+            .await
+            .unwrap();
+        assert_eq!(info.name, "Test Mod");
+
+        // a new version was uploaded and the mod renamed and verified since
+        modio_provider.modio.checkpoint();
+        modio_provider
+            .modio
+            .expect_fetch_mod()
+            .times(1)
+            .withf(|_, id| *id == 3)
+            .returning(|_, _| {
+                let mut mod_ = test_mod();
+                mod_.name = "Renamed Mod".to_string();
+                mod_.tags = HashSet::from(["Verified".to_string()]);
+                mod_.latest_modfile = Some(6);
+                mod_.modfiles.push(ModioFile {
+                    id: 6,
+                    date_added: 23456,
+                    version: None,
+                    changelog: None,
+                });
+                Ok(mod_)
+            });
+        modio_provider
+            .refresh_metadata(&spec, cache.clone())
+            .await
+            .unwrap();
+        let refreshed = resolve_in_provider(&modio_provider, &spec, false, cache)
+            .await
+            .unwrap();
+        assert_eq!(refreshed.name, "Renamed Mod");
+        assert_eq!(
+            refreshed.modio_tags.map(|t| t.approval_status),
+            Some(ApprovalStatus::Verified)
+        );
+        assert_eq!(refreshed.resolution, info.resolution);
+    }
+
     #[tokio::test]
     async fn test_check_pass() {
         let mut mock = MockDrgModio::new();
@@ -1064,6 +1572,51 @@ mod test {
         assert!(modio_provider.check().await.is_err());
     }
 
+    #[test]
+    fn test_get_mod_info_without_dependency_metadata() {
+        let cache = empty_cache();
+        {
+            let mut lock = cache.write().unwrap();
+            let modio_cache = lock.get_mut::<ModioCache>(MODIO_PROVIDER_ID);
+            modio_cache.mod_id_map.insert("test-mod".to_string(), 3);
+            modio_cache.mods.insert(
+                3,
+                ModioMod {
+                    stats: Some(ModioStats {
+                        downloads: 1500,
+                        subscribers: 1200,
+                        ratings_positive: 45,
+                        ratings_negative: 5,
+                    }),
+                    ..test_mod()
+                },
+            );
+            // dependency 4 is known to exist but its metadata was never cached
+            modio_cache.dependencies.insert(3, vec![4]);
+        }
+
+        let modio_provider = ModioProvider::new(MockDrgModio::new());
+        let info = modio_provider
+            .get_mod_info(
+                &ModSpecification::new("https://mod.io/g/drg/m/test-mod#3".to_string()),
+                cache,
+            )
+            .unwrap();
+
+        assert_eq!(info.name, "Test Mod");
+        assert!(info.suggested_dependencies.is_empty());
+        let stats = info.modio_stats.unwrap();
+        assert_eq!(stats.downloads, 1500);
+        assert_eq!(stats.rating_percent(), Some(90));
+        assert_eq!(stats.to_string(), "1.5k downloads, 90% positive");
+        let unrated = ModioStats {
+            ratings_positive: 0,
+            ratings_negative: 0,
+            ..stats
+        };
+        assert_eq!(unrated.rating_percent(), None);
+    }
+
     struct FullMod {
         mod_: ModioMod,
         dependencies: Vec<u32>,
@@ -1088,6 +1641,8 @@ mod test {
                             changelog: None,
                         }],
                         tags: HashSet::new(),
+                        description: None,
+                        stats: None,
                     },
                     dependencies: vec![],
                 },
@@ -0,0 +1,241 @@
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+use fs_err as fs;
+
+use crate::integrate::{get_pak_from_data, IntegrationError};
+use crate::pak_builder;
+
+/// Provider parameter holding comma separated patterns of paths to keep.
+pub const INCLUDE_PATHS_PARAMETER: &str = "include_paths";
+/// Provider parameter holding comma separated patterns of paths to drop.
+pub const EXCLUDE_PATHS_PARAMETER: &str = "exclude_paths";
+
+/// Paths to keep when a provider extracts a mod, so only the relevant part of a large archive
+/// ends up in the cached pak.
+///
+/// Patterns are matched case-insensitively against paths relative to the game root (e.g.
+/// `FSD/Content/UI/Menu.uasset`). `*` matches any run of characters and a pattern without one
+/// also matches everything below it. A path is kept if it matches any include pattern (or
+/// there are none) and no exclude pattern.
+///
+/// Filtering rewrites the pak, so the cached blob is identified by the hash of the filtered pak
+/// rather than that of the downloaded file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PathFilter {
+    include: Vec<String>,
+    exclude: Vec<String>,
+}
+
+fn parse_patterns(value: Option<&String>) -> Vec<String> {
+    value
+        .map(|v| {
+            v.split(',')
+                .map(|p| {
+                    p.trim()
+                        .trim_matches('/')
+                        .replace('\\', "/")
+                        .to_ascii_lowercase()
+                })
+                .filter(|p| !p.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn glob_matches(pattern: &str, path: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = path.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts = parts.peekable();
+    while let Some(part) = parts.next() {
+        if parts.peek().is_none() {
+            return rest.ends_with(part);
+        }
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.is_empty()
+}
+
+/// Whether the lowercase `path` matches the normalized lowercase `pattern`, see [`PathFilter`].
+pub(crate) fn pattern_matches(pattern: &str, path: &str) -> bool {
+    if pattern.contains('*') {
+        glob_matches(pattern, path)
+    } else {
+        path == pattern
+            || path
+                .strip_prefix(pattern)
+                .is_some_and(|rest| rest.starts_with('/'))
+    }
+}
+
+impl PathFilter {
+    pub fn new(include: &[&str], exclude: &[&str]) -> Self {
+        let join = |p: &[&str]| Some(p.join(","));
+        Self {
+            include: parse_patterns(join(include).as_ref()),
+            exclude: parse_patterns(join(exclude).as_ref()),
+        }
+    }
+
+    pub fn from_parameters(parameters: &HashMap<String, String>) -> Self {
+        Self {
+            include: parse_patterns(parameters.get(INCLUDE_PATHS_PARAMETER)),
+            exclude: parse_patterns(parameters.get(EXCLUDE_PATHS_PARAMETER)),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    pub fn matches(&self, path: &str) -> bool {
+        let path = path
+            .trim_start_matches('/')
+            .replace('\\', "/")
+            .to_ascii_lowercase();
+        (self.include.is_empty() || self.include.iter().any(|p| pattern_matches(p, &path)))
+            && !self.exclude.iter().any(|p| pattern_matches(p, &path))
+    }
+
+    /// Key to cache the filtered blob of `url` under, so changing the filter fetches it again.
+    pub fn cache_key(&self, url: &str) -> String {
+        if self.is_empty() {
+            url.to_string()
+        } else {
+            format!(
+                "{url}#include={}#exclude={}",
+                self.include.join(","),
+                self.exclude.join(",")
+            )
+        }
+    }
+
+    /// Rewrite the pak contained in `data` (a pak or a zip containing one) keeping only the
+    /// files matching the filter. Version and mount point of the original pak are preserved.
+    pub fn apply(&self, data: Vec<u8>) -> Result<Vec<u8>, IntegrationError> {
+        let mut reader = get_pak_from_data(Box::new(Cursor::new(data)))?;
+        let pak = pak_builder(None).reader(&mut reader)?;
+        let mount = PathBuf::from(pak.mount_point());
+
+        let mut writer = pak_builder(None)
+            .compression([repak::Compression::Zlib])
+            .writer(
+                Cursor::new(vec![]),
+                pak.version(),
+                pak.mount_point().to_string(),
+                None,
+            );
+        for file in pak.files() {
+            let path = mount.join(&file);
+            let path = path.strip_prefix("../../../").unwrap_or(&path);
+            if self.matches(&path.to_string_lossy()) {
+                writer.write_file(&file, &pak.get(&file, &mut reader)?)?;
+            }
+        }
+        Ok(writer.write_index()?.into_inner())
+    }
+
+    /// Filter the mod at `path`, reading it fully into memory.
+    pub fn apply_file<P: AsRef<Path>>(&self, path: P) -> Result<Vec<u8>, IntegrationError> {
+        self.apply(fs::read(path.as_ref())?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::BufReader;
+
+    fn pak_files(data: Vec<u8>) -> Vec<String> {
+        let mut reader = Cursor::new(data);
+        let pak = pak_builder(None).reader(&mut reader).unwrap();
+        let mut files = pak.files();
+        files.sort();
+        files
+    }
+
+    #[test]
+    fn test_matches() {
+        let filter = PathFilter::new(&["FSD/Content/UI", "FSD/Content/*.uasset"], &[]);
+        assert!(filter.matches("FSD/Content/UI/Menu.uexp"));
+        assert!(filter.matches("fsd/content/ui"));
+        assert!(filter.matches("FSD\\Content\\Foo\\Bar.uasset"));
+        assert!(!filter.matches("FSD/Content/UIExtra/Menu.uexp"));
+        assert!(!filter.matches("FSD/Content/Foo/Bar.uexp"));
+
+        let filter = PathFilter::new(&[], &["*.ubulk", "FSD/Content/Audio"]);
+        assert!(filter.matches("FSD/Content/UI/Menu.uasset"));
+        assert!(!filter.matches("FSD/Content/UI/Menu.ubulk"));
+        assert!(!filter.matches("FSD/Content/Audio/Boom.uexp"));
+
+        let filter = PathFilter::new(&["FSD/Content/UI"], &["FSD/Content/UI/Debug"]);
+        assert!(filter.matches("FSD/Content/UI/Menu.uasset"));
+        assert!(!filter.matches("FSD/Content/UI/Debug/Overlay.uasset"));
+
+        assert!(PathFilter::default().matches("anything"));
+        assert!(PathFilter::new(&[" ", ""], &[]).is_empty());
+    }
+
+    #[test]
+    fn test_from_parameters() {
+        let parameters = HashMap::from([
+            (
+                INCLUDE_PATHS_PARAMETER.to_string(),
+                "/FSD/Content/UI/, *.uasset".to_string(),
+            ),
+            (EXCLUDE_PATHS_PARAMETER.to_string(), "*.ubulk".to_string()),
+        ]);
+        let filter = PathFilter::from_parameters(&parameters);
+        assert_eq!(
+            filter,
+            PathFilter::new(&["fsd/content/ui", "*.uasset"], &["*.ubulk"])
+        );
+        assert!(PathFilter::from_parameters(&HashMap::new()).is_empty());
+        assert_eq!(PathFilter::default().cache_key("url"), "url");
+        assert_ne!(filter.cache_key("url"), "url");
+    }
+
+    #[test]
+    fn test_apply() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("test_assets/lints/A.pak");
+        let all = pak_files(fs::read(&path).unwrap());
+        assert!(!all.is_empty());
+
+        let first = all[0].clone();
+        let mount = {
+            let mut reader = BufReader::new(fs::File::open(&path).unwrap());
+            pak_builder(None)
+                .reader(&mut reader)
+                .unwrap()
+                .mount_point()
+                .to_string()
+        };
+        let full = PathBuf::from(&mount).join(&first);
+        let full = full
+            .strip_prefix("../../../")
+            .unwrap_or(&full)
+            .to_string_lossy()
+            .to_string();
+
+        let included = PathFilter::new(&[&full], &[]).apply_file(&path).unwrap();
+        assert_eq!(pak_files(included), vec![first.clone()]);
+
+        let excluded = PathFilter::new(&[], &[&full]).apply_file(&path).unwrap();
+        let expected = all
+            .iter()
+            .filter(|f| **f != first)
+            .cloned()
+            .collect::<Vec<_>>();
+        assert_eq!(pak_files(excluded), expected);
+
+        let unfiltered = PathFilter::default().apply_file(&path).unwrap();
+        assert_eq!(pak_files(unfiltered), all);
+    }
+}
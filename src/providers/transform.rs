@@ -0,0 +1,114 @@
+use std::io::{Cursor, Read};
+
+use crate::integrate::{get_pak_from_data, IntegrationError};
+use crate::providers::PathFilter;
+
+/// A fixup applied to a mod after it has been fetched and before it is integrated, for mods which
+/// are not usable as published. Mods declare the transforms they need after their URL, see
+/// [`ModSpecification::split_transforms`](crate::providers::ModSpecification::split_transforms).
+///
+/// Transforms are collected with [`inventory`] so new ones can be added anywhere in the crate
+/// with `inventory::submit!`.
+#[derive(Debug)]
+pub struct Transform {
+    pub id: &'static str,
+    pub description: &'static str,
+    pub apply: fn(Vec<u8>) -> Result<Vec<u8>, IntegrationError>,
+}
+
+inventory::collect!(Transform);
+
+inventory::submit! {
+    Transform {
+        id: "extract-pak",
+        description: "use the first pak of a zip archive instead of the whole archive",
+        apply: extract_pak,
+    }
+}
+
+inventory::submit! {
+    Transform {
+        id: "repack",
+        description: "rewrite the pak with zlib compression, e.g. if its index is unreadable",
+        apply: repack,
+    }
+}
+
+impl Transform {
+    pub fn get(id: &str) -> Option<&'static Transform> {
+        inventory::iter::<Transform>().find(|t| t.id == id)
+    }
+}
+
+fn extract_pak(data: Vec<u8>) -> Result<Vec<u8>, IntegrationError> {
+    let mut pak = get_pak_from_data(Box::new(Cursor::new(data)))?;
+    let mut buf = vec![];
+    pak.read_to_end(&mut buf)?;
+    Ok(buf)
+}
+
+fn repack(data: Vec<u8>) -> Result<Vec<u8>, IntegrationError> {
+    PathFilter::default().apply(data)
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use fs_err as fs;
+
+    use super::*;
+    use crate::pak_builder;
+    use crate::providers::ModSpecification;
+
+    fn pak_files(data: Vec<u8>) -> Vec<String> {
+        let mut reader = Cursor::new(data);
+        let pak = pak_builder(None).reader(&mut reader).unwrap();
+        let mut files = pak.files();
+        files.sort();
+        files
+    }
+
+    fn asset(name: &str) -> Vec<u8> {
+        fs::read(
+            Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("test_assets/lints")
+                .join(name),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_split_transforms() {
+        let spec =
+            ModSpecification::new("https://example.com/mod.zip@>=1.0|extract-pak, repack".into());
+        let (unversioned, range) = spec.split_version_range();
+        assert_eq!(unversioned.url, "https://example.com/mod.zip");
+        assert_eq!(range, Some(">=1.0"));
+        let (spec, transforms) = spec.split_transforms();
+        assert_eq!(spec.url, "https://example.com/mod.zip@>=1.0");
+        assert_eq!(transforms, vec!["extract-pak", "repack"]);
+
+        let spec = ModSpecification::new("https://example.com/mod.pak".into());
+        assert!(spec.split_transforms().1.is_empty());
+    }
+
+    #[test]
+    fn test_extract_pak() {
+        let extract = Transform::get("extract-pak").unwrap();
+        let pak = (extract.apply)(asset("multiple_paks.zip")).unwrap();
+        assert_eq!(pak_files(pak), pak_files(asset("A.pak")));
+
+        // paks are passed through as is
+        assert_eq!((extract.apply)(asset("A.pak")).unwrap(), asset("A.pak"));
+        assert!((extract.apply)(asset("only_non_pak_files.zip")).is_err());
+    }
+
+    #[test]
+    fn test_repack() {
+        let repack = Transform::get("repack").unwrap();
+        let pak = (repack.apply)(asset("multiple_paks.zip")).unwrap();
+        assert_eq!(pak_files(pak), pak_files(asset("A.pak")));
+        assert!(Transform::get("unknown").is_none());
+    }
+}
@@ -0,0 +1,158 @@
+use std::path::Path;
+use std::process::{Child, Command};
+use std::time::{Duration, Instant};
+
+use mint_lib::{smoke_test, DRGInstallation};
+use tracing::{info, warn};
+
+pub const DEFAULT_SMOKE_TEST_TIMEOUT_SECS: u64 = 180;
+
+/// How often the game is checked on while a smoke test runs.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How long the game gets to exit on its own after passing.
+const EXIT_GRACE: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmokeTestOutcome {
+    /// The game loaded mint's integration and was exited by the hook.
+    Passed { elapsed: Duration },
+    /// The game exited with a failure before loading mint's integration, e.g. it crashed.
+    Exited { code: Option<i32> },
+    /// The game did not load mint's integration in time and was terminated.
+    TimedOut,
+}
+
+impl std::fmt::Display for SmokeTestOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Passed { elapsed } => {
+                write!(f, "the game started in {}s", elapsed.as_secs())
+            }
+            Self::Exited { code: Some(code) } => {
+                write!(
+                    f,
+                    "the game exited with code {code} before it finished starting"
+                )
+            }
+            Self::Exited { code: None } => {
+                write!(f, "the game was terminated before it finished starting")
+            }
+            Self::TimedOut => write!(f, "the game did not finish starting in time"),
+        }
+    }
+}
+
+/// Launch the game of `installation` with `command` and wait until the hook reports that it loaded
+/// mint's integration, which needs mods to be installed together with the hook. The game is
+/// exited as soon as that happens or `timeout` passes, by the hook or else by terminating
+/// `command`.
+///
+/// A command which exits successfully while the game keeps running, e.g. a launcher, is not
+/// mistaken for a failure: the smoke test then waits for the hook until `timeout` passes.
+pub fn run_smoke_test(
+    installation: &DRGInstallation,
+    command: &[String],
+    timeout: Duration,
+) -> std::io::Result<SmokeTestOutcome> {
+    let bin_dir = installation.binaries_directory();
+    smoke_test::request(&bin_dir, timeout)?;
+    let result = launch_and_wait(&bin_dir, command, timeout);
+    if let Err(e) = smoke_test::clear(&bin_dir) {
+        warn!("failed to remove smoke test request: {e}");
+    }
+    result
+}
+
+fn launch_and_wait(
+    bin_dir: &Path,
+    command: &[String],
+    timeout: Duration,
+) -> std::io::Result<SmokeTestOutcome> {
+    let (program, args) = command.split_first().ok_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::InvalidInput, "empty launch command")
+    })?;
+    info!("launching game for smoke test: {command:?}");
+    let mut child = Command::new(program).args(args).spawn()?;
+    let start = Instant::now();
+    let mut running = true;
+    loop {
+        if smoke_test::passed(bin_dir) {
+            let elapsed = start.elapsed();
+            if running {
+                wait_or_kill(&mut child, EXIT_GRACE);
+            }
+            return Ok(SmokeTestOutcome::Passed { elapsed });
+        }
+        if running && let Some(status) = child.try_wait()? {
+            running = false;
+            if !status.success() {
+                return Ok(SmokeTestOutcome::Exited {
+                    code: status.code(),
+                });
+            }
+        }
+        if start.elapsed() >= timeout {
+            if running {
+                child.kill().ok();
+                child.wait().ok();
+            }
+            return Ok(SmokeTestOutcome::TimedOut);
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn wait_or_kill(child: &mut Child, grace: Duration) {
+    let start = Instant::now();
+    while start.elapsed() < grace {
+        if let Ok(Some(_)) = child.try_wait() {
+            return;
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+    warn!("game did not exit after passing the smoke test, terminating it");
+    child.kill().ok();
+    child.wait().ok();
+}
+
+#[cfg(test)]
+mod test {
+    use fs_err as fs;
+
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_smoke_test() {
+        let dir = tempfile::tempdir().unwrap();
+        let pak = dir.path().join("FSD/Content/Paks/FSD-WindowsNoEditor.pak");
+        let installation = DRGInstallation::from_pak_path(&pak).unwrap();
+        let bin_dir = installation.binaries_directory();
+        fs::create_dir_all(&bin_dir).unwrap();
+        let request = bin_dir.join(smoke_test::SMOKE_TEST_FILE);
+        let sh = |script: String| ["sh".to_string(), "-c".to_string(), script];
+        let timeout = Duration::from_secs(10);
+
+        // stands in for the hook passing the test
+        let outcome = run_smoke_test(
+            &installation,
+            &sh(format!(
+                "test -f '{0}' && echo passed > '{0}'",
+                request.display()
+            )),
+            timeout,
+        )
+        .unwrap();
+        assert!(matches!(outcome, SmokeTestOutcome::Passed { .. }));
+        assert!(!request.exists());
+
+        let outcome = run_smoke_test(&installation, &sh("exit 3".to_string()), timeout).unwrap();
+        assert_eq!(outcome, SmokeTestOutcome::Exited { code: Some(3) });
+        assert!(!request.exists());
+
+        let outcome =
+            run_smoke_test(&installation, &sh("sleep 10".to_string()), Duration::ZERO).unwrap();
+        assert_eq!(outcome, SmokeTestOutcome::TimedOut);
+    }
+}
@@ -0,0 +1,317 @@
+use std::io::{BufRead, BufReader, Read, Seek, Write};
+use std::path::{Path, PathBuf};
+
+use fs_err as fs;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use snafu::prelude::*;
+
+use crate::modpack::Modpack;
+use crate::providers::ModSpecification;
+use crate::state::ModConfig;
+
+const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+const MANIFEST_NAME: &str = "snapshot.json";
+
+#[derive(Debug, Snafu)]
+pub enum SnapshotError {
+    #[snafu(transparent)]
+    IoError { source: std::io::Error },
+    #[snafu(display("failed to read snapshot archive: {source}"))]
+    Zip { source: zip::result::ZipError },
+    #[snafu(display("failed to parse snapshot manifest: {source}"))]
+    Parse { source: serde_json::Error },
+    #[snafu(display("unsupported snapshot version {version}"))]
+    UnsupportedVersion { version: u32 },
+    #[snafu(display("snapshot does not contain {url}"))]
+    MissingBlob { url: String },
+    #[snafu(display("{file} does not match the hash in the snapshot manifest"))]
+    HashMismatch { file: String },
+}
+
+/// A profile together with the paks of all its mods, so it can be installed again without
+/// reaching the places the mods were downloaded from.
+///
+/// Snapshots are zip archives containing the mods under `mods/` and a `snapshot.json` manifest
+/// with the pinned [`Modpack`] and the SHA-256 of every mod.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub version: u32,
+    pub modpack: Modpack,
+    pub blobs: Vec<SnapshotBlob>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotBlob {
+    /// The mod of [`Snapshot::modpack`] this is the pak or archive of.
+    pub spec: ModSpecification,
+    /// Name of the mod when the snapshot was taken.
+    pub name: String,
+    /// Path of the mod inside the snapshot archive.
+    pub file: String,
+    pub sha256: String,
+}
+
+impl Snapshot {
+    /// Write `modpack` and its mods to the archive at `path`. `mods` are the names and fetched
+    /// paths of the mods in the same order as [`Modpack::mods`].
+    pub fn write<P: AsRef<Path>>(
+        path: P,
+        modpack: Modpack,
+        mods: &[(String, PathBuf)],
+    ) -> Result<Self, SnapshotError> {
+        let mut zip = zip::ZipWriter::new(fs::File::create(path.as_ref())?);
+        // paks are compressed on their own already
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Stored)
+            .large_file(true);
+
+        let mut blobs = vec![];
+        for (i, (m, (name, mod_path))) in modpack.mods.iter().zip(mods).enumerate() {
+            let mut reader = BufReader::new(fs::File::open(mod_path)?);
+            let extension = if reader.fill_buf()?.starts_with(b"PK\x03\x04") {
+                "zip"
+            } else {
+                "pak"
+            };
+            let file = format!("mods/{i}.{extension}");
+            zip.start_file(file.as_str(), options).context(ZipSnafu)?;
+            let sha256 = copy_hashed(&mut reader, &mut zip)?;
+            blobs.push(SnapshotBlob {
+                spec: m.spec.clone(),
+                name: name.clone(),
+                file,
+                sha256,
+            });
+        }
+
+        let snapshot = Self {
+            version: SNAPSHOT_FORMAT_VERSION,
+            modpack,
+            blobs,
+        };
+        zip.start_file(
+            MANIFEST_NAME,
+            options.compression_method(zip::CompressionMethod::Deflated),
+        )
+        .context(ZipSnafu)?;
+        zip.write_all(serde_json::to_string_pretty(&snapshot).unwrap().as_bytes())?;
+        zip.finish().context(ZipSnafu)?;
+        Ok(snapshot)
+    }
+
+    /// Read the snapshot at `path` and extract its mods to `dir`, checking each against the hash
+    /// in the manifest.
+    pub fn restore<P: AsRef<Path>>(path: P, dir: &Path) -> Result<Self, SnapshotError> {
+        let mut zip = zip::ZipArchive::new(BufReader::new(fs::File::open(path.as_ref())?))
+            .context(ZipSnafu)?;
+        let snapshot = Self::read_manifest(&mut zip)?;
+
+        fs::create_dir_all(dir)?;
+        for m in &snapshot.modpack.mods {
+            let blob = snapshot.blob(&m.spec).context(MissingBlobSnafu {
+                url: m.spec.url.clone(),
+            })?;
+            let mut file = match zip.by_name(&blob.file) {
+                Ok(file) => file,
+                Err(zip::result::ZipError::FileNotFound) => {
+                    return MissingBlobSnafu {
+                        url: m.spec.url.clone(),
+                    }
+                    .fail()
+                }
+                Err(e) => return Err(e).context(ZipSnafu),
+            };
+            let out_path = snapshot.blob_path(dir, blob);
+            let sha256 = copy_hashed(
+                &mut BufReader::new(&mut file),
+                &mut fs::File::create(&out_path)?,
+            )?;
+            if sha256 != blob.sha256 {
+                fs::remove_file(&out_path)?;
+                return HashMismatchSnafu {
+                    file: blob.file.clone(),
+                }
+                .fail();
+            }
+        }
+        Ok(snapshot)
+    }
+
+    fn read_manifest<R: Read + Seek>(zip: &mut zip::ZipArchive<R>) -> Result<Self, SnapshotError> {
+        let mut json = String::new();
+        zip.by_name(MANIFEST_NAME)
+            .context(ZipSnafu)?
+            .read_to_string(&mut json)?;
+        let snapshot: Self = serde_json::from_str(&json).context(ParseSnafu)?;
+        ensure!(
+            snapshot.version <= SNAPSHOT_FORMAT_VERSION,
+            UnsupportedVersionSnafu {
+                version: snapshot.version
+            }
+        );
+        Ok(snapshot)
+    }
+
+    fn blob(&self, spec: &ModSpecification) -> Option<&SnapshotBlob> {
+        self.blobs.iter().find(|b| b.spec == *spec)
+    }
+
+    /// Where [`Self::restore`] extracts `blob` to. Only the file name is used so a manifest
+    /// cannot point outside of `dir`.
+    fn blob_path(&self, dir: &Path, blob: &SnapshotBlob) -> PathBuf {
+        let name = Path::new(&blob.file)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| blob.sha256.clone());
+        dir.join(name)
+    }
+
+    /// Mod configs for the mods restored to `dir`, installing the extracted files under the
+    /// names the mods had when the snapshot was taken. Mods which are not installed, see
+    /// [`Modpack::is_installed`], are restored disabled.
+    pub fn to_mod_configs(&self, dir: &Path) -> Result<Vec<ModConfig>, SnapshotError> {
+        self.modpack
+            .mods
+            .iter()
+            .map(|m| {
+                let blob = self.blob(&m.spec).context(MissingBlobSnafu {
+                    url: m.spec.url.clone(),
+                })?;
+                Ok(ModConfig {
+                    spec: ModSpecification::new(
+                        self.blob_path(dir, blob).to_string_lossy().to_string(),
+                    ),
+                    display_name: Some(blob.name.clone()),
+                    enabled: self.modpack.is_installed(m),
+                    ..m.to_mod_config()
+                })
+            })
+            .collect()
+    }
+}
+
+fn copy_hashed<R: BufRead, W: Write>(reader: &mut R, writer: &mut W) -> std::io::Result<String> {
+    let mut hasher = Sha256::new();
+    loop {
+        let buf = reader.fill_buf()?;
+        if buf.is_empty() {
+            break;
+        }
+        hasher.update(buf);
+        writer.write_all(buf)?;
+        let len = buf.len();
+        reader.consume(len);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::modpack::ModpackMod;
+    use crate::state::PriorityOverride;
+
+    fn modpack(urls: &[&str]) -> Modpack {
+        Modpack {
+            version: 1,
+            name: "snap".to_string(),
+            description: String::new(),
+            disabled_groups: Default::default(),
+            mods: urls
+                .iter()
+                .map(|url| ModpackMod {
+                    spec: ModSpecification::new(url.to_string()),
+                    required: true,
+                    priority: 1,
+                    priority_overrides: vec![],
+                    enabled: true,
+                    group: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_snapshot_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let pak = dir.path().join("a");
+        let zip = dir.path().join("b");
+        fs::write(&pak, b"pak").unwrap();
+        fs::write(&zip, b"PK\x03\x04zip").unwrap();
+
+        let archive = dir.path().join("snap.zip");
+        let mut pack = modpack(&["https://example.com/a", "https://example.com/b"]);
+        pack.mods[0].priority_overrides = vec![PriorityOverride::new("FSD/Content/A", 5).unwrap()];
+        pack.mods[1].enabled = false;
+        let written = Snapshot::write(
+            &archive,
+            pack,
+            &[("A".to_string(), pak), ("B".to_string(), zip)],
+        )
+        .unwrap();
+        assert_eq!(written.blobs[0].file, "mods/0.pak");
+        assert_eq!(written.blobs[1].file, "mods/1.zip");
+
+        let out = dir.path().join("restored");
+        let restored = Snapshot::restore(&archive, &out).unwrap();
+        assert_eq!(restored, written);
+        assert_eq!(fs::read(out.join("0.pak")).unwrap(), b"pak");
+        assert_eq!(fs::read(out.join("1.zip")).unwrap(), b"PK\x03\x04zip");
+
+        let configs = restored.to_mod_configs(&out).unwrap();
+        assert_eq!(configs.len(), 2);
+        assert_eq!(configs[1].spec.url, out.join("1.zip").to_string_lossy());
+        assert_eq!(configs[1].display_name.as_deref(), Some("B"));
+        assert!(configs[1].required);
+        assert!(configs[0].enabled);
+        assert!(!configs[1].enabled);
+        assert_eq!(
+            configs[0].priority_overrides,
+            restored.modpack.mods[0].priority_overrides
+        );
+
+        // a mod without its file is an error rather than left out of the profile
+        let mut incomplete = restored;
+        incomplete.blobs.pop();
+        assert!(matches!(
+            incomplete.to_mod_configs(&out),
+            Err(SnapshotError::MissingBlob { .. })
+        ));
+    }
+
+    #[test]
+    fn test_snapshot_hash_mismatch() {
+        let dir = tempfile::tempdir().unwrap();
+        let pak = dir.path().join("a.pak");
+        fs::write(&pak, b"pak").unwrap();
+
+        let archive = dir.path().join("snap.zip");
+        let mut snapshot = Snapshot::write(
+            &archive,
+            modpack(&["https://example.com/a"]),
+            &[("A".to_string(), pak)],
+        )
+        .unwrap();
+
+        // copy the mod into a new archive with a manifest whose hash does not match
+        snapshot.blobs[0].sha256 = "0".repeat(64);
+        let tampered = dir.path().join("tampered.zip");
+        let mut source = zip::ZipArchive::new(fs::File::open(&archive).unwrap()).unwrap();
+        let mut zip = zip::ZipWriter::new(fs::File::create(&tampered).unwrap());
+        zip.raw_copy_file(source.by_name("mods/0.pak").unwrap())
+            .unwrap();
+        zip.start_file(MANIFEST_NAME, zip::write::SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(serde_json::to_string(&snapshot).unwrap().as_bytes())
+            .unwrap();
+        zip.finish().unwrap();
+
+        let out = dir.path().join("restored");
+        assert!(matches!(
+            Snapshot::restore(&tampered, &out),
+            Err(SnapshotError::HashMismatch { .. })
+        ));
+        assert!(!out.join("0.pak").exists());
+    }
+}
@@ -1,27 +1,41 @@
 pub mod config;
 
 use std::{
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, BTreeSet, HashMap},
     ops::{Deref, DerefMut},
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::Arc,
 };
 
 use fs_err as fs;
+use indexmap::IndexSet;
 use serde::{Deserialize, Serialize};
 use snafu::prelude::*;
+use tracing::{info, warn};
 
 use self::config::ConfigWrapper;
 use crate::{
+    game_update::PakBaseline,
     gui::GuiTheme,
-    providers::{ModSpecification, ModStore},
-    Dirs,
+    integrate::{IntegrateOptions, ModPriority},
+    integrate_hooks::IntegrateHooks,
+    modpack::{Modpack, ModpackMod},
+    providers::{path_filter::pattern_matches, ModSpecification, ModStore},
+    smoke_test::DEFAULT_SMOKE_TEST_TIMEOUT_SECS,
+    stats::UsageStats,
+    Dirs, PakKey,
+};
+use crate::{
+    gui::{SortBy, TagFilter},
+    providers::{ModInfo, ProviderError},
+};
+use mint_lib::{
+    mod_info::{ApprovalStatus, MetaConfig},
+    DRGInstallation,
 };
-use crate::{gui::SortBy, providers::ProviderError};
-use mint_lib::{mod_info::MetaConfig, DRGInstallation};
 
 /// Mod configuration, holds ModSpecification as well as other metadata
-#[derive(Debug, Clone, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ModConfig {
     pub spec: ModSpecification,
     pub required: bool,
@@ -30,6 +44,120 @@ pub struct ModConfig {
     pub enabled: bool,
     #[serde(default, skip_serializing_if = "is_zero")]
     pub priority: i32,
+    /// Name shown in place of the one from the provider. Only affects how the mod is listed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    /// Unix time in seconds the mod was added to its profile. `None` for mods added before this
+    /// was recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub added: Option<u64>,
+    /// What `spec` was before the profile was frozen, while `spec` holds the exact version it
+    /// resolved to then, see [`ModData::freeze_profile`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frozen_from: Option<ModSpecification>,
+    /// Priorities for assets with a file matching a pattern in place of [`Self::priority`]. The
+    /// first matching override applies.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub priority_overrides: Vec<PriorityOverride>,
+}
+
+/// Priority of a mod for the files matching `pattern`, e.g. to let a mod win for textures but not
+/// for blueprints. Patterns are matched case-insensitively against paths relative to the game
+/// root like `FSD/Content/UI/Menu.uasset`. `*` matches any run of characters and a pattern
+/// without one also matches everything below it.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PriorityOverride {
+    pub pattern: String,
+    pub priority: i32,
+}
+
+impl PriorityOverride {
+    pub fn new(pattern: &str, priority: i32) -> Result<Self, StateError> {
+        let priority_override = Self {
+            pattern: normalize_pattern(pattern),
+            priority,
+        };
+        priority_override.validate()?;
+        Ok(priority_override)
+    }
+
+    /// The override with its pattern normalized like [`Self::new`] does, for overrides which may
+    /// have been edited by hand in the mod data.
+    pub fn normalized(&self) -> Self {
+        Self {
+            pattern: normalize_pattern(&self.pattern),
+            priority: self.priority,
+        }
+    }
+
+    /// Check the pattern, which may have been edited by hand in the mod data.
+    pub fn validate(&self) -> Result<(), StateError> {
+        let fail = |reason| {
+            InvalidPriorityPatternSnafu {
+                pattern: self.pattern.clone(),
+                reason,
+            }
+            .fail()
+        };
+        if normalize_pattern(&self.pattern).is_empty() {
+            return fail("the pattern is empty");
+        }
+        if self.pattern.contains(['?', '[', ']', '{', '}']) {
+            return fail("only `*` is supported as a wildcard");
+        }
+        Ok(())
+    }
+
+    /// Whether the lowercase `path` matches the pattern, which has to be
+    /// [normalized](Self::normalized).
+    pub fn matches(&self, path: &str) -> bool {
+        pattern_matches(&self.pattern, path)
+    }
+}
+
+/// `name`, or `name` with the lowest number from 2 appended which is `free`.
+fn unique_name(name: &str, mut free: impl FnMut(&str) -> bool) -> String {
+    if free(name) {
+        return name.to_string();
+    }
+    (2..)
+        .map(|n| format!("{name} {n}"))
+        .find(|name| free(name))
+        .unwrap()
+}
+
+fn normalize_pattern(pattern: &str) -> String {
+    pattern
+        .trim()
+        .replace('\\', "/")
+        .trim_matches('/')
+        .to_ascii_lowercase()
+}
+
+impl ModConfig {
+    /// Name to show for the mod: the custom display name, the provider's name or the URL.
+    pub fn name<'a>(&'a self, info: Option<&'a ModInfo>) -> &'a str {
+        self.display_name
+            .as_deref()
+            .or(info.map(|i| i.name.as_str()))
+            .unwrap_or(&self.spec.url)
+    }
+
+    /// Whether the lowercase `search` is part of the display name or the provider's name.
+    pub fn matches_search(&self, info: Option<&ModInfo>, search: &str) -> bool {
+        self.display_name
+            .iter()
+            .chain(info.map(|i| &i.name))
+            .any(|name| name.to_lowercase().contains(search))
+    }
+}
+
+/// Current Unix time in seconds, for [`ModConfig::added`].
+pub fn unix_time_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
 }
 
 fn default_true() -> bool {
@@ -40,7 +168,11 @@ fn is_zero(value: &i32) -> bool {
     *value == 0
 }
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ModGroup {
     pub mods: Vec<ModConfig>,
 }
@@ -56,6 +188,34 @@ pub struct ModProfile {
     /// A profile can contain ordered individual mods mixed with mod groups.
     #[obake(cfg("0.1.0"))]
     pub mods: Vec<ModOrGroup>,
+
+    /// Asset conflicts the user chose to ignore, keyed by asset path and holding the URLs of
+    /// the mods involved at the time.
+    #[obake(cfg("0.1.0"))]
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub acknowledged_conflicts: BTreeMap<String, BTreeSet<String>>,
+
+    /// Named provider credentials to use while this profile is active, keyed by provider id.
+    /// Providers not listed use the globally selected credentials.
+    #[obake(cfg("0.1.0"))]
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub credentials: BTreeMap<String, String>,
+
+    /// Free-form labels used to filter the profile list.
+    #[obake(cfg("0.1.0"))]
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub tags: BTreeSet<String>,
+
+    /// Free-form notes on what the profile is for, e.g. "hardcore co-op build".
+    #[obake(cfg("0.1.0"))]
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+
+    /// Add the missing dependencies of a mod as soon as it is enabled in the GUI instead of only
+    /// warning about them.
+    #[obake(cfg("0.1.0"))]
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub auto_add_dependencies: bool,
 }
 
 #[derive(Debug, Clone, Hash, Serialize, Deserialize)]
@@ -65,6 +225,50 @@ pub enum ModOrGroup {
     Individual(ModConfig),
 }
 
+impl ModProfile!["0.1.0"] {
+    /// Whether the conflict on `path` was acknowledged for exactly this set of mods.
+    pub fn is_conflict_acknowledged<'a>(
+        &self,
+        path: &str,
+        mods: impl IntoIterator<Item = &'a ModSpecification>,
+    ) -> bool {
+        self.acknowledged_conflicts
+            .get(path)
+            .is_some_and(|acked| *acked == mods.into_iter().map(|m| m.url.clone()).collect())
+    }
+
+    pub fn acknowledge_conflict<'a>(
+        &mut self,
+        path: &str,
+        mods: impl IntoIterator<Item = &'a ModSpecification>,
+    ) {
+        self.acknowledged_conflicts.insert(
+            path.to_string(),
+            mods.into_iter().map(|m| m.url.clone()).collect(),
+        );
+    }
+
+    pub fn unacknowledge_conflict(&mut self, path: &str) {
+        self.acknowledged_conflicts.remove(path);
+    }
+
+    /// Drop acknowledgements that no longer match a reported conflict, e.g. because one of the
+    /// involved mods was removed or another mod started touching the same asset. Returns whether
+    /// anything was removed.
+    pub fn prune_acknowledged_conflicts(
+        &mut self,
+        conflicts: &BTreeMap<String, IndexSet<ModSpecification>>,
+    ) -> bool {
+        let before = self.acknowledged_conflicts.len();
+        self.acknowledged_conflicts.retain(|path, acked| {
+            conflicts.get(path).is_some_and(|mods| {
+                *acked == mods.iter().map(|m| m.url.clone()).collect::<BTreeSet<_>>()
+            })
+        });
+        before != self.acknowledged_conflicts.len()
+    }
+}
+
 impl From<ModProfile!["0.0.0"]> for ModProfile!["0.1.0"] {
     fn from(_legacy: ModProfile!["0.0.0"]) -> Self {
         // The migration requires `ModData` to handle instead.
@@ -84,6 +288,10 @@ pub struct ModData {
     pub profiles: BTreeMap<String, ModProfile!["0.1.0"]>,
     #[obake(cfg("0.1.0"))]
     pub groups: BTreeMap<String, ModGroup>,
+    /// Order the user arranged the profiles in. Profiles not listed follow alphabetically.
+    #[obake(cfg("0.1.0"))]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub profile_order: Vec<String>,
 }
 
 impl ModData!["0.1.0"] {
@@ -163,10 +371,161 @@ impl ModData!["0.1.0"] {
         self.for_each_mod_predicate(profile, f, std::convert::identity, |mc| mc.enabled)
     }
 
+    /// Enabled mods of `profile` in order with duplicates removed. A mod enabled more than once,
+    /// whether individually or through groups, is only kept the first time it appears.
+    pub fn enabled_mods_deduped(&self, profile: &str) -> Vec<ModConfig> {
+        let mut mods: Vec<ModConfig> = vec![];
+        self.for_each_enabled_mod(profile, |mc| {
+            if let Some(existing) = mods.iter().find(|m| m.spec.satisfies_dependency(&mc.spec)) {
+                warn!(
+                    "skipping duplicate mod {} (already enabled as {})",
+                    mc.spec.url, existing.spec.url
+                );
+            } else {
+                mods.push(mc.clone());
+            }
+        });
+        mods
+    }
+
+    /// Remove the mods of `profile` matching `p`. Mods which are part of a group are disabled
+    /// instead, which affects the group in every profile.
+    pub fn remove_mods<P: FnMut(&ModConfig) -> bool>(&mut self, profile: &str, mut p: P) {
+        let profile = self.profiles.get_mut(profile).unwrap();
+        profile.mods.retain(|m| match m {
+            ModOrGroup::Individual(mc) => !p(mc),
+            ModOrGroup::Group { .. } => true,
+        });
+        for m in &profile.mods {
+            if let ModOrGroup::Group { group_name, .. } = m {
+                for mc in &mut self.groups.get_mut(group_name).unwrap().mods {
+                    if p(mc) {
+                        mc.enabled = false;
+                    }
+                }
+            }
+        }
+    }
+
     pub fn for_each_mod_mut<F: FnMut(&mut ModConfig)>(&mut self, profile: &str, f: F) {
         self.for_each_mod_predicate_mut(profile, f, |_| true, |_| true)
     }
 
+    /// Replace every enabled mod of `profile` with the exact version it resolves to in `resolved`
+    /// so updating does not change the profile until it is thawed again. Mods of groups are frozen
+    /// in the group, which affects every profile using it. Returns the number of mods frozen.
+    pub fn freeze_profile(
+        &mut self,
+        profile: &str,
+        resolved: &HashMap<ModSpecification, ModInfo>,
+    ) -> usize {
+        let mut frozen = 0;
+        self.for_each_mod_predicate_mut(
+            profile,
+            |mc| {
+                if let Some(info) = resolved.get(&mc.spec) {
+                    let pinned = ModSpecification::new(info.resolution.url.0.clone());
+                    mc.frozen_from = Some(std::mem::replace(&mut mc.spec, pinned));
+                    frozen += 1;
+                }
+            },
+            std::convert::identity,
+            |mc| mc.enabled && mc.frozen_from.is_none(),
+        );
+        frozen
+    }
+
+    /// Undo [`Self::freeze_profile`] for every mod of `profile`, including disabled ones. Returns
+    /// the number of mods thawed.
+    pub fn thaw_profile(&mut self, profile: &str) -> usize {
+        let mut thawed = 0;
+        self.for_each_mod_mut(profile, |mc| {
+            if let Some(spec) = mc.frozen_from.take() {
+                mc.spec = spec;
+                thawed += 1;
+            }
+        });
+        thawed
+    }
+
+    /// Add a profile exported with [`Modpack::export_profile`] as `name`, or the name it was
+    /// exported with if `None`. Only an explicitly chosen name has to be free, otherwise a number
+    /// is appended to the exported name until it is. Groups are added the same way: an existing
+    /// group of the same name is reused if it holds the same mods and the imported group gets a
+    /// number appended otherwise. Returns the name of the new profile.
+    pub fn import_profile(
+        &mut self,
+        modpack: Modpack,
+        name: Option<String>,
+    ) -> Result<String, StateError> {
+        let name = match name {
+            Some(name) => {
+                ensure!(
+                    !self.profiles.contains_key(&name),
+                    ProfileExistsSnafu { name }
+                );
+                name
+            }
+            None => unique_name(&modpack.name, |name| !self.profiles.contains_key(name)),
+        };
+
+        let mut mods = vec![];
+        let mut groups: BTreeMap<String, Vec<ModpackMod>> = BTreeMap::new();
+        for m in modpack.mods {
+            let Some(group_name) = m.group.clone() else {
+                mods.push(ModOrGroup::Individual(m.to_mod_config()));
+                continue;
+            };
+            if !matches!(mods.last(), Some(ModOrGroup::Group { group_name: last, .. }) if *last == group_name)
+            {
+                mods.push(ModOrGroup::Group {
+                    enabled: !modpack.disabled_groups.contains(&group_name),
+                    group_name: group_name.clone(),
+                });
+            }
+            groups
+                .entry(group_name)
+                .or_default()
+                .push(ModpackMod { group: None, ..m });
+        }
+
+        let mut renamed = HashMap::new();
+        for (group_name, group) in groups {
+            let new_name = unique_name(&group_name, |name| {
+                self.groups.get(name).is_none_or(|existing| {
+                    existing
+                        .mods
+                        .iter()
+                        .map(ModpackMod::from)
+                        .eq(group.iter().cloned())
+                })
+            });
+            self.groups
+                .entry(new_name.clone())
+                .or_insert_with(|| ModGroup {
+                    mods: group.iter().map(ModpackMod::to_mod_config).collect(),
+                });
+            renamed.insert(group_name, new_name);
+        }
+        for m in &mut mods {
+            if let ModOrGroup::Group { group_name, .. } = m
+                && let Some(new_name) = renamed.get(group_name)
+            {
+                *group_name = new_name.clone();
+            }
+        }
+
+        self.profiles.insert(
+            name.clone(),
+            ModProfile_v0_1_0 {
+                mods,
+                description: modpack.description,
+                ..Default::default()
+            },
+        );
+        Ok(name)
+    }
+
     pub fn any_mod<F: FnMut(&ModConfig, Option<bool> /* mod group enabled? */) -> bool>(
         &self,
         profile: &str,
@@ -231,6 +590,7 @@ impl Default for ModData!["0.1.0"] {
             groups: [("default".to_string(), Default::default())]
                 .into_iter()
                 .collect(),
+            profile_order: vec![],
         }
     }
 }
@@ -245,6 +605,11 @@ impl From<ModData!["0.0.0"]> for ModData!["0.1.0"] {
                     .into_iter()
                     .map(ModOrGroup::Individual)
                     .collect(),
+                acknowledged_conflicts: BTreeMap::default(),
+                credentials: BTreeMap::default(),
+                tags: BTreeSet::default(),
+                description: String::new(),
+                auto_add_dependencies: false,
             };
             new_profiles.push((name, new_profile));
         }
@@ -253,6 +618,7 @@ impl From<ModData!["0.0.0"]> for ModData!["0.1.0"] {
             active_profile: legacy.active_profile,
             profiles: new_profiles.into_iter().collect(),
             groups: BTreeMap::default(),
+            profile_order: vec![],
         }
     }
 }
@@ -327,18 +693,180 @@ impl ModData!["0.1.0"] {
 
     pub fn remove_active_profile(&mut self) {
         self.profiles.remove(&self.active_profile);
+        self.profile_order.retain(|p| *p != self.active_profile);
         self.active_profile = self.profiles.keys().next().unwrap().to_string();
     }
+
+    /// Names of all profiles in the order arranged by the user.
+    pub fn ordered_profiles(&self) -> Vec<&String> {
+        let ordered = self
+            .profile_order
+            .iter()
+            .filter(|p| self.profiles.contains_key(*p))
+            .collect::<IndexSet<_>>();
+        let rest = self.profiles.keys().filter(|p| !ordered.contains(p));
+        ordered.iter().copied().chain(rest).collect()
+    }
+
+    /// Move `profile` by `offset` places in the profile order. Returns whether it moved.
+    pub fn move_profile(&mut self, profile: &str, offset: isize) -> bool {
+        let mut order = self
+            .ordered_profiles()
+            .into_iter()
+            .cloned()
+            .collect::<Vec<_>>();
+        let Some(from) = order.iter().position(|p| p == profile) else {
+            return false;
+        };
+        let to = from.saturating_add_signed(offset).min(order.len() - 1);
+        if from == to {
+            return false;
+        }
+        let name = order.remove(from);
+        order.insert(to, name);
+        self.profile_order = order;
+        true
+    }
+
+    /// Keep the position of a renamed profile.
+    pub fn rename_in_profile_order(&mut self, old: &str, new: &str) {
+        for p in &mut self.profile_order {
+            if p == old {
+                *p = new.to_string();
+            }
+        }
+    }
 }
 
 #[obake::versioned]
 #[obake(version("0.0.0"))]
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Config {
+    /// Parameters providers are currently initialized with.
     pub provider_parameters: HashMap<String, HashMap<String, String>>,
+    /// Saved sets of provider parameters (e.g. several mod.io accounts), keyed by provider id and
+    /// then by name.
+    #[serde(default)]
+    pub provider_credentials: HashMap<String, BTreeMap<String, HashMap<String, String>>>,
+    /// Name of the globally selected credentials per provider.
+    #[serde(default)]
+    pub active_credentials: HashMap<String, String>,
     pub drg_pak_path: Option<PathBuf>,
     pub gui_theme: Option<GuiTheme>,
     pub sorting_config: Option<SortingConfig>,
+    pub filter_config: Option<FilterConfig>,
+    /// Profile to select on launch. If unset, the last active profile is used.
+    pub startup_profile: Option<String>,
+    /// Key for reading mod paks which are encrypted.
+    pub pak_key: Option<PakKey>,
+    /// Mods saved for quickly adding to any profile.
+    #[serde(default)]
+    pub favorites: Vec<ModSpecification>,
+    /// Seconds after which a running operation offers to be cancelled. Defaults to
+    /// [`DEFAULT_OPERATION_TIMEOUT_SECS`], 0 disables the watchdog.
+    pub operation_timeout_secs: Option<u64>,
+    /// Open the directory containing the generated mods pak after a successful install.
+    #[serde(default)]
+    pub open_pak_location_after_install: bool,
+    /// Only write the mods pak on install and leave deploying the hook DLL to the user.
+    #[serde(default)]
+    pub skip_hook: bool,
+    /// Write an [`crate::integrate_log::IntegrateLog`] of what every mod contributed to the data
+    /// directory on install.
+    #[serde(default)]
+    pub verbose_integrate: bool,
+    /// Fall back to older versions of mods whose newest version fails to fetch or is unusable
+    /// instead of failing the install.
+    #[serde(default)]
+    pub auto_downgrade: bool,
+    /// Use every pak of mods distributed as archives with multiple paks instead of only the first.
+    #[serde(default)]
+    pub all_archive_paks: bool,
+    /// Leave out halves of `.uasset`/`.uexp` pairs whose other half is missing when installing.
+    #[serde(default)]
+    pub skip_split_assets: bool,
+    /// Write a plain text list of the installed mods into the mods pak.
+    #[serde(default)]
+    pub embed_mod_list: bool,
+    /// Show mods as a single compact line with only their toggle, name and provider.
+    #[serde(default)]
+    pub compact_mod_list: bool,
+    /// Append the version name to the name of each mod in the mod list.
+    #[serde(default)]
+    pub show_mod_versions: bool,
+    /// Accept any file as the game pak instead of checking for `FSD/FSD.uproject`, see
+    /// [`crate::validate_drg_pak`].
+    #[serde(default)]
+    pub skip_pak_validation: bool,
+    /// [`crate::install_hash`] of the last successful install.
+    #[serde(default)]
+    pub last_install_hash: Option<String>,
+    /// The game pak at the last successful install, to notice game updates after which mods have
+    /// to be installed again.
+    #[serde(default)]
+    pub game_pak_baseline: Option<PakBaseline>,
+    /// Also record a hash of the game pak on install, so only actual changes to it count as
+    /// updates rather than any change of its modification time.
+    #[serde(default)]
+    pub hash_game_pak: bool,
+    /// Command run before installing mods, see [`IntegrateHooks`]. Installing is aborted if it
+    /// fails.
+    #[serde(default)]
+    pub pre_integrate: Option<String>,
+    /// Command run after mods were installed successfully, see [`IntegrateHooks`].
+    #[serde(default)]
+    pub post_integrate: Option<String>,
+    /// Extra arguments appended to the game's command line when launching it from mint, see
+    /// [`crate::launch_args::launch_command`].
+    #[serde(default)]
+    pub launch_args: Vec<String>,
+    /// Also copy the mods pak here after every install, see [`crate::integrate::export_pak`].
+    #[serde(default)]
+    pub export_pak_path: Option<PathBuf>,
+    /// Launch the game after installing mods from the GUI to check that it still starts, see
+    /// [`crate::smoke_test`]. Only possible if mint was launched through Steam.
+    #[serde(default)]
+    pub smoke_test_after_install: bool,
+    /// Seconds the game gets to start during a smoke test. Defaults to
+    /// [`DEFAULT_SMOKE_TEST_TIMEOUT_SECS`].
+    #[serde(default)]
+    pub smoke_test_timeout_secs: Option<u64>,
+    /// How the mod a file is taken from is chosen when several enabled mods provide it.
+    #[serde(default)]
+    pub conflict_strategy: ConflictStrategy,
+    /// Count installs and fetches in the data directory, see [`UsageStats`].
+    #[serde(default)]
+    pub collect_stats: bool,
+}
+
+pub const DEFAULT_OPERATION_TIMEOUT_SECS: u64 = 120;
+
+impl Config!["0.0.0"] {
+    pub fn operation_timeout(&self) -> Option<std::time::Duration> {
+        match self
+            .operation_timeout_secs
+            .unwrap_or(DEFAULT_OPERATION_TIMEOUT_SECS)
+        {
+            0 => None,
+            secs => Some(std::time::Duration::from_secs(secs)),
+        }
+    }
+
+    pub fn smoke_test_timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(
+            self.smoke_test_timeout_secs
+                .unwrap_or(DEFAULT_SMOKE_TEST_TIMEOUT_SECS),
+        )
+    }
+
+    /// The configured integrate hooks for installing `profile`.
+    pub fn integrate_hooks(&self, profile: &str) -> IntegrateHooks {
+        IntegrateHooks {
+            pre_integrate: self.pre_integrate.clone(),
+            post_integrate: self.post_integrate.clone(),
+            profile: profile.to_string(),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -356,6 +884,67 @@ impl Default for SortingConfig {
     }
 }
 
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FilterConfig {
+    pub provider: Option<String>,
+    pub tags: BTreeSet<TagFilter>,
+}
+
+impl FilterConfig {
+    pub fn is_empty(&self) -> bool {
+        self.provider.is_none() && self.tags.is_empty()
+    }
+
+    /// Whether a mod passes the filter. Mods without any info only pass an empty filter.
+    pub fn matches(&self, info: Option<&ModInfo>) -> bool {
+        if self.is_empty() {
+            return true;
+        }
+        let Some(info) = info else {
+            return false;
+        };
+        if let Some(provider) = &self.provider
+            && provider != info.provider
+        {
+            return false;
+        }
+        self.tags.iter().all(|tag| {
+            info.modio_tags
+                .as_ref()
+                .is_some_and(|modio_tags| tag.matches(modio_tags))
+        })
+    }
+}
+
+/// How the order mods are integrated in is chosen. The first mod providing a file wins.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConflictStrategy {
+    /// Mods with a higher priority win, mods with equal priority in the order of the profile.
+    #[default]
+    Priority,
+    /// Mods with a higher priority win, ties are broken by mod.io approval: Verified before
+    /// Approved before Sandbox. Mods without mod.io metadata count as Sandbox.
+    Approval,
+}
+
+impl ConflictStrategy {
+    /// Sort `mods` into integration order. `approval` looks up the mod.io approval of a mod.
+    pub fn sort<F>(self, mods: &mut [ModConfig], approval: F)
+    where
+        F: Fn(&ModSpecification) -> Option<ApprovalStatus>,
+    {
+        match self {
+            Self::Priority => mods.sort_by_key(|m| -m.priority),
+            Self::Approval => mods.sort_by_cached_key(|m| {
+                (
+                    -m.priority,
+                    approval(&m.spec).unwrap_or(ApprovalStatus::Sandbox),
+                )
+            }),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(tag = "version")]
 pub enum VersionAnnotatedConfig {
@@ -408,11 +997,39 @@ impl Default for Config!["0.0.0"] {
     fn default() -> Self {
         Self {
             provider_parameters: Default::default(),
+            provider_credentials: Default::default(),
+            active_credentials: Default::default(),
             drg_pak_path: DRGInstallation::find()
                 .as_ref()
                 .map(DRGInstallation::main_pak),
             gui_theme: None,
             sorting_config: None,
+            filter_config: None,
+            startup_profile: None,
+            pak_key: None,
+            favorites: vec![],
+            operation_timeout_secs: None,
+            open_pak_location_after_install: false,
+            skip_hook: false,
+            verbose_integrate: false,
+            auto_downgrade: false,
+            all_archive_paks: false,
+            skip_split_assets: false,
+            embed_mod_list: false,
+            compact_mod_list: false,
+            show_mod_versions: false,
+            skip_pak_validation: false,
+            last_install_hash: None,
+            game_pak_baseline: None,
+            hash_game_pak: false,
+            pre_integrate: None,
+            post_integrate: None,
+            launch_args: vec![],
+            export_pak_path: None,
+            smoke_test_after_install: false,
+            smoke_test_timeout_secs: None,
+            conflict_strategy: ConflictStrategy::Priority,
+            collect_stats: false,
         }
     }
 }
@@ -423,6 +1040,18 @@ impl From<&VersionAnnotatedConfig> for MetaConfig {
     }
 }
 
+impl From<&VersionAnnotatedConfig> for IntegrateOptions {
+    fn from(value: &VersionAnnotatedConfig) -> Self {
+        IntegrateOptions {
+            all_archive_paks: value.all_archive_paks,
+            skip_split_assets: value.skip_split_assets,
+            embed_mod_list: value.embed_mod_list,
+            export_path: value.export_pak_path.clone(),
+            priorities: vec![],
+        }
+    }
+}
+
 #[derive(Debug, Snafu)]
 pub enum StateError {
     #[snafu(display("failed to deserialize user config"))]
@@ -445,6 +1074,25 @@ pub enum StateError {
     ModDataDeserializationFailed { source: serde_json::Error },
     #[snafu(display("failed to deserialize legacy profiles"))]
     LegacyProfilesDeserializationFailed { source: serde_json::Error },
+    #[snafu(display("invalid priority override pattern {pattern:?}: {reason}"))]
+    InvalidPriorityPattern {
+        pattern: String,
+        reason: &'static str,
+    },
+    #[snafu(display("profile {name:?} does not exist"))]
+    ProfileNotFound { name: String },
+    #[snafu(display("profile {name:?} already exists"))]
+    ProfileExists { name: String },
+}
+
+/// A file of mint's own state which could not be parsed on startup and was replaced with
+/// defaults so mint can still start.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecoveredFile {
+    pub path: PathBuf,
+    /// Where the unparsable file was moved to.
+    pub backup: PathBuf,
+    pub error: String,
 }
 
 pub struct State {
@@ -452,30 +1100,194 @@ pub struct State {
     pub config: ConfigWrapper<VersionAnnotatedConfig>,
     pub mod_data: ConfigWrapper<VersionAnnotatedModData>,
     pub store: Arc<ModStore>,
+    /// Files which had to be reset on startup.
+    pub recovered: Vec<RecoveredFile>,
 }
 
 impl State {
     pub fn init(dirs: Dirs) -> Result<Self, StateError> {
         let config_path = dirs.config_dir.join("config.json");
+        let mut recovered = vec![];
 
-        let config = read_config_or_default(&config_path)?;
+        let (config, config_recovered) =
+            recover_unparsable(&config_path, read_config_or_default(&config_path))?;
+        recovered.extend(config_recovered);
         let config = ConfigWrapper::<VersionAnnotatedConfig>::new(&config_path, config);
         config.save().unwrap();
 
         let legacy_mod_profiles_path = dirs.config_dir.join("profiles.json");
         let mod_data_path = dirs.config_dir.join("mod_data.json");
-        let mod_data = read_mod_data_or_default(&mod_data_path, legacy_mod_profiles_path)?;
+        let (mod_data, mod_data_recovered) = recover_unparsable(
+            &mod_data_path,
+            read_mod_data_or_default(&mod_data_path, legacy_mod_profiles_path),
+        )?;
+        recovered.extend(mod_data_recovered);
         let mod_data = ConfigWrapper::<VersionAnnotatedModData>::new(mod_data_path, mod_data);
         mod_data.save().unwrap();
 
         let store = ModStore::new(&dirs.cache_dir, &config.provider_parameters)?.into();
 
-        Ok(Self {
+        let mut state = Self {
             dirs,
             config,
             mod_data,
             store,
-        })
+            recovered,
+        };
+        if let Err(e) = state.apply_credentials() {
+            warn!("failed to apply provider credentials: {e}");
+        }
+        Ok(state)
+    }
+
+    /// Select the profile configured to be selected on launch, if any. Only the GUI does this, the
+    /// command line keeps working on the last active profile. Not saved until something else is.
+    pub fn apply_startup_profile(&mut self) {
+        let Some(startup_profile) = &self.config.startup_profile else {
+            return;
+        };
+        if self.mod_data.profiles.contains_key(startup_profile) {
+            self.mod_data.active_profile = startup_profile.clone();
+        } else {
+            warn!(
+                "startup profile {startup_profile:?} does not exist, falling back to {:?}",
+                self.mod_data.active_profile
+            );
+        }
+    }
+
+    /// Enabled mods of `profile` in the order they are integrated, see [`ConflictStrategy`].
+    pub fn integration_order(&self, profile: &str) -> Vec<ModSpecification> {
+        let mut mods = self.mod_data.enabled_mods_deduped(profile);
+        self.config.conflict_strategy.sort(&mut mods, |spec| {
+            Some(self.store.get_mod_info(spec)?.modio_tags?.approval_status)
+        });
+        mods.into_iter().map(|m| m.spec).collect()
+    }
+
+    /// [`ModPriority`] of each of `mods` as configured in `profile`, for
+    /// [`IntegrateOptions::priorities`]. Mods which are not part of the profile get the default
+    /// priority. Fails if a priority override of an enabled mod has an invalid pattern.
+    pub fn mod_priorities(
+        &self,
+        profile: &str,
+        mods: &[ModSpecification],
+    ) -> Result<Vec<ModPriority>, StateError> {
+        let enabled = self.mod_data.enabled_mods_deduped(profile);
+        for mc in &enabled {
+            for priority_override in &mc.priority_overrides {
+                priority_override.validate()?;
+            }
+        }
+        Ok(mods
+            .iter()
+            .map(|spec| {
+                enabled
+                    .iter()
+                    .find(|mc| mc.spec == *spec)
+                    .map(ModPriority::from)
+                    .unwrap_or_default()
+            })
+            .collect())
+    }
+
+    /// Add a profile exported with [`Modpack::export_profile`] and save it, see
+    /// [`ModData::import_profile`] for how `new_name` and collisions are handled. Returns the name
+    /// of the new profile.
+    pub fn import_profile(
+        &mut self,
+        modpack: Modpack,
+        new_name: Option<String>,
+    ) -> Result<String, StateError> {
+        let name = self.mod_data.import_profile(modpack, new_name)?;
+        self.mod_data.save()?;
+        Ok(name)
+    }
+
+    /// Count the fetches since the last call and, if `installed` is set, an install of those mods
+    /// in the [`UsageStats`]. Nothing is recorded unless stats are enabled.
+    pub fn record_usage(&self, installed: Option<&[ModSpecification]>) {
+        let fetches = self.store.take_fetch_stats();
+        if !self.config.collect_stats {
+            return;
+        }
+        let mut stats = UsageStats::read(&self.dirs.data_dir);
+        stats.record_fetches(fetches);
+        if let Some(mods) = installed {
+            stats.record_integrate(mods);
+        }
+        if let Err(e) = stats.write(&self.dirs.data_dir) {
+            warn!("failed to save usage stats: {e}");
+        }
+    }
+
+    /// Name of the credentials `provider` should use for the active profile: the profile's own
+    /// choice if it has one, otherwise the globally selected credentials.
+    pub fn selected_credentials(&self, provider: &str) -> Option<&str> {
+        self.mod_data
+            .get_active_profile()
+            .credentials
+            .get(provider)
+            .or_else(|| self.config.active_credentials.get(provider))
+            .map(String::as_str)
+    }
+
+    /// Re-initialize any provider whose selected credentials differ from the parameters it is
+    /// currently using.
+    pub fn apply_credentials(&mut self) -> Result<(), StateError> {
+        for factory in ModStore::get_provider_factories() {
+            let Some(name) = self.selected_credentials(factory.id).map(str::to_string) else {
+                continue;
+            };
+            let Some(params) = self
+                .config
+                .provider_credentials
+                .get(factory.id)
+                .and_then(|c| c.get(&name))
+                .cloned()
+            else {
+                warn!("{} credentials {name:?} do not exist", factory.id);
+                continue;
+            };
+            if self.config.provider_parameters.get(factory.id) != Some(&params) {
+                info!("switching {} provider to {name:?} credentials", factory.id);
+                self.store.add_provider(factory, &params)?;
+                self.config
+                    .provider_parameters
+                    .insert(factory.id.to_string(), params);
+                self.config.save()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Save `parameters` as the credentials called `name` and select them, both globally and for
+    /// the active profile if it overrides the credentials for `provider`.
+    pub fn save_credentials(
+        &mut self,
+        provider: &str,
+        name: String,
+        parameters: HashMap<String, String>,
+    ) -> Result<(), StateError> {
+        self.config
+            .provider_credentials
+            .entry(provider.to_string())
+            .or_default()
+            .insert(name.clone(), parameters.clone());
+        self.config
+            .active_credentials
+            .insert(provider.to_string(), name.clone());
+        self.config
+            .provider_parameters
+            .insert(provider.to_string(), parameters);
+        self.config.save()?;
+
+        let profile = self.mod_data.get_active_profile_mut();
+        if let Some(selected) = profile.credentials.get_mut(provider) {
+            *selected = name;
+            self.mod_data.save()?;
+        }
+        Ok(())
     }
 }
 
@@ -503,6 +1315,50 @@ fn read_config_or_default(config_path: &PathBuf) -> Result<VersionAnnotatedConfi
     })
 }
 
+/// Start over with defaults if `path` could not be parsed, e.g. after a bad manual edit, instead
+/// of refusing to start. The unparsable file is kept next to it for manual repair. Any other error,
+/// including a config written by a newer version of mint, is returned as is.
+fn recover_unparsable<T: Default>(
+    path: &Path,
+    read: Result<T, StateError>,
+) -> Result<(T, Option<RecoveredFile>), StateError> {
+    let error = match read {
+        Ok(value) => return Ok((value, None)),
+        Err(
+            ref e @ (StateError::CfgDeserializationFailed { ref source }
+            | StateError::ModDataDeserializationFailed { ref source }),
+        ) => format!("{e}: {source}"),
+        Err(e) => return Err(e),
+    };
+
+    let backup = (0..)
+        .map(|i| {
+            let mut name = path.file_name().unwrap().to_os_string();
+            name.push(if i == 0 {
+                ".corrupt".to_string()
+            } else {
+                format!(".corrupt.{i}")
+            });
+            path.with_file_name(name)
+        })
+        .find(|p| !p.exists())
+        .unwrap();
+    fs::rename(path, &backup)?;
+    warn!(
+        "{error}, starting with defaults, the old file was moved to {}",
+        backup.display()
+    );
+
+    Ok((
+        T::default(),
+        Some(RecoveredFile {
+            path: path.to_path_buf(),
+            backup,
+            error,
+        }),
+    ))
+}
+
 fn read_mod_data_or_default(
     mod_data_path: &PathBuf,
     legacy_mod_profiles_path: PathBuf,
@@ -539,11 +1395,46 @@ fn read_mod_data_or_default(
 }
 
 #[cfg(test)]
-mod mod_data_tests {
+pub(crate) mod mod_data_tests {
+    use mint_lib::mod_info::ApprovalStatus;
+
     use super::{
-        ModConfig, ModData_v0_1_0 as ModData, ModGroup, ModOrGroup, ModProfile_v0_1_0 as ModProfile,
+        unix_time_now, ConflictStrategy, ModConfig, ModData_v0_1_0 as ModData, ModGroup,
+        ModOrGroup, ModProfile_v0_1_0 as ModProfile, VersionAnnotatedModData,
     };
-    use crate::providers::ModSpecification;
+    use crate::modpack::Modpack;
+    use crate::providers::{ModInfo, ModResolution, ModSpecification};
+
+    /// An enabled mod with default settings.
+    pub(crate) fn mod_config(url: &str) -> ModConfig {
+        ModConfig {
+            spec: ModSpecification::new(url.to_string()),
+            required: false,
+            enabled: true,
+            priority: 0,
+            display_name: None,
+            added: None,
+            frozen_from: None,
+            priority_overrides: vec![],
+        }
+    }
+
+    /// A mod which resolves to `resolved`.
+    pub(crate) fn mod_info(url: &str, resolved: &str) -> ModInfo {
+        ModInfo {
+            provider: "test",
+            name: url.to_string(),
+            spec: ModSpecification::new(url.to_string()),
+            versions: vec![],
+            resolution: ModResolution::resolvable(resolved.to_string().into()),
+            suggested_require: false,
+            suggested_dependencies: vec![],
+            modio_tags: None,
+            modio_id: None,
+            modio_stats: None,
+            description: None,
+        }
+    }
 
     #[test]
     fn test_for_each_mod() {
@@ -552,6 +1443,10 @@ mod mod_data_tests {
             required: false,
             enabled: false,
             priority: 50,
+            display_name: None,
+            added: None,
+            frozen_from: None,
+            priority_overrides: vec![],
         };
 
         let mod_2 = ModConfig {
@@ -559,6 +1454,10 @@ mod mod_data_tests {
             required: true,
             enabled: false,
             priority: 50,
+            display_name: None,
+            added: None,
+            frozen_from: None,
+            priority_overrides: vec![],
         };
 
         let mod_3 = ModConfig {
@@ -566,6 +1465,10 @@ mod mod_data_tests {
             required: false,
             enabled: true,
             priority: 50,
+            display_name: None,
+            added: None,
+            frozen_from: None,
+            priority_overrides: vec![],
         };
 
         let mod_data = ModData {
@@ -580,6 +1483,7 @@ mod mod_data_tests {
                             enabled: false,
                         },
                     ],
+                    ..Default::default()
                 },
             )]
             .into(),
@@ -590,6 +1494,7 @@ mod mod_data_tests {
                 },
             )]
             .into(),
+            profile_order: vec![],
         };
 
         let mut counter = 0;
@@ -606,6 +1511,10 @@ mod mod_data_tests {
             required: false,
             enabled: false,
             priority: 50,
+            display_name: None,
+            added: None,
+            frozen_from: None,
+            priority_overrides: vec![],
         };
 
         let mod_2 = ModConfig {
@@ -613,6 +1522,10 @@ mod mod_data_tests {
             required: true,
             enabled: false,
             priority: 50,
+            display_name: None,
+            added: None,
+            frozen_from: None,
+            priority_overrides: vec![],
         };
 
         let mod_3 = ModConfig {
@@ -620,6 +1533,10 @@ mod mod_data_tests {
             required: false,
             enabled: true,
             priority: 50,
+            display_name: None,
+            added: None,
+            frozen_from: None,
+            priority_overrides: vec![],
         };
 
         let mod_data = ModData {
@@ -634,6 +1551,7 @@ mod mod_data_tests {
                             enabled: true,
                         },
                     ],
+                    ..Default::default()
                 },
             )]
             .into(),
@@ -644,6 +1562,7 @@ mod mod_data_tests {
                 },
             )]
             .into(),
+            profile_order: vec![],
         };
 
         let mut counter = 0;
@@ -653,6 +1572,66 @@ mod mod_data_tests {
         assert_eq!(counter, 1);
     }
 
+    #[test]
+    fn test_enabled_mods_deduped() {
+        let mod_data = ModData {
+            active_profile: "default".to_string(),
+            profiles: [(
+                "default".to_string(),
+                ModProfile {
+                    mods: vec![
+                        ModOrGroup::Individual(mod_config("a")),
+                        ModOrGroup::Group {
+                            group_name: "mg1".to_string(),
+                            enabled: true,
+                        },
+                        ModOrGroup::Group {
+                            group_name: "mg2".to_string(),
+                            enabled: true,
+                        },
+                    ],
+                    ..Default::default()
+                },
+            )]
+            .into(),
+            groups: [
+                (
+                    "mg1".to_string(),
+                    ModGroup {
+                        mods: vec![mod_config("a#1/2"), mod_config("b")],
+                    },
+                ),
+                (
+                    "mg2".to_string(),
+                    ModGroup {
+                        mods: vec![
+                            mod_config("b"),
+                            ModConfig {
+                                enabled: false,
+                                ..mod_config("c")
+                            },
+                        ],
+                    },
+                ),
+            ]
+            .into(),
+            profile_order: vec![],
+        };
+
+        let mut counter = 0;
+        mod_data.for_each_enabled_mod("default", |_| {
+            counter += 1;
+        });
+        assert_eq!(counter, 4);
+
+        let urls = mod_data
+            .enabled_mods_deduped("default")
+            .into_iter()
+            .map(|mc| mc.spec.url)
+            .collect::<Vec<_>>();
+        assert_eq!(urls, ["a", "b"]);
+    }
+
     #[test]
     fn test_any_mod() {
         let mod_1 = ModConfig {
@@ -660,6 +1639,10 @@ mod mod_data_tests {
             required: false,
             enabled: false,
             priority: 50,
+            display_name: None,
+            added: None,
+            frozen_from: None,
+            priority_overrides: vec![],
         };
 
         let mod_2 = ModConfig {
@@ -667,6 +1650,10 @@ mod mod_data_tests {
             required: true,
             enabled: false,
             priority: 50,
+            display_name: None,
+            added: None,
+            frozen_from: None,
+            priority_overrides: vec![],
         };
 
         let mod_3 = ModConfig {
@@ -674,6 +1661,10 @@ mod mod_data_tests {
             required: false,
             enabled: true,
             priority: 50,
+            display_name: None,
+            added: None,
+            frozen_from: None,
+            priority_overrides: vec![],
         };
 
         let mod_data = ModData {
@@ -688,6 +1679,7 @@ mod mod_data_tests {
                             enabled: true,
                         },
                     ],
+                    ..Default::default()
                 },
             )]
             .into(),
@@ -698,9 +1690,494 @@ mod mod_data_tests {
                 },
             )]
             .into(),
+            profile_order: vec![],
         };
 
         let any_required = mod_data.any_mod("default", |mc, _| mc.required);
         assert!(any_required);
     }
+
+    #[test]
+    fn test_prune_acknowledged_conflicts() {
+        let a = ModSpecification::new("a".to_string());
+        let b = ModSpecification::new("b".to_string());
+        let c = ModSpecification::new("c".to_string());
+
+        let mut profile = ModProfile::default();
+        profile.acknowledge_conflict("fsd/content/x.uasset", [&a, &b]);
+        profile.acknowledge_conflict("fsd/content/y.uasset", [&a, &b]);
+        profile.acknowledge_conflict("fsd/content/z.uasset", [&a, &c]);
+
+        let conflicts = [
+            (
+                "fsd/content/x.uasset".to_string(),
+                [b.clone(), a.clone()].into(),
+            ),
+            (
+                "fsd/content/y.uasset".to_string(),
+                [a.clone(), b.clone(), c.clone()].into(),
+            ),
+        ]
+        .into();
+
+        assert!(profile.prune_acknowledged_conflicts(&conflicts));
+        assert!(profile
+            .is_conflict_acknowledged("fsd/content/x.uasset", &conflicts["fsd/content/x.uasset"]));
+        assert!(!profile
+            .is_conflict_acknowledged("fsd/content/y.uasset", &conflicts["fsd/content/y.uasset"]));
+        assert!(!profile.is_conflict_acknowledged("fsd/content/z.uasset", [&a, &c]));
+        assert!(!profile.prune_acknowledged_conflicts(&conflicts));
+    }
+
+    #[test]
+    fn test_profile_tags() {
+        let untagged =
+            r#"{"version":"0.1.0","active_profile":"a","profiles":{"a":{"mods":[]}},"groups":{}}"#;
+        let VersionAnnotatedModData::V0_1_0(mut mod_data) = serde_json::from_str(untagged).unwrap()
+        else {
+            unreachable!()
+        };
+        assert!(mod_data.profiles["a"].tags.is_empty());
+
+        mod_data
+            .get_active_profile_mut()
+            .tags
+            .insert("coop".to_string());
+        let json = serde_json::to_string(&VersionAnnotatedModData::V0_1_0(mod_data)).unwrap();
+        let VersionAnnotatedModData::V0_1_0(mod_data) = serde_json::from_str(&json).unwrap() else {
+            unreachable!()
+        };
+        assert_eq!(mod_data.profiles["a"].tags, ["coop".to_string()].into());
+
+        let legacy = r#"{"version":"0.0.0","active_profile":"a","profiles":{"a":{"mods":[]}}}"#;
+        let VersionAnnotatedModData::V0_0_0(legacy) = serde_json::from_str(legacy).unwrap() else {
+            unreachable!()
+        };
+        let migrated: ModData = legacy.into();
+        assert!(migrated.profiles["a"].tags.is_empty());
+    }
+
+    #[test]
+    fn test_profile_description() {
+        let plain =
+            r#"{"version":"0.1.0","active_profile":"a","profiles":{"a":{"mods":[]}},"groups":{}}"#;
+        let VersionAnnotatedModData::V0_1_0(mut mod_data) = serde_json::from_str(plain).unwrap()
+        else {
+            unreachable!()
+        };
+        assert!(mod_data.profiles["a"].description.is_empty());
+
+        mod_data.get_active_profile_mut().description = "hardcore co-op build".to_string();
+        let json = serde_json::to_string(&VersionAnnotatedModData::V0_1_0(mod_data)).unwrap();
+        let VersionAnnotatedModData::V0_1_0(mod_data) = serde_json::from_str(&json).unwrap() else {
+            unreachable!()
+        };
+        assert_eq!(mod_data.profiles["a"].description, "hardcore co-op build");
+
+        let legacy = r#"{"version":"0.0.0","active_profile":"a","profiles":{"a":{"mods":[]}}}"#;
+        let VersionAnnotatedModData::V0_0_0(legacy) = serde_json::from_str(legacy).unwrap() else {
+            unreachable!()
+        };
+        let migrated: ModData = legacy.into();
+        assert!(migrated.profiles["a"].description.is_empty());
+    }
+
+    #[test]
+    fn test_profile_auto_add_dependencies() {
+        let plain =
+            r#"{"version":"0.1.0","active_profile":"a","profiles":{"a":{"mods":[]}},"groups":{}}"#;
+        let VersionAnnotatedModData::V0_1_0(mut mod_data) = serde_json::from_str(plain).unwrap()
+        else {
+            unreachable!()
+        };
+        assert!(!mod_data.profiles["a"].auto_add_dependencies);
+
+        mod_data.get_active_profile_mut().auto_add_dependencies = true;
+        let json = serde_json::to_string(&VersionAnnotatedModData::V0_1_0(mod_data)).unwrap();
+        assert!(json.contains(r#""auto_add_dependencies":true"#));
+        let VersionAnnotatedModData::V0_1_0(mod_data) = serde_json::from_str(&json).unwrap() else {
+            unreachable!()
+        };
+        assert!(mod_data.profiles["a"].auto_add_dependencies);
+    }
+
+    #[test]
+    fn test_profile_order() {
+        let mut mod_data = ModData {
+            active_profile: "b".to_string(),
+            profiles: ["a", "b", "c", "d"]
+                .map(|p| (p.to_string(), ModProfile::default()))
+                .into(),
+            groups: Default::default(),
+            profile_order: vec!["c".to_string(), "gone".to_string()],
+        };
+        let order = |mod_data: &ModData| {
+            mod_data
+                .ordered_profiles()
+                .into_iter()
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        // listed profiles first, the rest alphabetically
+        assert_eq!(order(&mod_data), ["c", "a", "b", "d"]);
+
+        assert!(mod_data.move_profile("b", -1));
+        assert_eq!(order(&mod_data), ["c", "b", "a", "d"]);
+        assert_eq!(mod_data.profile_order, ["c", "b", "a", "d"]);
+        assert!(!mod_data.move_profile("c", -1));
+        assert!(!mod_data.move_profile("d", 1));
+        assert!(!mod_data.move_profile("missing", 1));
+        assert!(mod_data.move_profile("c", 10));
+        assert_eq!(order(&mod_data), ["b", "a", "d", "c"]);
+
+        mod_data.rename_in_profile_order("a", "z");
+        mod_data
+            .profiles
+            .insert("z".to_string(), Default::default());
+        mod_data.profiles.remove("a");
+        mod_data
+            .profiles
+            .insert("e".to_string(), Default::default());
+        assert_eq!(order(&mod_data), ["b", "z", "d", "c", "e"]);
+
+        mod_data.remove_active_profile();
+        assert_eq!(mod_data.profile_order, ["z", "d", "c"]);
+        assert_eq!(order(&mod_data), ["z", "d", "c", "e"]);
+    }
+
+    #[test]
+    fn test_mod_display_name() {
+        let mut mc: ModConfig = serde_json::from_str(
+            r#"{"spec":{"url":"https://example.com/a.pak"},"required":false}"#,
+        )
+        .unwrap();
+        let info = ModInfo {
+            name: "a_v2_FINAL.pak".to_string(),
+            ..mod_info(&mc.spec.url, &mc.spec.url)
+        };
+        assert_eq!(mc.display_name, None);
+        assert_eq!(mc.name(None), "https://example.com/a.pak");
+        assert_eq!(mc.name(Some(&info)), "a_v2_FINAL.pak");
+        assert!(!serde_json::to_string(&mc).unwrap().contains("display_name"));
+
+        mc.display_name = Some("Better Scout".to_string());
+        assert_eq!(mc.name(None), "Better Scout");
+        assert_eq!(mc.name(Some(&info)), "Better Scout");
+        assert!(mc.matches_search(Some(&info), "scout"));
+        assert!(mc.matches_search(Some(&info), "final"));
+        assert!(!mc.matches_search(None, "final"));
+        // the URL stays what the mod is resolved by
+        assert_eq!(mc.spec.url, "https://example.com/a.pak");
+    }
+
+    #[test]
+    fn test_mod_added() {
+        // mods from before dates were recorded have none
+        let mut mc: ModConfig = serde_json::from_str(
+            r#"{"spec":{"url":"https://example.com/a.pak"},"required":false}"#,
+        )
+        .unwrap();
+        assert_eq!(mc.added, None);
+        assert!(!serde_json::to_string(&mc).unwrap().contains("added"));
+
+        mc.added = Some(1_790_000_000);
+        let mc: ModConfig = serde_json::from_str(&serde_json::to_string(&mc).unwrap()).unwrap();
+        assert_eq!(mc.added, Some(1_790_000_000));
+        assert!(unix_time_now() > 1_700_000_000);
+    }
+
+    #[test]
+    fn test_priority_override() {
+        let priority_override = PriorityOverride::new(" /FSD\\Content/Textures/ ", 5).unwrap();
+        assert_eq!(priority_override.pattern, "fsd/content/textures");
+        assert!(priority_override.matches("fsd/content/textures/rock.uasset"));
+        assert!(!priority_override.matches("fsd/content/ui/menu.uasset"));
+
+        assert!(PriorityOverride::new(" / ", 5).is_err());
+        assert!(PriorityOverride::new("FSD/Content/*.ua?set", 5).is_err());
+        assert!(PriorityOverride::new("FSD/Content/*.uasset", 5).is_ok());
+
+        let mut mc: ModConfig = serde_json::from_str(
+            r#"{"spec":{"url":"https://example.com/a.pak"},"required":false}"#,
+        )
+        .unwrap();
+        assert!(!serde_json::to_string(&mc)
+            .unwrap()
+            .contains("priority_overrides"));
+        mc.priority_overrides.push(priority_override);
+        let mc: ModConfig = serde_json::from_str(&serde_json::to_string(&mc).unwrap()).unwrap();
+        assert_eq!(mc.priority_overrides[0].priority, 5);
+    }
+
+    #[test]
+    fn test_freeze_profile() {
+        let info = |url: &str, resolved: &str| {
+            (
+                ModSpecification::new(url.to_string()),
+                mod_info(url, resolved),
+            )
+        };
+
+        let mut mod_data = ModData {
+            active_profile: "default".to_string(),
+            profiles: [(
+                "default".to_string(),
+                ModProfile {
+                    mods: vec![
+                        ModOrGroup::Individual(mod_config("a")),
+                        ModOrGroup::Individual(ModConfig {
+                            enabled: false,
+                            ..mod_config("b")
+                        }),
+                        ModOrGroup::Group {
+                            group_name: "g".to_string(),
+                            enabled: true,
+                        },
+                    ],
+                    ..Default::default()
+                },
+            )]
+            .into(),
+            groups: [(
+                "g".to_string(),
+                ModGroup {
+                    mods: vec![mod_config("c")],
+                },
+            )]
+            .into(),
+            profile_order: vec![],
+        };
+        let urls = |mod_data: &ModData| {
+            let mut urls = vec![];
+            mod_data.for_each_mod("default", |mc| urls.push(mc.spec.url.clone()));
+            urls
+        };
+
+        let resolved = [info("a", "a#2"), info("b", "b#5"), info("c", "c#1")].into();
+        assert_eq!(mod_data.freeze_profile("default", &resolved), 2);
+        // disabled mods are left alone
+        assert_eq!(urls(&mod_data), ["a#2", "b", "c#1"]);
+        // already frozen mods are not frozen again
+        let resolved = [info("a#2", "a#3")].into();
+        assert_eq!(mod_data.freeze_profile("default", &resolved), 0);
+
+        assert_eq!(mod_data.thaw_profile("default"), 2);
+        assert_eq!(urls(&mod_data), ["a", "b", "c"]);
+        assert_eq!(mod_data.thaw_profile("default"), 0);
+    }
+
+    #[test]
+    fn test_export_import_profile() {
+        let group = |urls: &[&str]| ModGroup {
+            mods: urls.iter().map(|url| mod_config(url)).collect(),
+        };
+        let uses_group = |name: &str, enabled| ModOrGroup::Group {
+            group_name: name.to_string(),
+            enabled,
+        };
+
+        let source = ModData {
+            active_profile: "shared".to_string(),
+            profiles: [(
+                "shared".to_string(),
+                ModProfile {
+                    mods: vec![
+                        ModOrGroup::Individual(mod_config("a")),
+                        ModOrGroup::Individual(ModConfig {
+                            enabled: false,
+                            ..mod_config("e")
+                        }),
+                        uses_group("same", true),
+                        uses_group("differs", false),
+                    ],
+                    credentials: [("modio".to_string(), "alt".to_string())].into(),
+                    description: "shared build".to_string(),
+                    ..Default::default()
+                },
+            )]
+            .into(),
+            groups: [
+                ("same".to_string(), group(&["b"])),
+                ("differs".to_string(), group(&["c"])),
+                ("unused".to_string(), group(&["d"])),
+            ]
+            .into(),
+            profile_order: vec![],
+        };
+        let exported = Modpack::export_profile(&source, "shared").unwrap();
+        let exported = Modpack::from_json(&exported.to_json()).unwrap();
+
+        let mut target = ModData {
+            active_profile: "shared".to_string(),
+            profiles: [("shared".to_string(), ModProfile::default())].into(),
+            groups: [
+                ("same".to_string(), group(&["b"])),
+                ("differs".to_string(), group(&["x"])),
+            ]
+            .into(),
+            profile_order: vec![],
+        };
+        assert_eq!(
+            target.import_profile(exported.clone(), None).unwrap(),
+            "shared 2"
+        );
+        assert!(target
+            .import_profile(exported.clone(), Some("shared".to_string()))
+            .is_err());
+        let group_names = |mod_data: &ModData, profile: &str| {
+            mod_data.profiles[profile]
+                .mods
+                .iter()
+                .filter_map(|m| match m {
+                    ModOrGroup::Group { group_name, .. } => Some(group_name.clone()),
+                    ModOrGroup::Individual(_) => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(group_names(&target, "shared 2"), ["same", "differs 2"]);
+        assert_eq!(target.groups["differs"], group(&["x"]));
+        let specs = |group: &ModGroup| {
+            group
+                .mods
+                .iter()
+                .map(|mc| mc.spec.url.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(specs(&target.groups["differs 2"]), ["c"]);
+
+        // disabled mods and groups stay disabled
+        let imported = &target.profiles["shared 2"];
+        assert_eq!(
+            imported
+                .mods
+                .iter()
+                .map(|m| match m {
+                    ModOrGroup::Individual(mc) => mc.enabled,
+                    ModOrGroup::Group { enabled, .. } => *enabled,
+                })
+                .collect::<Vec<_>>(),
+            [true, false, true, false]
+        );
+        assert_eq!(imported.description, "shared build");
+        assert!(imported.credentials.is_empty());
+
+        // importing again reuses the groups added the first time
+        assert_eq!(
+            target
+                .import_profile(exported, Some("again".to_string()))
+                .unwrap(),
+            "again"
+        );
+        assert_eq!(group_names(&target, "again"), ["same", "differs 2"]);
+        assert_eq!(target.groups.len(), 3);
+    }
+
+    #[test]
+    fn test_conflict_strategy() {
+        let with_priority = |url: &str, priority| ModConfig {
+            priority,
+            ..mod_config(url)
+        };
+        let approval = |spec: &ModSpecification| match spec.url.as_str() {
+            "verified" => Some(ApprovalStatus::Verified),
+            "approved" => Some(ApprovalStatus::Approved),
+            "sandbox" => Some(ApprovalStatus::Sandbox),
+            _ => None,
+        };
+        let order = |strategy: ConflictStrategy| {
+            let mut mods = vec![
+                with_priority("unknown", 0),
+                with_priority("sandbox", 0),
+                with_priority("approved", 0),
+                with_priority("low", -1),
+                with_priority("verified", 0),
+                with_priority("high", 1),
+            ];
+            strategy.sort(&mut mods, approval);
+            mods.into_iter().map(|m| m.spec.url).collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            order(ConflictStrategy::Priority),
+            ["high", "unknown", "sandbox", "approved", "verified", "low"]
+        );
+        // priority still comes first, approval only breaks ties
+        assert_eq!(
+            order(ConflictStrategy::Approval),
+            ["high", "verified", "approved", "unknown", "sandbox", "low"]
+        );
+    }
+}
+
+#[cfg(test)]
+mod recovery_tests {
+    use super::*;
+
+    #[test]
+    fn test_recover_unparsable() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("config.json");
+
+        // missing and valid files are not touched
+        let (_, recovered) =
+            recover_unparsable(&config_path, read_config_or_default(&config_path)).unwrap();
+        assert!(recovered.is_none());
+        fs::write(
+            &config_path,
+            r#"{"version": "0.0.0", "provider_parameters": {}, "drg_pak_path": "FSD.pak"}"#,
+        )
+        .unwrap();
+        let (config, recovered) =
+            recover_unparsable(&config_path, read_config_or_default(&config_path)).unwrap();
+        assert!(recovered.is_none());
+        assert_eq!(config.drg_pak_path, Some(PathBuf::from("FSD.pak")));
+
+        fs::write(&config_path, r#"{"version": "0.0.0", "drg_pak_path": "#).unwrap();
+        let (config, recovered) =
+            recover_unparsable(&config_path, read_config_or_default(&config_path)).unwrap();
+        let recovered = recovered.unwrap();
+        assert_eq!(config.drg_pak_path, None);
+        assert_eq!(recovered.path, config_path);
+        assert_eq!(recovered.backup, dir.path().join("config.json.corrupt"));
+        assert!(recovered
+            .error
+            .contains("failed to deserialize user config"));
+        assert!(!config_path.exists());
+        assert_eq!(
+            fs::read_to_string(&recovered.backup).unwrap(),
+            r#"{"version": "0.0.0", "drg_pak_path": "#
+        );
+
+        // earlier backups are kept
+        fs::write(&config_path, "{").unwrap();
+        let (_, recovered) =
+            recover_unparsable(&config_path, read_config_or_default(&config_path)).unwrap();
+        assert_eq!(
+            recovered.unwrap().backup,
+            dir.path().join("config.json.corrupt.1")
+        );
+
+        let mod_data_path = dir.path().join("mod_data.json");
+        fs::write(&mod_data_path, r#"{"version": "0.1.0", "profiles": 3}"#).unwrap();
+        let (mod_data, recovered) = recover_unparsable(
+            &mod_data_path,
+            read_mod_data_or_default(&mod_data_path, dir.path().join("profiles.json")),
+        )
+        .unwrap();
+        assert!(recovered.is_some());
+        assert!(mod_data.profiles.contains_key("default"));
+        assert!(dir.path().join("mod_data.json.corrupt").exists());
+    }
+
+    #[test]
+    fn test_keep_newer_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("config.json");
+        fs::write(&config_path, r#"{"version": "99.0.0"}"#).unwrap();
+        assert!(matches!(
+            recover_unparsable(&config_path, read_config_or_default(&config_path)),
+            Err(StateError::UnsupportedCfgVersion)
+        ));
+        assert!(config_path.exists());
+    }
 }
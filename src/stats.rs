@@ -0,0 +1,126 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use fs_err as fs;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::providers::ModSpecification;
+
+/// Name of the file in the data directory the stats are kept in.
+pub const STATS_FILE: &str = "stats.json";
+
+/// Usage counters for the user's own curiosity, only collected if enabled in the settings. They
+/// are kept in the data directory and never sent anywhere.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UsageStats {
+    /// Successful installs.
+    pub integrates: u64,
+    /// How many installs included each mod, keyed by URL. Mods are counted once per install no
+    /// matter which profile they were installed from.
+    pub mods: BTreeMap<String, u64>,
+    /// Mods downloaded by providers.
+    pub downloads: u64,
+    /// Mods taken from the cache instead of being downloaded.
+    pub cache_hits: u64,
+    /// Bytes downloaded by providers.
+    pub fetched_bytes: u64,
+}
+
+/// Fetches counted by [`crate::providers::ModStore`] since they were last taken.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FetchStats {
+    pub downloads: u64,
+    pub cache_hits: u64,
+    pub bytes: u64,
+}
+
+impl UsageStats {
+    /// Read the stats from `data_dir`. Missing or unreadable stats start over from zero.
+    pub fn read(data_dir: &Path) -> Self {
+        let path = data_dir.join(STATS_FILE);
+        match fs::read(&path) {
+            Ok(data) => serde_json::from_slice(&data).unwrap_or_else(|e| {
+                warn!("failed to parse {}, starting over: {e}", path.display());
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn write(&self, data_dir: &Path) -> std::io::Result<()> {
+        fs::write(data_dir.join(STATS_FILE), serde_json::to_vec_pretty(self)?)
+    }
+
+    /// Forget all stats collected in `data_dir`.
+    pub fn reset(data_dir: &Path) -> std::io::Result<()> {
+        match fs::remove_file(data_dir.join(STATS_FILE)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn record_integrate(&mut self, mods: &[ModSpecification]) {
+        self.integrates += 1;
+        for spec in mods {
+            *self.mods.entry(spec.url.clone()).or_default() += 1;
+        }
+    }
+
+    pub fn record_fetches(&mut self, fetches: FetchStats) {
+        self.downloads += fetches.downloads;
+        self.cache_hits += fetches.cache_hits;
+        self.fetched_bytes += fetches.bytes;
+    }
+
+    /// Share of fetched mods which were taken from the cache, if any were fetched yet.
+    pub fn cache_hit_rate(&self) -> Option<f64> {
+        let total = self.downloads + self.cache_hits;
+        (total > 0).then(|| self.cache_hits as f64 / total as f64)
+    }
+
+    /// The `n` mods included in the most installs with their counts, most used first.
+    pub fn most_used(&self, n: usize) -> Vec<(&str, u64)> {
+        let mut mods = self
+            .mods
+            .iter()
+            .map(|(url, count)| (url.as_str(), *count))
+            .collect::<Vec<_>>();
+        mods.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        mods.truncate(n);
+        mods
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_usage_stats() {
+        let dir = tempfile::tempdir().unwrap();
+        let spec = |url: &str| ModSpecification::new(url.to_string());
+
+        let mut stats = UsageStats::read(dir.path());
+        assert_eq!(stats, UsageStats::default());
+        assert_eq!(stats.cache_hit_rate(), None);
+
+        stats.record_integrate(&[spec("a"), spec("b")]);
+        stats.record_integrate(&[spec("b")]);
+        stats.record_fetches(FetchStats {
+            downloads: 1,
+            cache_hits: 3,
+            bytes: 1024,
+        });
+        assert_eq!(stats.integrates, 2);
+        assert_eq!(stats.most_used(1), [("b", 2)]);
+        assert_eq!(stats.most_used(5), [("b", 2), ("a", 1)]);
+        assert_eq!(stats.cache_hit_rate(), Some(0.75));
+
+        stats.write(dir.path()).unwrap();
+        assert_eq!(UsageStats::read(dir.path()), stats);
+        UsageStats::reset(dir.path()).unwrap();
+        UsageStats::reset(dir.path()).unwrap();
+        assert_eq!(UsageStats::read(dir.path()), UsageStats::default());
+    }
+}
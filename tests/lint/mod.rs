@@ -1,8 +1,16 @@
-use std::path::PathBuf;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
-use mint::mod_lints::{LintId, LintReport, SplitAssetPair};
+use indexmap::IndexSet;
+
+use mint::mod_lints::{
+    find_split_asset_pairs, LintBaseline, LintCache, LintFinding, LintId, LintReport, LintSummary,
+    MountPointOverlap, SplitAssetPair, LINT_CACHE_FILE, LINT_CACHE_VERSION,
+};
 use mint::providers::ModSpecification;
+use mint::PakKey;
 
 #[test]
 pub fn test_lint_conflicting_files() {
@@ -23,7 +31,7 @@ pub fn test_lint_conflicting_files() {
 
     let LintReport {
         conflicting_mods, ..
-    } = mint::mod_lints::run_lints(&[LintId::CONFLICTING].into(), mods.into(), None).unwrap();
+    } = mint::mod_lints::run_lints(&[LintId::CONFLICTING].into(), mods.into(), None, None).unwrap();
 
     println!("{:#?}", conflicting_mods);
 
@@ -51,7 +59,8 @@ pub fn test_lint_shader() {
 
     let LintReport {
         shader_file_mods, ..
-    } = mint::mod_lints::run_lints(&[LintId::SHADER_FILES].into(), mods.into(), None).unwrap();
+    } = mint::mod_lints::run_lints(&[LintId::SHADER_FILES].into(), mods.into(), None, None)
+        .unwrap();
 
     println!("{:#?}", shader_file_mods);
 
@@ -80,8 +89,13 @@ pub fn test_lint_asset_registry_bin() {
     let LintReport {
         asset_register_bin_mods,
         ..
-    } = mint::mod_lints::run_lints(&[LintId::ASSET_REGISTRY_BIN].into(), mods.into(), None)
-        .unwrap();
+    } = mint::mod_lints::run_lints(
+        &[LintId::ASSET_REGISTRY_BIN].into(),
+        mods.into(),
+        None,
+        None,
+    )
+    .unwrap();
 
     println!("{:#?}", asset_register_bin_mods);
 
@@ -91,6 +105,42 @@ pub fn test_lint_asset_registry_bin() {
     );
 }
 
+#[test]
+pub fn test_lint_encrypted_pak() {
+    let base_path = PathBuf::from_str("test_assets/lints/").unwrap();
+    assert!(base_path.exists());
+    let encrypted_path = base_path.clone().join("encrypted.pak");
+    assert!(encrypted_path.exists());
+    let encrypted_spec = ModSpecification {
+        url: "encrypted".to_string(),
+    };
+    let mods = || IndexSet::from([(encrypted_spec.clone(), encrypted_path.clone())]);
+
+    assert!(
+        mint::mod_lints::run_lints(&[LintId::ASSET_REGISTRY_BIN].into(), mods(), None, None)
+            .is_err()
+    );
+
+    let key =
+        PakKey::from_str("0x000102030405060708090A0B0C0D0E0F101112131415161718191A1B1C1D1E1F")
+            .unwrap();
+    let LintReport {
+        asset_register_bin_mods,
+        ..
+    } = mint::mod_lints::run_lints(
+        &[LintId::ASSET_REGISTRY_BIN].into(),
+        mods(),
+        None,
+        Some(key),
+    )
+    .unwrap();
+
+    assert_eq!(
+        asset_register_bin_mods.unwrap().get(&encrypted_spec),
+        Some(&["fsd/content/assetregistry.bin".to_string()].into())
+    );
+}
+
 #[test]
 pub fn test_lint_outdated_pak_version() {
     let base_path = PathBuf::from_str("test_assets/lints/").unwrap();
@@ -105,8 +155,13 @@ pub fn test_lint_outdated_pak_version() {
     let LintReport {
         outdated_pak_version_mods,
         ..
-    } = mint::mod_lints::run_lints(&[LintId::OUTDATED_PAK_VERSION].into(), mods.into(), None)
-        .unwrap();
+    } = mint::mod_lints::run_lints(
+        &[LintId::OUTDATED_PAK_VERSION].into(),
+        mods.into(),
+        None,
+        None,
+    )
+    .unwrap();
 
     println!("{:#?}", outdated_pak_version_mods);
 
@@ -129,7 +184,8 @@ pub fn test_lint_empty_archive() {
 
     let LintReport {
         empty_archive_mods, ..
-    } = mint::mod_lints::run_lints(&[LintId::EMPTY_ARCHIVE].into(), mods.into(), None).unwrap();
+    } = mint::mod_lints::run_lints(&[LintId::EMPTY_ARCHIVE].into(), mods.into(), None, None)
+        .unwrap();
 
     println!("{:#?}", empty_archive_mods);
 
@@ -162,6 +218,7 @@ pub fn test_lint_only_non_pak_files() {
         &[LintId::ARCHIVE_WITH_ONLY_NON_PAK_FILES].into(),
         mods.into(),
         None,
+        None,
     )
     .unwrap();
 
@@ -190,6 +247,7 @@ pub fn test_lint_multi_pak_archive() {
         &[LintId::ARCHIVE_WITH_MULTIPLE_PAKS].into(),
         mods.into(),
         None,
+        None,
     )
     .unwrap();
 
@@ -214,7 +272,8 @@ pub fn test_lint_non_asset_files() {
     let LintReport {
         non_asset_file_mods,
         ..
-    } = mint::mod_lints::run_lints(&[LintId::NON_ASSET_FILES].into(), mods.into(), None).unwrap();
+    } = mint::mod_lints::run_lints(&[LintId::NON_ASSET_FILES].into(), mods.into(), None, None)
+        .unwrap();
 
     println!("{:#?}", non_asset_file_mods);
 
@@ -238,7 +297,8 @@ pub fn test_lint_split_asset_pairs() {
     let LintReport {
         split_asset_pairs_mods,
         ..
-    } = mint::mod_lints::run_lints(&[LintId::SPLIT_ASSET_PAIRS].into(), mods.into(), None).unwrap();
+    } = mint::mod_lints::run_lints(&[LintId::SPLIT_ASSET_PAIRS].into(), mods.into(), None, None)
+        .unwrap();
 
     println!("{:#?}", split_asset_pairs_mods);
 
@@ -260,6 +320,34 @@ pub fn test_lint_split_asset_pairs() {
     );
 }
 
+#[test]
+pub fn test_find_split_asset_pairs() {
+    // integrate drops exactly these files when skipping split assets, keeping the original case
+    let paths = [
+        "FSD/Content/Complete.uasset",
+        "FSD/Content/complete.UEXP",
+        "FSD/Content/Orphan.uasset",
+        "FSD/Content/Other/Orphan.uexp",
+        "FSD/Content/Map.umap",
+        "FSD/Content/Readme",
+    ];
+    assert_eq!(
+        find_split_asset_pairs(paths),
+        [
+            (
+                "FSD/Content/Orphan.uasset".to_string(),
+                SplitAssetPair::MissingUexp
+            ),
+            (
+                "FSD/Content/Other/Orphan.uexp".to_string(),
+                SplitAssetPair::MissingUasset
+            ),
+        ]
+        .into()
+    );
+    assert!(find_split_asset_pairs([]).is_empty());
+}
+
 #[test]
 pub fn test_lint_unmodified_game_assets() {
     let base_path = PathBuf::from_str("test_assets/lints/").unwrap();
@@ -283,6 +371,7 @@ pub fn test_lint_unmodified_game_assets() {
         &[LintId::UNMODIFIED_GAME_ASSETS].into(),
         mods.into(),
         Some(reference_pak_path),
+        None,
     )
     .unwrap();
 
@@ -295,3 +384,502 @@ pub fn test_lint_unmodified_game_assets() {
         Some(&["a.uexp".to_string(), "a.uasset".to_string()].into())
     );
 }
+
+#[test]
+pub fn test_lint_overlapping_mount_points() {
+    let base_path = PathBuf::from_str("test_assets/lints/").unwrap();
+    assert!(base_path.exists());
+    let a_path = base_path.clone().join("A.pak");
+    assert!(a_path.exists());
+    let overlapping_mount_points_path = base_path.clone().join("overlapping_mount_points.pak");
+    assert!(overlapping_mount_points_path.exists());
+    let a_spec = ModSpecification {
+        url: "A".to_string(),
+    };
+    let overlapping_mount_points_spec = ModSpecification {
+        url: "overlapping_mount_points".to_string(),
+    };
+    let mods = [
+        (a_spec.clone(), a_path),
+        (
+            overlapping_mount_points_spec.clone(),
+            overlapping_mount_points_path,
+        ),
+    ];
+
+    let LintReport {
+        overlapping_mount_points_mods,
+        ..
+    } = mint::mod_lints::run_lints(
+        &[LintId::OVERLAPPING_MOUNT_POINTS].into(),
+        mods.into(),
+        None,
+        None,
+    )
+    .unwrap();
+
+    println!("{:#?}", overlapping_mount_points_mods);
+
+    let overlapping_mount_points_mods = overlapping_mount_points_mods.unwrap();
+    assert_eq!(overlapping_mount_points_mods.len(), 1);
+    assert_eq!(
+        overlapping_mount_points_mods.get(&(a_spec, overlapping_mount_points_spec)),
+        Some(&MountPointOverlap {
+            broad_mount_point: "../../../".to_string(),
+            narrow_mount_point: "../../../FSD/".to_string(),
+            shadowed_files: [
+                "fsd/content/a.uexp".to_string(),
+                "fsd/content/assetregistry.bin".to_string(),
+                "fsd/content/b.uexp".to_string(),
+                "fsd/content/c.ushaderbytecode".to_string(),
+            ]
+            .into(),
+        })
+    );
+}
+
+#[test]
+pub fn test_lint_mint_artifacts() {
+    let base_path = PathBuf::from_str("test_assets/lints/").unwrap();
+    assert!(base_path.exists());
+    let a_path = base_path.clone().join("A.pak");
+    assert!(a_path.exists());
+    let mint_artifacts_path = base_path.clone().join("mint_artifacts.zip");
+    assert!(mint_artifacts_path.exists());
+    let a_spec = ModSpecification {
+        url: "A".to_string(),
+    };
+    let mint_artifacts_spec = ModSpecification {
+        url: "mint_artifacts".to_string(),
+    };
+    let mods = [
+        (a_spec.clone(), a_path),
+        (mint_artifacts_spec.clone(), mint_artifacts_path),
+    ];
+
+    let LintReport {
+        mint_artifact_mods, ..
+    } = mint::mod_lints::run_lints(&[LintId::MINT_ARTIFACTS].into(), mods.into(), None, None)
+        .unwrap();
+
+    println!("{:#?}", mint_artifact_mods);
+
+    let mint_artifact_mods = mint_artifact_mods.unwrap();
+    assert_eq!(mint_artifact_mods.len(), 1);
+    assert_eq!(
+        mint_artifact_mods.get(&mint_artifacts_spec),
+        Some(&["mint_hook.log".to_string(), "x3daudio1_7.dll".to_string()].into())
+    );
+}
+
+#[test]
+pub fn test_lint_untagged_gameplay() {
+    use mint_lib::mod_info::{ApprovalStatus, ModioTags, RequiredStatus};
+
+    let base_path = PathBuf::from_str("test_assets/lints/").unwrap();
+    assert!(base_path.exists());
+    let a_path = base_path.clone().join("A.pak");
+    assert!(a_path.exists());
+    let untagged_gameplay_path = base_path.clone().join("untagged_gameplay.pak");
+    assert!(untagged_gameplay_path.exists());
+    let a_spec = ModSpecification {
+        url: "A".to_string(),
+    };
+    let untagged_gameplay_spec = ModSpecification {
+        url: "untagged_gameplay".to_string(),
+    };
+    let mods: IndexSet<_> = [
+        (a_spec.clone(), a_path),
+        (untagged_gameplay_spec.clone(), untagged_gameplay_path),
+    ]
+    .into();
+    let tags = |required_status, approval_status| ModioTags {
+        qol: false,
+        gameplay: true,
+        audio: false,
+        visual: false,
+        framework: false,
+        versions: BTreeSet::new(),
+        required_status,
+        approval_status,
+    };
+    let lint = |tags: Option<ModioTags>| {
+        let lint_ctxt = mint::mod_lints::LintCtxt::init(mods.clone(), None, None)
+            .unwrap()
+            .modio_tags(
+                tags.into_iter()
+                    .map(|tags| (untagged_gameplay_spec.clone(), tags))
+                    .collect(),
+            );
+        mint::mod_lints::run_lints_in(&[LintId::UNTAGGED_GAMEPLAY].into(), lint_ctxt)
+            .unwrap()
+            .untagged_gameplay_mods
+            .unwrap()
+    };
+
+    // only the blueprint counts, not the texture next to it
+    let untagged_gameplay_mods = lint(None);
+    println!("{:#?}", untagged_gameplay_mods);
+    assert_eq!(untagged_gameplay_mods.len(), 1);
+    assert_eq!(
+        untagged_gameplay_mods.get(&untagged_gameplay_spec),
+        Some(&["fsd/content/enemies/spider/bp_spider_grunt.uasset".to_string()].into())
+    );
+
+    let optional = lint(Some(tags(
+        RequiredStatus::Optional,
+        ApprovalStatus::Approved,
+    )));
+    assert_eq!(optional.len(), 1);
+    let required = lint(Some(tags(
+        RequiredStatus::RequiredByAll,
+        ApprovalStatus::Approved,
+    )));
+    assert!(required.is_empty());
+    let verified = lint(Some(tags(
+        RequiredStatus::Optional,
+        ApprovalStatus::Verified,
+    )));
+    assert!(verified.is_empty());
+}
+
+fn lint_cli(args: &[&str]) -> (Option<i32>, String) {
+    let appdata = tempfile::tempdir().unwrap();
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_mint"))
+        .arg("--appdata")
+        .arg(appdata.path())
+        .arg("lint")
+        .args(args)
+        .output()
+        .unwrap();
+    (
+        output.status.code(),
+        String::from_utf8(output.stdout).unwrap(),
+    )
+}
+
+#[test]
+pub fn test_lint_cli_exit_code() {
+    let outdated = "test_assets/lints/outdated_pak_version.pak";
+
+    let (code, stdout) = lint_cli(&["--format", "json", "--mods", outdated]);
+    assert_eq!(code, Some(0));
+    let summary: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(summary["mods_scanned"], 1);
+    assert_eq!(summary["findings"]["outdated_pak_version"], 1);
+    assert_eq!(summary["passed"], true);
+
+    let (code, stdout) = lint_cli(&[
+        "--format",
+        "json",
+        "--deny",
+        "outdated_pak_version",
+        "--mods",
+        outdated,
+    ]);
+    assert_eq!(code, Some(3));
+    let summary: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(
+        summary["denied"],
+        serde_json::json!(["outdated_pak_version"])
+    );
+    assert_eq!(summary["passed"], false);
+
+    let (code, _) = lint_cli(&["--deny", "not_a_lint", "--mods", outdated]);
+    assert_eq!(code, Some(1));
+}
+
+#[test]
+pub fn test_find_conflicts() {
+    let base_path = PathBuf::from_str("test_assets/lints/").unwrap();
+    let spec = |name: &str| ModSpecification {
+        url: name.to_string(),
+    };
+    // archives without a pak are skipped rather than failing the check
+    let mods = [
+        "A.pak",
+        "empty_archive.zip",
+        "B.pak",
+        "only_non_pak_files.zip",
+    ]
+    .map(|name| (spec(name), base_path.join(name)));
+
+    let conflicts = mint::mod_lints::find_conflicts(&mods.into(), None, false).unwrap();
+
+    assert_eq!(
+        conflicts.get("fsd/content/a.uexp"),
+        Some(&[spec("A.pak"), spec("B.pak")].into())
+    );
+}
+
+#[test]
+pub fn test_conflict_pairs() {
+    let spec = |name: &str| ModSpecification {
+        url: name.to_string(),
+    };
+    let conflicts: BTreeMap<String, IndexSet<ModSpecification>> = [
+        ("fsd/content/a.uasset", ["B.pak", "A.pak"].as_slice()),
+        ("fsd/content/a.uexp", &["A.pak", "B.pak", "C.pak"]),
+        ("fsd/content/c.uasset", &["C.pak", "B.pak"]),
+    ]
+    .into_iter()
+    .map(|(path, mods)| (path.to_string(), mods.iter().map(|m| spec(m)).collect()))
+    .collect();
+
+    let pairs = mint::mod_lints::conflict_pairs(&conflicts);
+    let shared = |a: &str, b: &str| {
+        pairs[&(spec(a), spec(b))]
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(pairs.len(), 3);
+    assert_eq!(
+        shared("A.pak", "B.pak"),
+        ["fsd/content/a.uasset", "fsd/content/a.uexp"]
+    );
+    assert_eq!(shared("A.pak", "C.pak"), ["fsd/content/a.uexp"]);
+    assert_eq!(
+        shared("B.pak", "C.pak"),
+        ["fsd/content/a.uexp", "fsd/content/c.uasset"]
+    );
+}
+
+#[test]
+pub fn test_lint_report_by_mod() {
+    let spec = |name: &str| ModSpecification {
+        url: name.to_string(),
+    };
+    let report = LintReport {
+        conflicting_mods: Some(
+            [(
+                "fsd/content/a.uexp".to_string(),
+                [spec("A.pak"), spec("B.pak")].into(),
+            )]
+            .into(),
+        ),
+        shader_file_mods: Some(
+            [(
+                spec("A.pak"),
+                ["fsd/content/shaders/a.ushaderbytecode".to_string()].into(),
+            )]
+            .into(),
+        ),
+        empty_archive_mods: Some([spec("C.zip")].into()),
+        // lints which found nothing add no mods
+        non_asset_file_mods: Some(BTreeMap::new()),
+        ..Default::default()
+    };
+
+    let by_mod = report.by_mod();
+    assert_eq!(by_mod.len(), 3);
+    assert_eq!(
+        by_mod[&spec("A.pak")],
+        [
+            (LintId::CONFLICTING, vec!["fsd/content/a.uexp".to_string()]),
+            (
+                LintId::SHADER_FILES,
+                vec!["fsd/content/shaders/a.ushaderbytecode".to_string()]
+            ),
+        ]
+        .into()
+    );
+    assert_eq!(
+        by_mod[&spec("B.pak")],
+        [(LintId::CONFLICTING, vec!["fsd/content/a.uexp".to_string()])].into()
+    );
+    assert_eq!(
+        by_mod[&spec("C.zip")],
+        [(LintId::EMPTY_ARCHIVE, vec![])].into()
+    );
+}
+
+#[test]
+pub fn test_lint_baseline() {
+    let spec = |name: &str| ModSpecification {
+        url: name.to_string(),
+    };
+    let finding = |lint: LintId, url: &str, detail: &str| LintFinding {
+        lint: lint.name().to_string(),
+        url: url.to_string(),
+        detail: detail.to_string(),
+    };
+    let old = LintReport {
+        shader_file_mods: Some(
+            [(
+                spec("A.pak"),
+                ["fsd/content/shaders/a.ushaderbytecode".to_string()].into(),
+            )]
+            .into(),
+        ),
+        empty_archive_mods: Some([spec("C.zip")].into()),
+        ..Default::default()
+    };
+    let new = LintReport {
+        shader_file_mods: Some(BTreeMap::new()),
+        empty_archive_mods: Some([spec("C.zip")].into()),
+        non_asset_file_mods: Some([(spec("B.pak"), ["fsd/readme.txt".to_string()].into())].into()),
+        ..Default::default()
+    };
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("baseline.json");
+    LintBaseline::new(&old).write(&path).unwrap();
+    let baseline = LintBaseline::read(&path).unwrap();
+    assert_eq!(baseline, LintBaseline::new(&old));
+
+    let diff = baseline.diff(&new);
+    assert_eq!(
+        diff.new,
+        [finding(LintId::NON_ASSET_FILES, "B.pak", "fsd/readme.txt")].into()
+    );
+    assert_eq!(
+        diff.fixed,
+        [finding(
+            LintId::SHADER_FILES,
+            "A.pak",
+            "fsd/content/shaders/a.ushaderbytecode"
+        )]
+        .into()
+    );
+
+    // only new findings are denied
+    let deny = BTreeSet::from([LintId::EMPTY_ARCHIVE, LintId::NON_ASSET_FILES]);
+    let summary = LintSummary::new(&new, 3, &deny).against_baseline(diff.clone(), &deny);
+    assert_eq!(summary.denied, [LintId::NON_ASSET_FILES.name()].into());
+    assert!(!summary.passed);
+    let deny = BTreeSet::from([LintId::EMPTY_ARCHIVE]);
+    let summary = LintSummary::new(&new, 3, &deny).against_baseline(diff, &deny);
+    assert!(summary.passed);
+}
+
+#[test]
+pub fn test_all_archive_paks() {
+    let base_path = PathBuf::from_str("test_assets/lints/").unwrap();
+    let spec = |name: &str| ModSpecification {
+        url: name.to_string(),
+    };
+    // multiple_paks.zip contains A.pak and B.pak, only B.pak contains C.uexp
+    let mods = ["multiple_paks.zip", "B.pak"].map(|name| (spec(name), base_path.join(name)));
+    let mods = IndexSet::from(mods);
+
+    let first_only = mint::mod_lints::find_conflicts(&mods, None, false).unwrap();
+    assert!(first_only.contains_key("fsd/content/a.uexp"));
+    assert!(!first_only.contains_key("fsd/content/c.uexp"));
+
+    let all = mint::mod_lints::find_conflicts(&mods, None, true).unwrap();
+    assert_eq!(
+        all.get("fsd/content/c.uexp"),
+        Some(&[spec("multiple_paks.zip"), spec("B.pak")].into())
+    );
+
+    // lints see the files of every pak as well
+    let lint_ctxt = mint::mod_lints::LintCtxt::init(mods, None, None)
+        .unwrap()
+        .all_archive_paks(true);
+    let LintReport {
+        conflicting_mods,
+        archive_with_multiple_paks_mods,
+        ..
+    } = mint::mod_lints::run_lints_in(
+        &[LintId::CONFLICTING, LintId::ARCHIVE_WITH_MULTIPLE_PAKS].into(),
+        lint_ctxt,
+    )
+    .unwrap();
+    assert!(conflicting_mods.unwrap().contains_key("fsd/content/c.uexp"));
+    assert!(archive_with_multiple_paks_mods
+        .unwrap()
+        .contains(&spec("multiple_paks.zip")));
+
+    assert!(
+        mint::integrate::pak_load_order_key(Path::new("Mod_P.pak"))
+            > mint::integrate::pak_load_order_key(Path::new("Zzz.pak"))
+    );
+    assert!(
+        mint::integrate::pak_load_order_key(Path::new("a.pak"))
+            < mint::integrate::pak_load_order_key(Path::new("B.pak"))
+    );
+}
+
+#[test]
+pub fn test_lint_cache() {
+    let base_path = PathBuf::from_str("test_assets/lints/").unwrap();
+    let spec = |name: &str| ModSpecification {
+        url: name.to_string(),
+    };
+    let mods = IndexSet::from(
+        [
+            "A.pak",
+            "B.pak",
+            "split_asset_pairs.pak",
+            "multiple_pak_files.zip",
+        ]
+        .map(|name| (spec(name), base_path.join(name))),
+    );
+    let lints = BTreeSet::from([
+        LintId::CONFLICTING,
+        LintId::SHADER_FILES,
+        LintId::SPLIT_ASSET_PAIRS,
+        LintId::ARCHIVE_WITH_MULTIPLE_PAKS,
+    ]);
+    let cache_dir = tempfile::tempdir().unwrap();
+    let lint_ctxt_with = |mods: &IndexSet<_>, pak_key: Option<PakKey>| {
+        mint::mod_lints::LintCtxt::init(mods.clone(), None, pak_key)
+            .unwrap()
+            .cache_dir(Some(cache_dir.path().to_path_buf()))
+    };
+    let lint_ctxt = || lint_ctxt_with(&mods, None);
+
+    let uncached = mint::mod_lints::run_lints(&lints, mods.clone(), None, None).unwrap();
+
+    let mut cache = LintCache::open(cache_dir.path());
+    let first = cache.run(&lints, lint_ctxt()).unwrap();
+    assert_eq!((cache.hits(), cache.misses()), (0, 4));
+    cache.save().unwrap();
+
+    let mut cache = LintCache::open(cache_dir.path());
+    let second = cache.run(&lints, lint_ctxt()).unwrap();
+    assert_eq!((cache.hits(), cache.misses()), (4, 0));
+    assert_eq!(format!("{first:?}"), format!("{uncached:?}"));
+    assert_eq!(format!("{second:?}"), format!("{uncached:?}"));
+
+    // lints which have not been cached yet still run
+    let mut cache = LintCache::open(cache_dir.path());
+    cache
+        .run(&[LintId::NON_ASSET_FILES].into(), lint_ctxt())
+        .unwrap();
+    assert_eq!(cache.misses(), 4);
+
+    // cached results of an older lint version are discarded
+    let path = cache_dir.path().join(LINT_CACHE_FILE);
+    let mut data: serde_json::Value = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+    data["version"] = (LINT_CACHE_VERSION - 1).into();
+    fs::write(&path, serde_json::to_vec(&data).unwrap()).unwrap();
+    let mut cache = LintCache::open(cache_dir.path());
+    cache.run(&lints, lint_ctxt()).unwrap();
+    assert_eq!(cache.misses(), 4);
+
+    // going through run_lints_in uses and updates the cache as well
+    let report = mint::mod_lints::run_lints_in(&lints, lint_ctxt()).unwrap();
+    assert_eq!(format!("{report:?}"), format!("{uncached:?}"));
+
+    // results for one pak key are not used for another
+    let key = |byte: &str| PakKey::from_str(&format!("0x{}", byte.repeat(32))).unwrap();
+    let mut cache = LintCache::open(cache_dir.path());
+    cache
+        .run(&lints, lint_ctxt_with(&mods, Some(key("01"))))
+        .unwrap();
+    assert_eq!(cache.misses(), 4);
+    cache
+        .run(&lints, lint_ctxt_with(&mods, Some(key("02"))))
+        .unwrap();
+    assert_eq!(cache.misses(), 4);
+
+    // mods which were not linted in the last run are dropped from the cache
+    let mut cache = LintCache::open(cache_dir.path());
+    let first_two = mods.iter().take(2).cloned().collect::<IndexSet<_>>();
+    cache.run(&lints, lint_ctxt_with(&first_two, None)).unwrap();
+    assert_eq!((cache.hits(), cache.misses()), (0, 2));
+    cache.run(&lints, lint_ctxt()).unwrap();
+    assert_eq!((cache.hits(), cache.misses()), (2, 2));
+}
@@ -0,0 +1,488 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::{self, Sender};
+
+use mint::providers::{
+    BlobCache, BlobRef, FetchProgress, ModInfo, ModProvider, ModProviderCache, ModResolution,
+    ModResponse, ModSearchResult, ModSpecification, ModStore, ProviderCache, ProviderError,
+    ProviderFactory, ProviderParameter, Transform,
+};
+use mint::state::State;
+use mint::{prefetch_with_provider_init, Dirs};
+
+/// Mods kept in memory: name, latest version and dependencies. Fetching `stuck` never finishes
+/// and fetching `late` only finishes after the other mods fetched alongside it.
+const MODS: &[(&str, u32, &[&str])] = &[
+    ("a", 2, &["memory://b"]),
+    ("b", 1, &[]),
+    ("c", 1, &[]),
+    ("late", 1, &[]),
+    ("renamed", 1, &[]),
+    ("stuck", 1, &[]),
+];
+
+/// Whether the author of `renamed` has renamed it since it was first resolved.
+static RENAMED: AtomicBool = AtomicBool::new(false);
+
+/// Name of a mod as the provider currently lists it.
+fn upstream_name(name: &str) -> String {
+    if name == "renamed" && RENAMED.load(Ordering::Relaxed) {
+        "New Name".to_string()
+    } else {
+        name.to_uppercase()
+    }
+}
+
+/// Resolutions the provider had to "download" rather than take from the blob cache.
+static DOWNLOADS: Mutex<Vec<String>> = Mutex::new(vec![]);
+
+fn downloads(url: &str) -> usize {
+    DOWNLOADS
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|u| *u == url)
+        .count()
+}
+
+static MEMORY_PROVIDER: ProviderFactory = ProviderFactory::new(
+    "memory",
+    MemoryProvider::new_provider,
+    |url| url.starts_with("memory://"),
+    &[],
+);
+
+#[derive(Debug)]
+struct MemoryProvider;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct MemoryProviderCache {
+    blobs: HashMap<String, BlobRef>,
+    names: HashMap<String, String>,
+}
+
+#[typetag::serde]
+impl ModProviderCache for MemoryProviderCache {
+    fn new() -> Self {
+        Default::default()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl MemoryProvider {
+    fn new_provider(
+        _parameters: &HashMap<String, String>,
+    ) -> Result<Arc<dyn ModProvider>, ProviderError> {
+        Ok(Arc::new(Self))
+    }
+}
+
+/// Split `memory://name#version` into the mod and the pinned version, if any.
+fn split_url(url: &str) -> Option<(&'static str, u32, &'static [&'static str], Option<u32>)> {
+    let (name, version) = match url.strip_prefix("memory://")?.split_once('#') {
+        Some((name, version)) => (name, Some(version.parse().ok()?)),
+        None => (url.strip_prefix("memory://")?, None),
+    };
+    let (name, latest, deps) = MODS.iter().find(|(n, ..)| *n == name)?;
+    Some((name, *latest, deps, version))
+}
+
+#[async_trait::async_trait]
+impl ModProvider for MemoryProvider {
+    async fn resolve_mod(
+        &self,
+        spec: &ModSpecification,
+        update: bool,
+        cache: ProviderCache,
+    ) -> Result<ModResponse, ProviderError> {
+        let Some((name, latest, deps, version)) = split_url(&spec.url) else {
+            return Err(ProviderError::InvalidUrl {
+                url: spec.url.clone(),
+            });
+        };
+        let Some(version) = version else {
+            return Ok(ModResponse::Redirect(ModSpecification::new(format!(
+                "memory://{name}#{latest}"
+            ))));
+        };
+        let cached = cache
+            .read()
+            .unwrap()
+            .get::<MemoryProviderCache>(MEMORY_PROVIDER.id)
+            .and_then(|c| c.names.get(name).cloned());
+        let display_name = match cached {
+            Some(cached) if !update => cached,
+            _ => {
+                let upstream = upstream_name(name);
+                cache
+                    .write()
+                    .unwrap()
+                    .get_mut::<MemoryProviderCache>(MEMORY_PROVIDER.id)
+                    .names
+                    .insert(name.to_string(), upstream.clone());
+                upstream
+            }
+        };
+        Ok(ModResponse::Resolve(ModInfo {
+            provider: MEMORY_PROVIDER.id,
+            name: display_name,
+            spec: ModSpecification::new(format!("memory://{name}")),
+            versions: (1..=latest)
+                .map(|v| ModSpecification::new(format!("memory://{name}#{v}")))
+                .collect(),
+            resolution: ModResolution::resolvable(format!("memory://{name}#{version}").into()),
+            suggested_require: false,
+            suggested_dependencies: deps
+                .iter()
+                .map(|d| ModSpecification::new(d.to_string()))
+                .collect(),
+            modio_tags: None,
+            modio_id: None,
+            modio_stats: None,
+            description: None,
+        }))
+    }
+
+    async fn fetch_mod(
+        &self,
+        res: &ModResolution,
+        update: bool,
+        cache: ProviderCache,
+        blob_cache: &BlobCache,
+        tx: Option<Sender<FetchProgress>>,
+    ) -> Result<PathBuf, ProviderError> {
+        let url = &res.url.0;
+        let cached = cache
+            .read()
+            .unwrap()
+            .get::<MemoryProviderCache>(MEMORY_PROVIDER.id)
+            .and_then(|c| c.blobs.get(url))
+            .and_then(|blob| blob_cache.get_path(blob));
+        let path = match cached {
+            Some(path) if !update => path,
+            _ => {
+                if url.starts_with("memory://stuck") {
+                    std::future::pending::<()>().await;
+                }
+                if url.starts_with("memory://late") {
+                    for _ in 0..100 {
+                        tokio::task::yield_now().await;
+                    }
+                }
+                DOWNLOADS.lock().unwrap().push(url.clone());
+                let blob = blob_cache.write(url.as_bytes())?;
+                let path = blob_cache.get_path(&blob).unwrap();
+                cache
+                    .write()
+                    .unwrap()
+                    .get_mut::<MemoryProviderCache>(MEMORY_PROVIDER.id)
+                    .blobs
+                    .insert(url.clone(), blob);
+                path
+            }
+        };
+        if let Some(tx) = tx {
+            tx.send(FetchProgress::Complete {
+                resolution: res.clone(),
+            })
+            .await
+            .unwrap();
+        }
+        Ok(path)
+    }
+
+    async fn update_cache(&self, _cache: ProviderCache) -> Result<(), ProviderError> {
+        Ok(())
+    }
+
+    async fn refresh_metadata(
+        &self,
+        spec: &ModSpecification,
+        cache: ProviderCache,
+    ) -> Result<(), ProviderError> {
+        let Some((name, ..)) = split_url(&spec.url) else {
+            return Err(ProviderError::InvalidUrl {
+                url: spec.url.clone(),
+            });
+        };
+        let mut cache = cache.write().unwrap();
+        let names = &mut cache
+            .get_mut::<MemoryProviderCache>(MEMORY_PROVIDER.id)
+            .names;
+        if names.contains_key(name) {
+            names.insert(name.to_string(), upstream_name(name));
+        }
+        Ok(())
+    }
+
+    async fn check(&self) -> Result<(), ProviderError> {
+        Ok(())
+    }
+
+    async fn search_mods(&self, _query: &str) -> Result<Vec<ModSearchResult>, ProviderError> {
+        Ok(vec![])
+    }
+
+    fn get_mod_info(&self, _spec: &ModSpecification, _cache: ProviderCache) -> Option<ModInfo> {
+        None
+    }
+
+    fn is_pinned(&self, spec: &ModSpecification, _cache: ProviderCache) -> bool {
+        split_url(&spec.url).is_some_and(|(.., version)| version.is_some())
+    }
+
+    fn get_version_name(&self, spec: &ModSpecification, _cache: ProviderCache) -> Option<String> {
+        split_url(&spec.url).and_then(|(.., version)| version.map(|v| v.to_string()))
+    }
+
+    fn get_version(&self, spec: &ModSpecification, cache: ProviderCache) -> Option<String> {
+        self.get_version_name(spec, cache)
+    }
+}
+
+#[tokio::test]
+async fn test_register_provider() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = ModStore::new_in_memory(dir.path(), &HashMap::new()).unwrap();
+
+    let a = ModSpecification::new("memory://a".to_string());
+    assert!(matches!(
+        store.resolve_mod(a.clone(), false).await,
+        Err(ProviderError::ProviderNotFound { .. })
+    ));
+
+    store.register_provider(&MEMORY_PROVIDER).unwrap();
+    assert!(matches!(
+        store.register_provider(&MEMORY_PROVIDER),
+        Err(ProviderError::DuplicateProvider { id: "memory" })
+    ));
+
+    let mods = store.resolve_mods(&[a.clone()], false).await.unwrap();
+    assert_eq!(mods.len(), 2);
+    let info = &mods[&a];
+    assert_eq!(info.provider, "memory");
+    assert_eq!(info.name, "A");
+    assert_eq!(info.resolution.url.0, "memory://a#2");
+    assert_eq!(
+        mods[&ModSpecification::new("memory://b".to_string())].name,
+        "B"
+    );
+
+    let path = store
+        .fetch_mod(&a, &info.resolution, false, None)
+        .await
+        .unwrap();
+    assert_eq!(std::fs::read(path).unwrap(), b"memory://a#2");
+
+    // version ranges go through the provider's versions as well
+    let (_, info) = store
+        .resolve_mod(ModSpecification::new("memory://a@<2".to_string()), false)
+        .await
+        .unwrap();
+    assert_eq!(info.resolution.url.0, "memory://a#1");
+
+    let versions = store
+        .list_versions(&ModSpecification::new("memory://a@<2".to_string()), false)
+        .await
+        .unwrap();
+    assert_eq!(
+        versions
+            .iter()
+            .map(|v| (v.spec.url.as_str(), v.name.as_str(), v.version.as_deref()))
+            .collect::<Vec<_>>(),
+        [
+            ("memory://a#1", "1", Some("1")),
+            ("memory://a#2", "2", Some("2")),
+        ]
+    );
+}
+
+#[test]
+fn test_register_provider_duplicate_builtin() {
+    static FILE_PROVIDER: ProviderFactory =
+        ProviderFactory::new("file", MemoryProvider::new_provider, |_| false, &[]);
+
+    let dir = tempfile::tempdir().unwrap();
+    let store = ModStore::new_in_memory(dir.path(), &HashMap::new()).unwrap();
+    assert!(matches!(
+        store.register_provider(&FILE_PROVIDER),
+        Err(ProviderError::DuplicateProvider { id: "file" })
+    ));
+}
+
+#[tokio::test]
+async fn test_fetch_resumes_after_cancel() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = Arc::new(ModStore::new(dir.path(), &HashMap::new()).unwrap());
+    store.register_provider(&MEMORY_PROVIDER).unwrap();
+
+    let spec = |url: &str| ModSpecification::new(url.to_string());
+    let c = ModResolution::resolvable("memory://c#1".to_string().into());
+    let stuck = ModResolution::resolvable("memory://stuck#1".to_string().into());
+    let (tx, mut rx) = mpsc::channel(10);
+    let fetch = tokio::spawn({
+        let (store, c, stuck) = (store.clone(), c.clone(), stuck.clone());
+        async move {
+            store
+                .fetch_mods_ordered(
+                    &[(&spec("memory://c"), &c), (&spec("memory://stuck"), &stuck)],
+                    false,
+                    Some(tx),
+                )
+                .await
+        }
+    });
+    match rx.recv().await {
+        Some(FetchProgress::Complete { resolution }) => assert_eq!(resolution, c),
+        progress => panic!("unexpected progress {progress:?}"),
+    }
+    fetch.abort();
+    assert!(fetch.await.unwrap_err().is_cancelled());
+    assert_eq!(downloads("memory://c#1"), 1);
+
+    // mint being killed after cancelling, the store is never dropped and does not save on exit
+    std::mem::forget(store);
+
+    let store = ModStore::new(dir.path(), &HashMap::new()).unwrap();
+    store.register_provider(&MEMORY_PROVIDER).unwrap();
+    let path = store
+        .fetch_mod(&spec("memory://c"), &c, false, None)
+        .await
+        .unwrap();
+    assert_eq!(std::fs::read(path).unwrap(), b"memory://c#1");
+    assert_eq!(downloads("memory://c#1"), 1);
+}
+
+/// Like the memory provider, but only created with the key "valid".
+static KEYED_PROVIDER: ProviderFactory = ProviderFactory::new(
+    "keyed",
+    |parameters| match parameters.get("key").map(String::as_str) {
+        Some("valid") => MemoryProvider::new_provider(parameters),
+        _ => Err(ProviderError::InitProviderFailed {
+            id: "keyed",
+            parameters: parameters.clone(),
+        }),
+    },
+    |url| url.starts_with("keyed://"),
+    &[ProviderParameter {
+        id: "key",
+        name: "Key",
+        description: "",
+        link: None,
+    }],
+);
+
+#[tokio::test]
+async fn test_check_provider() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = ModStore::new_in_memory(dir.path(), &HashMap::new()).unwrap();
+    store.register_provider(&KEYED_PROVIDER).unwrap();
+    let unavailable = |store: &ModStore| {
+        matches!(
+            store.get_provider("keyed://a"),
+            Err(ProviderError::NoProvider { .. })
+        )
+    };
+    assert!(unavailable(&store));
+
+    let key = |key: &str| HashMap::from([("key".to_string(), key.to_string())]);
+    assert!(ModStore::check_provider(&KEYED_PROVIDER, &key("invalid"))
+        .await
+        .is_err());
+    ModStore::check_provider(&KEYED_PROVIDER, &key("valid"))
+        .await
+        .unwrap();
+    // checking never adds the provider
+    assert!(unavailable(&store));
+
+    store
+        .add_provider_checked(&KEYED_PROVIDER, &key("valid"))
+        .await
+        .unwrap();
+    assert!(!unavailable(&store));
+}
+
+#[tokio::test]
+async fn test_refresh_metadata() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = ModStore::new_in_memory(dir.path(), &HashMap::new()).unwrap();
+    store.register_provider(&MEMORY_PROVIDER).unwrap();
+
+    let spec = ModSpecification::new("memory://renamed".to_string());
+    let mods = store.resolve_mods(&[spec.clone()], false).await.unwrap();
+    assert_eq!(mods[&spec].name, "RENAMED");
+    store
+        .fetch_mod(&spec, &mods[&spec].resolution, false, None)
+        .await
+        .unwrap();
+    assert_eq!(downloads("memory://renamed#1"), 1);
+
+    RENAMED.store(true, Ordering::Relaxed);
+    let cached = store.resolve_mods(&[spec.clone()], false).await.unwrap();
+    assert_eq!(cached[&spec].name, "RENAMED");
+
+    let refreshed = store.refresh_metadata(&[spec.clone()]).await.unwrap();
+    assert_eq!(refreshed[&spec].name, "New Name");
+    assert_eq!(refreshed[&spec].resolution, mods[&spec].resolution);
+    let path = store
+        .fetch_mods_ordered(&[(&spec, &refreshed[&spec].resolution)], false, None)
+        .await
+        .unwrap();
+    assert_eq!(std::fs::read(&path[0]).unwrap(), b"memory://renamed#1");
+    assert_eq!(downloads("memory://renamed#1"), 1);
+}
+
+#[tokio::test]
+async fn test_prefetch_keeps_spec_order() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut state = State::init(Dirs::from_path(dir.path()).unwrap()).unwrap();
+    state.store.register_provider(&MEMORY_PROVIDER).unwrap();
+
+    // `late` finishes last, the paths must still line up with the specs they were fetched for
+    let specs = ["memory://late", "memory://c", "memory://a"]
+        .map(|url| ModSpecification::new(url.to_string()));
+    let paths = prefetch_with_provider_init(&mut state, &specs, false, None, |_, url, _| {
+        panic!("no provider for {url}")
+    })
+    .await
+    .unwrap();
+    assert_eq!(
+        paths
+            .iter()
+            .map(|path| std::fs::read_to_string(path).unwrap())
+            .collect::<Vec<_>>(),
+        ["memory://late#1", "memory://c#1", "memory://a#2"]
+    );
+}
+
+#[tokio::test]
+async fn test_prefetch_applies_transforms() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut state = State::init(Dirs::from_path(dir.path()).unwrap()).unwrap();
+    assert!(!state.config.auto_downgrade);
+
+    // transforms are applied whenever a mod is fetched, not only when downgrading
+    let zip = "test_assets/lints/multiple_paks.zip";
+    let spec = ModSpecification::new(format!("{zip}|extract-pak"));
+    let paths = prefetch_with_provider_init(&mut state, &[spec], false, None, |_, url, _| {
+        panic!("no provider for {url}")
+    })
+    .await
+    .unwrap();
+    let extract = Transform::get("extract-pak").unwrap();
+    assert_eq!(
+        std::fs::read(&paths[0]).unwrap(),
+        (extract.apply)(std::fs::read(zip).unwrap()).unwrap()
+    );
+}
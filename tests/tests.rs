@@ -1 +1,2 @@
 mod lint;
+mod providers;